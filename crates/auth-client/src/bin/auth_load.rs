@@ -0,0 +1,98 @@
+use anyhow::{bail, Result};
+use clap::Parser;
+use enturion_auth_client::load::{self, Credentials, LoadConfig};
+use enturion_auth_client::local::{Accounts, LocalServer, DEFAULT_POLL_INTERVAL};
+use enturion_auth_client::ClientConfig;
+use std::net::{Ipv4Addr, SocketAddr};
+use std::process::ExitCode;
+use std::time::Duration;
+
+/// Opens simulated clients against an auth server and reports the latency of their logons.
+#[derive(Debug, Parser)]
+#[command(name = "auth-load")]
+struct Arguments {
+    /// Address of the auth server. Not needed with `--local`.
+    address: Option<SocketAddr>,
+    /// Runs against a local server with in-memory accounts instead.
+    #[arg(long, conflicts_with = "address")]
+    local: bool,
+    /// Interval, in milliseconds, the local server handles packets at.
+    #[arg(long, default_value_t = DEFAULT_POLL_INTERVAL.as_millis() as u64, requires = "local")]
+    poll_interval: u64,
+    /// Number of simulated clients.
+    #[arg(short, long, default_value_t = 100)]
+    clients: usize,
+    /// Clients started per second. All the clients start at once without it.
+    #[arg(short, long)]
+    ramp: Option<f64>,
+    /// Logons done by each client, one after the other.
+    #[arg(short, long, default_value_t = 1)]
+    logons: usize,
+    /// The clients log in with the accounts `<prefix>1` to `<prefix><accounts>`.
+    #[arg(long, default_value = "LOADTEST")]
+    account_prefix: String,
+    #[arg(long, default_value_t = 10)]
+    accounts: usize,
+    #[arg(short, long, default_value = "loadtest")]
+    password: String,
+    /// Time, in seconds, the server has to answer each packet.
+    #[arg(long, default_value_t = 10)]
+    timeout: u64,
+}
+
+async fn run(arguments: Arguments) -> Result<bool> {
+    let credentials = (1..=arguments.accounts)
+        .map(|index| Credentials {
+            account: format!("{}{}", arguments.account_prefix, index),
+            password: arguments.password.clone(),
+        })
+        .collect::<Vec<_>>();
+    if credentials.is_empty() {
+        bail!("At least one account is needed");
+    }
+
+    let mut local_server = None;
+    let address = match arguments.address {
+        Some(address) => address,
+        None if arguments.local => {
+            let mut accounts = Accounts::default();
+            for credentials in &credentials {
+                accounts.insert(&credentials.account, &credentials.password);
+            }
+
+            let poll_interval = Duration::from_millis(arguments.poll_interval.max(1));
+            let server =
+                LocalServer::bind((Ipv4Addr::LOCALHOST, 0), accounts, poll_interval).await?;
+            println!("Local server listening on {}", server.local_addr());
+            local_server.insert(server).local_addr()
+        }
+        None => bail!("An address or --local is needed"),
+    };
+
+    let config = LoadConfig {
+        clients: arguments.clients,
+        ramp: arguments.ramp,
+        logons_per_client: arguments.logons,
+        accounts: credentials,
+        client: ClientConfig {
+            timeout: Duration::from_secs(arguments.timeout),
+            ..ClientConfig::default()
+        },
+    };
+
+    let report = load::run(address, &config).await;
+    print!("{}", report);
+    Ok(report.failures.is_empty())
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    match run(Arguments::parse()).await {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(e) => {
+            eprintln!("Error: {:#}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,380 @@
+use crate::srp6::{reconnect_proof, version_proof, ClientProof, SessionKey, Sha1Digest};
+use anyhow::{bail, Error, Result};
+use enturion_authserver::packet::{
+    AuthCommand, AuthResult, LogonChallengeRequest, RealmListEntries, RealmListRequest,
+    RealmListResponse, BINCODE_CONFIGURATION,
+};
+use rand::RngCore;
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::net::{TcpStream, ToSocketAddrs};
+use tokio::time;
+
+/// The logon challenge asks for a TOTP token.
+const SECURITY_FLAG_TOKEN: u8 = 0x04;
+
+/// The client identity sent in the challenges.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientConfig {
+    pub build: u16,
+    pub version: [u8; 3],
+    pub platform: String,
+    pub os: String,
+    /// Locale, e.g. `enUS`.
+    pub country: String,
+    /// Seed of the version proof, only checked with `StrictVersionCheck`.
+    pub version_hash: Sha1Digest,
+    /// Time the server has to answer each packet.
+    pub timeout: Duration,
+}
+
+impl Default for ClientConfig {
+    fn default() -> Self {
+        Self {
+            build: 12340,
+            version: [3, 3, 5],
+            platform: "x86".to_string(),
+            os: "Win".to_string(),
+            country: "enUS".to_string(),
+            version_hash: [0; 20],
+            timeout: Duration::from_secs(10),
+        }
+    }
+}
+
+/// Result of a logon.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Logon {
+    pub result: AuthResult,
+    /// The session key, on success. It is needed to reconnect.
+    pub session_key: Option<SessionKey>,
+}
+
+impl Logon {
+    fn failed(result: AuthResult) -> Self {
+        Self {
+            result,
+            session_key: None,
+        }
+    }
+}
+
+/// A connection to the auth server, speaking the protocol of the configured client build.
+pub struct AuthClient<S = TcpStream> {
+    stream: S,
+    config: ClientConfig,
+}
+
+impl AuthClient<TcpStream> {
+    pub async fn connect<A: ToSocketAddrs>(address: A, config: ClientConfig) -> Result<Self> {
+        let stream = time::timeout(config.timeout, TcpStream::connect(address))
+            .await
+            .map_err(|_| Error::msg("Connection timed out"))??;
+        stream.set_nodelay(true)?;
+
+        Ok(Self::new(stream, config))
+    }
+}
+
+impl<S: AsyncRead + AsyncWrite + Unpin> AuthClient<S> {
+    pub fn new(stream: S, config: ClientConfig) -> Self {
+        Self { stream, config }
+    }
+
+    fn is_post_bc(&self) -> bool {
+        self.config.version[0] >= 2
+    }
+
+    async fn read(&mut self, length: usize) -> Result<Vec<u8>> {
+        let mut data = vec![0; length];
+        match time::timeout(self.config.timeout, self.stream.read_exact(&mut data)).await {
+            Ok(Ok(_)) => Ok(data),
+            Ok(Err(e)) => bail!("Connection closed by the server: {}", e),
+            Err(_) => bail!("No answer from the server in time"),
+        }
+    }
+
+    /// Reads the command and result at the start of an answer.
+    async fn read_result(&mut self, command: AuthCommand, skip: usize) -> Result<AuthResult> {
+        let header = self.read(2 + skip).await?;
+        if header[0] != command as u8 {
+            bail!(
+                "Expected {:?}, received command {:#04x}",
+                command,
+                header[0]
+            );
+        }
+
+        let result = header[1 + skip];
+        AuthResult::try_from(result)
+            .map_err(|_| Error::msg(format!("Unknown auth result {:#04x}", result)))
+    }
+
+    async fn send_challenge(&mut self, command: AuthCommand, account: &str) -> Result<()> {
+        let mut challenge = LogonChallengeRequest {
+            command,
+            error: 0,
+            size: 0,
+            game_name: "WoW".to_string(),
+            version: self.config.version,
+            build: self.config.build,
+            // Sent reversed
+            platform: self.config.platform.chars().rev().collect(),
+            os: self.config.os.chars().rev().collect(),
+            country: self.config.country.chars().rev().collect(),
+            timezone_bias: 0,
+            ip: [127, 0, 0, 1],
+            account_name: account.to_uppercase(),
+        };
+        challenge.compute_sizes()?;
+
+        let data = bincode::encode_to_vec(&challenge, BINCODE_CONFIGURATION)?;
+        Ok(self.stream.write_all(&data).await?)
+    }
+
+    /// Logs in with the given password, and TOTP token if the account needs one.
+    pub async fn logon(
+        &mut self,
+        account: &str,
+        password: &str,
+        token: Option<u32>,
+    ) -> Result<Logon> {
+        self.send_challenge(AuthCommand::AuthLogonChallenge, account)
+            .await?;
+        let result = self.read_result(AuthCommand::AuthLogonChallenge, 1).await?;
+        if result != AuthResult::WowSuccess {
+            return Ok(Logon::failed(result));
+        }
+
+        let b: [u8; 32] = self.read(32).await?.try_into().unwrap();
+        let g_length = self.read(1).await?[0] as usize;
+        let g = self.read(g_length).await?;
+        let n_length = self.read(1).await?[0] as usize;
+        let n = self.read(n_length).await?;
+        let salt = self.read(32).await?;
+        let _version_challenge = self.read(16).await?;
+        let security_flags = self.read(1).await?[0];
+        if security_flags & 0x01 != 0 {
+            // PIN input
+            self.read(20).await?;
+        }
+
+        if security_flags & 0x02 != 0 {
+            // Matrix input
+            self.read(12).await?;
+        }
+
+        if security_flags & SECURITY_FLAG_TOKEN != 0 {
+            self.read(1).await?;
+        }
+
+        let Some(proof) = ClientProof::new(account, password, &b, &g, &n, &salt) else {
+            bail!("Invalid SRP6 values sent by the server");
+        };
+
+        let mut packet = vec![AuthCommand::AuthLogonProof as u8];
+        packet.extend_from_slice(&proof.a);
+        packet.extend_from_slice(&proof.m1);
+        packet.extend_from_slice(&version_proof(&proof.a, &self.config.version_hash));
+        packet.push(0);
+        match token {
+            Some(token) => {
+                let token = token.to_string();
+                packet.push(SECURITY_FLAG_TOKEN);
+                packet.push(token.len() as u8);
+                packet.extend_from_slice(token.as_bytes());
+            }
+            None => packet.push(0),
+        }
+        self.stream.write_all(&packet).await?;
+
+        let result = self.read_result(AuthCommand::AuthLogonProof, 0).await?;
+        if result != AuthResult::WowSuccess {
+            return Ok(Logon::failed(result));
+        }
+
+        let m2 = self.read(20).await?;
+        // Account, survey and login flags
+        self.read(if self.is_post_bc() { 10 } else { 4 }).await?;
+        if m2 != proof.server_proof() {
+            bail!("Invalid server proof");
+        }
+
+        Ok(Logon {
+            result,
+            session_key: Some(proof.session_key),
+        })
+    }
+
+    /// Requests the realm list, once logged in.
+    /// Only the layout of the post-BC clients can be decoded.
+    pub async fn realm_list(&mut self) -> Result<RealmListEntries> {
+        let request = RealmListRequest {
+            command: AuthCommand::RealmList,
+            padding: 0,
+        };
+        self.stream
+            .write_all(&bincode::encode_to_vec(request, BINCODE_CONFIGURATION)?)
+            .await?;
+
+        let mut data = self.read(3).await?;
+        if data[0] != AuthCommand::RealmList as u8 {
+            bail!(
+                "Expected {:?}, received command {:#04x}",
+                AuthCommand::RealmList,
+                data[0]
+            );
+        }
+
+        let size = u16::from_le_bytes([data[1], data[2]]);
+        data.extend(self.read(size as usize).await?);
+        let (response, _): (RealmListResponse, usize) =
+            bincode::decode_from_slice(&data, BINCODE_CONFIGURATION)?;
+
+        Ok(response.realms().clone())
+    }
+
+    /// Reconnects with the session key of a previous logon.
+    pub async fn reconnect(
+        &mut self,
+        account: &str,
+        session_key: &SessionKey,
+    ) -> Result<AuthResult> {
+        self.send_challenge(AuthCommand::AuthReconnectChallenge, account)
+            .await?;
+        let result = self
+            .read_result(AuthCommand::AuthReconnectChallenge, 0)
+            .await?;
+        if result != AuthResult::WowSuccess {
+            return Ok(result);
+        }
+
+        let server_data = self.read(16).await?;
+        let _version_challenge = self.read(16).await?;
+
+        let mut client_data = [0_u8; 16];
+        rand::thread_rng().fill_bytes(&mut client_data);
+        let mut packet = vec![AuthCommand::AuthReconnectProof as u8];
+        packet.extend_from_slice(&client_data);
+        packet.extend_from_slice(&reconnect_proof(
+            account,
+            &client_data,
+            &server_data,
+            session_key,
+        ));
+        packet.extend_from_slice(&version_proof(&client_data, &[0; 20]));
+        packet.push(0);
+        self.stream.write_all(&packet).await?;
+
+        // The server disconnects on an invalid proof
+        let result = self.read_result(AuthCommand::AuthReconnectProof, 0).await?;
+        if result == AuthResult::WowSuccess {
+            // Login flags
+            self.read(2).await?;
+        }
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::client::{AuthClient, ClientConfig};
+    use crate::srp6::{private_key, sha1, sha1_interleave, to_bytes, EphemeralKey};
+    use enturion_authserver::packet::{
+        AuthCommand, AuthResult, LogonChallengeRequest, BINCODE_CONFIGURATION,
+    };
+    use num_bigint::BigUint;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt, DuplexStream};
+
+    const N: [u8; 32] = [
+        0xB7, 0x9B, 0x3E, 0x2A, 0x87, 0x82, 0x3C, 0xAB, 0x8F, 0x5E, 0xBF, 0xBF, 0x8E, 0xB1, 0x01,
+        0x08, 0x53, 0x50, 0x06, 0x29, 0x8B, 0x5B, 0xAD, 0xBD, 0x5B, 0x53, 0xE1, 0x89, 0x5E, 0x64,
+        0x4B, 0x89,
+    ];
+
+    async fn read_challenge(server: &mut DuplexStream) -> LogonChallengeRequest {
+        let mut data = vec![0; 4];
+        server.read_exact(&mut data).await.unwrap();
+        let size = u16::from_le_bytes([data[2], data[3]]) as usize;
+        data.resize(4 + size, 0);
+        server.read_exact(&mut data[4..]).await.unwrap();
+        bincode::decode_from_slice(&data, BINCODE_CONFIGURATION)
+            .unwrap()
+            .0
+    }
+
+    /// Server side of a logon, with the password `secret`.
+    async fn logon_server(mut server: DuplexStream) {
+        let challenge = read_challenge(&mut server).await;
+        assert_eq!(challenge.account_name, "PLAYER");
+        assert_eq!(challenge.build, 12340);
+        assert_eq!(challenge.os, "niW");
+
+        let salt = [0x11_u8; 32];
+        let (n, g) = (BigUint::from_bytes_le(&N), BigUint::from(7_u32));
+        let v = g.modpow(
+            &BigUint::from_bytes_le(&private_key("PLAYER", "SECRET", &salt)),
+            &n,
+        );
+        let b_private = BigUint::from_bytes_le(&[0x24; 32]);
+        let b: EphemeralKey =
+            to_bytes(&((BigUint::from(3_u32) * &v + g.modpow(&b_private, &n)) % &n));
+
+        let mut packet = vec![0x00, 0x00, 0x00];
+        packet.extend_from_slice(&b);
+        packet.extend_from_slice(&[1, 7, 32]);
+        packet.extend_from_slice(&N);
+        packet.extend_from_slice(&salt);
+        packet.extend_from_slice(&[0; 16]);
+        packet.push(0);
+        server.write_all(&packet).await.unwrap();
+
+        let mut proof = [0; 75];
+        server.read_exact(&mut proof).await.unwrap();
+        assert_eq!(proof[0], AuthCommand::AuthLogonProof as u8);
+        let a = &proof[1..33];
+        let u = BigUint::from_bytes_le(&sha1(&[a, &b]));
+        let s: EphemeralKey =
+            to_bytes(&(BigUint::from_bytes_le(a) * v.modpow(&u, &n)).modpow(&b_private, &n));
+        let key = sha1_interleave(&s);
+
+        let mut packet = vec![AuthCommand::AuthLogonProof as u8, 0];
+        packet.extend_from_slice(&sha1(&[a, &proof[33..53], &key]));
+        packet.extend_from_slice(&[0; 10]);
+        server.write_all(&packet).await.unwrap();
+
+        // Reconnect challenge
+        read_challenge(&mut server).await;
+        server
+            .write_all(&[
+                AuthCommand::AuthReconnectChallenge as u8,
+                AuthResult::WowFailUnknownAccount as u8,
+            ])
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    pub async fn logon() {
+        let (client, server) = tokio::io::duplex(1024);
+        let server = tokio::spawn(logon_server(server));
+
+        let mut client = AuthClient::new(client, ClientConfig::default());
+        let logon = client.logon("player", "secret", None).await.unwrap();
+        assert_eq!(logon.result, AuthResult::WowSuccess);
+
+        let session_key = logon.session_key.unwrap();
+        let result = client.reconnect("player", &session_key).await.unwrap();
+        assert_eq!(result, AuthResult::WowFailUnknownAccount);
+        server.await.unwrap();
+
+        // Wrong password
+        let (client, server) = tokio::io::duplex(1024);
+        let server = tokio::spawn(logon_server(server));
+        let mut client = AuthClient::new(client, ClientConfig::default());
+        assert!(client.logon("player", "wrong", None).await.is_err());
+        drop(client);
+        let _ = server.await;
+    }
+}
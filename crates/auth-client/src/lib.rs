@@ -0,0 +1,9 @@
+//! A headless auth protocol client, to run login smoke and load tests against an auth server.
+
+mod client;
+pub mod load;
+pub mod local;
+pub mod srp6;
+
+pub use crate::client::{AuthClient, ClientConfig, Logon};
+pub use enturion_authserver::packet::AuthResult;
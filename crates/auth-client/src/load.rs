@@ -0,0 +1,268 @@
+//! Load generator: simulated clients logging in at a configured ramp rate.
+//! It runs against a live auth server, whose database has the accounts of the clients, or
+//! against a [`LocalServer`](crate::local::LocalServer) with in-memory accounts.
+
+use crate::client::{AuthClient, ClientConfig};
+use anyhow::Result;
+use enturion_authserver::packet::AuthResult;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinSet;
+use tokio::time::{self, Instant};
+
+/// The credentials of an account used by the simulated clients.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Credentials {
+    pub account: String,
+    pub password: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct LoadConfig {
+    /// Number of simulated clients.
+    pub clients: usize,
+    /// Clients started per second. All the clients start at once if `None`.
+    pub ramp: Option<f64>,
+    /// Logons each client does one after the other, on a new connection each time.
+    pub logons_per_client: usize,
+    /// Accounts the clients log in with, in turn.
+    pub accounts: Vec<Credentials>,
+    pub client: ClientConfig,
+}
+
+/// Latency of a logon, from the connection to the server proof.
+type Sample = (Duration, Result<(), String>);
+
+/// The results of a load run.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct LoadReport {
+    /// Latencies of the successful logons, sorted.
+    pub latencies: Vec<Duration>,
+    /// Failed logons, by reason.
+    pub failures: BTreeMap<String, usize>,
+    /// Time from the start of the first client to the end of the last logon.
+    pub elapsed: Duration,
+}
+
+impl LoadReport {
+    fn new(samples: Vec<Sample>, elapsed: Duration) -> Self {
+        let mut report = Self {
+            elapsed,
+            ..Self::default()
+        };
+
+        for (latency, result) in samples {
+            match result {
+                Ok(()) => report.latencies.push(latency),
+                Err(reason) => *report.failures.entry(reason).or_default() += 1,
+            }
+        }
+
+        report.latencies.sort();
+        report
+    }
+
+    pub fn successes(&self) -> usize {
+        self.latencies.len()
+    }
+
+    pub fn logons(&self) -> usize {
+        self.successes() + self.failures.values().sum::<usize>()
+    }
+
+    /// The nearest-rank percentile of the successful logon latencies, `percentile` in `0..=100`.
+    pub fn percentile(&self, percentile: f64) -> Option<Duration> {
+        if self.latencies.is_empty() {
+            return None;
+        }
+
+        let rank = (percentile / 100.0 * self.latencies.len() as f64).ceil() as usize;
+        Some(self.latencies[rank.clamp(1, self.latencies.len()) - 1])
+    }
+
+    /// Successful logons per second over the run.
+    pub fn throughput(&self) -> f64 {
+        self.successes() as f64 / self.elapsed.as_secs_f64().max(f64::EPSILON)
+    }
+}
+
+impl Display for LoadReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "{} logons in {:.2} s: {} succeeded, {} failed ({:.1} logons/s)",
+            self.logons(),
+            self.elapsed.as_secs_f64(),
+            self.successes(),
+            self.logons() - self.successes(),
+            self.throughput()
+        )?;
+
+        if !self.latencies.is_empty() {
+            write!(f, "Latency:")?;
+            for percentile in [50.0, 90.0, 99.0, 100.0] {
+                let latency = self.percentile(percentile).unwrap_or_default();
+                write!(
+                    f,
+                    " p{} {:.1} ms",
+                    percentile,
+                    latency.as_secs_f64() * 1000.0
+                )?;
+            }
+            writeln!(f)?;
+        }
+
+        for (reason, count) in &self.failures {
+            writeln!(f, "  {} x {}", count, reason)?;
+        }
+
+        Ok(())
+    }
+}
+
+async fn logon(address: SocketAddr, credentials: &Credentials, config: ClientConfig) -> Sample {
+    let start = Instant::now();
+    let result = async {
+        let mut client = AuthClient::connect(address, config).await?;
+        client
+            .logon(&credentials.account, &credentials.password, None)
+            .await
+    }
+    .await;
+
+    let result = match result {
+        Ok(logon) if logon.result == AuthResult::WowSuccess => Ok(()),
+        Ok(logon) => Err(format!("{:?}", logon.result)),
+        Err(e) => Err(format!("{:#}", e)),
+    };
+
+    (start.elapsed(), result)
+}
+
+/// Starts the simulated clients against the server, and waits for all their logons.
+pub async fn run(address: SocketAddr, config: &LoadConfig) -> LoadReport {
+    assert!(!config.accounts.is_empty(), "No accounts to log in with");
+
+    let accounts = Arc::new(config.accounts.clone());
+    let interval = config
+        .ramp
+        .filter(|ramp| *ramp > 0.0)
+        .map(|ramp| Duration::from_secs_f64(1.0 / ramp));
+
+    let start = Instant::now();
+    let mut clients = JoinSet::new();
+    for index in 0..config.clients {
+        if let Some(interval) = interval {
+            time::sleep_until(start + interval * index as u32).await;
+        }
+
+        let (accounts, client) = (accounts.clone(), config.client.clone());
+        let logons = config.logons_per_client;
+        clients.spawn(async move {
+            let mut samples = Vec::with_capacity(logons);
+            for logon_index in 0..logons {
+                let credentials = &accounts[(index * logons + logon_index) % accounts.len()];
+                samples.push(logon(address, credentials, client.clone()).await);
+            }
+
+            samples
+        });
+    }
+
+    let mut samples = Vec::new();
+    while let Some(result) = clients.join_next().await {
+        samples.extend(result.expect("Simulated client panicked"));
+    }
+
+    LoadReport::new(samples, start.elapsed())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::load::{run, Credentials, LoadConfig, LoadReport};
+    use crate::local::{Accounts, LocalServer, DEFAULT_POLL_INTERVAL};
+    use crate::ClientConfig;
+    use std::net::{Ipv4Addr, TcpListener};
+    use std::time::Duration;
+
+    #[test]
+    pub fn percentiles() {
+        let samples = (1..=10)
+            .map(|ms| (Duration::from_millis(ms), Ok(())))
+            .chain([(Duration::ZERO, Err("Timeout".to_string()))])
+            .collect();
+        let report = LoadReport::new(samples, Duration::from_secs(2));
+
+        assert_eq!(report.logons(), 11);
+        assert_eq!(report.percentile(50.0), Some(Duration::from_millis(5)));
+        assert_eq!(report.percentile(99.0), Some(Duration::from_millis(10)));
+        assert_eq!(report.percentile(0.0), Some(Duration::from_millis(1)));
+        assert_eq!(report.failures["Timeout"], 1);
+        assert_eq!(report.throughput(), 5.0);
+        assert_eq!(LoadReport::default().percentile(50.0), None);
+    }
+
+    #[tokio::test]
+    pub async fn load_against_a_local_server() {
+        let mut accounts = Accounts::default();
+        accounts.insert("load1", "password");
+        accounts.insert("load2", "password");
+        let address = (Ipv4Addr::LOCALHOST, 0);
+        let server = LocalServer::bind(address, accounts, DEFAULT_POLL_INTERVAL)
+            .await
+            .unwrap();
+
+        let credentials = |account: &str, password: &str| Credentials {
+            account: account.to_string(),
+            password: password.to_string(),
+        };
+        let config = LoadConfig {
+            clients: 4,
+            ramp: Some(1000.0),
+            logons_per_client: 2,
+            accounts: vec![
+                credentials("load1", "password"),
+                credentials("load2", "password"),
+                credentials("load1", "wrong"),
+                credentials("unknown", "password"),
+            ],
+            client: ClientConfig::default(),
+        };
+
+        let report = run(server.local_addr(), &config).await;
+        assert_eq!(report.logons(), 8);
+        assert_eq!(report.successes(), 4);
+        // Wrong passwords are reported as unknown accounts
+        assert_eq!(report.failures["WowFailUnknownAccount"], 4);
+        assert_eq!(report.failures.len(), 1);
+    }
+
+    #[tokio::test]
+    pub async fn unreachable_servers_are_reported() {
+        // Nothing listens on the port once the listener is dropped
+        let address = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))
+            .unwrap()
+            .local_addr()
+            .unwrap();
+
+        let config = LoadConfig {
+            clients: 2,
+            ramp: Some(1000.0),
+            logons_per_client: 2,
+            accounts: vec![Credentials {
+                account: "load1".to_string(),
+                password: "password".to_string(),
+            }],
+            client: ClientConfig::default(),
+        };
+
+        let report = run(address, &config).await;
+        assert_eq!(report.logons(), 4);
+        assert_eq!(report.successes(), 0);
+        assert_eq!(report.failures.values().sum::<usize>(), 4);
+        assert_eq!(report.percentile(50.0), None);
+    }
+}
@@ -0,0 +1,246 @@
+//! A local auth server with in-memory accounts, speaking the logon flow of the auth server.
+//! Packets are handled on the ticks of a polling interval, like the C++ sessions are updated by
+//! `AuthSession_Update`, so load tests can be run without a database.
+
+use crate::srp6::{
+    logon_proof, private_key, sha1, sha1_interleave, to_bytes, EphemeralKey, SessionKey,
+};
+use anyhow::{bail, Result};
+use enturion_authserver::packet::{
+    AuthCommand, AuthResult, LogonChallengeRequest, BINCODE_CONFIGURATION,
+};
+use log::debug;
+use num_bigint::BigUint;
+use rand::RngCore;
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream, ToSocketAddrs};
+use tokio::task::JoinHandle;
+use tokio::time::{self, Interval, MissedTickBehavior};
+
+/// The safe prime of the auth server, little endian.
+const N: [u8; 32] = [
+    0xB7, 0x9B, 0x3E, 0x2A, 0x87, 0x82, 0x3C, 0xAB, 0x8F, 0x5E, 0xBF, 0xBF, 0x8E, 0xB1, 0x01, 0x08,
+    0x53, 0x50, 0x06, 0x29, 0x8B, 0x5B, 0xAD, 0xBD, 0x5B, 0x53, 0xE1, 0x89, 0x5E, 0x64, 0x4B, 0x89,
+];
+const G: u8 = 7;
+
+/// Interval the C++ sessions are updated at.
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+/// The stored credentials of an account.
+struct Account {
+    salt: [u8; 32],
+    verifier: BigUint,
+}
+
+/// The in-memory account store of a local server.
+#[derive(Default)]
+pub struct Accounts {
+    accounts: HashMap<String, Account>,
+}
+
+impl Accounts {
+    /// Adds an account, computing its verifier like `AccountMgr` does.
+    pub fn insert(&mut self, name: &str, password: &str) {
+        let mut salt = [0; 32];
+        rand::thread_rng().fill_bytes(&mut salt);
+
+        let x = BigUint::from_bytes_le(&private_key(name, password, &salt));
+        let verifier = BigUint::from(G).modpow(&x, &BigUint::from_bytes_le(&N));
+        self.accounts
+            .insert(name.to_uppercase(), Account { salt, verifier });
+    }
+
+    pub fn len(&self) -> usize {
+        self.accounts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.accounts.is_empty()
+    }
+}
+
+/// A running local server. It stops when dropped.
+pub struct LocalServer {
+    address: SocketAddr,
+    accept_loop: JoinHandle<()>,
+}
+
+impl LocalServer {
+    pub async fn bind<A: ToSocketAddrs>(
+        address: A,
+        accounts: Accounts,
+        poll_interval: Duration,
+    ) -> Result<Self> {
+        let listener = TcpListener::bind(address).await?;
+        let address = listener.local_addr()?;
+        let accounts = Arc::new(accounts);
+        let accept_loop = tokio::spawn(async move {
+            loop {
+                let Ok((stream, peer)) = listener.accept().await else {
+                    continue;
+                };
+
+                let accounts = accounts.clone();
+                tokio::spawn(async move {
+                    let mut session = LocalSession::new(stream, accounts, poll_interval);
+                    if let Err(e) = session.run().await {
+                        debug!(target: "session", "Local session of {} ended: {}", peer, e);
+                    }
+                });
+            }
+        });
+
+        Ok(Self {
+            address,
+            accept_loop,
+        })
+    }
+
+    pub fn local_addr(&self) -> SocketAddr {
+        self.address
+    }
+}
+
+impl Drop for LocalServer {
+    fn drop(&mut self) {
+        self.accept_loop.abort();
+    }
+}
+
+/// The challenge sent to the client, kept to check its proof.
+struct Challenge {
+    account: String,
+    salt: [u8; 32],
+    verifier: BigUint,
+    b_private: BigUint,
+    b: EphemeralKey,
+}
+
+struct LocalSession {
+    stream: TcpStream,
+    accounts: Arc<Accounts>,
+    ticks: Interval,
+    challenge: Option<Challenge>,
+}
+
+impl LocalSession {
+    fn new(stream: TcpStream, accounts: Arc<Accounts>, poll_interval: Duration) -> Self {
+        let mut ticks = time::interval(poll_interval);
+        ticks.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        Self {
+            stream,
+            accounts,
+            ticks,
+            challenge: None,
+        }
+    }
+
+    async fn read(&mut self, length: usize) -> Result<Vec<u8>> {
+        let mut data = vec![0; length];
+        self.stream.read_exact(&mut data).await?;
+        Ok(data)
+    }
+
+    async fn run(&mut self) -> Result<()> {
+        loop {
+            let command = self.read(1).await?[0];
+            // Received packets wait for the next update of the session
+            self.ticks.tick().await;
+
+            match AuthCommand::try_from(command) {
+                Ok(AuthCommand::AuthLogonChallenge) if self.challenge.is_none() => {
+                    self.handle_logon_challenge(command).await?
+                }
+                Ok(AuthCommand::AuthLogonProof) if self.challenge.is_some() => {
+                    return self.handle_logon_proof().await;
+                }
+                _ => bail!("Unexpected command {:#04x}", command),
+            }
+        }
+    }
+
+    async fn handle_logon_challenge(&mut self, command: u8) -> Result<()> {
+        let mut data = vec![command];
+        data.extend(self.read(3).await?);
+        let size = u16::from_le_bytes([data[2], data[3]]);
+        data.extend(self.read(size as usize).await?);
+        let (request, _): (LogonChallengeRequest, usize) =
+            bincode::decode_from_slice(&data, BINCODE_CONFIGURATION)?;
+
+        let name = request.account_name.to_uppercase();
+        let Some(account) = self.accounts.accounts.get(&name) else {
+            let result = AuthResult::WowFailUnknownAccount as u8;
+            self.stream.write_all(&[command, 0, result]).await?;
+            return Ok(());
+        };
+
+        let n = BigUint::from_bytes_le(&N);
+        let mut b_private = [0; 19];
+        rand::thread_rng().fill_bytes(&mut b_private);
+        let b_private = BigUint::from_bytes_le(&b_private);
+        let b: EphemeralKey = to_bytes(
+            &((BigUint::from(3_u32) * &account.verifier + BigUint::from(G).modpow(&b_private, &n))
+                % &n),
+        );
+
+        let mut packet = vec![command, 0, AuthResult::WowSuccess as u8];
+        packet.extend_from_slice(&b);
+        packet.extend_from_slice(&[1, G, N.len() as u8]);
+        packet.extend_from_slice(&N);
+        packet.extend_from_slice(&account.salt);
+        packet.extend_from_slice(&[0; 16]);
+        // No security flags
+        packet.push(0);
+
+        self.challenge = Some(Challenge {
+            account: name,
+            salt: account.salt,
+            verifier: account.verifier.clone(),
+            b_private,
+            b,
+        });
+        Ok(self.stream.write_all(&packet).await?)
+    }
+
+    async fn handle_logon_proof(&mut self) -> Result<()> {
+        // A, M1, CRC hash, key count and security flags
+        let proof = self.read(74).await?;
+        let challenge = self.challenge.take().unwrap();
+        let a: EphemeralKey = proof[..32].try_into().unwrap();
+        let m1 = &proof[32..52];
+
+        let n = BigUint::from_bytes_le(&N);
+        let u = BigUint::from_bytes_le(&sha1(&[&a, &challenge.b]));
+        let s: EphemeralKey = to_bytes(
+            &(BigUint::from_bytes_le(&a) * challenge.verifier.modpow(&u, &n))
+                .modpow(&challenge.b_private, &n),
+        );
+        let session_key: SessionKey = sha1_interleave(&s);
+        let expected = logon_proof(
+            &challenge.account,
+            &[G],
+            &N,
+            &challenge.salt,
+            &a,
+            &challenge.b,
+            &session_key,
+        );
+
+        let command = AuthCommand::AuthLogonProof as u8;
+        if m1 != expected {
+            let result = AuthResult::WowFailUnknownAccount as u8;
+            return Ok(self.stream.write_all(&[command, result, 3, 0]).await?);
+        }
+
+        let mut packet = vec![command, AuthResult::WowSuccess as u8];
+        packet.extend_from_slice(&sha1(&[&a, m1, &session_key]));
+        // Account, survey and login flags
+        packet.extend_from_slice(&[0; 10]);
+        Ok(self.stream.write_all(&packet).await?)
+    }
+}
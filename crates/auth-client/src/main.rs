@@ -0,0 +1,168 @@
+use anyhow::{bail, Error, Result};
+use clap::Parser;
+use enturion_auth_client::{AuthClient, AuthResult, ClientConfig};
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+/// Logs in to an auth server like a game client would, and checks the results.
+#[derive(Debug, Parser)]
+#[command(name = "auth-client")]
+struct Arguments {
+    /// Address of the auth server.
+    #[arg(default_value = "127.0.0.1:3724")]
+    address: String,
+    #[arg(short, long)]
+    account: String,
+    #[arg(short, long)]
+    password: String,
+    /// TOTP token, for accounts with two-factor authentication.
+    #[arg(short, long)]
+    token: Option<u32>,
+    /// Expected result of the logon, as a name (`WowFailBanned`) or a code.
+    #[arg(short, long, default_value = "WowSuccess", value_parser = parse_auth_result)]
+    expect: AuthResult,
+    /// Requests the realm list once logged in.
+    #[arg(long)]
+    realm_list: bool,
+    /// Reconnects with the session key once logged in.
+    #[arg(long)]
+    reconnect: bool,
+    #[arg(long, default_value_t = 12340)]
+    build: u16,
+    #[arg(long = "client-version", default_value = "3.3.5", value_parser = parse_version)]
+    version: [u8; 3],
+    #[arg(long, default_value = "Win")]
+    os: String,
+    #[arg(long, default_value = "enUS")]
+    locale: String,
+    /// Hex encoded version check seed of the build, for servers with `StrictVersionCheck`.
+    #[arg(long, value_parser = parse_version_hash)]
+    version_hash: Option<[u8; 20]>,
+    /// Time, in seconds, the server has to answer each packet.
+    #[arg(long, default_value_t = 10)]
+    timeout: u64,
+}
+
+fn parse_auth_result(value: &str) -> Result<AuthResult> {
+    let code = match value.strip_prefix("0x") {
+        Some(hex) => u8::from_str_radix(hex, 16).ok(),
+        None => value.parse::<u8>().ok(),
+    };
+
+    // Names are accepted in any case, with or without underscores
+    let name = value.replace('_', "").to_lowercase();
+    (0..=u8::MAX)
+        .filter_map(|code| AuthResult::try_from(code).ok())
+        .find(|result| {
+            Some(*result as u8) == code || format!("{:?}", result).to_lowercase() == name
+        })
+        .ok_or_else(|| Error::msg(format!("Unknown auth result '{}'", value)))
+}
+
+fn parse_version(value: &str) -> Result<[u8; 3]> {
+    let parts = value
+        .split('.')
+        .map(str::parse::<u8>)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    parts
+        .try_into()
+        .map_err(|_| Error::msg("Expected a version like 3.3.5"))
+}
+
+fn parse_version_hash(value: &str) -> Result<[u8; 20]> {
+    if value.len() != 40 {
+        bail!("Expected 40 hex digits");
+    }
+
+    let mut hash = [0; 20];
+    for (i, byte) in hash.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&value[2 * i..2 * i + 2], 16)?;
+    }
+
+    Ok(hash)
+}
+
+fn check(step: &str, result: AuthResult, expected: AuthResult, start: Instant) -> bool {
+    let elapsed = start.elapsed().as_millis();
+    if result == expected {
+        println!("{}: {:?} ({} ms)", step, result, elapsed);
+        true
+    } else {
+        println!(
+            "{}: {:?}, expected {:?} ({} ms)",
+            step, result, expected, elapsed
+        );
+        false
+    }
+}
+
+async fn run(arguments: Arguments) -> Result<bool> {
+    let config = ClientConfig {
+        build: arguments.build,
+        version: arguments.version,
+        os: arguments.os.clone(),
+        country: arguments.locale.clone(),
+        version_hash: arguments.version_hash.unwrap_or_default(),
+        timeout: Duration::from_secs(arguments.timeout),
+        ..ClientConfig::default()
+    };
+
+    let start = Instant::now();
+    let mut client = AuthClient::connect(&arguments.address, config.clone()).await?;
+    let logon = client
+        .logon(&arguments.account, &arguments.password, arguments.token)
+        .await?;
+    if !check("Logon", logon.result, arguments.expect, start) {
+        return Ok(false);
+    }
+
+    let Some(session_key) = logon.session_key else {
+        return Ok(true);
+    };
+
+    if arguments.realm_list {
+        let start = Instant::now();
+        let realms = client.realm_list().await?;
+        println!(
+            "Realm list: {} realms ({} ms)",
+            realms.realms.len(),
+            start.elapsed().as_millis()
+        );
+        for realm in &realms.realms {
+            println!(
+                "  {} - {} ({} characters, population {:.1}, flags {:#04x})",
+                realm.name, realm.address, realm.characters, realm.population, realm.flags
+            );
+        }
+    }
+
+    if arguments.reconnect {
+        drop(client);
+        let start = Instant::now();
+        let mut client = AuthClient::connect(&arguments.address, config).await?;
+        let result = client.reconnect(&arguments.account, &session_key).await?;
+        if !check("Reconnect", result, AuthResult::WowSuccess, start) {
+            return Ok(false);
+        }
+
+        if arguments.realm_list {
+            let realms = client.realm_list().await?;
+            println!("Realm list after reconnect: {} realms", realms.realms.len());
+        }
+    }
+
+    Ok(true)
+}
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() -> ExitCode {
+    match run(Arguments::parse()).await {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(e) => {
+            eprintln!("Error: {:#}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,206 @@
+//! Client side of the SRP6 variant used by the auth protocol.
+//! Numbers are sent as little endian byte arrays.
+
+use num_bigint::BigUint;
+use rand::RngCore;
+use sha1::{Digest, Sha1};
+
+pub const EPHEMERAL_KEY_LENGTH: usize = 32;
+pub const SESSION_KEY_LENGTH: usize = 40;
+
+pub type EphemeralKey = [u8; EPHEMERAL_KEY_LENGTH];
+pub type SessionKey = [u8; SESSION_KEY_LENGTH];
+pub type Sha1Digest = [u8; 20];
+
+/// The multiplier parameter.
+const K: u32 = 3;
+
+pub(crate) fn sha1(parts: &[&[u8]]) -> Sha1Digest {
+    let mut sha = Sha1::new();
+    for part in parts {
+        sha.update(part);
+    }
+
+    sha.finalize().into()
+}
+
+pub(crate) fn to_bytes<const N: usize>(value: &BigUint) -> [u8; N] {
+    let mut bytes = [0; N];
+    let le = value.to_bytes_le();
+    bytes[..le.len()].copy_from_slice(&le);
+    bytes
+}
+
+/// `SHA1(s | SHA1(I | ':' | P))`, with the account name and password in upper case.
+pub fn private_key(account: &str, password: &str, salt: &[u8]) -> Sha1Digest {
+    let credentials = format!("{}:{}", account.to_uppercase(), password.to_uppercase());
+    sha1(&[salt, &sha1(&[credentials.as_bytes()])])
+}
+
+/// Hashes the even and odd bytes of `S` separately, skipping its leading zero bytes.
+pub fn sha1_interleave(s: &EphemeralKey) -> SessionKey {
+    let even = s.iter().step_by(2).copied().collect::<Vec<_>>();
+    let odd = s.iter().skip(1).step_by(2).copied().collect::<Vec<_>>();
+
+    let mut p = s.iter().take_while(|byte| **byte == 0).count();
+    if p & 1 != 0 {
+        p += 1;
+    }
+    p /= 2;
+
+    let hash0 = sha1(&[&even[p..]]);
+    let hash1 = sha1(&[&odd[p..]]);
+
+    let mut key = [0; SESSION_KEY_LENGTH];
+    for i in 0..20 {
+        key[2 * i] = hash0[i];
+        key[2 * i + 1] = hash1[i];
+    }
+
+    key
+}
+
+/// `SHA1(SHA1(N) xor SHA1(g) | SHA1(I) | s | A | B | K)`, the `M1` proof of the client.
+pub fn logon_proof(
+    account: &str,
+    g: &[u8],
+    n: &[u8],
+    salt: &[u8],
+    a: &EphemeralKey,
+    b: &EphemeralKey,
+    session_key: &SessionKey,
+) -> Sha1Digest {
+    let n_hash = sha1(&[n]);
+    let g_hash = sha1(&[g]);
+    let mut ng_hash = [0; 20];
+    for (ng, (n, g)) in ng_hash.iter_mut().zip(n_hash.iter().zip(g_hash)) {
+        *ng = n ^ g;
+    }
+
+    let account_hash = sha1(&[account.to_uppercase().as_bytes()]);
+    sha1(&[&ng_hash, &account_hash, salt, a, b, session_key])
+}
+
+/// The values the client sends in its logon proof.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientProof {
+    /// The client public ephemeral value.
+    pub a: EphemeralKey,
+    pub m1: Sha1Digest,
+    pub session_key: SessionKey,
+}
+
+impl ClientProof {
+    /// Computes the proof from the values of the logon challenge.
+    /// Returns `None` if the server values are invalid.
+    pub fn new(
+        account: &str,
+        password: &str,
+        b: &EphemeralKey,
+        g: &[u8],
+        n: &[u8],
+        salt: &[u8],
+    ) -> Option<Self> {
+        let mut private = [0_u8; 19];
+        rand::thread_rng().fill_bytes(&mut private);
+        Self::with_private_ephemeral(account, password, &private, b, g, n, salt)
+    }
+
+    /// Computes the proof with the given private ephemeral value, instead of a random one.
+    pub fn with_private_ephemeral(
+        account: &str,
+        password: &str,
+        private: &[u8],
+        b: &EphemeralKey,
+        g: &[u8],
+        n: &[u8],
+        salt: &[u8],
+    ) -> Option<Self> {
+        let big_n = BigUint::from_bytes_le(n);
+        let big_g = BigUint::from_bytes_le(g);
+        let big_b = BigUint::from_bytes_le(b);
+        let private = BigUint::from_bytes_le(private);
+        if big_n.bits() == 0 || (&big_b % &big_n).bits() == 0 {
+            return None;
+        }
+
+        let a: EphemeralKey = to_bytes(&big_g.modpow(&private, &big_n));
+        let x = BigUint::from_bytes_le(&private_key(account, password, salt));
+        let u = BigUint::from_bytes_le(&sha1(&[&a, b]));
+
+        // S = (B - k * g^x) ^ (a + u * x) mod N
+        let kv = (BigUint::from(K) * big_g.modpow(&x, &big_n)) % &big_n;
+        let base = (&big_b + &big_n - kv) % &big_n;
+        let s: EphemeralKey = to_bytes(&base.modpow(&(private + u * x), &big_n));
+        let session_key = sha1_interleave(&s);
+        let m1 = logon_proof(account, g, n, salt, &a, b, &session_key);
+
+        Some(Self { a, m1, session_key })
+    }
+
+    /// The proof the server is expected to answer with: `SHA1(A | M1 | K)`.
+    pub fn server_proof(&self) -> Sha1Digest {
+        sha1(&[&self.a, &self.m1, &self.session_key])
+    }
+}
+
+/// `SHA1(I | R1 | R2 | K)`, the reconnect proof of the client.
+/// `client_data` is the random `R1`, `server_data` the random value of the reconnect challenge.
+pub fn reconnect_proof(
+    account: &str,
+    client_data: &[u8],
+    server_data: &[u8],
+    session_key: &SessionKey,
+) -> Sha1Digest {
+    sha1(&[
+        account.to_uppercase().as_bytes(),
+        client_data,
+        server_data,
+        session_key,
+    ])
+}
+
+/// `SHA1(A | hash seed)`, the version proof. Reconnecting clients use an empty seed.
+pub fn version_proof(a: &[u8], hash_seed: &Sha1Digest) -> Sha1Digest {
+    sha1(&[a, hash_seed])
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::srp6::{private_key, sha1, sha1_interleave, to_bytes, ClientProof, EphemeralKey};
+    use num_bigint::BigUint;
+
+    const N: [u8; 32] = [
+        0xB7, 0x9B, 0x3E, 0x2A, 0x87, 0x82, 0x3C, 0xAB, 0x8F, 0x5E, 0xBF, 0xBF, 0x8E, 0xB1, 0x01,
+        0x08, 0x53, 0x50, 0x06, 0x29, 0x8B, 0x5B, 0xAD, 0xBD, 0x5B, 0x53, 0xE1, 0x89, 0x5E, 0x64,
+        0x4B, 0x89,
+    ];
+
+    #[test]
+    pub fn proof_matches_the_server_computation() {
+        let salt = [0x5A_u8; 32];
+        let (n, g) = (BigUint::from_bytes_le(&N), BigUint::from(7_u32));
+
+        // Server side: v = g^x, B = k * v + g^b
+        let v = g.modpow(
+            &BigUint::from_bytes_le(&private_key("player", "secret", &salt)),
+            &n,
+        );
+        let b_private = BigUint::from_bytes_le(&[0x42; 32]);
+        let b: EphemeralKey =
+            to_bytes(&((BigUint::from(3_u32) * &v + g.modpow(&b_private, &n)) % &n));
+
+        let proof = ClientProof::new("player", "Secret", &b, &[7], &N, &salt).unwrap();
+
+        // Server side: S = (A * v^u)^b
+        let a = BigUint::from_bytes_le(&proof.a);
+        let u = BigUint::from_bytes_le(&sha1(&[&proof.a, &b]));
+        let s: EphemeralKey = to_bytes(&(a * v.modpow(&u, &n)).modpow(&b_private, &n));
+        assert_eq!(sha1_interleave(&s), proof.session_key);
+
+        // A wrong password gives another key
+        let wrong = ClientProof::new("PLAYER", "wrong", &b, &[7], &N, &salt).unwrap();
+        assert_ne!(wrong.session_key, proof.session_key);
+        assert!(ClientProof::new("PLAYER", "secret", &[0; 32], &[7], &N, &salt).is_none());
+    }
+}
@@ -2,7 +2,7 @@ use cbindgen::Config;
 use std::path::PathBuf;
 
 fn main() {
-    let path = PathBuf::from(std::env::current_dir().unwrap());
+    let path = std::env::current_dir().unwrap();
     let current_dir = path.to_str().unwrap();
     println!("{} {}", file!(), current_dir);
 
@@ -6,6 +6,11 @@
 #include <stdint.h>
 #include <stdlib.h>
 
+/**
+ * Size added to a TOTP secret by its encryption: the trailing 12 bytes IV and 12 bytes tag.
+ */
+#define TOTP_ENCRYPTION_OVERHEAD 24
+
 enum AuthCommand
 #ifdef __cplusplus
   : uint8_t
@@ -62,7 +67,51 @@ enum AuthResult
 typedef uint8_t AuthResult;
 #endif // __cplusplus
 
-typedef void (*TickCallback)(void);
+/**
+ * Result of `AuthSession_DispatchPacket`.
+ */
+typedef enum PacketDispatchResult {
+  /**
+   * The packet was handled, its size is returned.
+   */
+  PACKET_HANDLED,
+  /**
+   * More data is needed to decode the packet.
+   */
+  PACKET_INCOMPLETE,
+  /**
+   * No handler for this command, the packet is left to the C++ handlers.
+   */
+  PACKET_UNHANDLED,
+  /**
+   * The packet is invalid, unexpected in the current state or its handler failed.
+   * The session has to be disconnected.
+   */
+  PACKET_REJECTED,
+} PacketDispatchResult;
+
+/**
+ * Result of `Totp_DecryptSecret`.
+ */
+typedef enum TotpSecretResult {
+  TOTP_SECRET_OK,
+  /**
+   * The secret was decrypted with the old master key, it should be stored again.
+   */
+  TOTP_SECRET_ROTATED,
+  TOTP_SECRET_INVALID,
+} TotpSecretResult;
+
+/**
+ * The first packet of a login, also sent to reconnect.
+ * Platform, OS and country are sent reversed, e.g. `68x`, `niW` and `SUne`.
+ */
+typedef struct LogonChallengeRequest LogonChallengeRequest;
+
+/**
+ * Runs an update of the C++ server, with the time elapsed (in milliseconds) since the previous one.
+ */
+typedef void (*TickCallback)(uint32_t diff);
 
 typedef struct LogonChallengeErrorResponse {
   AuthCommand command;
@@ -70,22 +119,81 @@ typedef struct LogonChallengeErrorResponse {
   AuthResult auth_result;
 } LogonChallengeErrorResponse;
 
+/**
+ * A realm, as passed to `RealmListSnapshot_AddRealm`.
+ */
+typedef struct RealmListSnapshotEntry {
+  uint32_t id;
+  uint32_t build;
+  const char *name;
+  const char *external_address;
+  const char *local_address;
+  const char *local_subnet_mask;
+  uint16_t port;
+  uint8_t realm_type;
+  uint8_t flags;
+  uint8_t timezone;
+  uint8_t allowed_security_level;
+  float population;
+} RealmListSnapshotEntry;
+
+/**
+ * A live auth session, as returned by `AuthSessionRegistry_List`.
+ */
+typedef struct AuthSessionInfo {
+  uint64_t id;
+  const char *address;
+  uint16_t port;
+  /**
+   * One of the `AuthStatus` values.
+   */
+  uint8_t status;
+  /**
+   * Account name, or a null pointer if not known yet.
+   */
+  const char *account;
+  uint64_t age_seconds;
+} AuthSessionInfo;
+
 #ifdef __cplusplus
 extern "C" {
 #endif // __cplusplus
 
+extern const uint8_t VERSION_CHALLENGE[16];
+
 extern void AbortHandler(void);
 
 extern const Config *ConfigGetInstance(void);
 
 void AuthServerRsInit(void);
 
-void AuthServerRsMain(TickCallback tick_callback);
+/**
+ * Runs the auth server until it is stopped.
+ * Returns the exit code of the process, the error one if the server could not be started.
+ */
+uint8_t AuthServerRsMain(TickCallback tick_callback);
+
+/**
+ * Prints the known configuration keys with their default value and description, as a
+ * configuration file.
+ */
+void AuthServerRsPrintConfig(void);
+
+/**
+ * Replays a packet capture into an auth session, then returns.
+ * Returns false if the capture could not be replayed or the session replied differently.
+ *
+ * # Safety
+ * Unsafe: path must be a valid C string.
+ */
+bool AuthServerRsReplay(const char *path);
 
 extern void AuthSession_Free(void *auth_session);
 
 extern void *AuthSession_New(void *rs_auth_session);
 
+extern void AuthSession_RequestRealmList(const void *auth_session);
+
 extern void AuthSession_Start(const void *auth_session);
 
 extern void AuthSession_Update(const void *auth_session);
@@ -94,20 +202,252 @@ extern void AuthSession_WriteIntoBuffer(const void *auth_session, const void *da
 
 const char *AuthSession_GetRemoteIpAddress(const void *this_);
 
+/**
+ * Gets the address of the proxy the client connected through,
+ * or a null pointer if the client connected directly.
+ */
+const char *AuthSession_GetProxyIpAddress(const void *this_);
+
+/**
+ * Gets the country code of the client address,
+ * or a null pointer if the address is not in the IP location database.
+ */
+const char *AuthSession_GetIpCountry(const void *this_);
+
+/**
+ * Checks whether an account locked to the given country can log in from the client address.
+ */
+bool AuthSession_IsCountryAllowed(const void *this_, const char *lock_country);
+
 uint16_t AuthSession_GetRemotePort(const void *this_);
 
 void AuthSession_WritePacket(const void *this_, const uint8_t *data, uintptr_t size);
 
+/**
+ * Gets a new handle on the notifier of the session, to wake it up from any thread.
+ *
+ * # Safety
+ * Unsafe: the handle must be freed with `Notifier_Free`.
+ */
+const Notifier *AuthSession_GetNotifier(const void *this_);
+
 void AuthSession_Disconnect(const void *this_);
 
 void AuthSession_Shutdown(const void *this_);
 
+/**
+ * Builds the realm list for the client and sends it.
+ * `realm_ids` and `character_counts` are `count` long arrays with the number of characters
+ * of the account on each realm.
+ */
+void AuthSession_SendRealmList(const void *this_,
+                               uint32_t build,
+                               uint8_t expansion_flags,
+                               uint8_t security_level,
+                               const uint32_t *realm_ids,
+                               const uint8_t *character_counts,
+                               uintptr_t count);
+
+/**
+ * Updates the state reported by the session registry. `status` is an `AuthStatus` value.
+ */
+void AuthSession_SetStatus(const void *this_, uint8_t status);
+
+/**
+ * Handles the packet at the start of the data if it has a Rust handler.
+ * consumed is set to the size of the packet when it has been handled.
+ *
+ * # Safety
+ * Unsafe: data must be `size` bytes long.
+ */
+enum PacketDispatchResult AuthSession_DispatchPacket(const void *this_,
+                                                     const uint8_t *data,
+                                                     uintptr_t size,
+                                                     uintptr_t *consumed);
+
+/**
+ * Records the random bytes generated by the session in its capture.
+ * When replaying a capture, the bytes are replaced by the recorded ones.
+ *
+ * # Safety
+ * Unsafe: data must be `size` bytes long.
+ */
+void AuthSession_UseRandomBytes(const void *this_, uint8_t *data, uintptr_t size);
+
+/**
+ * Sets the account name reported by the session registry.
+ */
+void AuthSession_SetAccountName(const void *this_, const char *name);
+
+/**
+ * Checks whether a client with the given build can log in.
+ * Returns `WOW_SUCCESS`, `WOW_FAIL_VERSION_UPDATE` or `WOW_FAIL_VERSION_INVALID`.
+ */
+AuthResult BuildRegistry_CheckBuild(uint32_t build);
+
+bool BuildRegistry_IsAcceptedBuild(uint32_t build);
+
+bool BuildRegistry_IsPreBCAcceptedBuild(uint32_t build);
+
+bool BuildRegistry_IsPostBCAcceptedBuild(uint32_t build);
+
+/**
+ * Checks the version proof sent by the client.
+ *
+ * # Safety
+ * Unsafe: os must be a valid C string, a must be `a_length` bytes long
+ * and version_proof 20 bytes long.
+ */
+bool BuildRegistry_VerifyVersion(uint32_t build,
+                                 const char *os,
+                                 const uint8_t *a,
+                                 uintptr_t a_length,
+                                 const uint8_t *version_proof,
+                                 bool is_reconnect);
+
 struct LogonChallengeErrorResponse LogonChallengeErrorResponse_New(AuthCommand command,
                                                                    uint8_t padding,
                                                                    AuthResult auth_result);
 
 void LogonChallengeErrorResponse_Send(struct LogonChallengeErrorResponse self, const void *session);
 
+/**
+ * Decodes a packet from the start of the data, consumed is set to its size.
+ * Returns a null pointer if the packet is incomplete or invalid.
+ * The packet must be freed with the free function.
+ */
+struct LogonChallengeRequest *LogonChallengeRequest_Decode(const uint8_t *data,
+                                                           uintptr_t size,
+                                                           uintptr_t *consumed);
+
+void LogonChallengeRequest_Free(struct LogonChallengeRequest *this_);
+
+AuthCommand LogonChallengeRequest_GetCommand(const struct LogonChallengeRequest *this_);
+
+uint8_t LogonChallengeRequest_GetError(const struct LogonChallengeRequest *this_);
+
+uint16_t LogonChallengeRequest_GetSize(const struct LogonChallengeRequest *this_);
+
+/**
+ * The returned bytes are not NUL terminated and live as long as the packet.
+ */
+const uint8_t *LogonChallengeRequest_GetGameName(const struct LogonChallengeRequest *this_,
+                                                 uintptr_t *length);
+
+const uint8_t *LogonChallengeRequest_GetVersion(const struct LogonChallengeRequest *this_);
+
+uint16_t LogonChallengeRequest_GetBuild(const struct LogonChallengeRequest *this_);
+
+/**
+ * The returned bytes are not NUL terminated and live as long as the packet.
+ */
+const uint8_t *LogonChallengeRequest_GetPlatform(const struct LogonChallengeRequest *this_,
+                                                 uintptr_t *length);
+
+/**
+ * The returned bytes are not NUL terminated and live as long as the packet.
+ */
+const uint8_t *LogonChallengeRequest_GetOs(const struct LogonChallengeRequest *this_,
+                                           uintptr_t *length);
+
+/**
+ * The returned bytes are not NUL terminated and live as long as the packet.
+ */
+const uint8_t *LogonChallengeRequest_GetCountry(const struct LogonChallengeRequest *this_,
+                                                uintptr_t *length);
+
+uint32_t LogonChallengeRequest_GetTimezoneBias(const struct LogonChallengeRequest *this_);
+
+const uint8_t *LogonChallengeRequest_GetIp(const struct LogonChallengeRequest *this_);
+
+/**
+ * The returned bytes are not NUL terminated and live as long as the packet.
+ */
+const uint8_t *LogonChallengeRequest_GetAccountName(const struct LogonChallengeRequest *this_,
+                                                    uintptr_t *length);
+
+/**
+ * Fills the snapshot with the realms and builds known by `sRealmList`.
+ */
+extern void RealmList_Snapshot(void *snapshot);
+
+/**
+ * Adds a realm to the snapshot passed to `RealmList_Snapshot`.
+ *
+ * # Safety
+ * Unsafe: snapshot must come from `RealmList_Snapshot`, the strings must be valid C strings.
+ */
+void RealmListSnapshot_AddRealm(void *snapshot, const struct RealmListSnapshotEntry *realm);
+
+/**
+ * Adds a client build to the snapshot passed to `RealmList_Snapshot`.
+ * The hashes are 20 bytes long, all zeros when not set.
+ *
+ * # Safety
+ * Unsafe: snapshot must come from `RealmList_Snapshot`.
+ */
+void RealmListSnapshot_AddBuild(void *snapshot,
+                                uint32_t build,
+                                uint32_t major_version,
+                                uint32_t minor_version,
+                                uint32_t bugfix_version,
+                                const uint8_t *windows_hash,
+                                const uint8_t *mac_hash);
+
+/**
+ * Gets the number of live auth sessions.
+ */
+uintptr_t AuthSessionRegistry_Count(void);
+
+/**
+ * Gets the live auth sessions.
+ * The sessions are returned in the sessions array (must be passed as pointer). The return
+ * value represents the length of the array.
+ *
+ * # Safety
+ * Unsafe: the array must be freed with `AuthSessionRegistry_FreeList`.
+ */
+uintptr_t AuthSessionRegistry_List(struct AuthSessionInfo **sessions);
+
+/**
+ * Frees an array returned by `AuthSessionRegistry_List`.
+ *
+ * # Safety
+ * Unsafe: sessions and length must come from `AuthSessionRegistry_List`.
+ */
+void AuthSessionRegistry_FreeList(struct AuthSessionInfo *sessions, uintptr_t length);
+
+/**
+ * Terminates a live auth session. Returns false if no such session exists.
+ */
+bool AuthSessionRegistry_Kick(uint64_t id);
+
+/**
+ * Decrypts a stored TOTP secret in place, length is updated with the length of the secret.
+ *
+ * # Safety
+ * Unsafe: data must be `*length` bytes long.
+ */
+enum TotpSecretResult Totp_DecryptSecret(uint8_t *data, uintptr_t *length);
+
+/**
+ * Encrypts a TOTP secret with the master key, for storage.
+ * Returns the length of the encrypted secret.
+ *
+ * # Safety
+ * Unsafe: secret must be `length` bytes long, encrypted must be at least
+ * `length + TOTP_ENCRYPTION_OVERHEAD` bytes long.
+ */
+uintptr_t Totp_EncryptSecret(const uint8_t *secret, uintptr_t length, uint8_t *encrypted);
+
+/**
+ * Checks a token sent by the client.
+ *
+ * # Safety
+ * Unsafe: secret must be `length` bytes long.
+ */
+bool Totp_ValidateToken(const uint8_t *secret, uintptr_t length, uint32_t token);
+
 #ifdef __cplusplus
 } // extern "C"
 #endif // __cplusplus
@@ -0,0 +1,166 @@
+use crate::session_registry::{SessionInfo, SessionRegistry};
+use anyhow::Result;
+use enturion_shared::config::Config;
+use enturion_shared::net::listener::bind;
+use log::{debug, info, warn};
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+use tokio::task::JoinHandle;
+
+/// Admin socket settings, read from the `AdminSocket` config section.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AdminSocketConfig {
+    pub enabled: bool,
+    pub address: SocketAddr,
+}
+
+impl Default for AdminSocketConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            address: SocketAddr::from(([127, 0, 0, 1], 3725)),
+        }
+    }
+}
+
+impl AdminSocketConfig {
+    pub fn from_config(config: &Config) -> Result<Self> {
+        config.section("AdminSocket")
+    }
+}
+
+/// Starts the admin socket, a line based text protocol to inspect the live sessions.
+///
+/// Supported commands:
+/// - `count`: number of live sessions
+/// - `list`: one line per live session (id, address, state, account, age in seconds)
+/// - `kick <id>`: terminates a session
+///
+/// Every reply ends with a line containing either `OK` or `ERR <reason>`.
+pub fn start(config: &AdminSocketConfig, registry: Arc<SessionRegistry>) -> Result<JoinHandle<()>> {
+    if !config.address.ip().is_loopback() {
+        warn!(
+            target: "server::authserver",
+            "Admin socket bound to non loopback address {}, it is not authenticated",
+            config.address
+        );
+    }
+
+    let listener = bind(config.address)?;
+    info!(target: "server::authserver", "Admin socket listening on {}", listener.local_addr()?);
+
+    Ok(tokio::spawn(async move {
+        loop {
+            match listener.accept().await {
+                Ok((stream, address)) => {
+                    debug!(target: "server::authserver", "Admin connection from {}", address);
+                    tokio::spawn(handle_client(stream, registry.clone()));
+                }
+                Err(e) => {
+                    warn!(target: "server::authserver", "Failed to accept admin connection: {}", e);
+                    tokio::time::sleep(Duration::from_millis(100)).await;
+                }
+            }
+        }
+    }))
+}
+
+async fn handle_client(stream: TcpStream, registry: Arc<SessionRegistry>) {
+    let (rx, mut tx) = stream.into_split();
+    let mut lines = BufReader::new(rx).lines();
+
+    while let Ok(Some(line)) = lines.next_line().await {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        if line == "quit" {
+            break;
+        }
+
+        let reply = execute(&registry, line);
+        if tx.write_all(reply.as_bytes()).await.is_err() {
+            break;
+        }
+    }
+}
+
+/// Executes an admin command, returns the reply to send back.
+pub fn execute(registry: &SessionRegistry, command: &str) -> String {
+    let mut parts = command.split_whitespace();
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some("count"), None, _) => format!("{}\nOK\n", registry.len()),
+        (Some("list"), None, _) => {
+            let mut reply = String::new();
+            for session in registry.list() {
+                let _ = writeln!(reply, "{}", format_session(&session));
+            }
+
+            reply.push_str("OK\n");
+            reply
+        }
+        (Some("kick"), Some(id), None) => match id.parse() {
+            Ok(id) if registry.kick(id) => "OK\n".to_string(),
+            Ok(id) => format!("ERR no session {}\n", id),
+            Err(_) => format!("ERR invalid session id '{}'\n", id),
+        },
+        _ => format!("ERR unknown command '{}'\n", command),
+    }
+}
+
+fn format_session(session: &SessionInfo) -> String {
+    format!(
+        "{} {} {} {} {}",
+        session.id,
+        session.address,
+        session.state,
+        session.account.as_deref().unwrap_or("-"),
+        session.age.as_secs()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::admin::execute;
+    use crate::session_registry::{SessionMessage, SessionRegistry, SessionState};
+
+    #[test]
+    pub fn admin_commands() {
+        let registry = SessionRegistry::new();
+        let mut session = registry.register("127.0.0.1:50000".parse().unwrap());
+        let mut messages = session.take_messages().unwrap();
+        session.set_state(SessionState::LogonProof);
+        session.set_account("PLAYER");
+
+        assert_eq!(execute(&registry, "count"), "1\nOK\n");
+        assert_eq!(
+            execute(&registry, "list"),
+            format!(
+                "{} 127.0.0.1:50000 logon_proof PLAYER 0\nOK\n",
+                session.id()
+            )
+        );
+        assert_eq!(
+            execute(&registry, &format!("kick {}", session.id())),
+            "OK\n"
+        );
+        assert_eq!(messages.try_recv(), Ok(SessionMessage::Close));
+
+        assert_eq!(execute(&registry, "kick 999"), "ERR no session 999\n");
+        assert_eq!(
+            execute(&registry, "kick abc"),
+            "ERR invalid session id 'abc'\n"
+        );
+        assert_eq!(
+            execute(&registry, "reboot"),
+            "ERR unknown command 'reboot'\n"
+        );
+    }
+}
@@ -1,26 +1,33 @@
+use crate::build_info::BuildRegistry;
+use crate::capture::{FrameKind, PacketCapture, SessionRecorder};
+use crate::flood_protection::{ConnectionGuard, LogonChallengeTracker};
+use crate::handlers::{handlers, PacketDispatchResult};
+use crate::packet::BINCODE_CONFIGURATION;
+use crate::realm_list::{RealmListClient, RealmRegistry};
+use crate::session_registry::{SessionMessage, SessionRegistration, SessionState};
 use anyhow::Result;
-use bincode::config as bincode_config;
-use bincode::config::Configuration;
+use bincode::error::EncodeError;
 use bytes::{Buf, Bytes, BytesMut};
-use enturion_shared::net::{Session, WoWPacket};
-use enturion_shared::AsyncResult;
-use log::{error, trace};
-use std::ffi::{c_char, c_void, CString};
-use std::mem::MaybeUninit;
+use enturion_shared::net::ip_location::{is_country_allowed, IpLocation};
+use enturion_shared::net::notifier::Notifier;
+use enturion_shared::net::WoWPacket;
+use log::{debug, error, trace};
+use std::collections::{HashMap, VecDeque};
+use std::ffi::{c_char, c_void, CStr, CString};
 use std::net::SocketAddr;
-use std::pin::Pin;
 use std::ptr::slice_from_raw_parts;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
 use std::time::Duration;
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
 use tokio::net::TcpStream;
-use tokio::runtime::Handle;
-use tokio::time;
+use tokio::sync::mpsc::UnboundedReceiver;
+use tokio::time::{self, Instant};
 
 extern "C" {
     fn AuthSession_Free(auth_session: *mut c_void);
     fn AuthSession_New(rs_auth_session: *mut c_void) -> *mut c_void;
+    fn AuthSession_RequestRealmList(auth_session: *const c_void);
     fn AuthSession_Start(auth_session: *const c_void);
     fn AuthSession_Update(auth_session: *const c_void);
     fn AuthSession_WriteIntoBuffer(auth_session: *const c_void, data: *const c_void, size: usize);
@@ -28,74 +35,143 @@ extern "C" {
 
 struct CxxAuthSession(*const c_void);
 unsafe impl Send for CxxAuthSession {}
+// The C++ session is only used from the session task, or from C++ code it runs.
+unsafe impl Sync for CxxAuthSession {}
 impl CxxAuthSession {
     fn write_into_buffer(&self, data: Bytes) {
         unsafe {
-            AuthSession_WriteIntoBuffer(self.0, std::mem::transmute(data.as_ptr()), data.len());
+            AuthSession_WriteIntoBuffer(self.0, data.as_ptr().cast(), data.len());
         }
     }
 }
 
+/// Time limits of a session, `None` when disabled.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SessionTimeouts {
+    /// Time the client has to send a full logon challenge.
+    pub logon_challenge: Option<Duration>,
+    /// Time without incoming data after which the session is closed.
+    pub idle: Option<Duration>,
+}
+
+/// The state of a session shared with C++, which only ever gets a pointer to this handle.
+/// The connection itself is owned by the session task.
+pub struct AuthSessionHandle {
+    socket_address: SocketAddr,
+    socket_address_as_str: CString,
+    proxy_address_as_str: Option<CString>,
+    ip_location: Option<IpLocation>,
+    ip_country_as_str: Option<CString>,
+    cxx_auth_session: OnceLock<CxxAuthSession>,
+    registration: SessionRegistration,
+    notifier: Arc<Notifier>,
+    state: Mutex<SessionState>,
+    capture: Option<SessionRecorder>,
+    /// Random bytes of a replayed capture, used instead of the generated ones.
+    replayed_random: Mutex<VecDeque<Vec<u8>>>,
+}
+
 pub struct AuthSession {
     rx: OwnedReadHalf,
     tx: OwnedWriteHalf,
-    socket_address: SocketAddr,
-    socket_address_as_str: CString,
-    cxx_auth_session: MaybeUninit<CxxAuthSession>,
-    should_disconnect: AtomicBool,
-    should_shutdown: AtomicBool,
-    bincode_configuration: Configuration,
+    handle: Arc<AuthSessionHandle>,
+    messages: Option<UnboundedReceiver<SessionMessage>>,
+    timeouts: SessionTimeouts,
+    _connection_guard: ConnectionGuard,
 }
 
 impl AuthSession {
-    pub fn new(stream: TcpStream, address: SocketAddr) -> Pin<Box<Self>> {
+    pub fn new(
+        stream: TcpStream,
+        address: SocketAddr,
+        proxy_address: Option<SocketAddr>,
+        ip_location: Option<IpLocation>,
+        connection_guard: ConnectionGuard,
+        mut registration: SessionRegistration,
+        timeouts: SessionTimeouts,
+    ) -> Self {
         let (rx, tx) = stream.into_split();
-        let address_as_string = match address {
-            SocketAddr::V4(addr) => addr.ip().to_string(),
-            SocketAddr::V6(addr) => addr.ip().to_string(),
+        let address_as_string = |address: &SocketAddr| {
+            let ip = address.ip().to_canonical();
+            CString::new(ip.to_string()).unwrap()
         };
 
-        let result = Self {
-            rx,
-            tx,
+        let messages = registration.take_messages();
+        let handle = Arc::new(AuthSessionHandle {
             socket_address: address,
-            socket_address_as_str: CString::new(address_as_string).unwrap(),
-            cxx_auth_session: MaybeUninit::uninit(),
-            should_disconnect: AtomicBool::new(false),
-            should_shutdown: AtomicBool::new(false),
-            bincode_configuration: bincode_config::standard().with_little_endian(),
-        };
-
-        let mut boxed = Box::pin(result);
+            socket_address_as_str: address_as_string(&address),
+            proxy_address_as_str: proxy_address.as_ref().map(address_as_string),
+            ip_country_as_str: ip_location
+                .as_ref()
+                .and_then(|location| CString::new(location.country_code.as_str()).ok()),
+            ip_location,
+            cxx_auth_session: OnceLock::new(),
+            capture: PacketCapture::global().start_session(registration.id(), address),
+            registration,
+            notifier: Notifier::new(),
+            state: Mutex::new(SessionState::Challenge),
+            replayed_random: Mutex::default(),
+        });
 
-        let cxx_auth_session = unsafe { AuthSession_New(std::mem::transmute(boxed.as_ref())) };
-        let _ = boxed
+        let cxx_auth_session = unsafe { AuthSession_New(Arc::as_ptr(&handle) as *mut c_void) };
+        let _ = handle
             .cxx_auth_session
-            .write(CxxAuthSession(cxx_auth_session));
+            .set(CxxAuthSession(cxx_auth_session));
+
+        Self {
+            rx,
+            tx,
+            handle,
+            messages,
+            timeouts,
+            _connection_guard: connection_guard,
+        }
+    }
+
+    /// Makes the session use the recorded random bytes, in order, instead of generating them.
+    pub fn replay_random(&self, values: impl IntoIterator<Item = Vec<u8>>) {
+        self.handle.replayed_random.lock().unwrap().extend(values);
+    }
 
-        boxed
+    fn cxx_auth_session(&self) -> &CxxAuthSession {
+        self.handle.cxx_auth_session.get().unwrap()
     }
 
+    /// Runs the session until the connection is closed. The session only wakes up when data is
+    /// received, on messages, when C++ has pending work or when a timeout expires.
     pub async fn start(&mut self) -> Result<()> {
-        trace!(target: "session", "Starting session for {}", self.socket_address);
-        unsafe { AuthSession_Start(self.cxx_auth_session.assume_init_read().0) };
+        trace!(
+            target: "session",
+            "Starting session {} for {}",
+            self.handle.registration.id(),
+            self.handle.socket_address
+        );
+        let Some(mut messages) = self.messages.take() else {
+            return Ok(());
+        };
+
+        let notifier = self.handle.notifier.clone();
+        unsafe { AuthSession_Start(self.cxx_auth_session().0) };
 
         let mut buf = BytesMut::with_capacity(4096);
-        let mut interval = time::interval(Duration::from_millis(5));
+        let mut data_received = false;
+
+        // Clients that do not send a full logon challenge in time are dropped.
+        let mut challenge = LogonChallengeTracker::default();
+        let mut challenge_received = self.timeouts.logon_challenge.is_none();
+        let challenge_deadline =
+            time::sleep(self.timeouts.logon_challenge.unwrap_or(Duration::MAX));
+        tokio::pin!(challenge_deadline);
+
+        let idle_deadline = time::sleep(self.timeouts.idle.unwrap_or(Duration::MAX));
+        tokio::pin!(idle_deadline);
 
         loop {
             tokio::select! {
                 result = self.rx.read_buf(&mut buf) => {
                     let n = match result {
-                        Ok(n) if n == 0 => return Ok(()),
-                        Ok(n) => {
-                            if self.should_shutdown.load(Ordering::Relaxed) {
-                                let _ = self.tx.flush().await;
-                                0
-                            } else {
-                                n
-                            }
-                        },
+                        Ok(0) => return Ok(()),
+                        Ok(n) => n,
                         Err(e) => {
                             error!(target: "session", "Failed to read from socket. Err = {}", e);
                             return Err(e.into());
@@ -103,40 +179,165 @@ impl AuthSession {
                     };
 
                     trace!(target: "session", "Received {} bytes", n);
-                    unsafe { self.cxx_auth_session.assume_init_read() }.write_into_buffer(buf.copy_to_bytes(n));
+                    data_received = true;
+                    if let Some(idle) = self.timeouts.idle {
+                        idle_deadline.as_mut().reset(Instant::now() + idle);
+                    }
+
+                    let data = buf.copy_to_bytes(n);
+                    if let Some(capture) = &self.handle.capture {
+                        capture.record(FrameKind::Inbound, &data);
+                    }
+
+                    if !challenge_received {
+                        challenge_received = challenge.feed(&data);
+                    }
+
+                    self.cxx_auth_session().write_into_buffer(data);
                 },
-                _ = interval.tick() => {
-                    unsafe { AuthSession_Update(self.cxx_auth_session.assume_init_read().0); }
-                    if self.should_disconnect.load(Ordering::Relaxed) {
+                message = messages.recv() => match message {
+                    Some(SessionMessage::Write(data)) => {
+                        if let Err(e) = self.tx.write_all(&data).await {
+                            error!(target: "session", "Error writing packet to tcp stream: {}", e);
+                            let _ = self.tx.shutdown().await;
+                        }
+
+                        continue;
+                    }
+                    Some(SessionMessage::Disconnect) => {
                         let _ = self.tx.flush().await;
                         let _ = self.tx.shutdown().await;
+                        continue;
+                    }
+                    Some(SessionMessage::Shutdown) => {
+                        // The writes queued before are already sent
+                        let _ = self.tx.shutdown().await;
+                        trace!(target: "session", "Shutting down session for {}", self.handle.socket_address);
+                        return Ok(());
+                    }
+                    Some(SessionMessage::Drain) => {
+                        // A session that has not started a login has nothing to complete
+                        if !data_received && self.handle.state() == SessionState::Challenge {
+                            trace!(target: "session", "Closing idle session for {}", self.handle.socket_address);
+                            return Ok(());
+                        }
+
+                        continue;
                     }
+                    Some(SessionMessage::Close) | None => {
+                        trace!(target: "session", "Closing session for {}", self.handle.socket_address);
+                        return Ok(());
+                    }
+                },
+                _ = notifier.notified() => {},
+                _ = &mut challenge_deadline, if !challenge_received => {
+                    debug!(target: "session", "No logon challenge received from {} in time, dropping connection", self.handle.socket_address);
+                    return Ok(());
+                },
+                _ = &mut idle_deadline, if self.timeouts.idle.is_some() => {
+                    debug!(target: "session", "No data received from {} in time, dropping connection", self.handle.socket_address);
+                    return Ok(());
                 }
             }
+
+            unsafe { AuthSession_Update(self.cxx_auth_session().0) };
         }
     }
+}
 
+impl Drop for AuthSession {
+    fn drop(&mut self) {
+        trace!(target: "session", "Connection to {} closed.", self.handle.get_ip_address());
+        unsafe { AuthSession_Free(self.cxx_auth_session().0 as *mut c_void) };
+    }
+}
+
+impl AuthSessionHandle {
+    /// Queues data to be written to the connection by the session task.
+    fn write(&self, data: Vec<u8>) {
+        if let Some(capture) = &self.capture {
+            capture.record(FrameKind::Outbound, &data);
+        }
+
+        self.registration
+            .control()
+            .send(SessionMessage::Write(data));
+    }
+
+    /// Encodes and queues a typed packet.
+    pub fn send_packet<P: WoWPacket>(&self, packet: &P) -> Result<(), EncodeError> {
+        self.write(bincode::encode_to_vec(packet, BINCODE_CONFIGURATION)?);
+        Ok(())
+    }
+
+    /// Gets the state last set by C++.
+    pub fn state(&self) -> SessionState {
+        *self.state.lock().unwrap()
+    }
+
+    /// Gets the address of the client.
+    /// When the client connected through a trusted proxy, this is the address sent in the PROXY header.
     pub fn get_ip_address(&self) -> &SocketAddr {
         &self.socket_address
     }
 
+    /// Loads the character counts of the account then sends the realm list.
+    pub fn request_realm_list(&self) {
+        if let Some(cxx_auth_session) = self.cxx_auth_session.get() {
+            unsafe { AuthSession_RequestRealmList(cxx_auth_session.0) };
+        }
+    }
+
     pub fn disconnect(&self) {
-        self.should_disconnect.store(true, Ordering::SeqCst);
+        self.registration.control().disconnect();
     }
 
     pub fn shutdown(&self) {
-        self.should_shutdown.store(true, Ordering::SeqCst);
+        self.registration.control().shutdown();
     }
 
     #[no_mangle]
     pub unsafe extern "C" fn AuthSession_GetRemoteIpAddress(this: *const c_void) -> *const c_char {
-        let this_obj = std::mem::transmute::<_, &Self>(this);
+        let this_obj = &*this.cast::<Self>();
         this_obj.socket_address_as_str.as_ptr()
     }
 
+    /// Gets the address of the proxy the client connected through,
+    /// or a null pointer if the client connected directly.
+    #[no_mangle]
+    pub unsafe extern "C" fn AuthSession_GetProxyIpAddress(this: *const c_void) -> *const c_char {
+        let this_obj = &*this.cast::<Self>();
+        this_obj
+            .proxy_address_as_str
+            .as_ref()
+            .map_or(std::ptr::null(), |address| address.as_ptr())
+    }
+
+    /// Gets the country code of the client address,
+    /// or a null pointer if the address is not in the IP location database.
+    #[no_mangle]
+    pub unsafe extern "C" fn AuthSession_GetIpCountry(this: *const c_void) -> *const c_char {
+        let this_obj = &*this.cast::<Self>();
+        this_obj
+            .ip_country_as_str
+            .as_ref()
+            .map_or(std::ptr::null(), |country| country.as_ptr())
+    }
+
+    /// Checks whether an account locked to the given country can log in from the client address.
+    #[no_mangle]
+    pub unsafe extern "C" fn AuthSession_IsCountryAllowed(
+        this: *const c_void,
+        lock_country: *const c_char,
+    ) -> bool {
+        let this_obj = &*this.cast::<Self>();
+        let lock_country = CStr::from_ptr(lock_country).to_string_lossy();
+        is_country_allowed(this_obj.ip_location.as_ref(), &lock_country)
+    }
+
     #[no_mangle]
     pub unsafe extern "C" fn AuthSession_GetRemotePort(this: *const c_void) -> u16 {
-        let this_obj = std::mem::transmute::<_, &Self>(this);
+        let this_obj = &*this.cast::<Self>();
         this_obj.socket_address.port()
     }
 
@@ -146,45 +347,158 @@ impl AuthSession {
         data: *const u8,
         size: usize,
     ) {
-        let this_obj = std::mem::transmute::<_, &mut Self>(this);
+        let this_obj = &*this.cast::<Self>();
         let buf = slice_from_raw_parts(data, size);
-        let buf = buf.as_ref().unwrap().to_vec();
-
-        Handle::try_current().unwrap().spawn(async move {
-            let res = this_obj.tx.write_all(buf.as_slice()).await;
+        this_obj.write(buf.as_ref().unwrap().to_vec());
+    }
 
-            if let Err(e) = res {
-                error!(target: "session", "Error writing packet to tcp stream: {}", e.to_string());
-                this_obj.disconnect();
-            }
-        });
+    /// Gets a new handle on the notifier of the session, to wake it up from any thread.
+    ///
+    /// # Safety
+    /// Unsafe: the handle must be freed with `Notifier_Free`.
+    #[no_mangle]
+    pub unsafe extern "C" fn AuthSession_GetNotifier(this: *const c_void) -> *const Notifier {
+        let this_obj = &*this.cast::<Self>();
+        this_obj.notifier.to_raw()
     }
 
     #[no_mangle]
     pub unsafe extern "C" fn AuthSession_Disconnect(this: *const c_void) {
-        let this_obj = std::mem::transmute::<_, &Self>(this);
+        let this_obj = &*this.cast::<Self>();
         this_obj.disconnect();
     }
 
     #[no_mangle]
     pub unsafe extern "C" fn AuthSession_Shutdown(this: *const c_void) {
-        let this_obj = std::mem::transmute::<_, &Self>(this);
+        let this_obj = &*this.cast::<Self>();
         this_obj.shutdown();
     }
-}
 
-impl Session for AuthSession {
-    fn send_packet<'a, T: WoWPacket + Send + 'a>(&'a mut self, pkt: T) -> AsyncResult<()> {
-        Box::pin(async move {
-            let v = bincode::encode_to_vec(pkt, self.bincode_configuration)?;
-            Ok(self.tx.write_all(v.as_slice()).await?)
-        })
+    /// Builds the realm list for the client and sends it.
+    /// `realm_ids` and `character_counts` are `count` long arrays with the number of characters
+    /// of the account on each realm.
+    #[no_mangle]
+    pub unsafe extern "C" fn AuthSession_SendRealmList(
+        this: *const c_void,
+        build: u32,
+        expansion_flags: u8,
+        security_level: u8,
+        realm_ids: *const u32,
+        character_counts: *const u8,
+        count: usize,
+    ) {
+        let this_obj = &*this.cast::<Self>();
+        let character_counts = match count {
+            0 => HashMap::new(),
+            _ => std::slice::from_raw_parts(realm_ids, count)
+                .iter()
+                .copied()
+                .zip(
+                    std::slice::from_raw_parts(character_counts, count)
+                        .iter()
+                        .copied(),
+                )
+                .collect(),
+        };
+
+        let client = RealmListClient {
+            build,
+            expansion_flags,
+            security_level,
+            address: this_obj.socket_address.ip().to_canonical(),
+        };
+
+        let response = match RealmRegistry::global().realm_list_response(
+            BuildRegistry::global(),
+            &client,
+            &character_counts,
+        ) {
+            Ok(response) => response,
+            Err(e) => {
+                error!(target: "session", "Failed to build realm list: {}", e);
+                this_obj.disconnect();
+                return;
+            }
+        };
+
+        match bincode::encode_to_vec(response, BINCODE_CONFIGURATION) {
+            Ok(data) => this_obj.write(data),
+            Err(e) => {
+                error!(target: "session", "Failed to encode realm list: {}", e);
+                this_obj.disconnect();
+            }
+        }
     }
-}
 
-impl Drop for AuthSession {
-    fn drop(&mut self) {
-        trace!(target: "session", "Connection to {} closed.", self.get_ip_address());
-        unsafe { AuthSession_Free(self.cxx_auth_session.assume_init_read().0 as *mut c_void) };
+    /// Updates the state reported by the session registry. `status` is an `AuthStatus` value.
+    #[no_mangle]
+    pub unsafe extern "C" fn AuthSession_SetStatus(this: *const c_void, status: u8) {
+        let this_obj = &*this.cast::<Self>();
+        match SessionState::try_from(status) {
+            Ok(state) => {
+                *this_obj.state.lock().unwrap() = state;
+                this_obj.registration.set_state(state);
+            }
+            Err(status) => error!(target: "session", "Unknown auth status {}", status),
+        }
+    }
+
+    /// Handles the packet at the start of the data if it has a Rust handler.
+    /// consumed is set to the size of the packet when it has been handled.
+    ///
+    /// # Safety
+    /// Unsafe: data must be `size` bytes long.
+    #[no_mangle]
+    pub unsafe extern "C" fn AuthSession_DispatchPacket(
+        this: *const c_void,
+        data: *const u8,
+        size: usize,
+        consumed: *mut usize,
+    ) -> PacketDispatchResult {
+        let this_obj = &*this.cast::<Self>();
+        let data = std::slice::from_raw_parts(data, size);
+        let (result, size) = handlers().dispatch(this_obj, this_obj.state(), data);
+        *consumed = size;
+        result
+    }
+
+    /// Records the random bytes generated by the session in its capture.
+    /// When replaying a capture, the bytes are replaced by the recorded ones.
+    ///
+    /// # Safety
+    /// Unsafe: data must be `size` bytes long.
+    #[no_mangle]
+    pub unsafe extern "C" fn AuthSession_UseRandomBytes(
+        this: *const c_void,
+        data: *mut u8,
+        size: usize,
+    ) {
+        let this_obj = &*this.cast::<Self>();
+        let data = std::slice::from_raw_parts_mut(data, size);
+        if let Some(recorded) = this_obj.replayed_random.lock().unwrap().pop_front() {
+            match recorded.len() == size {
+                true => data.copy_from_slice(&recorded),
+                false => {
+                    error!(target: "session", "Recorded random bytes do not match the session")
+                }
+            }
+        }
+
+        if let Some(capture) = &this_obj.capture {
+            capture.record(FrameKind::Random, data);
+        }
+    }
+
+    /// Sets the account name reported by the session registry.
+    #[no_mangle]
+    pub unsafe extern "C" fn AuthSession_SetAccountName(this: *const c_void, name: *const c_char) {
+        let this_obj = &*this.cast::<Self>();
+        if !name.is_null() {
+            let name = CStr::from_ptr(name).to_string_lossy();
+            this_obj.registration.set_account(&name);
+            if let Some(capture) = &this_obj.capture {
+                capture.set_account(&name);
+            }
+        }
     }
 }
@@ -0,0 +1,329 @@
+use crate::packet::AuthResult;
+use anyhow::{Error, Result};
+use enturion_shared::config::Config;
+use serde::Deserialize;
+use sha1::{Digest, Sha1};
+use std::ffi::{c_char, CStr};
+use std::sync::{OnceLock, RwLock};
+
+/// Last client build using the pre-BC protocol.
+pub(crate) const MAX_PRE_BC_CLIENT_BUILD: u32 = 6141;
+
+/// Challenge sent to the client in the logon challenge response, used to compute the version proof.
+#[no_mangle]
+pub static VERSION_CHALLENGE: [u8; 16] = [
+    0xBA, 0xA3, 0x1E, 0x99, 0xA0, 0x0B, 0x21, 0x57, 0xFC, 0x37, 0x3F, 0xB3, 0x69, 0xCD, 0xD2, 0xF1,
+];
+
+static REGISTRY: OnceLock<BuildRegistry> = OnceLock::new();
+
+type VersionHash = [u8; 20];
+
+/// A client build accepted by the auth server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClientBuild {
+    pub build: u32,
+    pub major_version: u32,
+    pub minor_version: u32,
+    pub bugfix_version: u32,
+    /// Hash seed of the Windows client, `None` to skip the version check.
+    pub windows_hash: Option<VersionHash>,
+    /// Hash seed of the macOS client, `None` to skip the version check.
+    pub mac_hash: Option<VersionHash>,
+}
+
+/// A client build declared in the `ClientBuilds` config list.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ConfiguredBuild {
+    build: u32,
+    major_version: u32,
+    minor_version: u32,
+    bugfix_version: u32,
+    #[serde(default)]
+    windows_hash: Option<String>,
+    #[serde(default)]
+    mac_hash: Option<String>,
+}
+
+impl TryFrom<ConfiguredBuild> for ClientBuild {
+    type Error = Error;
+
+    fn try_from(value: ConfiguredBuild) -> Result<Self> {
+        Ok(Self {
+            build: value.build,
+            major_version: value.major_version,
+            minor_version: value.minor_version,
+            bugfix_version: value.bugfix_version,
+            windows_hash: value.windows_hash.as_deref().map(parse_hash).transpose()?,
+            mac_hash: value.mac_hash.as_deref().map(parse_hash).transpose()?,
+        })
+    }
+}
+
+/// Parses an hex encoded version hash.
+fn parse_hash(hash: &str) -> Result<VersionHash> {
+    let mut result = [0_u8; 20];
+    if hash.len() != result.len() * 2 || !hash.is_ascii() {
+        return Err(Error::msg(format!("Invalid version hash '{}'", hash)));
+    }
+
+    for (i, byte) in result.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hash[i * 2..i * 2 + 2], 16)
+            .map_err(|_| Error::msg(format!("Invalid version hash '{}'", hash)))?;
+    }
+
+    Ok(result)
+}
+
+#[derive(Default)]
+struct Builds {
+    configured: Vec<ClientBuild>,
+    database: Vec<ClientBuild>,
+    strict_version_check: bool,
+}
+
+/// The client builds accepted by the auth server.
+/// Builds come from the `build_info` table and from the `ClientBuilds` config list,
+/// the latter taking precedence.
+#[derive(Default)]
+pub struct BuildRegistry {
+    builds: RwLock<Builds>,
+}
+
+impl BuildRegistry {
+    /// Gets the build registry of the auth server.
+    pub fn global() -> &'static BuildRegistry {
+        REGISTRY.get_or_init(BuildRegistry::default)
+    }
+
+    /// Loads the `ClientBuilds` and `StrictVersionCheck` settings.
+    pub fn configure(&self, config: &Config) -> Result<()> {
+        let configured = config
+            .get::<Vec<ConfiguredBuild>>("ClientBuilds", Some(vec![]))?
+            .into_iter()
+            .map(ClientBuild::try_from)
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut builds = self.builds.write().unwrap();
+        builds.configured = configured;
+        builds.strict_version_check = config.get("StrictVersionCheck", Some(false))?;
+
+        Ok(())
+    }
+
+    /// Replaces the builds loaded from the database.
+    pub fn update(&self, database: Vec<ClientBuild>) {
+        self.builds.write().unwrap().database = database;
+    }
+
+    pub fn get(&self, build: u32) -> Option<ClientBuild> {
+        let builds = self.builds.read().unwrap();
+        builds
+            .configured
+            .iter()
+            .chain(builds.database.iter())
+            .find(|info| info.build == build)
+            .copied()
+    }
+
+    pub fn is_accepted(&self, build: u32) -> bool {
+        self.get(build).is_some()
+    }
+
+    pub fn is_pre_bc_accepted(&self, build: u32) -> bool {
+        build <= MAX_PRE_BC_CLIENT_BUILD && self.is_accepted(build)
+    }
+
+    pub fn is_post_bc_accepted(&self, build: u32) -> bool {
+        build > MAX_PRE_BC_CLIENT_BUILD && self.is_accepted(build)
+    }
+
+    /// The accepted builds, in ascending order.
+    pub fn accepted_builds(&self) -> Vec<u32> {
+        let builds = self.builds.read().unwrap();
+        let mut accepted = builds
+            .configured
+            .iter()
+            .chain(builds.database.iter())
+            .map(|info| info.build)
+            .collect::<Vec<_>>();
+
+        accepted.sort_unstable();
+        accepted.dedup();
+        accepted
+    }
+
+    /// Checks whether a client with the given build can log in.
+    /// Clients older than the newest accepted build are asked to update.
+    pub fn check_build(&self, build: u32) -> Result<(), AuthResult> {
+        if self.is_accepted(build) {
+            return Ok(());
+        }
+
+        match self.accepted_builds().last() {
+            Some(newest) if build < *newest => Err(AuthResult::WowFailVersionUpdate),
+            _ => Err(AuthResult::WowFailVersionInvalid),
+        }
+    }
+
+    /// Checks the version proof sent by the client: `SHA1(a | hash seed)`.
+    /// Reconnecting clients use an empty hash seed.
+    pub fn verify_version(
+        &self,
+        build: u32,
+        os: &str,
+        a: &[u8],
+        version_proof: &[u8],
+        is_reconnect: bool,
+    ) -> bool {
+        if !self.builds.read().unwrap().strict_version_check {
+            return true;
+        }
+
+        let hash = if is_reconnect {
+            [0_u8; 20]
+        } else {
+            let Some(info) = self.get(build) else {
+                return false;
+            };
+
+            let hash = match os {
+                "Win" => info.windows_hash,
+                "OSX" => info.mac_hash,
+                _ => return false,
+            };
+
+            match hash {
+                Some(hash) if hash != [0_u8; 20] => hash,
+                // Not filled server side
+                _ => return true,
+            }
+        };
+
+        let mut sha = Sha1::new();
+        sha.update(a);
+        sha.update(hash);
+        sha.finalize().as_slice() == version_proof
+    }
+}
+
+// C/C++ interface
+
+/// Checks whether a client with the given build can log in.
+/// Returns `WOW_SUCCESS`, `WOW_FAIL_VERSION_UPDATE` or `WOW_FAIL_VERSION_INVALID`.
+#[no_mangle]
+pub extern "C" fn BuildRegistry_CheckBuild(build: u32) -> AuthResult {
+    match BuildRegistry::global().check_build(build) {
+        Ok(()) => AuthResult::WowSuccess,
+        Err(result) => result,
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn BuildRegistry_IsAcceptedBuild(build: u32) -> bool {
+    BuildRegistry::global().is_accepted(build)
+}
+
+#[no_mangle]
+pub extern "C" fn BuildRegistry_IsPreBCAcceptedBuild(build: u32) -> bool {
+    BuildRegistry::global().is_pre_bc_accepted(build)
+}
+
+#[no_mangle]
+pub extern "C" fn BuildRegistry_IsPostBCAcceptedBuild(build: u32) -> bool {
+    BuildRegistry::global().is_post_bc_accepted(build)
+}
+
+/// Checks the version proof sent by the client.
+///
+/// # Safety
+/// Unsafe: os must be a valid C string, a must be `a_length` bytes long
+/// and version_proof 20 bytes long.
+#[no_mangle]
+pub unsafe extern "C" fn BuildRegistry_VerifyVersion(
+    build: u32,
+    os: *const c_char,
+    a: *const u8,
+    a_length: usize,
+    version_proof: *const u8,
+    is_reconnect: bool,
+) -> bool {
+    let os = CStr::from_ptr(os).to_string_lossy();
+    let a = std::slice::from_raw_parts(a, a_length);
+    let version_proof = std::slice::from_raw_parts(version_proof, 20);
+
+    BuildRegistry::global().verify_version(build, &os, a, version_proof, is_reconnect)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::build_info::{parse_hash, BuildRegistry, ClientBuild};
+    use crate::packet::AuthResult;
+    use sha1::{Digest, Sha1};
+
+    fn registry(strict_version_check: bool) -> BuildRegistry {
+        let build = |build: u32, windows_hash: Option<[u8; 20]>| ClientBuild {
+            build,
+            major_version: 3,
+            minor_version: 3,
+            bugfix_version: 5,
+            windows_hash,
+            mac_hash: None,
+        };
+
+        let registry = BuildRegistry::default();
+        registry.builds.write().unwrap().strict_version_check = strict_version_check;
+        registry.update(vec![build(5875, None), build(12340, Some([0xAB; 20]))]);
+        registry
+    }
+
+    #[test]
+    pub fn unknown_builds_are_rejected() {
+        let registry = registry(false);
+        assert_eq!(registry.accepted_builds(), vec![5875, 12340]);
+        assert_eq!(registry.check_build(12340), Ok(()));
+        assert_eq!(
+            registry.check_build(8606),
+            Err(AuthResult::WowFailVersionUpdate)
+        );
+        assert_eq!(
+            registry.check_build(15595),
+            Err(AuthResult::WowFailVersionInvalid)
+        );
+        assert!(registry.is_pre_bc_accepted(5875));
+        assert!(!registry.is_post_bc_accepted(5875));
+        assert!(registry.is_post_bc_accepted(12340));
+    }
+
+    #[test]
+    pub fn version_proof() {
+        let a = [0x11_u8; 32];
+        let proof = |seed: [u8; 20]| {
+            let mut sha = Sha1::new();
+            sha.update(a);
+            sha.update(seed);
+            sha.finalize().to_vec()
+        };
+
+        let strict = registry(true);
+        assert!(strict.verify_version(12340, "Win", &a, &proof([0xAB; 20]), false));
+        assert!(!strict.verify_version(12340, "Win", &a, &proof([0x00; 20]), false));
+        assert!(strict.verify_version(12340, "Win", &a, &proof([0x00; 20]), true));
+        // No macOS hash seed: not checked
+        assert!(strict.verify_version(12340, "OSX", &a, &[0; 20], false));
+        assert!(!strict.verify_version(12340, "Linux", &a, &[0; 20], false));
+        assert!(!strict.verify_version(8606, "Win", &a, &[0; 20], false));
+
+        assert!(registry(false).verify_version(8606, "Win", &a, &[0; 20], false));
+    }
+
+    #[test]
+    pub fn hash_parsing() {
+        let hash = parse_hash("CDCBBD5188315E6B4D19449D492DBCFAF156A347").unwrap();
+        assert_eq!(hash[0], 0xCD);
+        assert_eq!(hash[19], 0x47);
+        assert!(parse_hash("CDCB").is_err());
+        assert!(parse_hash("ZZCBBD5188315E6B4D19449D492DBCFAF156A347").is_err());
+    }
+}
@@ -0,0 +1,529 @@
+use crate::packet::BINCODE_CONFIGURATION;
+use crate::session_registry::SessionId;
+use anyhow::{bail, Error, Result};
+use bincode::{Decode, Encode};
+use enturion_shared::config::Config;
+use enturion_shared::net::proxy_protocol::parse_network;
+use ipnet::IpNet;
+use kitros_derive::WowEnum;
+use log::{debug, error};
+use serde::{Deserialize, Serialize};
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock, RwLock};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+static CAPTURE: OnceLock<PacketCapture> = OnceLock::new();
+
+const CAPTURE_MAGIC: [u8; 4] = *b"ECAP";
+/// Version 2 added the random frames, version 1 captures can still be read.
+const CAPTURE_VERSION: u16 = 2;
+/// Bytes kept in memory while waiting for the account name, the session is not recorded past it.
+const MAX_PENDING_BYTES: usize = 64 * 1024;
+
+/// The `PacketCapture` config section.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CaptureSection {
+    pub enabled: bool,
+    pub directory: String,
+    pub networks: Vec<String>,
+    pub accounts: Vec<String>,
+}
+
+impl Default for CaptureSection {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            directory: "captures".to_string(),
+            networks: vec![],
+            accounts: vec![],
+        }
+    }
+}
+
+/// Packet capture settings, read from the `PacketCapture` config section.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CaptureConfig {
+    pub enabled: bool,
+    pub directory: PathBuf,
+    /// Only the sessions from these networks or of these accounts are recorded.
+    /// All the sessions are recorded when both are empty.
+    pub networks: Vec<IpNet>,
+    pub accounts: Vec<String>,
+}
+
+impl CaptureConfig {
+    pub fn from_config(config: &Config) -> Result<Self> {
+        let section: CaptureSection = config.section("PacketCapture")?;
+        let networks = section
+            .networks
+            .iter()
+            .map(|network| parse_network(network))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            enabled: section.enabled,
+            directory: PathBuf::from(section.directory),
+            networks,
+            accounts: section.accounts,
+        })
+    }
+
+    fn is_unfiltered(&self) -> bool {
+        self.networks.is_empty() && self.accounts.is_empty()
+    }
+
+    fn matches_address(&self, address: &SocketAddr) -> bool {
+        let ip = address.ip().to_canonical();
+        self.networks.iter().any(|network| network.contains(&ip))
+    }
+}
+
+fn matches_account(accounts: &[String], account: &str) -> bool {
+    accounts
+        .iter()
+        .any(|filter| filter.eq_ignore_ascii_case(account))
+}
+
+/// Direction, or content, of a captured frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, WowEnum)]
+#[repr(u8)]
+pub enum FrameKind {
+    /// Bytes received from the client.
+    Inbound = 0,
+    /// Bytes sent to the client.
+    Outbound = 1,
+    /// The account name, once known.
+    Account = 2,
+    /// Random bytes generated by the session, given back to the session when replayed.
+    Random = 3,
+}
+
+/// Start of a capture file, followed by the frames.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct CaptureHeader {
+    magic: [u8; 4],
+    version: u16,
+    pub session_id: SessionId,
+    /// Unix time of the connection, in microseconds.
+    pub started_at: u64,
+    pub address: SocketAddr,
+}
+
+impl CaptureHeader {
+    /// Header of a session starting now.
+    pub fn new(session_id: SessionId, address: SocketAddr) -> Self {
+        let started_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+
+        Self {
+            magic: CAPTURE_MAGIC,
+            version: CAPTURE_VERSION,
+            session_id,
+            started_at: started_at.as_micros() as u64,
+            address,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+struct FrameHeader {
+    /// Time since the connection, in microseconds.
+    elapsed: u64,
+    kind: FrameKind,
+    length: u32,
+}
+
+/// A captured chunk of the byte stream, as read from or written to the socket.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    pub elapsed: Duration,
+    pub kind: FrameKind,
+    pub data: Vec<u8>,
+}
+
+fn encode_frame(elapsed: Duration, kind: FrameKind, data: &[u8]) -> Vec<u8> {
+    let header = FrameHeader {
+        elapsed: elapsed.as_micros() as u64,
+        kind,
+        length: data.len() as u32,
+    };
+
+    let mut frame = bincode::encode_to_vec(header, BINCODE_CONFIGURATION).unwrap();
+    frame.extend_from_slice(data);
+    frame
+}
+
+/// A capture file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Capture {
+    pub header: CaptureHeader,
+    pub frames: Vec<Frame>,
+}
+
+impl Capture {
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        let (header, mut offset): (CaptureHeader, usize) =
+            bincode::decode_from_slice(data, BINCODE_CONFIGURATION)?;
+        if header.magic != CAPTURE_MAGIC {
+            bail!("Not a packet capture");
+        }
+
+        if !(1..=CAPTURE_VERSION).contains(&header.version) {
+            bail!("Unsupported packet capture version {}", header.version);
+        }
+
+        let mut frames = vec![];
+        while offset < data.len() {
+            let (frame, size): (FrameHeader, usize) =
+                bincode::decode_from_slice(&data[offset..], BINCODE_CONFIGURATION)?;
+            offset += size;
+
+            let end = offset + frame.length as usize;
+            if end > data.len() {
+                // The session may have been recording when the server stopped
+                bail!("Truncated frame at offset {}", offset - size);
+            }
+
+            frames.push(Frame {
+                elapsed: Duration::from_micros(frame.elapsed),
+                kind: frame.kind,
+                data: data[offset..end].to_vec(),
+            });
+            offset = end;
+        }
+
+        Ok(Self { header, frames })
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let data = std::fs::read(path)
+            .map_err(|e| Error::msg(format!("Cannot open {}: {}", path.display(), e)))?;
+
+        Self::parse(&data)
+    }
+
+    /// Gets the account name of the session, if it was known.
+    pub fn account(&self) -> Option<String> {
+        self.frames
+            .iter()
+            .find(|frame| frame.kind == FrameKind::Account)
+            .map(|frame| String::from_utf8_lossy(&frame.data).into_owned())
+    }
+}
+
+enum RecorderState {
+    /// Waiting for the account name to check the account filters.
+    Pending {
+        path: PathBuf,
+        buffer: Vec<u8>,
+        accounts: Vec<String>,
+    },
+    Recording(File),
+    Stopped,
+}
+
+/// Records the byte stream of a session into a capture file.
+pub struct SessionRecorder {
+    started: Instant,
+    state: Mutex<RecorderState>,
+}
+
+impl SessionRecorder {
+    fn write(&self, state: &mut RecorderState, data: &[u8]) {
+        match state {
+            RecorderState::Pending { buffer, .. } => {
+                buffer.extend_from_slice(data);
+                if buffer.len() > MAX_PENDING_BYTES {
+                    *state = RecorderState::Stopped;
+                }
+            }
+            RecorderState::Recording(file) => {
+                if let Err(e) = file.write_all(data) {
+                    error!(target: "session", "Failed to write packet capture: {}", e);
+                    *state = RecorderState::Stopped;
+                }
+            }
+            RecorderState::Stopped => {}
+        }
+    }
+
+    /// Records a chunk of the byte stream.
+    pub fn record(&self, kind: FrameKind, data: &[u8]) {
+        if data.is_empty() {
+            return;
+        }
+
+        let frame = encode_frame(self.started.elapsed(), kind, data);
+        self.write(&mut self.state.lock().unwrap(), &frame);
+    }
+
+    /// Records the account name, starting or dropping a capture waiting for it.
+    pub fn set_account(&self, account: &str) {
+        let mut state = self.state.lock().unwrap();
+        if let RecorderState::Pending {
+            path,
+            buffer,
+            accounts,
+        } = &*state
+        {
+            let file = match matches_account(accounts, account) {
+                true => create_capture_file(path, buffer),
+                false => None,
+            };
+
+            *state = file.map_or(RecorderState::Stopped, RecorderState::Recording);
+        }
+
+        let frame = encode_frame(
+            self.started.elapsed(),
+            FrameKind::Account,
+            account.as_bytes(),
+        );
+        self.write(&mut state, &frame);
+    }
+}
+
+fn create_capture_file(path: &Path, data: &[u8]) -> Option<File> {
+    let mut options = OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+
+    // Captures contain the login data of the accounts
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+    let result = options.open(path).and_then(|mut file| {
+        file.write_all(data)?;
+        Ok(file)
+    });
+
+    match result {
+        Ok(file) => {
+            debug!(target: "session", "Recording packet capture {}", path.display());
+            Some(file)
+        }
+        Err(e) => {
+            error!(target: "session", "Cannot create packet capture {}: {}", path.display(), e);
+            None
+        }
+    }
+}
+
+/// Creates the recorders of the new sessions.
+#[derive(Default)]
+pub struct PacketCapture {
+    config: RwLock<Arc<CaptureConfig>>,
+}
+
+impl PacketCapture {
+    /// Gets the packet capture settings of the server.
+    pub fn global() -> &'static PacketCapture {
+        CAPTURE.get_or_init(PacketCapture::default)
+    }
+
+    pub fn configure(&self, config: &Config) -> Result<()> {
+        self.set_config(CaptureConfig::from_config(config)?)
+    }
+
+    pub fn set_config(&self, config: CaptureConfig) -> Result<()> {
+        if config.enabled {
+            std::fs::create_dir_all(&config.directory).map_err(|e| {
+                Error::msg(format!(
+                    "Cannot create packet capture directory {}: {}",
+                    config.directory.display(),
+                    e
+                ))
+            })?;
+        }
+
+        *self.config.write().unwrap() = Arc::new(config);
+        Ok(())
+    }
+
+    /// Creates the recorder of a new session, if it may pass the filters.
+    pub fn start_session(&self, id: SessionId, address: SocketAddr) -> Option<SessionRecorder> {
+        let config = self.config.read().unwrap().clone();
+        if !config.enabled {
+            return None;
+        }
+
+        let header = CaptureHeader::new(id, address);
+        let buffer = bincode::encode_to_vec(&header, BINCODE_CONFIGURATION).unwrap();
+
+        // Colons are not allowed in Windows file names
+        let ip = address.ip().to_canonical().to_string().replace(':', "_");
+        let path = config.directory.join(format!(
+            "{}-{}-{}.cap",
+            header.started_at / 1_000_000,
+            id,
+            ip
+        ));
+
+        let state = if config.is_unfiltered() || config.matches_address(&address) {
+            RecorderState::Recording(create_capture_file(&path, &buffer)?)
+        } else if !config.accounts.is_empty() {
+            RecorderState::Pending {
+                path,
+                buffer,
+                accounts: config.accounts.clone(),
+            }
+        } else {
+            return None;
+        };
+
+        Some(SessionRecorder {
+            started: Instant::now(),
+            state: Mutex::new(state),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::capture::{Capture, CaptureConfig, FrameKind, PacketCapture};
+    use std::path::PathBuf;
+
+    fn capture_directory(name: &str) -> PathBuf {
+        let directory =
+            std::env::temp_dir().join(format!("enturion-capture-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&directory);
+        directory
+    }
+
+    fn captures(directory: &PathBuf) -> Vec<Capture> {
+        std::fs::read_dir(directory)
+            .unwrap()
+            .map(|entry| Capture::load(&entry.unwrap().path()).unwrap())
+            .collect()
+    }
+
+    #[test]
+    pub fn sessions_are_recorded() {
+        let directory = capture_directory("record");
+        let capture = PacketCapture::default();
+        capture
+            .set_config(CaptureConfig {
+                enabled: true,
+                directory: directory.clone(),
+                ..CaptureConfig::default()
+            })
+            .unwrap();
+
+        let address = "[::ffff:10.0.0.1]:50000".parse().unwrap();
+        let recorder = capture.start_session(7, address).unwrap();
+        recorder.record(FrameKind::Inbound, &[0x00, 0x08]);
+        recorder.record(FrameKind::Outbound, &[]);
+        recorder.set_account("PLAYER");
+        recorder.record(FrameKind::Outbound, &[0x00, 0x00, 0x04]);
+        drop(recorder);
+
+        let captures = captures(&directory);
+        assert_eq!(captures.len(), 1);
+        let capture = &captures[0];
+        assert_eq!(capture.header.session_id, 7);
+        assert_eq!(capture.header.address, address);
+        assert_eq!(capture.account().as_deref(), Some("PLAYER"));
+
+        let frames = capture
+            .frames
+            .iter()
+            .map(|frame| (frame.kind, frame.data.as_slice()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            frames,
+            [
+                (FrameKind::Inbound, &[0x00, 0x08][..]),
+                (FrameKind::Account, b"PLAYER"),
+                (FrameKind::Outbound, &[0x00, 0x00, 0x04]),
+            ]
+        );
+        assert!(capture
+            .frames
+            .windows(2)
+            .all(|w| w[0].elapsed <= w[1].elapsed));
+
+        let data = std::fs::read(
+            std::fs::read_dir(&directory)
+                .unwrap()
+                .next()
+                .unwrap()
+                .unwrap()
+                .path(),
+        )
+        .unwrap();
+        assert!(Capture::parse(&data[..data.len() - 1]).is_err());
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let path = std::fs::read_dir(&directory)
+                .unwrap()
+                .next()
+                .unwrap()
+                .unwrap()
+                .path();
+            let mode = std::fs::metadata(path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+        let _ = std::fs::remove_dir_all(&directory);
+    }
+
+    #[test]
+    pub fn sessions_are_filtered() {
+        let directory = capture_directory("filter");
+        let capture = PacketCapture::default();
+        capture
+            .set_config(CaptureConfig {
+                enabled: true,
+                directory: directory.clone(),
+                networks: vec!["192.168.0.0/16".parse().unwrap()],
+                accounts: vec!["player".to_string()],
+            })
+            .unwrap();
+
+        // Matching network
+        let recorder = capture
+            .start_session(1, "192.168.1.1:1".parse().unwrap())
+            .unwrap();
+        recorder.record(FrameKind::Inbound, &[1]);
+
+        // Account filter, checked once the account is known
+        let other = capture
+            .start_session(2, "10.0.0.1:1".parse().unwrap())
+            .unwrap();
+        other.record(FrameKind::Inbound, &[2]);
+        other.set_account("OTHER");
+
+        let player = capture
+            .start_session(3, "10.0.0.2:1".parse().unwrap())
+            .unwrap();
+        player.record(FrameKind::Inbound, &[3]);
+        player.set_account("PLAYER");
+        drop((recorder, other, player));
+
+        let mut captures = captures(&directory);
+        captures.sort_by_key(|capture| capture.header.session_id);
+        let sessions = captures
+            .iter()
+            .map(|capture| (capture.header.session_id, capture.frames[0].data[0]))
+            .collect::<Vec<_>>();
+        assert_eq!(sessions, [(1, 1), (3, 3)]);
+
+        capture
+            .set_config(CaptureConfig {
+                enabled: true,
+                directory: directory.clone(),
+                networks: vec!["192.168.0.0/16".parse().unwrap()],
+                accounts: vec![],
+            })
+            .unwrap();
+        assert!(capture
+            .start_session(4, "10.0.0.1:1".parse().unwrap())
+            .is_none());
+        let _ = std::fs::remove_dir_all(&directory);
+    }
+}
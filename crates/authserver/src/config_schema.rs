@@ -0,0 +1,264 @@
+use crate::admin::AdminSocketConfig;
+use crate::capture::CaptureSection;
+use crate::flood_protection::FloodProtectionSection;
+use enturion_shared::config::schema::{ConfigSchema, ValueType};
+use enturion_shared::lifecycle::SignalsSection;
+use enturion_shared::net::proxy_protocol::ProxyProtocolSection;
+use enturion_shared::tick::TickSection;
+
+const CLIENT_BUILDS_DOC: &str = "\
+Client builds accepted in addition to the `build_info` table. A configured build replaces the
+database entry with the same number. The hashes are the hex encoded version check seeds, leave them
+out to skip the check for that platform. Clients with an unknown build are asked to update when
+older than the newest accepted build, otherwise they are rejected.
+Example:
+  - Build: 12340
+    MajorVersion: 3
+    MinorVersion: 3
+    BugfixVersion: 5
+    WindowsHash: CDCBBD5188315E6B4D19449D492DBCFAF156A347
+    MacHash: B706D13FF2F4018839729461E3F8A0E2B5FDC034";
+
+const APPENDERS: &str = "\
+Console:
+    type: console
+    level: debug
+Auth:
+    type: file
+    level: debug
+    filename: Auth.log
+    append: true";
+
+const LOGGERS: &str = "\
+root:
+    level: info
+    appenders:
+        - Console
+        - Auth";
+
+/// The keys of the auth server configuration, read by the Rust and C++ code.
+pub fn schema() -> ConfigSchema {
+    use ValueType::*;
+
+    ConfigSchema::new()
+        .key("LogsDir", String, "''", "Logs directory.")
+        .key("RealmServerPort", Integer, "3724", "TCP port to reach the auth server.")
+        .key(
+            "BindIP",
+            Any,
+            "'0.0.0.0'",
+            "Bind auth server to IP/hostname.\n\
+             Can be a single endpoint or a list of endpoints, each one with an optional port\n\
+             (RealmServerPort is used otherwise). IPv6 addresses with a port must be enclosed in brackets.\n\
+             Example: ['0.0.0.0', '::', '[::1]:3725']",
+        )
+        .key("Tick.MinInterval", Integer, "5", "Time (in milliseconds) between two updates of the auth server.")
+        .section::<TickSection>(
+            "Tick",
+            &[
+                (
+                    "Overrun",
+                    "What to do when an update takes longer than MinInterval:\n\
+                     'skip' drops the missed ticks and keeps the original schedule,\n\
+                     'delay' runs the next tick right away and schedules the following ones from it.",
+                ),
+                ("SlowThreshold", "Updates taking longer than this time (in milliseconds) are logged. (0 = disabled)"),
+            ],
+        )
+        .key("MaxCoreStuckTime", Integer, "60", "Time (in seconds) before the server is forced to crash if it is frozen. (0 = disabled)")
+        .section::<SignalsSection>(
+            "Signals",
+            &[
+                (
+                    "User1",
+                    "Action on SIGUSR1: 'none', 'reload' (the configuration, like SIGHUP), 'shutdown' or 'restart'.\n\
+                     The exit code of a restart is 2, for the restarter scripts.\n\
+                     SIGHUP reloads the configuration and SIGQUIT logs the state of the update loop.",
+                ),
+                ("User2", "Action on SIGUSR2, like User1."),
+                ("StopDelay", "Time (in seconds) before the shutdowns and restarts requested by a signal."),
+            ],
+        )
+        .section::<FloodProtectionSection>(
+            "FloodProtection",
+            &[
+                ("MaxConnectionsPerIp", "Maximum number of concurrent connections from a single IP address. (0 = disabled)"),
+                ("MaxSessions", "Maximum number of concurrent auth sessions. (0 = disabled)"),
+                ("ConnectionRate", "Number of new connections accepted per second. (0 = disabled)"),
+                ("LogonChallengeTimeout", "Time (in seconds) a client has to send its logon challenge before being dropped. (0 = disabled)"),
+                ("IdleTimeout", "Time (in seconds) without any data from the client after which the session is closed. (0 = disabled)"),
+            ],
+        )
+        .key(
+            "FloodProtection.ConnectionBurst",
+            Integer,
+            "~",
+            "Number of new connections that can be accepted at once before the rate limit applies.\n\
+             Twice the ConnectionRate by default.",
+        )
+        .section::<ProxyProtocolSection>(
+            "ProxyProtocol",
+            &[
+                ("Enabled", "Expect a PROXY protocol (v1 or v2) header from trusted proxies, to get the real client address."),
+                (
+                    "TrustedProxies",
+                    "Networks (CIDR notation) of the proxies allowed to send a PROXY header.\n\
+                     Connections from other addresses are handled as direct client connections.",
+                ),
+                ("HeaderTimeout", "Time (in seconds) a trusted proxy has to send the PROXY header."),
+            ],
+        )
+        .section::<AdminSocketConfig>(
+            "AdminSocket",
+            &[
+                (
+                    "Enabled",
+                    "Enable the admin socket, a line based text protocol to inspect the live sessions.\n\
+                     Commands: 'count', 'list', 'kick <id>' and 'quit'. Every reply ends with 'OK' or 'ERR <reason>'.\n\
+                     The socket is not authenticated: only bind it to a loopback address.",
+                ),
+                ("Address", "Address (with port) the admin socket listens on."),
+            ],
+        )
+        .section::<CaptureSection>(
+            "PacketCapture",
+            &[
+                (
+                    "Enabled",
+                    "Record the byte stream of the auth sessions, to debug failed logins.\n\
+                     A capture can be replayed against the database with 'authserver --replay <file>'.\n\
+                     Captures contain the login proofs of the accounts: keep them private.",
+                ),
+                ("Directory", "Directory the captures are written to, one file per session."),
+                (
+                    "Networks",
+                    "Only record the sessions from these networks (CIDR notation) or of these accounts.\n\
+                     All the sessions are recorded when both lists are empty.",
+                ),
+                ("Accounts", "Accounts whose sessions are recorded."),
+            ],
+        )
+        .key("PidFile", String, "~", "Auth server PID file.")
+        .key("UseProcessors", Integer, "~", "Processors mask for Windows and Linux based multi-processor systems.")
+        .key("ProcessPriority", Integer, "0", "Process priority setting for Windows and Linux based systems. 0 - Normal, 1 - High")
+        .key("MaxPingTime", Integer, "30", "Time (in minutes) between database pings.")
+        .key("RealmsStateUpdateDelay", Integer, "20", "Time (in seconds) between realm list updates.")
+        .key("BanExpiryCheckInterval", Integer, "60", "Time (in seconds) between checks for expired bans.")
+        .key("ShutdownDrainTimeout", Integer, "10", "Time (in seconds) given to the live sessions to complete on shutdown before being closed.")
+        .key("WrongPass.MaxCount", Integer, "0", "Number of login attempts with wrong password before the account or IP will be banned. (0 = disabled)")
+        .key("WrongPass.BanTime", Integer, "600", "Time (in seconds) for banning account or IP for invalid login attempts.")
+        .key("WrongPass.BanType", Integer, "0", "Ban type for invalid login attempts. 0 - Ban IP, 1 - Ban Account")
+        .key("WrongPass.Logging", Boolean, "false", "Additionally log attempted wrong password logging.")
+        .key("StrictVersionCheck", Boolean, "false", "Prevent modified clients from connecting.")
+        .key("ClientBuilds", List, "[]", CLIENT_BUILDS_DOC)
+        .key("IPLocationFile", String, "~", "The path to your IP2Location database CSV file, either the IPv4 or the IPv6 one.")
+        .key(
+            "IPLocationReloadInterval",
+            Integer,
+            "60",
+            "Interval in seconds between checks for changes of the IP location file.\n\
+             The file is reloaded when modified.",
+        )
+        .key("AllowLoggingIPAddressesInDatabase", Boolean, "true", "Specifies if IP addresses can be logged to the database.")
+        .key(
+            "LoginDatabaseInfo",
+            String,
+            "\"127.0.0.1;3306;Kitron;Kitron;auth\"",
+            "Database connection settings: \"hostname;port;username;password;database;ssl\".\n\
+             The hostname '.' uses named pipes on Windows (the port being the pipe) or a Unix socket\n\
+             (the port being the path of the socket). The 'ssl' option enables TLS.",
+        )
+        .key(
+            "LoginDatabase.WorkerThreads",
+            Integer,
+            "1",
+            "The amount of worker threads spawned to handle asynchronous (delayed) MySQL\n\
+             statements. Each worker thread is mirrored with its own connection to the\n\
+             MySQL server and their own thread on the MySQL server.",
+        )
+        .key("LoginDatabase.SynchThreads", Integer, "1", "The amount of MySQL connections spawned to handle.")
+        .key("TOTPMasterSecret", String, "~", "The master key used to encrypt TOTP secrets for database storage.")
+        .key(
+            "TOTPOldMasterSecret",
+            String,
+            "~",
+            "The previous master key, while changing it: accounts whose secret is still encrypted\n\
+             with the old key can log in and get their secret encrypted again with the new key.",
+        )
+        .key(
+            "TOTPSkewWindow",
+            Integer,
+            "1",
+            "Number of 30 seconds time steps accepted before and after the current one\n\
+             when checking TOTP tokens, to tolerate client clock skew.",
+        )
+        .key(
+            "Updates.EnableDatabases",
+            Integer,
+            "0",
+            "A mask that describes which databases shall be updated. DATABASE_LOGIN = 1 (auth database)",
+        )
+        .key("Updates.AutoSetup", Boolean, "true", "Auto populate empty databases.")
+        .key(
+            "Updates.Redundancy",
+            Boolean,
+            "true",
+            "Perform data redundancy checks through hashing\n\
+             to detect changes on sql updates and reapply it.",
+        )
+        .key("Updates.ArchivedRedundancy", Boolean, "false", "Check hashes of archived updates (slows down startup).")
+        .key(
+            "Updates.AllowRehash",
+            Boolean,
+            "true",
+            "Inserts the current file hash in the database if it is left empty.\n\
+             Useful if you want to mark a file as applied but you don't know its hash.",
+        )
+        .key(
+            "Updates.CleanDeadRefMaxCount",
+            Integer,
+            "3",
+            "Cleans dead/ orphaned references that occur if an update was removed or renamed and edited in one step,\n\
+             if the count of the missing updates is below or equal this value.\n\
+             3 - (Enabled), 0 - (Disabled), -1 - (Enabled - unlimited)",
+        )
+        .key(
+            "Appender",
+            Any,
+            APPENDERS,
+            "Log appenders, by name.\n\
+             type: 'console' or 'file', level: one of 'trace', 'debug', 'info', 'warn' or 'error'.\n\
+             Console appenders can map every level to a color with color_map: { error: 9, warn: 11, info: 12, debug: 5, trace: 2 }\n\
+             File appenders write to filename, in 'append' mode or reset on server starts.",
+        )
+        .key(
+            "Logger",
+            Any,
+            LOGGERS,
+            "Loggers, by target. The root logger is the logger used if no other logger can be used.",
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config_schema::schema;
+    use std::fs;
+
+    #[test]
+    pub fn config_file_is_known() {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../../src/server/authserver/authserver.yaml"
+        );
+        let config = enturion_shared::config::Config::load_config(path).unwrap();
+        assert_eq!(schema().validate(&config), []);
+
+        // The described configuration is a valid one
+        let described =
+            std::env::temp_dir().join(format!("enturion-described-{}.yaml", std::process::id()));
+        fs::write(&described, schema().describe()).unwrap();
+        let config = enturion_shared::config::Config::load_config(described.to_string_lossy());
+        fs::remove_file(&described).unwrap();
+        assert_eq!(schema().validate(&config.unwrap()), []);
+    }
+}
@@ -0,0 +1,459 @@
+use anyhow::Result;
+use enturion_shared::config::Config;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::net::IpAddr;
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// The `FloodProtection` config section, the durations being in seconds.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FloodProtectionSection {
+    pub max_connections_per_ip: u32,
+    pub max_sessions: u32,
+    pub connection_rate: u32,
+    /// Twice the connection rate if `None`.
+    pub connection_burst: Option<u32>,
+    pub logon_challenge_timeout: u64,
+    pub idle_timeout: u64,
+}
+
+impl Default for FloodProtectionSection {
+    fn default() -> Self {
+        Self {
+            max_connections_per_ip: 10,
+            max_sessions: 5000,
+            connection_rate: 100,
+            connection_burst: None,
+            logon_challenge_timeout: 10,
+            idle_timeout: 60,
+        }
+    }
+}
+
+/// Flood protection settings, read from the `FloodProtection` config section.
+/// A value of zero disables the corresponding limit.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct FloodProtectionConfig {
+    pub max_connections_per_ip: u32,
+    pub max_sessions: u32,
+    pub connection_rate: u32,
+    pub connection_burst: u32,
+    pub logon_challenge_timeout: Option<Duration>,
+    pub idle_timeout: Option<Duration>,
+}
+
+impl FloodProtectionConfig {
+    pub fn from_config(config: &Config) -> Result<Self> {
+        let section: FloodProtectionSection = config.section("FloodProtection")?;
+        let timeout = section.logon_challenge_timeout;
+        let idle_timeout = section.idle_timeout;
+
+        Ok(Self {
+            max_connections_per_ip: section.max_connections_per_ip,
+            max_sessions: section.max_sessions,
+            connection_rate: section.connection_rate,
+            connection_burst: section
+                .connection_burst
+                .unwrap_or(section.connection_rate.saturating_mul(2)),
+            logon_challenge_timeout: (timeout > 0).then(|| Duration::from_secs(timeout)),
+            idle_timeout: (idle_timeout > 0).then(|| Duration::from_secs(idle_timeout)),
+        })
+    }
+}
+
+/// The reason why an incoming connection has been refused.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RefuseReason {
+    RateLimited,
+    TooManySessions,
+    TooManyConnectionsFromIp,
+}
+
+impl Display for RefuseReason {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RefuseReason::RateLimited => write!(f, "connection rate limit exceeded"),
+            RefuseReason::TooManySessions => write!(f, "session limit reached"),
+            RefuseReason::TooManyConnectionsFromIp => write!(f, "too many connections from ip"),
+        }
+    }
+}
+
+/// Token bucket used to limit the rate of new connections.
+struct TokenBucket {
+    rate: f64,
+    capacity: f64,
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(rate: u32, capacity: u32, now: Instant) -> Self {
+        let capacity = capacity.max(1) as f64;
+        Self {
+            rate: rate as f64,
+            capacity,
+            tokens: capacity,
+            last_refill: now,
+        }
+    }
+
+    fn try_take(&mut self, now: Instant) -> bool {
+        let elapsed = now.saturating_duration_since(self.last_refill);
+        self.tokens = (self.tokens + elapsed.as_secs_f64() * self.rate).min(self.capacity);
+        self.last_refill = now;
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Keeps track of the live connections and decides whether a new one can be accepted.
+pub struct ConnectionLimiter {
+    config: FloodProtectionConfig,
+    per_ip: Mutex<HashMap<IpAddr, u32>>,
+    sessions: AtomicU32,
+    bucket: Option<Mutex<TokenBucket>>,
+    refused: AtomicU64,
+}
+
+impl ConnectionLimiter {
+    pub fn new(config: FloodProtectionConfig) -> Arc<Self> {
+        Self::new_at(config, Instant::now())
+    }
+
+    fn new_at(config: FloodProtectionConfig, now: Instant) -> Arc<Self> {
+        let bucket = (config.connection_rate > 0).then(|| {
+            Mutex::new(TokenBucket::new(
+                config.connection_rate,
+                config.connection_burst,
+                now,
+            ))
+        });
+
+        Arc::new(Self {
+            config,
+            per_ip: Mutex::default(),
+            sessions: AtomicU32::new(0),
+            bucket,
+            refused: AtomicU64::new(0),
+        })
+    }
+
+    pub fn config(&self) -> &FloodProtectionConfig {
+        &self.config
+    }
+
+    /// Number of live sessions.
+    pub fn sessions(&self) -> u32 {
+        self.sessions.load(Ordering::Relaxed)
+    }
+
+    /// Total number of refused connections since start.
+    pub fn refused(&self) -> u64 {
+        self.refused.load(Ordering::Relaxed)
+    }
+
+    /// Tries to reserve a slot for a new connection from the given address.
+    /// The slot is released when the returned guard is dropped.
+    pub fn try_acquire(self: &Arc<Self>, ip: IpAddr) -> Result<ConnectionGuard, RefuseReason> {
+        self.try_acquire_at(Some(ip), Instant::now())
+    }
+
+    /// Tries to reserve a slot for a connection from a trusted proxy, whose client address is
+    /// not known before the PROXY header: the per-IP limit is applied by
+    /// [`ConnectionGuard::bind_ip`] once it is read.
+    pub fn try_acquire_proxied(self: &Arc<Self>) -> Result<ConnectionGuard, RefuseReason> {
+        self.try_acquire_at(None, Instant::now())
+    }
+
+    fn try_acquire_at(
+        self: &Arc<Self>,
+        ip: Option<IpAddr>,
+        now: Instant,
+    ) -> Result<ConnectionGuard, RefuseReason> {
+        self.check(ip, now).inspect_err(|_| {
+            self.refused.fetch_add(1, Ordering::Relaxed);
+        })
+    }
+
+    /// Checks the session caps first, so that the refused connections do not drain the rate
+    /// limit bucket, then takes the token.
+    fn check(
+        self: &Arc<Self>,
+        ip: Option<IpAddr>,
+        now: Instant,
+    ) -> Result<ConnectionGuard, RefuseReason> {
+        let max_sessions = self.config.max_sessions;
+        let reserved =
+            self.sessions
+                .fetch_update(Ordering::AcqRel, Ordering::Acquire, |sessions| {
+                    (max_sessions == 0 || sessions < max_sessions).then_some(sessions + 1)
+                });
+        if reserved.is_err() {
+            return Err(RefuseReason::TooManySessions);
+        }
+
+        let mut per_ip = self.per_ip.lock().unwrap();
+        if ip.is_some_and(|ip| self.is_ip_full(&per_ip, ip)) {
+            self.sessions.fetch_sub(1, Ordering::AcqRel);
+            return Err(RefuseReason::TooManyConnectionsFromIp);
+        }
+
+        if let Some(bucket) = &self.bucket {
+            if !bucket.lock().unwrap().try_take(now) {
+                self.sessions.fetch_sub(1, Ordering::AcqRel);
+                return Err(RefuseReason::RateLimited);
+            }
+        }
+
+        if let Some(ip) = ip {
+            *per_ip.entry(ip).or_default() += 1;
+        }
+        Ok(ConnectionGuard {
+            limiter: self.clone(),
+            ip,
+        })
+    }
+
+    fn is_ip_full(&self, per_ip: &HashMap<IpAddr, u32>, ip: IpAddr) -> bool {
+        let max_connections = self.config.max_connections_per_ip;
+        max_connections > 0 && per_ip.get(&ip).copied().unwrap_or(0) >= max_connections
+    }
+
+    fn release(&self, ip: Option<IpAddr>) {
+        if let Some(ip) = ip {
+            let mut per_ip = self.per_ip.lock().unwrap();
+            if let Some(connections) = per_ip.get_mut(&ip) {
+                *connections -= 1;
+                if *connections == 0 {
+                    per_ip.remove(&ip);
+                }
+            }
+        }
+
+        self.sessions.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+/// A reserved connection slot. Dropping it frees the slot.
+pub struct ConnectionGuard {
+    limiter: Arc<ConnectionLimiter>,
+    /// `None` until the client address of a proxied connection is known.
+    ip: Option<IpAddr>,
+}
+
+impl ConnectionGuard {
+    /// Counts the slot of a proxied connection against the per-IP limit of its client address.
+    /// The refused slot is still reserved until the guard is dropped.
+    pub fn bind_ip(&mut self, ip: IpAddr) -> Result<(), RefuseReason> {
+        if self.ip.is_some() {
+            return Ok(());
+        }
+
+        let limiter = &self.limiter;
+        let mut per_ip = limiter.per_ip.lock().unwrap();
+        if limiter.is_ip_full(&per_ip, ip) {
+            limiter.refused.fetch_add(1, Ordering::Relaxed);
+            return Err(RefuseReason::TooManyConnectionsFromIp);
+        }
+
+        *per_ip.entry(ip).or_default() += 1;
+        self.ip = Some(ip);
+        Ok(())
+    }
+}
+
+impl Drop for ConnectionGuard {
+    fn drop(&mut self) {
+        self.limiter.release(self.ip);
+    }
+}
+
+/// Tracks the bytes of the first packet sent by the client, to know when a full
+/// logon (or reconnect) challenge has been received.
+///
+/// The challenge starts with a 4 bytes header (command, error, size) followed by `size` bytes.
+#[derive(Default)]
+pub struct LogonChallengeTracker {
+    header: [u8; 4],
+    received: usize,
+}
+
+impl LogonChallengeTracker {
+    const HEADER_SIZE: usize = 4;
+
+    /// Feeds the received bytes, returns true when the whole challenge has been received.
+    pub fn feed(&mut self, data: &[u8]) -> bool {
+        if self.received < Self::HEADER_SIZE {
+            let missing = (Self::HEADER_SIZE - self.received).min(data.len());
+            self.header[self.received..self.received + missing].copy_from_slice(&data[..missing]);
+        }
+
+        self.received += data.len();
+        self.is_complete()
+    }
+
+    pub fn is_complete(&self) -> bool {
+        if self.received < Self::HEADER_SIZE {
+            return false;
+        }
+
+        let size = u16::from_le_bytes([self.header[2], self.header[3]]) as usize;
+        self.received >= Self::HEADER_SIZE + size
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::flood_protection::{
+        ConnectionLimiter, FloodProtectionConfig, LogonChallengeTracker, RefuseReason,
+    };
+    use std::net::{IpAddr, Ipv4Addr};
+    use std::time::{Duration, Instant};
+
+    const FIRST: IpAddr = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1));
+    const SECOND: IpAddr = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 2));
+
+    #[test]
+    pub fn per_ip_limit() {
+        let limiter = ConnectionLimiter::new(FloodProtectionConfig {
+            max_connections_per_ip: 2,
+            ..Default::default()
+        });
+
+        let first = limiter.try_acquire(FIRST).unwrap();
+        let _second = limiter.try_acquire(FIRST).unwrap();
+        assert_eq!(
+            limiter.try_acquire(FIRST).err(),
+            Some(RefuseReason::TooManyConnectionsFromIp)
+        );
+        assert!(limiter.try_acquire(SECOND).is_ok());
+
+        drop(first);
+        assert!(limiter.try_acquire(FIRST).is_ok());
+        assert_eq!(limiter.refused(), 1);
+    }
+
+    #[test]
+    pub fn session_limit() {
+        let limiter = ConnectionLimiter::new(FloodProtectionConfig {
+            max_sessions: 1,
+            ..Default::default()
+        });
+
+        let first = limiter.try_acquire(FIRST).unwrap();
+        assert_eq!(
+            limiter.try_acquire(SECOND).err(),
+            Some(RefuseReason::TooManySessions)
+        );
+        assert_eq!(limiter.sessions(), 1);
+
+        drop(first);
+        assert_eq!(limiter.sessions(), 0);
+        assert!(limiter.try_acquire(SECOND).is_ok());
+    }
+
+    #[test]
+    pub fn rate_limit() {
+        let now = Instant::now();
+        let limiter = ConnectionLimiter::new_at(
+            FloodProtectionConfig {
+                connection_rate: 2,
+                connection_burst: 2,
+                ..Default::default()
+            },
+            now,
+        );
+
+        assert!(limiter.try_acquire_at(Some(FIRST), now).is_ok());
+        assert!(limiter.try_acquire_at(Some(FIRST), now).is_ok());
+        assert_eq!(
+            limiter.try_acquire_at(Some(FIRST), now).err(),
+            Some(RefuseReason::RateLimited)
+        );
+
+        let later = now + Duration::from_millis(500);
+        assert!(limiter.try_acquire_at(Some(FIRST), later).is_ok());
+        assert!(limiter.try_acquire_at(Some(FIRST), later).is_err());
+    }
+
+    #[test]
+    pub fn refused_connections_keep_their_token() {
+        let now = Instant::now();
+        let limiter = ConnectionLimiter::new_at(
+            FloodProtectionConfig {
+                max_connections_per_ip: 1,
+                connection_rate: 1,
+                connection_burst: 2,
+                ..Default::default()
+            },
+            now,
+        );
+
+        let _first = limiter.try_acquire_at(Some(FIRST), now).unwrap();
+        for _ in 0..5 {
+            assert_eq!(
+                limiter.try_acquire_at(Some(FIRST), now).err(),
+                Some(RefuseReason::TooManyConnectionsFromIp)
+            );
+        }
+        assert!(limiter.try_acquire_at(Some(SECOND), now).is_ok());
+    }
+
+    #[test]
+    pub fn proxied_connections_are_limited_by_client_ip() {
+        let limiter = ConnectionLimiter::new(FloodProtectionConfig {
+            max_connections_per_ip: 1,
+            max_sessions: 3,
+            ..Default::default()
+        });
+
+        // The connections of a proxy share its address until their PROXY header is read
+        let mut first = limiter.try_acquire_proxied().unwrap();
+        let mut second = limiter.try_acquire_proxied().unwrap();
+        let _third = limiter.try_acquire_proxied().unwrap();
+        assert_eq!(
+            limiter.try_acquire_proxied().err(),
+            Some(RefuseReason::TooManySessions)
+        );
+
+        assert!(first.bind_ip(FIRST).is_ok());
+        assert_eq!(
+            second.bind_ip(FIRST).err(),
+            Some(RefuseReason::TooManyConnectionsFromIp)
+        );
+        assert_eq!(
+            limiter.try_acquire(FIRST).err(),
+            Some(RefuseReason::TooManySessions)
+        );
+        drop(second);
+        assert_eq!(
+            limiter.try_acquire(FIRST).err(),
+            Some(RefuseReason::TooManyConnectionsFromIp)
+        );
+
+        drop(first);
+        assert!(limiter.try_acquire(FIRST).is_ok());
+        assert_eq!(limiter.refused(), 4);
+    }
+
+    #[test]
+    pub fn logon_challenge_tracker() {
+        let mut tracker = LogonChallengeTracker::default();
+        assert!(!tracker.feed(&[0x00, 0x08]));
+        assert!(!tracker.feed(&[0x03, 0x00, 0x01]));
+        assert!(tracker.feed(&[0x02, 0x03]));
+
+        let mut tracker = LogonChallengeTracker::default();
+        assert!(tracker.feed(&[0x02, 0x00, 0x01, 0x00, 0xFF]));
+    }
+}
@@ -0,0 +1,241 @@
+use crate::auth_session::AuthSessionHandle;
+use crate::packet::{decode_packet, ClientPacket, RealmListRequest};
+use crate::session_registry::SessionState;
+use anyhow::Result;
+use log::debug;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+static HANDLERS: OnceLock<Handlers<AuthSessionHandle>> = OnceLock::new();
+
+/// Result of `AuthSession_DispatchPacket`.
+#[repr(C)]
+#[allow(clippy::enum_variant_names)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PacketDispatchResult {
+    /// The packet was handled, its size is returned.
+    PacketHandled,
+    /// More data is needed to decode the packet.
+    PacketIncomplete,
+    /// No handler for this command, the packet is left to the C++ handlers.
+    PacketUnhandled,
+    /// The packet is invalid, unexpected in the current state or its handler failed.
+    /// The session has to be disconnected.
+    PacketRejected,
+}
+
+type Handler<S> = Box<dyn Fn(&S, &[u8]) -> (PacketDispatchResult, usize) + Send + Sync>;
+
+struct AuthHandler<S> {
+    status: SessionState,
+    handler: Handler<S>,
+}
+
+/// The client packet handlers, by command.
+pub struct Handlers<S> {
+    handlers: HashMap<u8, AuthHandler<S>>,
+}
+
+impl<S> Default for Handlers<S> {
+    fn default() -> Self {
+        Self {
+            handlers: HashMap::new(),
+        }
+    }
+}
+
+impl<S: 'static> Handlers<S> {
+    /// Registers the handler of a client packet, only accepted in the given session state.
+    pub fn register<P: ClientPacket + 'static>(
+        &mut self,
+        status: SessionState,
+        handler: fn(&S, P) -> Result<()>,
+    ) {
+        let handler = move |session: &S, data: &[u8]| match decode_packet::<P>(data) {
+            Ok(Some((packet, size))) => match handler(session, packet) {
+                Ok(()) => (PacketDispatchResult::PacketHandled, size),
+                Err(e) => {
+                    debug!(target: "session", "Failed to handle {:?}: {}", P::COMMAND, e);
+                    (PacketDispatchResult::PacketRejected, 0)
+                }
+            },
+            Ok(None) => (PacketDispatchResult::PacketIncomplete, 0),
+            Err(e) => {
+                debug!(target: "session", "Invalid {:?} packet: {}", P::COMMAND, e);
+                (PacketDispatchResult::PacketRejected, 0)
+            }
+        };
+
+        self.handlers.insert(
+            P::COMMAND as u8,
+            AuthHandler {
+                status,
+                handler: Box::new(handler),
+            },
+        );
+    }
+
+    /// Decodes the packet at the start of the data and calls its handler.
+    /// Returns the dispatch result and the size of the handled packet.
+    pub fn dispatch(
+        &self,
+        session: &S,
+        status: SessionState,
+        data: &[u8],
+    ) -> (PacketDispatchResult, usize) {
+        let Some(handler) = data.first().and_then(|command| self.handlers.get(command)) else {
+            return (PacketDispatchResult::PacketUnhandled, 0);
+        };
+
+        if handler.status != status {
+            return (PacketDispatchResult::PacketRejected, 0);
+        }
+
+        (handler.handler)(session, data)
+    }
+}
+
+/// Gets the handlers of the auth sessions.
+pub fn handlers() -> &'static Handlers<AuthSessionHandle> {
+    HANDLERS.get_or_init(|| {
+        let mut handlers = Handlers::default();
+        handlers.register::<RealmListRequest>(SessionState::Authed, handle_realm_list);
+        handlers
+    })
+}
+
+fn handle_realm_list(session: &AuthSessionHandle, _packet: RealmListRequest) -> Result<()> {
+    debug!(target: "server::authserver", "Entering _HandleRealmList");
+    session.request_realm_list();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::handlers::{Handlers, PacketDispatchResult};
+    use crate::packet::{
+        decode_packet, AuthCommand, AuthResult, LogonChallengeRequest, RealmListRequest,
+        BINCODE_CONFIGURATION,
+    };
+    use crate::session_registry::SessionState;
+    use anyhow::Error;
+    use std::cell::Cell;
+
+    #[derive(Default)]
+    struct TestSession {
+        realm_list_requests: Cell<u32>,
+    }
+
+    fn handlers() -> Handlers<TestSession> {
+        let mut handlers = Handlers::default();
+        handlers.register::<RealmListRequest>(
+            SessionState::Authed,
+            |session: &TestSession, packet| {
+                assert_eq!(packet.command, AuthCommand::RealmList);
+                match packet.padding {
+                    0 => {
+                        session
+                            .realm_list_requests
+                            .set(session.realm_list_requests.get() + 1);
+                        Ok(())
+                    }
+                    _ => Err(Error::msg("unexpected padding")),
+                }
+            },
+        );
+
+        handlers
+    }
+
+    #[test]
+    pub fn packets_are_dispatched_by_command() {
+        let handlers = handlers();
+        let session = TestSession::default();
+        let authed = SessionState::Authed;
+
+        assert_eq!(
+            handlers.dispatch(&session, authed, &[0x10, 0, 0]),
+            (PacketDispatchResult::PacketIncomplete, 0)
+        );
+        assert_eq!(
+            handlers.dispatch(&session, authed, &[0x10, 0, 0, 0, 0, 0x10]),
+            (PacketDispatchResult::PacketHandled, 5)
+        );
+        assert_eq!(session.realm_list_requests.get(), 1);
+
+        assert_eq!(
+            handlers.dispatch(&session, authed, &[0x10, 1, 0, 0, 0]),
+            (PacketDispatchResult::PacketRejected, 0)
+        );
+        assert_eq!(
+            handlers.dispatch(&session, SessionState::Challenge, &[0x10, 0, 0, 0, 0]),
+            (PacketDispatchResult::PacketRejected, 0)
+        );
+        assert_eq!(
+            handlers.dispatch(&session, authed, &[0x00, 0x08]),
+            (PacketDispatchResult::PacketUnhandled, 0)
+        );
+        assert_eq!(
+            handlers.dispatch(&session, authed, &[]),
+            (PacketDispatchResult::PacketUnhandled, 0)
+        );
+        assert_eq!(session.realm_list_requests.get(), 1);
+    }
+
+    #[test]
+    pub fn enums_are_checked() {
+        assert_eq!(AuthCommand::try_from(0x10), Ok(AuthCommand::RealmList));
+        assert_eq!(AuthCommand::try_from(0x11), Err(0x11));
+        assert_eq!(
+            AuthResult::try_from(0x19),
+            Ok(AuthResult::WowFailUnlockableLock)
+        );
+
+        let decoded = bincode::decode_from_slice::<AuthResult, _>(&[0x21], BINCODE_CONFIGURATION);
+        assert!(decoded.is_err());
+    }
+
+    #[test]
+    pub fn variable_length_fields() {
+        let mut data = vec![0x00, 0x08, 0x24, 0x00];
+        data.extend_from_slice(b"WoW\0\x03\x03\x05\x34\x30");
+        data.extend_from_slice(b"68x\0niW\0SUne");
+        data.extend_from_slice(&[0x3C, 0, 0, 0, 127, 0, 0, 1]);
+        data.extend_from_slice(b"\x06PLAYER");
+
+        let (mut request, size) = decode_packet::<LogonChallengeRequest>(&data)
+            .unwrap()
+            .unwrap();
+        assert_eq!(size, data.len());
+        assert_eq!(request.size, 0x24);
+        assert_eq!(request.game_name, "WoW");
+        assert_eq!(request.build, 12340);
+        assert_eq!(request.os, "niW");
+        assert_eq!(request.country, "SUne");
+        assert_eq!(request.account_name, "PLAYER");
+        assert_eq!(
+            decode_packet::<LogonChallengeRequest>(&data[..data.len() - 1]).unwrap(),
+            None
+        );
+
+        // The size field has to match the length of the packet
+        let mut wrong_size = data.clone();
+        wrong_size[2] += 1;
+        wrong_size.push(0);
+        assert!(decode_packet::<LogonChallengeRequest>(&wrong_size).is_err());
+        wrong_size[2] -= 2;
+        assert!(decode_packet::<LogonChallengeRequest>(&wrong_size).is_err());
+
+        // Sizes are computed from the encoded fields
+        request.account_name = "ADMINISTRATOR".to_string();
+        request.size = 0;
+        request.compute_sizes().unwrap();
+        assert_eq!(request.size, 0x24 + 7);
+        let encoded = bincode::encode_to_vec(&request, BINCODE_CONFIGURATION).unwrap();
+        assert_eq!(encoded.len(), 4 + 0x24 + 7);
+        assert_eq!(&encoded[2..4], &[0x24 + 7, 0]);
+
+        request.game_name = "World".to_string();
+        assert!(bincode::encode_to_vec(&request, BINCODE_CONFIGURATION).is_err());
+    }
+}
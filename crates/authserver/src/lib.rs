@@ -1,66 +1,325 @@
 extern crate self as enturion_authserver;
 
+mod admin;
 mod auth_session;
-pub(crate) mod packet;
+mod build_info;
+mod capture;
+mod config_schema;
+mod flood_protection;
+mod handlers;
+pub mod packet;
+mod realm_list;
+mod replay;
+mod session_registry;
+mod totp;
 
-use crate::auth_session::AuthSession;
+use crate::admin::AdminSocketConfig;
+use crate::auth_session::{AuthSession, SessionTimeouts};
+use crate::build_info::BuildRegistry;
+use crate::capture::PacketCapture;
+use crate::flood_protection::{ConnectionLimiter, FloodProtectionConfig};
+use crate::session_registry::SessionRegistry;
+use crate::totp::Totp;
 use anyhow::Result;
 use enturion_shared::config::Config;
-use enturion_shared::signals::{Signal, Signals};
+use enturion_shared::lifecycle::{ExitCode, Lifecycle, SignalAction, SignalActions};
+use enturion_shared::net::ip_location::IpLocationStore;
+use enturion_shared::net::listener::{resolve_endpoints, Accepted, Listeners};
+use enturion_shared::net::proxy_protocol::ProxyProtocolConfig;
+use enturion_shared::signals::Signals;
+use enturion_shared::tick::{TickConfig, TickScheduler};
+use enturion_shared::timer::{create_timer, stop_timers};
+use enturion_shared::watchdog::{Watchdog, WatchdogConfig};
 use enturion_shared::RUNTIME;
-use log::trace;
+use log::{debug, error, info, trace, warn};
+use std::convert::Infallible;
+use std::ffi::{c_char, CStr};
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
 use std::time::Duration;
-use tokio::net::TcpListener;
-use tokio::runtime::Runtime;
-use tokio::time;
+use tokio::runtime::{Handle, Runtime};
 
-type TickCallback = unsafe extern "C" fn();
+/// Runs an update of the C++ server, with the time elapsed (in milliseconds) since the previous one.
+type TickCallback = unsafe extern "C" fn(diff: u32);
 extern "C" {
     pub fn AbortHandler();
     pub fn ConfigGetInstance() -> &'static Config;
 }
 
-fn get_bind_addr() -> Result<(String, u16)> {
+/// Gets the addresses the auth server listens on.
+/// `BindIp` can either be a single endpoint or a list of endpoints,
+/// `RealmServerPort` is used for the endpoints without an explicit port.
+async fn get_listen_addresses() -> Result<Vec<SocketAddr>> {
+    let (endpoints, port) = {
+        let config = unsafe { ConfigGetInstance() };
+        let endpoints = config.get::<Vec<String>>("BindIp", None).or_else(|_| {
+            config
+                .get("BindIp", Some("0.0.0.0".to_string()))
+                .map(|endpoint| vec![endpoint])
+        })?;
+
+        (endpoints, config.get("RealmServerPort", Some(3724_u16))?)
+    };
+
+    resolve_endpoints(&endpoints, port).await
+}
+
+/// Periodically logs the amount of connections refused by the flood protection.
+fn report_refused_connections(limiter: Arc<ConnectionLimiter>) {
+    let last_reported = AtomicU64::new(0);
+    create_timer(Duration::from_secs(60), move || {
+        let refused = limiter.refused();
+        let previous = last_reported.swap(refused, Ordering::Relaxed);
+        if refused > previous {
+            warn!(
+                target: "server::authserver",
+                "Refused {} connections in the last minute ({} total, {} live sessions)",
+                refused - previous,
+                refused,
+                limiter.sessions()
+            );
+        }
+
+        async { Ok::<(), Infallible>(()) }
+    });
+}
+
+/// Resolves the real client address (if behind a trusted proxy) and runs the auth session.
+async fn handle_connection(
+    accepted: Accepted,
+    limiter: Arc<ConnectionLimiter>,
+    proxy_protocol: Arc<ProxyProtocolConfig>,
+    registry: Arc<SessionRegistry>,
+) {
+    let Accepted {
+        stream: mut tcp_stream,
+        peer_address,
+        local_address,
+    } = accepted;
+
+    // The slot is taken before waiting for the PROXY header, so that the pending handshakes are
+    // bounded by the session cap too
+    let trusted = proxy_protocol.is_trusted(peer_address.ip());
+    let acquired = match trusted {
+        true => limiter.try_acquire_proxied(),
+        false => limiter.try_acquire(peer_address.ip()),
+    };
+    let mut connection_guard = match acquired {
+        Ok(guard) => guard,
+        Err(reason) => {
+            debug!(target: "session", "Refusing connection from {}: {}", peer_address, reason);
+            return;
+        }
+    };
+
+    let (address, proxy_address) = match proxy_protocol.resolve(&mut tcp_stream, peer_address).await
+    {
+        Ok(addresses) => addresses,
+        Err(e) => {
+            debug!(target: "session", "Invalid PROXY header from {}: {:#}", peer_address, e);
+            return;
+        }
+    };
+
+    if let Err(reason) = connection_guard.bind_ip(address.ip()) {
+        debug!(target: "session", "Refusing connection from {}: {}", address, reason);
+        return;
+    }
+
+    match proxy_address {
+        Some(proxy) => {
+            trace!(target: "session", "Accepting incoming connection from {} on {} (via {})", address, local_address, proxy)
+        }
+        None => {
+            trace!(target: "session", "Accepting incoming connection from {} on {}", address, local_address)
+        }
+    }
+
+    let ip_location = IpLocationStore::global().location(address.ip()).await;
+    let timeouts = SessionTimeouts {
+        logon_challenge: limiter.config().logon_challenge_timeout,
+        idle: limiter.config().idle_timeout,
+    };
+    let mut session = AuthSession::new(
+        tcp_stream,
+        address,
+        proxy_address,
+        ip_location,
+        connection_guard,
+        registry.register(address),
+        timeouts,
+    );
+    let _ = session.start().await;
+}
+
+/// Stops all the live sessions, giving them up to `drain_timeout` to complete.
+async fn drain_sessions(registry: &SessionRegistry, drain_timeout: Duration) {
+    if registry.is_empty() {
+        return;
+    }
+
+    info!(
+        target: "server::authserver",
+        "Waiting up to {}s for {} sessions to complete...",
+        drain_timeout.as_secs(),
+        registry.len()
+    );
+
+    registry.drain_all();
+    if registry.wait_until_empty(drain_timeout).await {
+        return;
+    }
+
+    warn!(
+        target: "server::authserver",
+        "{} sessions did not complete in time, closing them",
+        registry.len()
+    );
+
+    registry.close_all();
+    if !registry.wait_until_empty(Duration::from_secs(1)).await {
+        error!(
+            target: "server::authserver",
+            "{} sessions could not be closed",
+            registry.len()
+        );
+    }
+}
+
+/// Loads the settings and data the sessions depend on, live or replayed.
+fn start_services() -> Result<()> {
     let config = unsafe { ConfigGetInstance() };
-    let addr = config.get("BindIp", Some("0.0.0.0"))?;
-    let port = config.get("RealmServerPort", Some(3724_u16))?;
+    let realms_update_delay =
+        Duration::from_secs(config.get("RealmsStateUpdateDelay", Some(20_u64))?);
+    let ip_location_file = config
+        .get::<Option<String>>("IPLocationFile", Some(None))?
+        .filter(|path| !path.is_empty());
+    let ip_location_reload_interval =
+        Duration::from_secs(config.get("IPLocationReloadInterval", Some(60_u64))?);
+    BuildRegistry::global().configure(config)?;
+    config.subscribe_all(&["ClientBuilds", "StrictVersionCheck"], |config, _| {
+        if let Err(e) = BuildRegistry::global().configure(config) {
+            error!(target: "server::authserver", "Cannot apply the client build settings: {:#}", e);
+        }
+    });
+    Totp::global().configure(config)?;
+    config.subscribe("TOTP*", |config, _| {
+        if let Err(e) = Totp::global().configure(config) {
+            error!(target: "server::authserver", "Cannot apply the TOTP settings: {:#}", e);
+        }
+    });
+    realm_list::start_refresh(realms_update_delay);
+    IpLocationStore::global().watch(
+        ip_location_file.map(PathBuf::from),
+        ip_location_reload_interval,
+    );
 
-    Ok((addr.to_string(), port))
+    Ok(())
 }
 
-async fn async_main(tick_callback: TickCallback) -> Result<()> {
-    let listener = TcpListener::bind(get_bind_addr()?).await?;
+async fn async_main(tick_callback: TickCallback) -> Result<ExitCode> {
+    let schema = config_schema::schema();
+    schema.report(unsafe { ConfigGetInstance() }, "server::loading");
+    let flood_protection = FloodProtectionConfig::from_config(unsafe { ConfigGetInstance() })?;
+    let limiter = ConnectionLimiter::new(flood_protection);
+    let proxy_protocol = Arc::new(ProxyProtocolConfig::from_config(unsafe {
+        ConfigGetInstance()
+    })?);
+    let drain_timeout = Duration::from_secs(
+        unsafe { ConfigGetInstance() }.get("ShutdownDrainTimeout", Some(10_u64))?,
+    );
+    let admin_socket = AdminSocketConfig::from_config(unsafe { ConfigGetInstance() })?;
+    PacketCapture::global().configure(unsafe { ConfigGetInstance() })?;
+    unsafe { ConfigGetInstance() }.subscribe("PacketCapture.*", |config, _| {
+        if let Err(e) = PacketCapture::global().configure(config) {
+            error!(target: "server::authserver", "Cannot apply the packet capture settings: {:#}", e);
+        }
+    });
+    start_services()?;
+    let registry = SessionRegistry::global().clone();
+    let mut listeners = Listeners::bind(&get_listen_addresses().await?, "server::authserver")?;
+    let admin_handle = match admin_socket.enabled {
+        true => Some(admin::start(&admin_socket, registry.clone())?),
+        false => None,
+    };
+    report_refused_connections(limiter.clone());
 
-    let mut interval = time::interval(Duration::from_millis(5));
+    let tick_config =
+        TickConfig::from_config(unsafe { ConfigGetInstance() }, "Tick.MinInterval", 5)?;
+    let mut scheduler = TickScheduler::new(tick_config, "server::authserver");
+    let watchdog = Watchdog::start(
+        WatchdogConfig::from_config(unsafe { ConfigGetInstance() })?,
+        scheduler.heartbeat(),
+        Handle::current(),
+        "server::authserver",
+        || unsafe { AbortHandler() },
+    )?;
+    let signal_actions = SignalActions::from_config(unsafe { ConfigGetInstance() })?;
     let mut signals = Signals::default();
+    let lifecycle = Lifecycle::global();
+    lifecycle.on_announcement(|announcement| {
+        info!(target: "server::authserver", "{}", announcement);
+    });
 
-    loop {
+    let exit_code = loop {
         tokio::select! {
-            Ok((tcp_stream, socket_addr)) = listener.accept() => {
-                trace!(target: "session", "Accepting incoming connection from {}", socket_addr);
-                let mut session = AuthSession::new(tcp_stream, socket_addr);
-                let _ = tokio::spawn(async move {
-                    let _ = session.start().await;
-                });
+            Some(accepted) = listeners.accept() => {
+                tokio::spawn(handle_connection(
+                    accepted,
+                    limiter.clone(),
+                    proxy_protocol.clone(),
+                    registry.clone(),
+                ));
             },
             signal = signals.as_mut() => {
-                match signal {
-                    Some(Signal::Abort) => {
+                match signal.map(|signal| signal_actions.action(signal)) {
+                    Some(SignalAction::Abort) => {
                         unsafe { AbortHandler(); }
                     },
-                    _ => {
-                        break;
+                    Some(SignalAction::Reload) => {
+                        match unsafe { ConfigGetInstance() }.reload() {
+                            Ok(_) => {
+                                schema.report(unsafe { ConfigGetInstance() }, "server::loading");
+                            },
+                            Err(e) => {
+                                error!(target: "server::authserver", "Cannot reload the configuration: {:#}", e);
+                            },
+                        }
                     },
+                    Some(SignalAction::Dump) => scheduler.log_diagnostics(),
+                    Some(SignalAction::Shutdown(delay)) => lifecycle.shutdown(delay, ""),
+                    Some(SignalAction::Restart(delay)) => lifecycle.restart(delay),
+                    Some(SignalAction::Ignore) => {},
+                    None => lifecycle.stop_now(ExitCode::Shutdown),
                 }
             },
-            _ = interval.tick() => {
-                unsafe { tick_callback(); }
+            exit_code = lifecycle.stopped() => {
+                break exit_code;
+            },
+            diff = scheduler.tick() => {
+                scheduler.run(diff, || unsafe { tick_callback(diff.as_millis() as u32) });
                 ::log::logger().flush();
+                lifecycle.update();
             }
         }
+    };
+
+    if let Some(watchdog) = watchdog {
+        watchdog.stop();
     }
 
-    Ok(())
+    // Stop accepting new connections before draining the live ones
+    drop(listeners);
+    if let Some(admin_handle) = admin_handle {
+        admin_handle.abort();
+    }
+    drain_sessions(&registry, drain_timeout).await;
+    stop_timers().await;
+    ::log::logger().flush();
+
+    Ok(exit_code)
 }
 
 #[no_mangle]
@@ -69,12 +328,58 @@ pub extern "C" fn AuthServerRsInit() {
     let _ = RUNTIME.set(Runtime::new().unwrap());
 }
 
+/// Runs the auth server until it is stopped.
+/// Returns the exit code of the process, the error one if the server could not be started.
 #[no_mangle]
-pub extern "C" fn AuthServerRsMain(tick_callback: TickCallback) {
+pub extern "C" fn AuthServerRsMain(tick_callback: TickCallback) -> u8 {
     let rt = RUNTIME.get().unwrap();
     let main_handle = rt.spawn(async move {
-        async_main(tick_callback).await.unwrap();
+        let result = async_main(tick_callback).await;
+        if let Err(e) = &result {
+            error!(target: "server::authserver", "{:#}", e);
+            ::log::logger().flush();
+        }
+
+        result.unwrap_or(ExitCode::Error)
+    });
+
+    rt.block_on(main_handle).unwrap_or(ExitCode::Error) as u8
+}
+
+/// Prints the known configuration keys with their default value and description, as a
+/// configuration file.
+#[no_mangle]
+pub extern "C" fn AuthServerRsPrintConfig() {
+    print!("{}", config_schema::schema().describe());
+}
+
+/// Replays a packet capture into an auth session, then returns.
+/// Returns false if the capture could not be replayed or the session replied differently.
+///
+/// # Safety
+/// Unsafe: path must be a valid C string.
+#[no_mangle]
+pub unsafe extern "C" fn AuthServerRsReplay(path: *const c_char) -> bool {
+    let path = PathBuf::from(CStr::from_ptr(path).to_string_lossy().as_ref());
+    let rt = RUNTIME.get().unwrap();
+    let replay_handle = rt.spawn(async move {
+        let result = async {
+            start_services()?;
+            replay::replay_capture(Path::new(&path)).await
+        }
+        .await;
+
+        stop_timers().await;
+        match result {
+            Ok(report) => report.is_identical(),
+            Err(e) => {
+                error!(target: "server::authserver", "Cannot replay {}: {:#}", path.display(), e);
+                false
+            }
+        }
     });
 
-    let _ = rt.block_on(main_handle);
+    let identical = rt.block_on(replay_handle).unwrap_or(false);
+    ::log::logger().flush();
+    identical
 }
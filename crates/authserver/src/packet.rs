@@ -1,10 +1,21 @@
+use bincode::config::{self as bincode_config, Configuration, Fixint, LittleEndian};
 use bincode::de::Decoder;
+use bincode::enc::write::Writer;
 use bincode::enc::Encoder;
 use bincode::error::{DecodeError, EncodeError};
 use bincode::{impl_borrow_decode, Decode, Encode};
-use kitros_derive::wow_auth_packet;
+use enturion_shared::net::fields::{decode_c_string, encode_c_string};
+use kitros_derive::{wow_auth_packet, WowEnum};
 
-#[derive(Copy, Clone)]
+/// Wire encoding of the auth packets: little endian, fixed size integers.
+pub const BINCODE_CONFIGURATION: Configuration<LittleEndian, Fixint> = bincode_config::standard()
+    .with_little_endian()
+    .with_fixed_int_encoding();
+
+/// The realm build has to be shown to the client.
+pub(crate) const REALM_FLAG_SPECIFYBUILD: u8 = 0x04;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, WowEnum)]
 #[repr(u8)]
 #[non_exhaustive]
 #[allow(dead_code)]
@@ -21,10 +32,10 @@ pub enum AuthCommand {
     XferCancel = 0x34,
 }
 
-#[derive(Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, WowEnum)]
 #[repr(u8)]
 #[non_exhaustive]
-#[allow(dead_code)]
+#[allow(dead_code, clippy::enum_variant_names)]
 pub enum AuthResult {
     WowSuccess = 0x00,
     WowFailBanned = 0x03,
@@ -54,39 +65,211 @@ pub enum AuthResult {
     WowFailDisconnected = 0xFF,
 }
 
-impl Encode for AuthResult {
-    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
-        let repr = *self as u8;
-        ::bincode::Encode::encode(&repr, encoder)
-    }
+/// A packet sent by the client, see `#[wow_auth_packet(client = <Command>)]`.
+pub trait ClientPacket: Decode {
+    const COMMAND: AuthCommand;
 }
 
-impl_borrow_decode!(AuthResult);
-impl Decode for AuthResult {
-    fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
-        let repr: u8 = ::bincode::Decode::decode(decoder)?;
-        Ok(unsafe { std::mem::transmute(repr) })
+/// Decodes a client packet from the start of the given data.
+/// Returns the packet and its size, or `None` if the packet is incomplete.
+pub fn decode_packet<P: ClientPacket>(data: &[u8]) -> Result<Option<(P, usize)>, DecodeError> {
+    match bincode::decode_from_slice(data, BINCODE_CONFIGURATION) {
+        Ok(result) => Ok(Some(result)),
+        Err(DecodeError::UnexpectedEnd { .. }) => Ok(None),
+        Err(e) => Err(e),
     }
 }
 
-impl Encode for AuthCommand {
+#[wow_auth_packet]
+pub struct LogonChallengeErrorResponse {
+    command: AuthCommand,
+    padding: u8,
+    auth_result: AuthResult,
+}
+
+/// The first packet of a login, also sent to reconnect.
+/// Platform, OS and country are sent reversed, e.g. `68x`, `niW` and `SUne`.
+#[wow_auth_packet(client = AuthLogonChallenge)]
+#[derive(Debug, PartialEq)]
+pub struct LogonChallengeRequest {
+    pub command: AuthCommand,
+    pub error: u8,
+    #[wow(size)]
+    pub size: u16,
+    #[wow(fixed = 4)]
+    pub game_name: String,
+    pub version: [u8; 3],
+    pub build: u16,
+    #[wow(fixed = 4)]
+    pub platform: String,
+    #[wow(fixed = 4)]
+    pub os: String,
+    #[wow(fixed = 4)]
+    pub country: String,
+    pub timezone_bias: u32,
+    pub ip: [u8; 4],
+    #[wow(prefix = u8)]
+    pub account_name: String,
+}
+
+#[wow_auth_packet(client = RealmList)]
+#[derive(Debug, PartialEq)]
+pub struct RealmListRequest {
+    pub command: AuthCommand,
+    pub padding: u32,
+}
+
+/// Version of the client a realm is built for, sent with `REALM_FLAG_SPECIFYBUILD`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RealmBuild {
+    pub major_version: u8,
+    pub minor_version: u8,
+    pub bugfix_version: u8,
+    pub build: u16,
+}
+
+/// A realm as shown in the realm list.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RealmListEntry {
+    pub realm_type: u8,
+    /// Only sent to post-BC clients.
+    pub locked: bool,
+    pub flags: u8,
+    pub name: String,
+    pub address: String,
+    pub population: f32,
+    pub characters: u8,
+    pub timezone: u8,
+    /// Pre-BC clients always get 0.
+    pub id: u8,
+    /// Only sent to post-BC clients, when `REALM_FLAG_SPECIFYBUILD` is set.
+    pub build: Option<RealmBuild>,
+}
+
+/// The realms of a `RealmListResponse`.
+/// The layout differs between pre-BC (1.12.x) and post-BC (2.x and 3.x) clients.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RealmListEntries {
+    pub post_bc: bool,
+    pub realms: Vec<RealmListEntry>,
+}
+
+impl Encode for RealmListEntries {
     fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
-        let repr = *self as u8;
-        ::bincode::Encode::encode(&repr, encoder)
+        if self.post_bc {
+            (self.realms.len() as u16).encode(encoder)?;
+        } else {
+            (self.realms.len() as u32).encode(encoder)?;
+        }
+
+        for realm in &self.realms {
+            realm.realm_type.encode(encoder)?;
+            if self.post_bc {
+                (realm.locked as u8).encode(encoder)?;
+            }
+
+            realm.flags.encode(encoder)?;
+            encode_c_string(&realm.name, encoder)?;
+            encode_c_string(&realm.address, encoder)?;
+            realm.population.encode(encoder)?;
+            realm.characters.encode(encoder)?;
+            realm.timezone.encode(encoder)?;
+            if self.post_bc {
+                realm.id.encode(encoder)?;
+            } else {
+                0_u8.encode(encoder)?;
+            }
+
+            if let (true, Some(build)) = (self.post_bc, realm.build) {
+                build.major_version.encode(encoder)?;
+                build.minor_version.encode(encoder)?;
+                build.bugfix_version.encode(encoder)?;
+                build.build.encode(encoder)?;
+            }
+        }
+
+        let footer: [u8; 2] = if self.post_bc {
+            [0x10, 0x00]
+        } else {
+            [0x00, 0x02]
+        };
+        encoder.writer().write(&footer)
     }
 }
 
-impl_borrow_decode!(AuthCommand);
-impl Decode for AuthCommand {
+impl_borrow_decode!(RealmListEntries);
+/// Decoding only supports the post-BC layout, the pre-BC one cannot be told apart.
+impl Decode for RealmListEntries {
     fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
-        let repr: u8 = ::bincode::Decode::decode(decoder)?;
-        Ok(unsafe { std::mem::transmute(repr) })
+        let count: u16 = Decode::decode(decoder)?;
+        let mut realms = Vec::with_capacity(count as usize);
+        for _ in 0..count {
+            let realm_type = Decode::decode(decoder)?;
+            let locked = u8::decode(decoder)? != 0;
+            let flags: u8 = Decode::decode(decoder)?;
+            let name = decode_c_string::<String, _>(decoder)?;
+            let address = decode_c_string::<String, _>(decoder)?;
+            let population = Decode::decode(decoder)?;
+            let characters = Decode::decode(decoder)?;
+            let timezone = Decode::decode(decoder)?;
+            let id = Decode::decode(decoder)?;
+            let build = if flags & REALM_FLAG_SPECIFYBUILD != 0 {
+                Some(RealmBuild {
+                    major_version: Decode::decode(decoder)?,
+                    minor_version: Decode::decode(decoder)?,
+                    bugfix_version: Decode::decode(decoder)?,
+                    build: Decode::decode(decoder)?,
+                })
+            } else {
+                None
+            };
+
+            realms.push(RealmListEntry {
+                realm_type,
+                locked,
+                flags,
+                name,
+                address,
+                population,
+                characters,
+                timezone,
+                id,
+                build,
+            });
+        }
+
+        let _footer: [u8; 2] = Decode::decode(decoder)?;
+        Ok(Self {
+            post_bc: true,
+            realms,
+        })
     }
 }
 
-#[wow_auth_packet]
-pub struct LogonChallengeErrorResponse {
+#[wow_auth_packet(no_ffi)]
+#[derive(Debug, PartialEq)]
+pub struct RealmListResponse {
     command: AuthCommand,
-    padding: u8,
-    auth_result: AuthResult,
+    #[wow(size)]
+    size: u16,
+    padding: u32,
+    realms: RealmListEntries,
+}
+
+impl RealmListResponse {
+    pub fn new(realms: RealmListEntries) -> Result<Self, EncodeError> {
+        let mut response = Self {
+            command: AuthCommand::RealmList,
+            size: 0,
+            padding: 0,
+            realms,
+        };
+
+        response.compute_sizes()?;
+        Ok(response)
+    }
+
+    pub fn realms(&self) -> &RealmListEntries {
+        &self.realms
+    }
 }
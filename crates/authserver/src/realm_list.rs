@@ -0,0 +1,478 @@
+use crate::build_info::{BuildRegistry, ClientBuild};
+use crate::packet::{
+    RealmBuild, RealmListEntries, RealmListEntry, RealmListResponse, REALM_FLAG_SPECIFYBUILD,
+};
+use enturion_shared::timer::create_timer;
+use log::{debug, error};
+use std::collections::{BTreeMap, HashMap};
+use std::convert::Infallible;
+use std::ffi::{c_char, c_void, CStr};
+use std::net::{IpAddr, SocketAddr};
+use std::sync::{OnceLock, RwLock};
+use std::time::Duration;
+
+/// `AuthSession::_expversion` flags.
+pub(crate) const POST_BC_EXP_FLAG: u8 = 0x2;
+pub(crate) const PRE_BC_EXP_FLAG: u8 = 0x1;
+
+/// The realm is offline.
+const REALM_FLAG_OFFLINE: u8 = 0x02;
+
+static REGISTRY: OnceLock<RealmRegistry> = OnceLock::new();
+
+extern "C" {
+    /// Fills the snapshot with the realms and builds known by `sRealmList`.
+    fn RealmList_Snapshot(snapshot: *mut c_void);
+}
+
+/// A realm, as loaded from the `realmlist` table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Realm {
+    pub id: u32,
+    pub build: u32,
+    pub name: String,
+    pub external_address: IpAddr,
+    pub local_address: IpAddr,
+    pub local_subnet_mask: IpAddr,
+    pub port: u16,
+    pub realm_type: u8,
+    pub flags: u8,
+    pub timezone: u8,
+    pub allowed_security_level: u8,
+    pub population: f32,
+}
+
+impl Realm {
+    /// Gets the best address for the client to reach the realm.
+    pub fn address_for_client(&self, client: IpAddr) -> SocketAddr {
+        let ip = if client.is_loopback() {
+            // Try guessing if realm is also connected locally
+            if self.local_address.is_loopback() || self.external_address.is_loopback() {
+                client
+            } else {
+                // Assume that a client on the auth server machine has all realms in its local network
+                self.local_address
+            }
+        } else {
+            match (client, self.local_address, self.local_subnet_mask) {
+                (IpAddr::V4(client), IpAddr::V4(local), IpAddr::V4(mask))
+                    if u32::from(client) & u32::from(mask)
+                        == u32::from(local) & u32::from(mask) =>
+                {
+                    self.local_address
+                }
+                _ => self.external_address,
+            }
+        };
+
+        SocketAddr::new(ip, self.port)
+    }
+}
+
+/// The known realms and client builds at a given time.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct RealmListSnapshot {
+    pub realms: BTreeMap<u32, Realm>,
+    pub builds: Vec<ClientBuild>,
+}
+
+/// The client requesting the realm list.
+#[derive(Debug, Clone, Copy)]
+pub struct RealmListClient {
+    pub build: u32,
+    /// `PRE_BC_EXP_FLAG` and/or `POST_BC_EXP_FLAG`.
+    pub expansion_flags: u8,
+    pub security_level: u8,
+    pub address: IpAddr,
+}
+
+impl RealmListClient {
+    fn is_post_bc(&self) -> bool {
+        self.expansion_flags & POST_BC_EXP_FLAG != 0
+    }
+
+    fn is_pre_bc(&self) -> bool {
+        self.expansion_flags & PRE_BC_EXP_FLAG != 0
+    }
+}
+
+/// Keeps a copy of the realm list, to build the realm list packets.
+#[derive(Default)]
+pub struct RealmRegistry {
+    realms: RwLock<BTreeMap<u32, Realm>>,
+}
+
+impl RealmRegistry {
+    /// Gets the realm registry of the auth server.
+    pub fn global() -> &'static RealmRegistry {
+        REGISTRY.get_or_init(RealmRegistry::default)
+    }
+
+    /// Replaces the known realms.
+    pub fn update(&self, realms: BTreeMap<u32, Realm>) {
+        let mut current = self.realms.write().unwrap();
+        for (id, realm) in &realms {
+            if !current.contains_key(id) {
+                debug!(target: "server::authserver", "Realm registry: added realm \"{}\"", realm.name);
+            }
+        }
+
+        for (id, realm) in current.iter() {
+            if !realms.contains_key(id) {
+                debug!(target: "server::authserver", "Realm registry: removed realm \"{}\"", realm.name);
+            }
+        }
+
+        *current = realms;
+    }
+
+    /// Builds the realm list sent to the given client.
+    /// `character_counts` maps realm ids to the number of characters of the account.
+    pub fn realm_list(
+        &self,
+        builds: &BuildRegistry,
+        client: &RealmListClient,
+        character_counts: &HashMap<u32, u8>,
+    ) -> RealmListEntries {
+        let mut realms = vec![];
+        for realm in self.realms.read().unwrap().values() {
+            // Skip the realms not compatible with the client
+            let ok_build = (client.is_post_bc() && realm.build == client.build)
+                || (client.is_pre_bc() && !builds.is_pre_bc_accepted(realm.build));
+
+            let info = builds.get(realm.build);
+            let mut flags = realm.flags;
+            if !ok_build {
+                if info.is_none() {
+                    continue;
+                }
+
+                // Tell the client what build the realm is for
+                flags |= REALM_FLAG_OFFLINE | REALM_FLAG_SPECIFYBUILD;
+            }
+
+            let info = match info {
+                Some(info) if flags & REALM_FLAG_SPECIFYBUILD != 0 => Some(info),
+                _ => {
+                    flags &= !REALM_FLAG_SPECIFYBUILD;
+                    None
+                }
+            };
+
+            let name = match info {
+                Some(info) if client.is_pre_bc() => format!(
+                    "{} ({}.{}.{})",
+                    realm.name, info.major_version, info.minor_version, info.bugfix_version
+                ),
+                _ => realm.name.clone(),
+            };
+
+            realms.push(RealmListEntry {
+                realm_type: realm.realm_type,
+                locked: realm.allowed_security_level > client.security_level,
+                flags,
+                name,
+                address: realm.address_for_client(client.address).to_string(),
+                population: realm.population,
+                characters: character_counts.get(&realm.id).copied().unwrap_or(0),
+                timezone: realm.timezone,
+                id: realm.id as u8,
+                build: info.map(|info| RealmBuild {
+                    major_version: info.major_version as u8,
+                    minor_version: info.minor_version as u8,
+                    bugfix_version: info.bugfix_version as u8,
+                    build: info.build as u16,
+                }),
+            });
+        }
+
+        RealmListEntries {
+            post_bc: client.is_post_bc(),
+            realms,
+        }
+    }
+
+    /// Builds the realm list packet sent to the given client.
+    pub fn realm_list_response(
+        &self,
+        builds: &BuildRegistry,
+        client: &RealmListClient,
+        character_counts: &HashMap<u32, u8>,
+    ) -> anyhow::Result<RealmListResponse> {
+        Ok(RealmListResponse::new(self.realm_list(
+            builds,
+            client,
+            character_counts,
+        ))?)
+    }
+
+    /// Reloads the realms and builds from `sRealmList`.
+    fn refresh(&self) {
+        let mut snapshot = RealmListSnapshot::default();
+        unsafe { RealmList_Snapshot(&mut snapshot as *mut RealmListSnapshot as *mut c_void) };
+        self.update(snapshot.realms);
+        BuildRegistry::global().update(snapshot.builds);
+    }
+}
+
+/// Loads the realms, then refreshes them every `interval`. A zero interval disables the refresh.
+pub fn start_refresh(interval: Duration) {
+    let registry = RealmRegistry::global();
+    if interval.is_zero() {
+        registry.refresh();
+    } else {
+        create_timer(interval, move || {
+            registry.refresh();
+            async { Ok::<(), Infallible>(()) }
+        });
+    }
+}
+
+// C/C++ interface
+
+/// A realm, as passed to `RealmListSnapshot_AddRealm`.
+#[repr(C)]
+pub struct RealmListSnapshotEntry {
+    pub id: u32,
+    pub build: u32,
+    pub name: *const c_char,
+    pub external_address: *const c_char,
+    pub local_address: *const c_char,
+    pub local_subnet_mask: *const c_char,
+    pub port: u16,
+    pub realm_type: u8,
+    pub flags: u8,
+    pub timezone: u8,
+    pub allowed_security_level: u8,
+    pub population: f32,
+}
+
+/// Adds a realm to the snapshot passed to `RealmList_Snapshot`.
+///
+/// # Safety
+/// Unsafe: snapshot must come from `RealmList_Snapshot`, the strings must be valid C strings.
+#[no_mangle]
+pub unsafe extern "C" fn RealmListSnapshot_AddRealm(
+    snapshot: *mut c_void,
+    realm: *const RealmListSnapshotEntry,
+) {
+    let snapshot = &mut *snapshot.cast::<RealmListSnapshot>();
+    let realm = &*realm;
+    let name = CStr::from_ptr(realm.name).to_string_lossy().into_owned();
+    let address = |address: *const c_char| CStr::from_ptr(address).to_str().ok()?.parse().ok();
+
+    let (Some(external_address), Some(local_address), Some(local_subnet_mask)) = (
+        address(realm.external_address),
+        address(realm.local_address),
+        address(realm.local_subnet_mask),
+    ) else {
+        error!(target: "server::authserver", "Invalid address for realm \"{}\" id {}", name, realm.id);
+        return;
+    };
+
+    snapshot.realms.insert(
+        realm.id,
+        Realm {
+            id: realm.id,
+            build: realm.build,
+            name,
+            external_address,
+            local_address,
+            local_subnet_mask,
+            port: realm.port,
+            realm_type: realm.realm_type,
+            flags: realm.flags,
+            timezone: realm.timezone,
+            allowed_security_level: realm.allowed_security_level,
+            population: realm.population,
+        },
+    );
+}
+
+/// Adds a client build to the snapshot passed to `RealmList_Snapshot`.
+/// The hashes are 20 bytes long, all zeros when not set.
+///
+/// # Safety
+/// Unsafe: snapshot must come from `RealmList_Snapshot`.
+#[no_mangle]
+pub unsafe extern "C" fn RealmListSnapshot_AddBuild(
+    snapshot: *mut c_void,
+    build: u32,
+    major_version: u32,
+    minor_version: u32,
+    bugfix_version: u32,
+    windows_hash: *const u8,
+    mac_hash: *const u8,
+) {
+    let snapshot = &mut *snapshot.cast::<RealmListSnapshot>();
+    let hash = |hash: *const u8| {
+        let hash: [u8; 20] = std::slice::from_raw_parts(hash, 20).try_into().unwrap();
+        (hash != [0; 20]).then_some(hash)
+    };
+
+    snapshot.builds.push(ClientBuild {
+        build,
+        major_version,
+        minor_version,
+        bugfix_version,
+        windows_hash: hash(windows_hash),
+        mac_hash: hash(mac_hash),
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::build_info::{BuildRegistry, ClientBuild};
+    use crate::packet::BINCODE_CONFIGURATION;
+    use crate::realm_list::{
+        Realm, RealmListClient, RealmRegistry, POST_BC_EXP_FLAG, PRE_BC_EXP_FLAG,
+    };
+    use std::collections::{BTreeMap, HashMap};
+
+    fn registries() -> (RealmRegistry, BuildRegistry) {
+        let realm = |id: u32, name: &str, build: u32| Realm {
+            id,
+            build,
+            name: name.to_string(),
+            external_address: "1.2.3.4".parse().unwrap(),
+            local_address: "192.168.1.10".parse().unwrap(),
+            local_subnet_mask: "255.255.255.0".parse().unwrap(),
+            port: 8085,
+            realm_type: 1,
+            flags: 0,
+            timezone: 1,
+            allowed_security_level: 0,
+            population: 0.0,
+        };
+
+        let build =
+            |build: u32, major_version: u32, minor_version: u32, bugfix_version: u32| ClientBuild {
+                build,
+                major_version,
+                minor_version,
+                bugfix_version,
+                windows_hash: None,
+                mac_hash: None,
+            };
+
+        let realms = RealmRegistry::default();
+        realms.update(BTreeMap::from([
+            (1, realm(1, "Wrath", 12340)),
+            (2, realm(2, "Old", 5875)),
+        ]));
+
+        let builds = BuildRegistry::default();
+        builds.update(vec![build(5875, 1, 12, 1), build(12340, 3, 3, 5)]);
+
+        (realms, builds)
+    }
+
+    #[test]
+    pub fn post_bc_realm_list() {
+        let (registry, builds) = registries();
+        let client = RealmListClient {
+            build: 12340,
+            expansion_flags: POST_BC_EXP_FLAG,
+            security_level: 0,
+            address: "192.168.1.20".parse().unwrap(),
+        };
+
+        let response = registry
+            .realm_list_response(&builds, &client, &HashMap::from([(1, 3)]))
+            .unwrap();
+        let bytes = bincode::encode_to_vec(&response, BINCODE_CONFIGURATION).unwrap();
+
+        #[rustfmt::skip]
+        let expected: Vec<u8> = [
+            &[0x10, 0x4F, 0x00][..],          // command, size
+            &[0x00, 0x00, 0x00, 0x00],        // padding
+            &[0x02, 0x00],                    // realm count
+            // Realm 1: same build as the client, local network address
+            &[0x01, 0x00, 0x00],              // type, lock, flags
+            b"Wrath\0",
+            b"192.168.1.10:8085\0",
+            &[0x00, 0x00, 0x00, 0x00],        // population
+            &[0x03, 0x01, 0x01],              // characters, timezone, id
+            // Realm 2: other build, shown as offline with its version
+            &[0x01, 0x00, 0x06],              // type, lock, flags
+            b"Old\0",
+            b"192.168.1.10:8085\0",
+            &[0x00, 0x00, 0x00, 0x00],        // population
+            &[0x00, 0x01, 0x02],              // characters, timezone, id
+            &[0x01, 0x0C, 0x01, 0xF3, 0x16],  // 1.12.1.5875
+            &[0x10, 0x00],                    // footer
+        ]
+        .concat();
+
+        assert_eq!(bytes, expected);
+        assert_eq!(bytes.len(), 3 + 0x4F);
+        assert_eq!(
+            bincode::decode_from_slice(&bytes, BINCODE_CONFIGURATION)
+                .map(|(decoded, _)| decoded)
+                .ok(),
+            Some(response)
+        );
+    }
+
+    #[test]
+    pub fn pre_bc_realm_list() {
+        let (registry, builds) = registries();
+        let client = RealmListClient {
+            build: 5875,
+            expansion_flags: PRE_BC_EXP_FLAG,
+            security_level: 0,
+            address: "5.6.7.8".parse().unwrap(),
+        };
+
+        let response = registry
+            .realm_list_response(&builds, &client, &HashMap::new())
+            .unwrap();
+        let bytes = bincode::encode_to_vec(&response, BINCODE_CONFIGURATION).unwrap();
+
+        #[rustfmt::skip]
+        let expected: Vec<u8> = [
+            &[0x10, 0x49, 0x00][..],          // command, size
+            &[0x00, 0x00, 0x00, 0x00],        // padding
+            &[0x02, 0x00, 0x00, 0x00],        // realm count
+            // Realm 1
+            &[0x01, 0x00],                    // type, flags
+            b"Wrath\0",
+            b"1.2.3.4:8085\0",
+            &[0x00, 0x00, 0x00, 0x00],        // population
+            &[0x00, 0x01, 0x00],              // characters, timezone, id
+            // Realm 2: pre-BC build, shown as offline with its version in the name
+            &[0x01, 0x06],                    // type, flags
+            b"Old (1.12.1)\0",
+            b"1.2.3.4:8085\0",
+            &[0x00, 0x00, 0x00, 0x00],        // population
+            &[0x00, 0x01, 0x00],              // characters, timezone, id
+            &[0x00, 0x02],                    // footer
+        ]
+        .concat();
+
+        assert_eq!(bytes, expected);
+        assert_eq!(bytes.len(), 3 + 0x49);
+    }
+
+    #[test]
+    pub fn locked_realms() {
+        let (registry, builds) = registries();
+        let client = RealmListClient {
+            build: 12340,
+            expansion_flags: POST_BC_EXP_FLAG,
+            security_level: 0,
+            address: "127.0.0.1".parse().unwrap(),
+        };
+
+        let mut realms = registry.realms.read().unwrap().clone();
+        realms.get_mut(&1).unwrap().allowed_security_level = 3;
+        registry.update(realms);
+
+        let realms = registry
+            .realm_list(&builds, &client, &HashMap::new())
+            .realms;
+        assert!(realms[0].locked);
+        assert!(!realms[1].locked);
+        assert_eq!(realms[0].address, "192.168.1.10:8085");
+    }
+}
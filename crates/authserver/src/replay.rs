@@ -0,0 +1,201 @@
+use crate::auth_session::{AuthSession, SessionTimeouts};
+use crate::capture::{Capture, FrameKind};
+use crate::flood_protection::{ConnectionLimiter, FloodProtectionConfig};
+use crate::session_registry::SessionRegistry;
+use anyhow::{Error, Result};
+use enturion_shared::net::ip_location::IpLocationStore;
+use log::{info, warn};
+use std::net::Ipv4Addr;
+use std::path::Path;
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::time::{self, Instant};
+
+/// Time the session has to send each of the recorded replies.
+const REPLY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Outcome of a replayed capture.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ReplayReport {
+    /// Number of frames sent or received.
+    pub frames: usize,
+    /// Indexes of the outbound frames the session replied differently to.
+    pub mismatches: Vec<usize>,
+    /// Index of the outbound frame the session did not send, because it closed the connection
+    /// or did not reply in time.
+    pub stopped_at: Option<usize>,
+}
+
+impl ReplayReport {
+    pub fn is_identical(&self) -> bool {
+        self.mismatches.is_empty() && self.stopped_at.is_none()
+    }
+}
+
+/// Plays the client side of a capture on the stream: sends the inbound frames with their recorded
+/// timing, and compares the replies of the session with the outbound frames.
+pub async fn play<S>(
+    capture: &Capture,
+    mut stream: S,
+    reply_timeout: Duration,
+) -> Result<ReplayReport>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let start = Instant::now();
+    let mut report = ReplayReport::default();
+    for (index, frame) in capture.frames.iter().enumerate() {
+        match frame.kind {
+            FrameKind::Inbound => {
+                time::sleep_until(start + frame.elapsed).await;
+                stream.write_all(&frame.data).await?;
+            }
+            FrameKind::Outbound => {
+                let mut reply = vec![0; frame.data.len()];
+                match time::timeout(reply_timeout, stream.read_exact(&mut reply)).await {
+                    Ok(Ok(_)) => {}
+                    Ok(Err(_)) | Err(_) => {
+                        report.stopped_at = Some(index);
+                        break;
+                    }
+                }
+
+                if reply != frame.data {
+                    report.mismatches.push(index);
+                }
+            }
+            FrameKind::Account | FrameKind::Random => continue,
+        }
+
+        report.frames += 1;
+    }
+
+    Ok(report)
+}
+
+/// Replays a capture file into a new auth session, through a loopback connection.
+/// The session sees the address of the captured client, and gets back the random bytes it
+/// generated, so the logon proofs of the capture are still valid. Captures without random frames
+/// (version 1) differ from the first reply depending on a random value.
+pub async fn replay_capture(path: &Path) -> Result<ReplayReport> {
+    let capture = Capture::load(path)?;
+    let address = capture.header.address;
+    info!(
+        target: "server::authserver",
+        "Replaying session {} of {} ({}), {} frames",
+        capture.header.session_id,
+        address,
+        capture.account().as_deref().unwrap_or("unknown account"),
+        capture.frames.len()
+    );
+
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).await?;
+    let client = TcpStream::connect(listener.local_addr()?).await?;
+    let (stream, _) = listener.accept().await?;
+
+    let limiter = ConnectionLimiter::new(FloodProtectionConfig::default());
+    let connection_guard = limiter
+        .try_acquire(address.ip())
+        .map_err(|reason| Error::msg(reason.to_string()))?;
+    let ip_location = IpLocationStore::global().location(address.ip()).await;
+    let mut session = AuthSession::new(
+        stream,
+        address,
+        None,
+        ip_location,
+        connection_guard,
+        SessionRegistry::global().register(address),
+        SessionTimeouts::default(),
+    );
+    session.replay_random(
+        capture
+            .frames
+            .iter()
+            .filter(|frame| frame.kind == FrameKind::Random)
+            .map(|frame| frame.data.clone()),
+    );
+    let session = tokio::spawn(async move {
+        let _ = session.start().await;
+    });
+
+    let report = play(&capture, client, REPLY_TIMEOUT).await?;
+
+    // The session ends with the connection
+    if time::timeout(REPLY_TIMEOUT, session).await.is_err() {
+        warn!(target: "server::authserver", "Replayed session did not end in time");
+    }
+
+    for index in &report.mismatches {
+        warn!(target: "server::authserver", "Reply frame {} differs from the capture", index);
+    }
+
+    if let Some(index) = report.stopped_at {
+        warn!(target: "server::authserver", "Reply frame {} was not sent by the session", index);
+    }
+
+    info!(
+        target: "server::authserver",
+        "Replayed {} of {} frames, {} replies differ",
+        report.frames,
+        capture.frames.len(),
+        report.mismatches.len()
+    );
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::capture::{Capture, CaptureHeader, Frame, FrameKind};
+    use crate::replay::play;
+    use std::time::Duration;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    fn capture(frames: &[(FrameKind, &[u8])]) -> Capture {
+        Capture {
+            header: CaptureHeader::new(1, "127.0.0.1:1".parse().unwrap()),
+            frames: frames
+                .iter()
+                .map(|(kind, data)| Frame {
+                    elapsed: Duration::ZERO,
+                    kind: *kind,
+                    data: data.to_vec(),
+                })
+                .collect(),
+        }
+    }
+
+    #[tokio::test]
+    pub async fn replies_are_compared() {
+        let capture = capture(&[
+            (FrameKind::Inbound, &[0x10, 0, 0, 0, 0]),
+            (FrameKind::Account, b"PLAYER"),
+            (FrameKind::Random, &[0xAB; 16]),
+            (FrameKind::Outbound, &[0x10, 0x02]),
+            (FrameKind::Inbound, &[0x10, 0, 0, 0, 0]),
+            (FrameKind::Outbound, &[0x10, 0x02]),
+            (FrameKind::Outbound, &[0x10, 0x02]),
+        ]);
+
+        // Replies to the first request as recorded, differently to the second one,
+        // then closes the connection.
+        let (client, mut server) = tokio::io::duplex(64);
+        let server = tokio::spawn(async move {
+            let mut request = [0; 5];
+            server.read_exact(&mut request).await.unwrap();
+            server.write_all(&[0x10, 0x02]).await.unwrap();
+            server.read_exact(&mut request).await.unwrap();
+            server.write_all(&[0x10, 0x03]).await.unwrap();
+        });
+
+        let report = play(&capture, client, Duration::from_secs(5))
+            .await
+            .unwrap();
+        server.await.unwrap();
+        assert_eq!(report.frames, 4);
+        assert_eq!(report.mismatches, [5]);
+        assert_eq!(report.stopped_at, Some(6));
+        assert!(!report.is_identical());
+    }
+}
@@ -0,0 +1,451 @@
+use std::collections::HashMap;
+use std::ffi::{c_char, CString};
+use std::fmt::{Display, Formatter};
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use tokio::sync::Notify;
+use tokio::time;
+
+pub type SessionId = u64;
+
+static REGISTRY: OnceLock<Arc<SessionRegistry>> = OnceLock::new();
+
+/// State of an auth session, mirrors the C++ `AuthStatus` enum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum SessionState {
+    Challenge = 0,
+    LogonProof = 1,
+    ReconnectProof = 2,
+    Authed = 3,
+    WaitingForRealmList = 4,
+    Closed = 5,
+}
+
+impl TryFrom<u8> for SessionState {
+    type Error = u8;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Ok(match value {
+            0 => Self::Challenge,
+            1 => Self::LogonProof,
+            2 => Self::ReconnectProof,
+            3 => Self::Authed,
+            4 => Self::WaitingForRealmList,
+            5 => Self::Closed,
+            _ => return Err(value),
+        })
+    }
+}
+
+impl Display for SessionState {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            SessionState::Challenge => "challenge",
+            SessionState::LogonProof => "logon_proof",
+            SessionState::ReconnectProof => "reconnect_proof",
+            SessionState::Authed => "authed",
+            SessionState::WaitingForRealmList => "waiting_for_realm_list",
+            SessionState::Closed => "closed",
+        };
+
+        write!(f, "{}", name)
+    }
+}
+
+/// Messages sent to a running session from outside its task.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SessionMessage {
+    /// Writes the data to the connection, in the order the messages were sent.
+    Write(Vec<u8>),
+    /// Flushes and closes the write side of the connection.
+    Disconnect,
+    /// Sends the pending writes then ends the session.
+    Shutdown,
+    /// Lets the session complete the login in progress, or ends it if none was started.
+    Drain,
+    /// Terminates the session as soon as possible.
+    Close,
+}
+
+/// Sends messages to a running session.
+pub struct SessionControl {
+    sender: UnboundedSender<SessionMessage>,
+}
+
+impl SessionControl {
+    /// Sends a message to the session. Messages sent after the session ended are dropped.
+    pub fn send(&self, message: SessionMessage) {
+        let _ = self.sender.send(message);
+    }
+
+    pub fn disconnect(&self) {
+        self.send(SessionMessage::Disconnect);
+    }
+
+    pub fn shutdown(&self) {
+        self.send(SessionMessage::Shutdown);
+    }
+
+    pub fn drain(&self) {
+        self.send(SessionMessage::Drain);
+    }
+
+    pub fn close(&self) {
+        self.send(SessionMessage::Close);
+    }
+}
+
+/// A snapshot of a live session.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionInfo {
+    pub id: SessionId,
+    pub address: SocketAddr,
+    pub state: SessionState,
+    pub account: Option<String>,
+    pub age: Duration,
+}
+
+struct RegisteredSession {
+    address: SocketAddr,
+    connected_at: Instant,
+    state: SessionState,
+    account: Option<String>,
+    control: Arc<SessionControl>,
+}
+
+/// Keeps track of the live auth sessions.
+pub struct SessionRegistry {
+    next_id: AtomicU64,
+    sessions: Mutex<HashMap<SessionId, RegisteredSession>>,
+    changed: Notify,
+}
+
+impl SessionRegistry {
+    pub fn new() -> Arc<Self> {
+        Arc::new(Self {
+            next_id: AtomicU64::new(1),
+            sessions: Mutex::default(),
+            changed: Notify::new(),
+        })
+    }
+
+    /// Gets the registry of the auth server sessions.
+    pub fn global() -> &'static Arc<SessionRegistry> {
+        REGISTRY.get_or_init(SessionRegistry::new)
+    }
+
+    /// Registers a new session. The session is removed from the registry
+    /// when the returned registration is dropped.
+    pub fn register(self: &Arc<Self>, address: SocketAddr) -> SessionRegistration {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let (sender, messages) = mpsc::unbounded_channel();
+        let control = Arc::new(SessionControl { sender });
+        self.sessions.lock().unwrap().insert(
+            id,
+            RegisteredSession {
+                address,
+                connected_at: Instant::now(),
+                state: SessionState::Challenge,
+                account: None,
+                control: control.clone(),
+            },
+        );
+
+        SessionRegistration {
+            registry: self.clone(),
+            id,
+            control,
+            messages: Some(messages),
+        }
+    }
+
+    /// Number of live sessions.
+    pub fn len(&self) -> usize {
+        self.sessions.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Lists the live sessions, oldest first.
+    pub fn list(&self) -> Vec<SessionInfo> {
+        let now = Instant::now();
+        let mut sessions = self
+            .sessions
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(id, session)| SessionInfo {
+                id: *id,
+                address: session.address,
+                state: session.state,
+                account: session.account.clone(),
+                age: now.saturating_duration_since(session.connected_at),
+            })
+            .collect::<Vec<_>>();
+
+        sessions.sort_by_key(|session| session.id);
+        sessions
+    }
+
+    /// Terminates the given session. Returns false if no such session exists.
+    pub fn kick(&self, id: SessionId) -> bool {
+        match self.sessions.lock().unwrap().get(&id) {
+            Some(session) => {
+                session.control.close();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Asks every live session to complete the login in progress.
+    pub fn drain_all(&self) {
+        for session in self.sessions.lock().unwrap().values() {
+            session.control.drain();
+        }
+    }
+
+    /// Terminates every live session.
+    pub fn close_all(&self) {
+        for session in self.sessions.lock().unwrap().values() {
+            session.control.close();
+        }
+    }
+
+    /// Waits until all the sessions are gone.
+    /// Returns false if some session is still alive after the timeout.
+    pub async fn wait_until_empty(&self, timeout: Duration) -> bool {
+        let wait = async {
+            loop {
+                let changed = self.changed.notified();
+                if self.is_empty() {
+                    return;
+                }
+
+                changed.await;
+            }
+        };
+
+        time::timeout(timeout, wait).await.is_ok()
+    }
+
+    fn update<F>(&self, id: SessionId, f: F)
+    where
+        F: FnOnce(&mut RegisteredSession),
+    {
+        if let Some(session) = self.sessions.lock().unwrap().get_mut(&id) {
+            f(session);
+        }
+    }
+
+    fn unregister(&self, id: SessionId) {
+        self.sessions.lock().unwrap().remove(&id);
+        self.changed.notify_waiters();
+    }
+}
+
+/// The registration of a live session.
+pub struct SessionRegistration {
+    registry: Arc<SessionRegistry>,
+    id: SessionId,
+    control: Arc<SessionControl>,
+    messages: Option<UnboundedReceiver<SessionMessage>>,
+}
+
+impl SessionRegistration {
+    pub fn id(&self) -> SessionId {
+        self.id
+    }
+
+    pub fn control(&self) -> &SessionControl {
+        &self.control
+    }
+
+    /// Takes the receiver of the messages sent through the control of the session.
+    /// Returns `None` once taken.
+    pub fn take_messages(&mut self) -> Option<UnboundedReceiver<SessionMessage>> {
+        self.messages.take()
+    }
+
+    pub fn set_state(&self, state: SessionState) {
+        self.registry
+            .update(self.id, |session| session.state = state);
+    }
+
+    pub fn set_account(&self, account: &str) {
+        self.registry.update(self.id, |session| {
+            session.account = Some(account.to_string())
+        });
+    }
+}
+
+impl Drop for SessionRegistration {
+    fn drop(&mut self) {
+        self.registry.unregister(self.id);
+    }
+}
+
+// C/C++ interface
+
+/// A live auth session, as returned by `AuthSessionRegistry_List`.
+#[repr(C)]
+pub struct AuthSessionInfo {
+    pub id: u64,
+    pub address: *const c_char,
+    pub port: u16,
+    /// One of the `AuthStatus` values.
+    pub status: u8,
+    /// Account name, or a null pointer if not known yet.
+    pub account: *const c_char,
+    pub age_seconds: u64,
+}
+
+/// Gets the number of live auth sessions.
+#[no_mangle]
+pub extern "C" fn AuthSessionRegistry_Count() -> usize {
+    SessionRegistry::global().len()
+}
+
+/// Gets the live auth sessions.
+/// The sessions are returned in the sessions array (must be passed as pointer). The return
+/// value represents the length of the array.
+///
+/// # Safety
+/// Unsafe: the array must be freed with `AuthSessionRegistry_FreeList`.
+#[no_mangle]
+pub unsafe extern "C" fn AuthSessionRegistry_List(sessions: *mut *mut AuthSessionInfo) -> usize {
+    let list = SessionRegistry::global()
+        .list()
+        .into_iter()
+        .map(|session| AuthSessionInfo {
+            id: session.id,
+            address: CString::new(session.address.ip().to_canonical().to_string())
+                .unwrap()
+                .into_raw(),
+            port: session.address.port(),
+            status: session.state as u8,
+            account: session
+                .account
+                .and_then(|account| CString::new(account).ok())
+                .map_or(std::ptr::null(), |account| account.into_raw()),
+            age_seconds: session.age.as_secs(),
+        })
+        .collect::<Vec<_>>()
+        .into_boxed_slice();
+
+    let length = list.len();
+    *sessions = Box::into_raw(list) as *mut AuthSessionInfo;
+
+    length
+}
+
+/// Frees an array returned by `AuthSessionRegistry_List`.
+///
+/// # Safety
+/// Unsafe: sessions and length must come from `AuthSessionRegistry_List`.
+#[no_mangle]
+pub unsafe extern "C" fn AuthSessionRegistry_FreeList(
+    sessions: *mut AuthSessionInfo,
+    length: usize,
+) {
+    if sessions.is_null() {
+        return;
+    }
+
+    let list = Box::from_raw(std::ptr::slice_from_raw_parts_mut(sessions, length));
+    for session in list.iter() {
+        drop(CString::from_raw(session.address as *mut c_char));
+        if !session.account.is_null() {
+            drop(CString::from_raw(session.account as *mut c_char));
+        }
+    }
+}
+
+/// Terminates a live auth session. Returns false if no such session exists.
+#[no_mangle]
+pub extern "C" fn AuthSessionRegistry_Kick(id: u64) -> bool {
+    SessionRegistry::global().kick(id)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::session_registry::{SessionMessage, SessionRegistry, SessionState};
+    use std::net::SocketAddr;
+    use std::time::Duration;
+
+    #[tokio::test]
+    pub async fn sessions_are_drained() {
+        let registry = SessionRegistry::new();
+        let address: SocketAddr = "127.0.0.1:50000".parse().unwrap();
+
+        let mut first = registry.register(address);
+        let mut second = registry.register(address);
+        assert_ne!(first.id(), second.id());
+        assert_eq!(registry.len(), 2);
+
+        let mut first_messages = first.take_messages().unwrap();
+        let mut second_messages = second.take_messages().unwrap();
+        assert!(first.take_messages().is_none());
+
+        registry.drain_all();
+        assert_eq!(first_messages.try_recv(), Ok(SessionMessage::Drain));
+        assert!(first_messages.try_recv().is_err());
+        assert!(!registry.wait_until_empty(Duration::from_millis(10)).await);
+
+        registry.close_all();
+        assert_eq!(second_messages.try_recv(), Ok(SessionMessage::Drain));
+        assert_eq!(second_messages.try_recv(), Ok(SessionMessage::Close));
+
+        let drop_sessions = tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            drop(first);
+            drop(second);
+        });
+
+        assert!(registry.wait_until_empty(Duration::from_secs(1)).await);
+        assert!(registry.is_empty());
+        drop_sessions.await.unwrap();
+    }
+
+    #[test]
+    pub fn sessions_can_be_listed_and_kicked() {
+        let registry = SessionRegistry::new();
+        let mut first = registry.register("127.0.0.1:50000".parse().unwrap());
+        let mut second = registry.register("[::1]:50001".parse().unwrap());
+        let mut first_messages = first.take_messages().unwrap();
+        let mut second_messages = second.take_messages().unwrap();
+
+        second.set_state(SessionState::Authed);
+        second.set_account("PLAYER");
+
+        let sessions = registry.list();
+        assert_eq!(sessions.len(), 2);
+        assert_eq!(sessions[0].id, first.id());
+        assert_eq!(sessions[0].state, SessionState::Challenge);
+        assert_eq!(sessions[0].account, None);
+        assert_eq!(sessions[1].address.port(), 50001);
+        assert_eq!(sessions[1].state, SessionState::Authed);
+        assert_eq!(sessions[1].account.as_deref(), Some("PLAYER"));
+
+        assert!(registry.kick(first.id()));
+        assert_eq!(first_messages.try_recv(), Ok(SessionMessage::Close));
+        assert!(second_messages.try_recv().is_err());
+
+        let id = first.id();
+        drop(first);
+        assert!(!registry.kick(id));
+        assert_eq!(registry.len(), 1);
+    }
+
+    #[test]
+    pub fn session_state_from_u8() {
+        assert_eq!(SessionState::try_from(3), Ok(SessionState::Authed));
+        assert_eq!(SessionState::try_from(6), Err(6));
+    }
+}
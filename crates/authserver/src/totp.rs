@@ -0,0 +1,339 @@
+use aes_gcm::aead::consts::U12;
+use aes_gcm::aead::{AeadCore, AeadInPlace, KeyInit, OsRng};
+use aes_gcm::aes::Aes128;
+use aes_gcm::{AesGcm, Nonce, Tag};
+use anyhow::{Error, Result};
+use enturion_shared::config::Config;
+use hmac::{Hmac, Mac};
+use log::warn;
+use sha1::Sha1;
+use std::sync::{OnceLock, RwLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Length of the TOTP time steps, in seconds.
+const TOTP_INTERVAL: u64 = 30;
+const IV_LENGTH: usize = 12;
+
+/// Size added to a TOTP secret by its encryption: the trailing 12 bytes IV and 12 bytes tag.
+pub const TOTP_ENCRYPTION_OVERHEAD: usize = 24;
+
+/// AES-128-GCM with the 12 bytes tag used by `Kitron::Crypto::AES`.
+type Aes128Gcm12 = AesGcm<Aes128, U12, U12>;
+
+type MasterKey = [u8; 16];
+
+static TOTP: OnceLock<Totp> = OnceLock::new();
+
+/// Parses a master key, an hexadecimal integer of up to 128 bits.
+/// The key bytes are little endian, like `BigNumber::ToByteArray`.
+fn parse_master_key(key: &str) -> Result<MasterKey> {
+    u128::from_str_radix(key, 16)
+        .map(u128::to_le_bytes)
+        .map_err(|_| {
+            Error::msg("Invalid TOTP master key - specify a hexadecimal integer of up to 128 bits with no prefix")
+        })
+}
+
+/// Reads an optional master key from the configuration.
+fn master_key_from_config(config: &Config, key: &str) -> Result<Option<MasterKey>> {
+    match config.get::<Option<String>>(key, Some(None))? {
+        Some(value) if !value.trim().is_empty() => Ok(Some(parse_master_key(value.trim())?)),
+        _ => Ok(None),
+    }
+}
+
+/// Generates the 6 digits token of the given Unix timestamp, as described by RFC 6238.
+pub fn generate_token(secret: &[u8], timestamp: u64) -> u32 {
+    let mut mac = <Hmac<Sha1> as Mac>::new_from_slice(secret).expect("HMAC accepts any key length");
+    mac.update(&(timestamp / TOTP_INTERVAL).to_be_bytes());
+    let digest = mac.finalize().into_bytes();
+
+    let offset = (digest[19] & 0xF) as usize;
+    let truncated = u32::from_be_bytes(digest[offset..offset + 4].try_into().unwrap()) & 0x7FFFFFFF;
+    truncated % 1_000_000
+}
+
+/// Checks a token against the tokens of the `skew_window` time steps before and after the timestamp.
+pub fn validate_token(secret: &[u8], token: u32, timestamp: u64, skew_window: u32) -> bool {
+    let skew = skew_window as u64 * TOTP_INTERVAL;
+    (timestamp.saturating_sub(skew)..=timestamp.saturating_add(skew))
+        .step_by(TOTP_INTERVAL as usize)
+        .any(|timestamp| generate_token(secret, timestamp) == token)
+}
+
+/// Decrypts a secret followed by its IV and tag.
+fn decrypt(stored: &[u8], key: &MasterKey) -> Option<Vec<u8>> {
+    let data_length = stored.len().checked_sub(TOTP_ENCRYPTION_OVERHEAD)?;
+    let (data, trailer) = stored.split_at(data_length);
+    let (iv, tag) = trailer.split_at(IV_LENGTH);
+
+    let mut secret = data.to_vec();
+    Aes128Gcm12::new(key.into())
+        .decrypt_in_place_detached(
+            Nonce::from_slice(iv),
+            b"",
+            &mut secret,
+            Tag::from_slice(tag),
+        )
+        .ok()?;
+
+    Some(secret)
+}
+
+/// Encrypts a secret with a random IV, the IV and tag are appended to the result.
+fn encrypt(secret: &[u8], key: &MasterKey) -> Vec<u8> {
+    let iv = Aes128Gcm12::generate_nonce(&mut OsRng);
+    let mut encrypted = secret.to_vec();
+    let tag = Aes128Gcm12::new(key.into())
+        .encrypt_in_place_detached(&iv, b"", &mut encrypted)
+        .expect("TOTP secrets are small enough to be encrypted");
+
+    encrypted.extend_from_slice(&iv);
+    encrypted.extend_from_slice(&tag);
+    encrypted
+}
+
+/// A decrypted TOTP secret.
+#[derive(Debug, PartialEq, Eq)]
+pub struct DecryptedSecret {
+    pub secret: Vec<u8>,
+    /// The secret was stored with the old master key and should be stored again with `Totp::encrypt_secret`.
+    pub needs_rotation: bool,
+}
+
+/// The keys used to store the TOTP secrets in the database.
+/// Without master key, the secrets are stored in plain text.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct TotpKeys {
+    pub master: Option<MasterKey>,
+    /// The previous master key, to read the secrets not migrated yet.
+    pub old_master: Option<MasterKey>,
+}
+
+impl TotpKeys {
+    /// Decrypts a stored secret, trying the master key first then the old one.
+    pub fn decrypt(&self, stored: &[u8]) -> Result<DecryptedSecret> {
+        if let Some(secret) = self.master.as_ref().and_then(|key| decrypt(stored, key)) {
+            return Ok(DecryptedSecret {
+                secret,
+                needs_rotation: false,
+            });
+        }
+
+        match (self.master, self.old_master) {
+            (None, None) => Ok(DecryptedSecret {
+                secret: stored.to_vec(),
+                needs_rotation: false,
+            }),
+            (_, Some(old_master)) => decrypt(stored, &old_master)
+                .map(|secret| DecryptedSecret {
+                    secret,
+                    needs_rotation: true,
+                })
+                .ok_or_else(|| Error::msg("Invalid TOTP secret ciphertext")),
+            (Some(_), None) => Err(Error::msg("Invalid TOTP secret ciphertext")),
+        }
+    }
+
+    /// Encrypts a secret with the master key, for storage.
+    pub fn encrypt(&self, secret: &[u8]) -> Vec<u8> {
+        match &self.master {
+            Some(key) => encrypt(secret, key),
+            None => secret.to_vec(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct TotpSettings {
+    keys: TotpKeys,
+    skew_window: u32,
+}
+
+impl Default for TotpSettings {
+    fn default() -> Self {
+        Self {
+            keys: TotpKeys::default(),
+            skew_window: 1,
+        }
+    }
+}
+
+/// TOTP two-factor authentication of the accounts having a `totp_secret`.
+#[derive(Default)]
+pub struct Totp {
+    settings: RwLock<TotpSettings>,
+}
+
+impl Totp {
+    /// Gets the TOTP settings of the auth server.
+    pub fn global() -> &'static Totp {
+        TOTP.get_or_init(Totp::default)
+    }
+
+    /// Loads the `TOTPMasterSecret`, `TOTPOldMasterSecret` and `TOTPSkewWindow` settings.
+    pub fn configure(&self, config: &Config) -> Result<()> {
+        let settings = TotpSettings {
+            keys: TotpKeys {
+                master: master_key_from_config(config, "TOTPMasterSecret")?,
+                old_master: master_key_from_config(config, "TOTPOldMasterSecret")?,
+            },
+            skew_window: config.get("TOTPSkewWindow", Some(1_u32))?,
+        };
+
+        *self.settings.write().unwrap() = settings;
+        Ok(())
+    }
+
+    pub fn decrypt_secret(&self, stored: &[u8]) -> Result<DecryptedSecret> {
+        self.settings.read().unwrap().keys.decrypt(stored)
+    }
+
+    pub fn encrypt_secret(&self, secret: &[u8]) -> Vec<u8> {
+        self.settings.read().unwrap().keys.encrypt(secret)
+    }
+
+    /// Checks a token sent by the client against the current time.
+    pub fn validate_token(&self, secret: &[u8], token: u32) -> bool {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+
+        validate_token(
+            secret,
+            token,
+            now,
+            self.settings.read().unwrap().skew_window,
+        )
+    }
+}
+
+// C/C++ interface
+
+/// Result of `Totp_DecryptSecret`.
+#[repr(C)]
+#[allow(clippy::enum_variant_names)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TotpSecretResult {
+    TotpSecretOk,
+    /// The secret was decrypted with the old master key, it should be stored again.
+    TotpSecretRotated,
+    TotpSecretInvalid,
+}
+
+/// Decrypts a stored TOTP secret in place, length is updated with the length of the secret.
+///
+/// # Safety
+/// Unsafe: data must be `*length` bytes long.
+#[no_mangle]
+pub unsafe extern "C" fn Totp_DecryptSecret(data: *mut u8, length: *mut usize) -> TotpSecretResult {
+    let data = std::slice::from_raw_parts_mut(data, *length);
+    match Totp::global().decrypt_secret(data) {
+        Ok(decrypted) => {
+            data[..decrypted.secret.len()].copy_from_slice(&decrypted.secret);
+            *length = decrypted.secret.len();
+            match decrypted.needs_rotation {
+                true => TotpSecretResult::TotpSecretRotated,
+                false => TotpSecretResult::TotpSecretOk,
+            }
+        }
+        Err(e) => {
+            warn!(target: "server::authserver", "{}", e);
+            TotpSecretResult::TotpSecretInvalid
+        }
+    }
+}
+
+/// Encrypts a TOTP secret with the master key, for storage.
+/// Returns the length of the encrypted secret.
+///
+/// # Safety
+/// Unsafe: secret must be `length` bytes long, encrypted must be at least
+/// `length + TOTP_ENCRYPTION_OVERHEAD` bytes long.
+#[no_mangle]
+pub unsafe extern "C" fn Totp_EncryptSecret(
+    secret: *const u8,
+    length: usize,
+    encrypted: *mut u8,
+) -> usize {
+    let secret = std::slice::from_raw_parts(secret, length);
+    let result = Totp::global().encrypt_secret(secret);
+    std::ptr::copy_nonoverlapping(result.as_ptr(), encrypted, result.len());
+
+    result.len()
+}
+
+/// Checks a token sent by the client.
+///
+/// # Safety
+/// Unsafe: secret must be `length` bytes long.
+#[no_mangle]
+pub unsafe extern "C" fn Totp_ValidateToken(secret: *const u8, length: usize, token: u32) -> bool {
+    let secret = std::slice::from_raw_parts(secret, length);
+    Totp::global().validate_token(secret, token)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::totp::{
+        generate_token, parse_master_key, validate_token, DecryptedSecret, TotpKeys,
+        TOTP_ENCRYPTION_OVERHEAD,
+    };
+
+    const SECRET: &[u8] = b"12345678901234567890";
+
+    #[test]
+    pub fn rfc6238_tokens() {
+        assert_eq!(generate_token(SECRET, 59), 287082);
+        assert_eq!(generate_token(SECRET, 1111111109), 81804);
+        assert_eq!(generate_token(SECRET, 1111111111), 50471);
+        assert_eq!(generate_token(SECRET, 1234567890), 5924);
+        assert_eq!(generate_token(SECRET, 2000000000), 279037);
+
+        assert!(validate_token(SECRET, 287082, 59, 0));
+        assert!(!validate_token(SECRET, 287082, 89, 0));
+        assert!(validate_token(SECRET, 287082, 89, 1));
+        assert!(validate_token(SECRET, 287082, 0, 1));
+        assert!(!validate_token(SECRET, 287082, 119, 1));
+    }
+
+    #[test]
+    pub fn secrets_are_rotated() {
+        let old_master = parse_master_key("0123456789ABCDEF").unwrap();
+        assert_eq!(old_master[0], 0xEF);
+        assert_eq!(old_master[8..], [0; 8]);
+        assert!(parse_master_key("0x1234").is_err());
+
+        let old = TotpKeys {
+            master: Some(old_master),
+            old_master: None,
+        };
+        let stored = old.encrypt(SECRET);
+        assert_eq!(stored.len(), SECRET.len() + TOTP_ENCRYPTION_OVERHEAD);
+
+        let new = TotpKeys {
+            master: Some(parse_master_key("FEDCBA9876543210").unwrap()),
+            old_master: Some(old_master),
+        };
+        let decrypted = new.decrypt(&stored).unwrap();
+        assert_eq!(
+            decrypted,
+            DecryptedSecret {
+                secret: SECRET.to_vec(),
+                needs_rotation: true,
+            }
+        );
+
+        let stored = new.encrypt(&decrypted.secret);
+        assert!(!new.decrypt(&stored).unwrap().needs_rotation);
+        assert!(old.decrypt(&stored).is_err());
+
+        let mut tampered = stored.clone();
+        tampered[0] ^= 1;
+        assert!(new.decrypt(&tampered).is_err());
+
+        let plain = TotpKeys::default();
+        assert_eq!(plain.decrypt(SECRET).unwrap().secret, SECRET);
+    }
+}
@@ -1,39 +1,539 @@
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::{quote, ToTokens};
-use syn::{parse_macro_input, DeriveInput};
+use syn::parse::Parser;
+use syn::punctuated::Punctuated;
+use syn::{parse_macro_input, DeriveInput, Expr, Meta, MetaNameValue, Token};
 
-fn wow_packet(
-    input: proc_macro::TokenStream,
-    session_struct: TokenStream,
-) -> proc_macro::TokenStream {
-    let cloned = input.clone();
-    let ast = parse_macro_input!(cloned as DeriveInput);
-    let name = ast.ident;
+/// Encoding of a packet field, set with the `#[wow(...)]` field attribute.
+enum FieldEncoding {
+    /// Plain bincode encoding.
+    Plain,
+    /// `#[wow(c_string)]`: NUL terminated `String` or `Vec<u8>`.
+    CString,
+    /// `#[wow(prefix = u8)]`: `String` or `Vec<u8>` preceded by its length.
+    Prefixed(Box<syn::Type>),
+    /// `#[wow(fixed = 4)]`: `String` or `Vec<u8>` padded with NUL bytes.
+    Fixed(syn::LitInt),
+    /// `#[wow(size)]`: integer set to the encoded size of the following fields.
+    Size,
+}
+
+impl FieldEncoding {
+    fn parse(field: &syn::Field) -> syn::Result<Self> {
+        let mut encoding = FieldEncoding::Plain;
+        for attr in field
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("wow"))
+        {
+            attr.parse_nested_meta(|meta| {
+                encoding = if meta.path.is_ident("c_string") {
+                    FieldEncoding::CString
+                } else if meta.path.is_ident("prefix") {
+                    FieldEncoding::Prefixed(Box::new(meta.value()?.parse()?))
+                } else if meta.path.is_ident("fixed") {
+                    FieldEncoding::Fixed(meta.value()?.parse()?)
+                } else if meta.path.is_ident("size") {
+                    FieldEncoding::Size
+                } else {
+                    return Err(meta.error("unknown wow field attribute"));
+                };
+
+                Ok(())
+            })?;
+        }
+
+        Ok(encoding)
+    }
+
+    /// Whether the field is stored as raw bytes.
+    fn is_bytes(&self) -> bool {
+        matches!(
+            self,
+            FieldEncoding::CString | FieldEncoding::Prefixed(_) | FieldEncoding::Fixed(_)
+        )
+    }
+}
+
+/// What the packet attributes of a protocol generate.
+struct Protocol {
+    /// Module with the `BINCODE_CONFIGURATION` of the packets.
+    packet_module: TokenStream,
+    /// Name of the attribute argument identifying a packet, e.g. `client = AuthLogonChallenge`.
+    id_argument: &'static str,
+    /// The trait implementation of an identified packet.
+    id_impl: fn(&Ident, &Ident, &TokenStream) -> TokenStream,
+    /// Whether an identified packet is only sent by the client, so has no outbound functions.
+    is_client: fn(&Ident) -> bool,
+    /// Statements sending the packet expression of the given type through the `session` pointer.
+    send: fn(&Ident, &TokenStream) -> TokenStream,
+}
+
+struct PacketField {
+    name: Ident,
+    ty: syn::Type,
+    encoding: FieldEncoding,
+}
+
+/// Statements encoding the given fields of `self` into `encoder`.
+fn encode_fields(fields: &[PacketField]) -> TokenStream {
+    let Some((field, rest)) = fields.split_first() else {
+        return quote! {};
+    };
+
+    let name = &field.name;
+    let ty = &field.ty;
+    let fields_module = quote! { ::enturion_shared::net::fields };
+    let statement = match &field.encoding {
+        FieldEncoding::Plain => quote! { ::bincode::Encode::encode(&self.#name, encoder)?; },
+        FieldEncoding::CString => {
+            quote! { #fields_module::encode_c_string(&self.#name, encoder)?; }
+        }
+        FieldEncoding::Prefixed(prefix) => {
+            quote! { #fields_module::encode_prefixed::<#prefix, _, _>(&self.#name, encoder)?; }
+        }
+        FieldEncoding::Fixed(length) => {
+            quote! { #fields_module::encode_fixed(&self.#name, #length, encoder)?; }
+        }
+        FieldEncoding::Size => {
+            let encode_rest = encode_fields(rest);
+            return quote! {
+                let size = {
+                    let config = *::bincode::enc::Encoder::config(encoder);
+                    let mut sizer = ::bincode::enc::EncoderImpl::new(
+                        ::bincode::enc::write::SizeWriter::default(),
+                        config,
+                    );
+                    {
+                        let encoder = &mut sizer;
+                        #encode_rest
+                    }
+                    sizer.into_writer().bytes_written
+                };
+                ::bincode::Encode::encode(
+                    &<#ty>::try_from(size).map_err(|_| {
+                        ::bincode::error::EncodeError::Other("packet too large for its size field")
+                    })?,
+                    encoder,
+                )?;
+                #encode_rest
+            };
+        }
+    };
+
+    let encode_rest = encode_fields(rest);
+    quote! {
+        #statement
+        #encode_rest
+    }
+}
+
+/// Expression decoding a field from `decoder`.
+fn decode_field(field: &PacketField) -> TokenStream {
+    let fields_module = quote! { ::enturion_shared::net::fields };
+    match &field.encoding {
+        FieldEncoding::Plain | FieldEncoding::Size => {
+            quote! { ::bincode::Decode::decode(decoder)? }
+        }
+        FieldEncoding::CString => quote! { #fields_module::decode_c_string(decoder)? },
+        FieldEncoding::Prefixed(prefix) => {
+            quote! { #fields_module::decode_prefixed::<#prefix, _, _>(decoder)? }
+        }
+        FieldEncoding::Fixed(length) => quote! { #fields_module::decode_fixed(decoder, #length)? },
+    }
+}
+
+/// Expression of the encoded size of the given fields of `self`, as a `Result`.
+fn fields_size(fields: &[PacketField], packet_module: &TokenStream) -> TokenStream {
+    let encode = encode_fields(fields);
+    quote! {
+        (|| {
+            let mut sizer = ::bincode::enc::EncoderImpl::new(
+                ::bincode::enc::write::SizeWriter::default(),
+                #packet_module::BINCODE_CONFIGURATION,
+            );
+            {
+                let encoder = &mut sizer;
+                #encode
+            }
+            Ok::<_, ::bincode::error::EncodeError>(sizer.into_writer().bytes_written)
+        })()
+    }
+}
+
+/// The bincode implementations of a packet with `#[wow(...)]` fields.
+fn packet_codec(name: &Ident, fields: &[PacketField], packet_module: &TokenStream) -> TokenStream {
+    let encode = encode_fields(fields);
+    let field_names = fields.iter().map(|field| &field.name);
+    let decoders = fields.iter().map(decode_field);
+
+    let size_fields = fields
+        .iter()
+        .enumerate()
+        .filter(|(_, field)| matches!(field.encoding, FieldEncoding::Size))
+        .map(|(i, field)| (field, fields_size(&fields[i + 1..], packet_module)))
+        .collect::<Vec<_>>();
+    let computed_sizes = size_fields.iter().map(|(field, size)| {
+        let name = &field.name;
+        let ty = &field.ty;
+        quote! {
+            self.#name = <#ty>::try_from(#size?).map_err(|_| {
+                ::bincode::error::EncodeError::Other("packet too large for its size field")
+            })?;
+        }
+    });
+    let checked_sizes = size_fields.iter().map(|(field, size)| {
+        let name = &field.name;
+        quote! {
+            let size = #size.map_err(|_| {
+                ::bincode::error::DecodeError::Other("packet fields that cannot be encoded")
+            })?;
+            if usize::try_from(self.#name).ok() != Some(size) {
+                return Err(::bincode::error::DecodeError::Other(
+                    "size field not matching the packet length",
+                ));
+            }
+        }
+    });
+
+    let (compute_sizes, check_sizes) = if size_fields.is_empty() {
+        (quote! {}, quote! {})
+    } else {
+        (
+            quote! {
+                impl #name {
+                    /// Sets the size fields to the encoded size of the fields following them.
+                    /// Encoding always writes the computed sizes, whatever the field values.
+                    pub fn compute_sizes(&mut self) -> ::std::result::Result<(), ::bincode::error::EncodeError> {
+                        #(#computed_sizes)*
+                        Ok(())
+                    }
+
+                    /// Checks that the decoded size fields match the size of the fields following them.
+                    fn check_sizes(&self) -> ::std::result::Result<(), ::bincode::error::DecodeError> {
+                        #(#checked_sizes)*
+                        Ok(())
+                    }
+                }
+            },
+            quote! { packet.check_sizes()?; },
+        )
+    };
+
+    quote! {
+        impl ::bincode::Encode for #name {
+            fn encode<E: ::bincode::enc::Encoder>(
+                &self,
+                encoder: &mut E,
+            ) -> ::std::result::Result<(), ::bincode::error::EncodeError> {
+                #encode
+                Ok(())
+            }
+        }
+
+        impl ::bincode::Decode for #name {
+            fn decode<D: ::bincode::de::Decoder>(
+                decoder: &mut D,
+            ) -> ::std::result::Result<Self, ::bincode::error::DecodeError> {
+                let packet = Self {
+                    #(#field_names: #decoders),*
+                };
+                #check_sizes
+                Ok(packet)
+            }
+        }
+
+        ::bincode::impl_borrow_decode!(#name);
+
+        #compute_sizes
+    }
+}
+
+/// `snake_case` to `PascalCase`.
+fn pascal_case(name: &str) -> String {
+    name.split('_')
+        .map(|part| {
+            let mut chars = part.chars();
+            chars.next().map_or(String::new(), |first| {
+                first.to_uppercase().chain(chars).collect()
+            })
+        })
+        .collect()
+}
+
+/// C accessors of a packet with `#[wow(...)]` fields, which cannot be passed by value.
+/// Packets are heap allocated and only handled through pointers.
+fn packet_accessors(
+    name: &Ident,
+    fields: &[PacketField],
+    client: bool,
+    protocol: &Protocol,
+) -> TokenStream {
+    let packet_module = &protocol.packet_module;
+    let fn_name = |suffix: &str| Ident::new(&format!("{}_{}", name, suffix), Span::call_site());
+    let decode_fn_name = fn_name("Decode");
+    let free_fn_name = fn_name("Free");
+
+    let getters = fields.iter().map(|field| {
+        let field_name = &field.name;
+        let getter_name = fn_name(&format!("Get{}", pascal_case(&field_name.to_string())));
+        let ty = &field.ty;
+        if field.encoding.is_bytes() {
+            quote! {
+                /// The returned bytes are not NUL terminated and live as long as the packet.
+                #[no_mangle]
+                pub unsafe extern "C" fn #getter_name(this: *const Self, length: *mut usize) -> *const u8 {
+                    let bytes = ::enturion_shared::net::fields::FieldBytes::field_bytes(&(*this).#field_name);
+                    *length = bytes.len();
+                    bytes.as_ptr()
+                }
+            }
+        } else if let syn::Type::Array(_) = ty {
+            quote! {
+                #[no_mangle]
+                pub unsafe extern "C" fn #getter_name(this: *const Self) -> *const u8 {
+                    (*this).#field_name.as_ptr().cast()
+                }
+            }
+        } else {
+            quote! {
+                #[no_mangle]
+                pub unsafe extern "C" fn #getter_name(this: *const Self) -> #ty {
+                    (*this).#field_name
+                }
+            }
+        }
+    });
+
+    let outbound = if client {
+        quote! {}
+    } else {
+        let new_fn_name = fn_name("New");
+        let send_fn_name = fn_name("Send");
+        let mut arguments = vec![];
+        let mut initializers = vec![];
+        for field in fields {
+            let field_name = &field.name;
+            let ty = &field.ty;
+            match &field.encoding {
+                FieldEncoding::Size => initializers.push(quote! { #field_name: 0 }),
+                encoding if encoding.is_bytes() => {
+                    let length = Ident::new(&format!("{}_length", field_name), Span::call_site());
+                    arguments.push(quote! { #field_name: *const u8, #length: usize });
+                    initializers.push(quote! {
+                        #field_name: match ::enturion_shared::net::fields::FieldBytes::from_field_bytes(
+                            ::std::slice::from_raw_parts(#field_name, #length).to_vec(),
+                        ) {
+                            Ok(value) => value,
+                            Err(_) => return ::std::ptr::null_mut(),
+                        }
+                    });
+                }
+                _ => {
+                    if let syn::Type::Array(_) = ty {
+                        arguments.push(quote! { #field_name: *const u8 });
+                        initializers.push(
+                            quote! { #field_name: ::std::ptr::read(#field_name.cast::<#ty>()) },
+                        );
+                    } else {
+                        arguments.push(quote! { #field_name: #ty });
+                        initializers.push(quote! { #field_name });
+                    }
+                }
+            }
+        }
+
+        let has_size = fields
+            .iter()
+            .any(|field| matches!(field.encoding, FieldEncoding::Size));
+        let compute_sizes = if has_size {
+            quote! {
+                if packet.compute_sizes().is_err() {
+                    return ::std::ptr::null_mut();
+                }
+            }
+        } else {
+            quote! {}
+        };
+
+        let send = (protocol.send)(name, &quote! { packet });
+        quote! {
+            /// Returns a null pointer if a field is invalid.
+            /// The packet must be sent with the send function or freed with the free function.
+            #[no_mangle]
+            pub unsafe extern "C" fn #new_fn_name(#(#arguments),*) -> *mut Self {
+                let mut packet = Self {
+                    #(#initializers),*
+                };
+                #compute_sizes
+                ::std::boxed::Box::into_raw(::std::boxed::Box::new(packet))
+            }
+
+            /// Sends and frees the packet.
+            #[no_mangle]
+            pub unsafe extern "C" fn #send_fn_name(this: *mut Self, session: *const ::std::ffi::c_void) {
+                let packet = *::std::boxed::Box::from_raw(this);
+                #send
+            }
+        }
+    };
+
+    quote! {
+        impl #name {
+            /// Decodes a packet from the start of the data, consumed is set to its size.
+            /// Returns a null pointer if the packet is incomplete or invalid.
+            /// The packet must be freed with the free function.
+            #[no_mangle]
+            pub unsafe extern "C" fn #decode_fn_name(data: *const u8, size: usize, consumed: *mut usize) -> *mut Self {
+                let data = ::std::slice::from_raw_parts(data, size);
+                match ::bincode::decode_from_slice::<Self, _>(data, #packet_module::BINCODE_CONFIGURATION) {
+                    Ok((packet, size)) => {
+                        *consumed = size;
+                        ::std::boxed::Box::into_raw(::std::boxed::Box::new(packet))
+                    }
+                    Err(_) => {
+                        *consumed = 0;
+                        ::std::ptr::null_mut()
+                    }
+                }
+            }
+
+            #[no_mangle]
+            pub unsafe extern "C" fn #free_fn_name(this: *mut Self) {
+                if !this.is_null() {
+                    drop(::std::boxed::Box::from_raw(this));
+                }
+            }
+
+            #(#getters)*
+
+            #outbound
+        }
+    }
+}
+
+fn wow_packet(args: TokenStream, input: TokenStream, protocol: Protocol) -> TokenStream {
+    // `no_ffi` skips the C functions, for packets with non FFI-safe fields.
+    // `<id argument> = <Id>` identifies the packet, e.g. `client = <Command>` declares an auth
+    // packet sent by the client, decoded by the handler of the command.
+    let args = match Punctuated::<Meta, Token![,]>::parse_terminated.parse2(args) {
+        Ok(args) => args,
+        Err(e) => return e.to_compile_error(),
+    };
+
+    let mut ffi = true;
+    let mut packet_id = None;
+    for arg in args {
+        match &arg {
+            Meta::Path(path) if path.is_ident("no_ffi") => ffi = false,
+            Meta::NameValue(MetaNameValue {
+                path,
+                value: Expr::Path(id),
+                ..
+            }) if path.is_ident(protocol.id_argument) && id.path.get_ident().is_some() => {
+                packet_id = id.path.get_ident().cloned()
+            }
+            _ => return syn::Error::new_spanned(arg, "unknown packet argument").to_compile_error(),
+        }
+    }
+
+    let packet_module = &protocol.packet_module;
+    let client = packet_id.as_ref().is_some_and(protocol.is_client);
+
+    let mut ast = match syn::parse2::<DeriveInput>(input) {
+        Ok(ast) => ast,
+        Err(e) => return e.to_compile_error(),
+    };
+    let name = ast.ident.clone();
 
     let fields = if let syn::Data::Struct(syn::DataStruct {
-        fields: syn::Fields::Named(ref fields),
+        fields: syn::Fields::Named(ref mut fields),
         ..
     }) = ast.data
     {
         fields
     } else {
-        panic!("Only support Struct")
+        return syn::Error::new_spanned(&ast.ident, "only structs with named fields are supported")
+            .to_compile_error();
     };
 
-    let mut field_names = vec![];
-    let mut field_types = vec![];
-    for field in fields.named.iter() {
-        let field_name = field.ident.as_ref().unwrap();
-        let ty = &field.ty;
-        let type_name = ty.to_token_stream();
+    let mut packet_fields = vec![];
+    for field in fields.named.iter_mut() {
+        let encoding = match FieldEncoding::parse(field) {
+            Ok(encoding) => encoding,
+            Err(e) => return e.to_compile_error(),
+        };
 
-        field_names.push(field_name);
-        field_types.push(type_name);
+        // The field attributes are only meant for this macro
+        field.attrs.retain(|attr| !attr.path().is_ident("wow"));
+        packet_fields.push(PacketField {
+            name: field.ident.clone().unwrap(),
+            ty: field.ty.clone(),
+            encoding,
+        });
     }
 
+    let input = ast.to_token_stream();
+    let id_impl = packet_id
+        .as_ref()
+        .map(|id| (protocol.id_impl)(&name, id, packet_module));
+
+    let has_attributes = packet_fields
+        .iter()
+        .any(|field| !matches!(field.encoding, FieldEncoding::Plain));
+    if has_attributes {
+        let codec = packet_codec(&name, &packet_fields, packet_module);
+        let accessors = if ffi {
+            packet_accessors(&name, &packet_fields, client, &protocol)
+        } else {
+            quote! {}
+        };
+
+        let output = quote! {
+            #input
+
+            #codec
+            impl ::enturion_shared::net::WoWPacket for #name {}
+            #id_impl
+            #accessors
+        };
+
+        return output;
+    }
+
+    if client {
+        let output = quote! {
+            #[derive(::bincode::Encode, ::bincode::Decode)]
+            #input
+
+            impl ::enturion_shared::net::WoWPacket for #name {}
+            #id_impl
+        };
+
+        return output;
+    }
+
+    if !ffi {
+        let output = quote! {
+            #[derive(::bincode::Encode, ::bincode::Decode)]
+            #[must_use]
+            #input
+
+            impl ::enturion_shared::net::WoWPacket for #name {}
+            #id_impl
+        };
+
+        return output;
+    }
+
+    let field_names = packet_fields
+        .iter()
+        .map(|field| &field.name)
+        .collect::<Vec<_>>();
+    let field_types = packet_fields.iter().map(|field| &field.ty);
     let new_fn_name = Ident::new(&format!("{}_New", name), Span::call_site());
     let send_fn_name = Ident::new(&format!("{}_Send", name), Span::call_site());
-    let input: TokenStream = input.into();
+    let send = (protocol.send)(&name, &quote! { self });
     let output = quote! {
         #[derive(::bincode::Encode, ::bincode::Decode)]
         #[repr(C)]
@@ -41,6 +541,7 @@ fn wow_packet(
         #input
 
         impl ::enturion_shared::net::WoWPacket for #name {}
+        #id_impl
         impl #name {
             #[no_mangle]
             pub unsafe extern "C" fn #new_fn_name(#(#field_names: #field_types),*) -> Self {
@@ -51,22 +552,224 @@ fn wow_packet(
 
             #[no_mangle]
             pub unsafe extern "C" fn #send_fn_name(self, session: *const ::std::ffi::c_void) {
-                let session = std::mem::transmute::<_, #session_struct>(session);
-                let _ = ::tokio::runtime::Handle::try_current().unwrap().spawn(async move {
-                    let _ = ::enturion_shared::net::Session::send_packet::<#name>(session, self).await;
-                });
+                #send
             }
         }
     };
 
-    output.into()
+    output
+}
+
+fn auth_protocol() -> Protocol {
+    Protocol {
+        packet_module: quote! { ::enturion_authserver::packet },
+        id_argument: "client",
+        id_impl: |name, command, packet_module| {
+            quote! {
+                impl #packet_module::ClientPacket for #name {
+                    const COMMAND: #packet_module::AuthCommand = #packet_module::AuthCommand::#command;
+                }
+            }
+        },
+        is_client: |_| true,
+        send: |_, packet| {
+            quote! {
+                let session = &*(session as *const ::enturion_authserver::auth_session::AuthSessionHandle);
+                if let Err(e) = session.send_packet(&#packet) {
+                    ::log::error!(target: "session", "Cannot encode packet: {}", e);
+                }
+            }
+        },
+    }
 }
 
 #[proc_macro_attribute]
 pub fn wow_auth_packet(
-    _args: proc_macro::TokenStream,
+    args: proc_macro::TokenStream,
+    input: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    wow_packet(args.into(), input.into(), auth_protocol()).into()
+}
+
+fn world_protocol() -> Protocol {
+    Protocol {
+        packet_module: quote! { ::enturion_worldserver::packet },
+        id_argument: "opcode",
+        id_impl: |name, opcode, packet_module| {
+            quote! {
+                impl #packet_module::WorldPacket for #name {
+                    const OPCODE: ::enturion_worldserver::opcodes::Opcode =
+                        ::enturion_worldserver::opcodes::Opcode::#opcode;
+                }
+            }
+        },
+        is_client: |opcode| opcode.to_string().starts_with("CMSG_"),
+        send: |_, packet| {
+            quote! {
+                let control = &*(session as *const ::enturion_worldserver::world_socket::WorldSocketControl);
+                if let Err(e) = control.send_packet(&#packet) {
+                    ::log::error!(target: "network", "Cannot encode packet: {}", e);
+                }
+            }
+        },
+    }
+}
+
+/// A world packet, `#[wow_world_packet(opcode = <Opcode>)]`. Packets with a `CMSG_` opcode are
+/// only decoded, the others can also be built and sent from C++ through the control handle of
+/// a world socket.
+#[proc_macro_attribute]
+pub fn wow_world_packet(
+    args: proc_macro::TokenStream,
     input: proc_macro::TokenStream,
 ) -> proc_macro::TokenStream {
-    let session_type = quote! { &mut ::enturion_authserver::auth_session::AuthSession };
-    wow_packet(input, session_type)
+    wow_packet(args.into(), input.into(), world_protocol()).into()
+}
+
+/// Implements `TryFrom<u8>` and the bincode traits for a `#[repr(u8)]` enum.
+/// Decoding fails on values that are not a variant of the enum.
+#[proc_macro_derive(WowEnum)]
+pub fn wow_enum(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    let name = ast.ident;
+    let variants = if let syn::Data::Enum(syn::DataEnum { ref variants, .. }) = ast.data {
+        variants
+            .iter()
+            .map(|variant| {
+                if !matches!(variant.fields, syn::Fields::Unit) {
+                    panic!("Only support unit variants")
+                }
+                &variant.ident
+            })
+            .collect::<Vec<_>>()
+    } else {
+        panic!("Only support Enum")
+    };
+
+    let type_name = name.to_string();
+    let output = quote! {
+        impl ::std::convert::TryFrom<u8> for #name {
+            type Error = u8;
+
+            fn try_from(value: u8) -> ::std::result::Result<Self, Self::Error> {
+                #(
+                    if value == #name::#variants as u8 {
+                        return Ok(#name::#variants);
+                    }
+                )*
+
+                Err(value)
+            }
+        }
+
+        impl ::bincode::Encode for #name {
+            fn encode<E: ::bincode::enc::Encoder>(
+                &self,
+                encoder: &mut E,
+            ) -> ::std::result::Result<(), ::bincode::error::EncodeError> {
+                ::bincode::Encode::encode(&(*self as u8), encoder)
+            }
+        }
+
+        impl ::bincode::Decode for #name {
+            fn decode<D: ::bincode::de::Decoder>(
+                decoder: &mut D,
+            ) -> ::std::result::Result<Self, ::bincode::error::DecodeError> {
+                static ALLOWED: ::bincode::error::AllowedEnumVariants =
+                    ::bincode::error::AllowedEnumVariants::Allowed(&[#(#name::#variants as u8 as u32),*]);
+
+                let repr: u8 = ::bincode::Decode::decode(decoder)?;
+                #name::try_from(repr).map_err(|found| ::bincode::error::DecodeError::UnexpectedVariant {
+                    type_name: #type_name,
+                    allowed: &ALLOWED,
+                    found: found as u32,
+                })
+            }
+        }
+
+        ::bincode::impl_borrow_decode!(#name);
+    };
+
+    output.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{auth_protocol, world_protocol, wow_packet, Protocol};
+    use proc_macro2::TokenStream;
+    use quote::quote;
+
+    /// Expands a packet attribute, without whitespace to ease the comparisons.
+    fn expand(protocol: Protocol, args: TokenStream, input: TokenStream) -> String {
+        wow_packet(args, input, protocol)
+            .to_string()
+            .replace(' ', "")
+    }
+
+    #[test]
+    pub fn packet_id_is_parsed() {
+        let output = expand(
+            auth_protocol(),
+            quote! { client = RealmList },
+            quote! { pub struct RealmListRequest { pub padding: u32 } },
+        );
+        assert!(
+            output.contains("impl::enturion_authserver::packet::ClientPacketforRealmListRequest")
+        );
+        assert!(output.contains("::enturion_authserver::packet::AuthCommand::RealmList;"));
+        assert!(!output.contains("RealmListRequest_Send"));
+
+        let output = expand(
+            world_protocol(),
+            quote! { no_ffi, opcode = SMSG_PONG, },
+            quote! { pub struct Pong { pub serial: u32 } },
+        );
+        assert!(output.contains("::enturion_worldserver::opcodes::Opcode::SMSG_PONG;"));
+        assert!(!output.contains("Pong_New"));
+    }
+
+    #[test]
+    pub fn auth_packets_are_sent_through_the_session_handle() {
+        let output = expand(
+            auth_protocol(),
+            TokenStream::new(),
+            quote! { pub struct LogonChallengeResponse { pub result: u8 } },
+        );
+        assert!(
+            output.contains("LogonChallengeResponse_Send(self,session:*const::std::ffi::c_void)")
+        );
+        assert!(output
+            .contains("&*(sessionas*const::enturion_authserver::auth_session::AuthSessionHandle)"));
+        assert!(!output.contains("&mut"));
+        assert!(!output.contains("spawn"));
+    }
+
+    #[test]
+    pub fn invalid_arguments_are_compile_errors() {
+        let input = quote! { pub struct RealmListRequest { pub padding: u32 } };
+        for args in [
+            quote! { server = RealmList },
+            quote! { client = 1 },
+            quote! { client = auth::RealmList },
+            quote! { client },
+            quote! { client = },
+            quote! { no_ffi(true) },
+            quote! { opcode = SMSG_PONG },
+        ] {
+            let output = expand(auth_protocol(), args.clone(), input.clone());
+            assert!(
+                output.starts_with("::core::compile_error!"),
+                "{}: {}",
+                args,
+                output
+            );
+        }
+
+        let output = expand(
+            auth_protocol(),
+            TokenStream::new(),
+            quote! { pub enum AuthCommand { RealmList } },
+        );
+        assert!(output.starts_with("::core::compile_error!"));
+    }
 }
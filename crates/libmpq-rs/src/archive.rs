@@ -140,7 +140,7 @@ impl Archive {
 
             // file if encrypted, generate decryption key
             if block.flags & FILE_ENCRYPTED != 0 {
-                match filename.split(&['\\', '/'][..]).last() {
+                match filename.split(&['\\', '/'][..]).next_back() {
                     Some(basename) => file_key = hash_string(basename, 0x300),
                     None => {
                         return Err(Error::Other("Unable to extract filename from path"));
@@ -175,6 +175,7 @@ impl File {
 
         let mut file = fs::OpenOptions::new()
             .create(true)
+            .truncate(false)
             .write(true)
             .open(&path)
             .await
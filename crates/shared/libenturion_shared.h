@@ -30,15 +30,30 @@ enum DbcFieldFormat
 typedef uint8_t DbcFieldFormat;
 #endif // __cplusplus
 
+/**
+ * The loaded configuration and what is needed to reload it.
+ */
+typedef struct ConfigInner ConfigInner;
+
+/**
+ * The logger of the servers. The `Appender.*` and `Logger.*` settings are applied again when
+ * they are reloaded.
+ */
 typedef struct LogMgr LogMgr;
 
+/**
+ * Wakes a task up to process pending C++ work, e.g. when a database query completed.
+ * C++ gets handles on it through the FFI of the task owner, and can use them from any thread.
+ */
+typedef struct Notifier Notifier;
+
 typedef struct Config {
 #if defined(FFI_CONFIG)
   const void *inner
 #endif
   ;
 #if !defined(FFI_CONFIG)
-  struct Config inner
+  struct ConfigInner *inner
 #endif
   ;
 } Config;
@@ -126,6 +141,17 @@ int64_t Config_GetIntDefault(const struct Config *self, const char *name, int64_
 double Config_GetFloatDefault(const struct Config *self, const char *name, double default_);
 #endif
 
+#if defined(FFI_CONFIG)
+/**
+ * Reloads the configuration, see [`Config::reload`].
+ * If reload error is not NULL, the configuration could not be reloaded and is unchanged.
+ *
+ * # Safety
+ * Unsafe: reload error must be freed after use.
+ */
+void Config_Reload(const struct Config *self, char **reload_error);
+#endif
+
 #if defined(FFI_CONFIG)
 /**
  * De-allocates the current configuration, freeing memory
@@ -161,6 +187,22 @@ const struct LogMgr *LogMgr_Initialize(const struct Config *config);
 void LogMgr_Free(struct LogMgr *logmgr);
 #endif
 
+/**
+ * Wakes the task of the notifier up. Can be called from any thread.
+ *
+ * # Safety
+ * Unsafe: notifier must be a handle that is not freed yet.
+ */
+void Notifier_Notify(const struct Notifier *notifier);
+
+/**
+ * Frees a notifier handle.
+ *
+ * # Safety
+ * Unsafe: notifier must be a handle that is not freed yet.
+ */
+void Notifier_Free(const struct Notifier *notifier);
+
 void CreateTimer(uint64_t interval_seconds, TimerCallback callback);
 
 #if defined(_WIN32)
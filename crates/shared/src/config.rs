@@ -1,21 +1,197 @@
 use crate::error::DummyError;
-use anyhow::{Error, Result};
+use anyhow::{Context, Error, Result};
 use config::builder::DefaultState;
-use config::{Case, ConfigBuilder, Environment, File, FileFormat, Source, Value, ValueKind};
+use config::{
+    Case, ConfigBuilder, ConfigError, Environment, File, FileFormat, Map, Source, Value, ValueKind,
+};
 use glob::glob;
-use serde::Deserialize;
-use std::ffi::{c_char, c_void, CStr, CString};
+use log::{debug, info};
+use serde::de::{DeserializeOwned, Visitor};
+use serde::{forward_to_deserialize_any, Deserialize, Deserializer};
+use std::collections::BTreeMap;
+#[cfg(feature = "ffi_config")]
+use std::ffi::c_void;
+use std::ffi::{c_char, CStr, CString};
+use std::fs;
 use std::mem::ManuallyDrop;
 use std::path::PathBuf;
 use std::ptr::null_mut;
-use std::{fs, ptr};
+use std::sync::{Arc, Mutex, RwLock};
+
+pub mod schema;
+
+type DefaultSource = Box<dyn Fn(&mut ConfigBuilder<DefaultState>) -> Result<()> + Send + Sync>;
+type Subscriber = (
+    Vec<String>,
+    Arc<dyn Fn(&Config, &[ConfigChange]) + Send + Sync>,
+);
+
+/// A value changed by a reload, `None` being a missing value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigChange {
+    pub key: String,
+    pub old: Option<String>,
+    pub new: Option<String>,
+}
+
+/// The loaded configuration and what is needed to reload it.
+struct ConfigInner {
+    conf_dir: String,
+    default_source: DefaultSource,
+    current: RwLock<Arc<config::Config>>,
+    subscribers: Mutex<Vec<Subscriber>>,
+}
 
 #[repr(C)]
 pub struct Config {
     #[cfg(feature = "ffi_config")]
     inner: *const c_void,
     #[cfg(not(feature = "ffi_config"))]
-    inner: Config,
+    inner: Box<ConfigInner>,
+}
+
+/// Reads the configuration files and the `APP_` environment variables.
+fn build(conf_dir: &str, default_source: &DefaultSource) -> Result<config::Config> {
+    let mut builder = config::Config::builder();
+    default_source(&mut builder)?;
+
+    let conf_dir_meta = fs::metadata(conf_dir)?;
+    let sources = if conf_dir_meta.is_dir() {
+        let mut path_buf = PathBuf::new();
+        path_buf.push(conf_dir);
+        path_buf.push("*");
+
+        glob(path_buf.to_string_lossy().as_ref())
+            .unwrap()
+            .map(|path| File::from(path.unwrap()))
+            .collect::<Vec<_>>()
+    } else {
+        let mut path_buf = PathBuf::new();
+        path_buf.push(conf_dir);
+        let is_conf = path_buf.extension().is_some_and(|ext| ext == "conf");
+
+        let mut file = File::from(path_buf);
+        if is_conf {
+            file = file.format(FileFormat::Ini);
+        }
+
+        vec![file]
+    };
+
+    Ok(builder
+        .add_source(sources)
+        .add_source(
+            Environment::with_prefix("app")
+                .convert_case(Case::Pascal)
+                .prefix_separator("_")
+                .separator("__"),
+        )
+        .build()?)
+}
+
+/// Gets the values of a configuration by their dotted keys.
+fn flatten_values(config: &config::Config) -> BTreeMap<String, Value> {
+    fn process_map(map: &Map<String, Value>, values: &mut BTreeMap<String, Value>, prefix: &str) {
+        for (key, value) in map.iter() {
+            match &value.kind {
+                ValueKind::Table(table) => {
+                    process_map(table, values, &format!("{}{}.", prefix, key))
+                }
+                _ => {
+                    values.insert(format!("{}{}", prefix, key), value.clone());
+                }
+            }
+        }
+    }
+
+    let mut values = BTreeMap::new();
+    process_map(&config.collect().unwrap_or_default(), &mut values, "");
+    values
+}
+
+fn flatten(config: &config::Config) -> BTreeMap<String, String> {
+    flatten_values(config)
+        .into_iter()
+        .map(|(key, value)| (key, value.to_string()))
+        .collect()
+}
+
+/// Makes a key or field name comparable: `MaxCount` and `max_count` are the same.
+fn normalize(name: &str) -> String {
+    name.replace('_', "").to_lowercase()
+}
+
+/// Deserializer of a section, which renames its keys after the fields of the struct.
+struct Section(Value);
+
+impl<'de> Deserializer<'de> for Section {
+    type Error = ConfigError;
+
+    fn deserialize_any<V>(self, visitor: V) -> std::result::Result<V::Value, ConfigError>
+    where
+        V: Visitor<'de>,
+    {
+        self.0.deserialize_any(visitor)
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> std::result::Result<V::Value, ConfigError>
+    where
+        V: Visitor<'de>,
+    {
+        let kind = match self.0.kind {
+            ValueKind::Table(table) => ValueKind::Table(
+                table
+                    .into_iter()
+                    .map(|(key, value)| {
+                        let field = fields
+                            .iter()
+                            .find(|field| normalize(field) == normalize(&key));
+                        (field.map_or(key, |field| field.to_string()), value)
+                    })
+                    .collect(),
+            ),
+            kind => kind,
+        };
+
+        Value::new(None, kind).deserialize_struct(name, fields, visitor)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf
+        option unit unit_struct newtype_struct seq tuple tuple_struct map enum identifier
+        ignored_any
+    }
+}
+
+fn diff(old: &BTreeMap<String, String>, new: &BTreeMap<String, String>) -> Vec<ConfigChange> {
+    let mut keys: Vec<_> = old.keys().chain(new.keys()).collect();
+    keys.sort();
+    keys.dedup();
+
+    keys.into_iter()
+        .filter(|key| old.get(*key) != new.get(*key))
+        .map(|key| ConfigChange {
+            key: key.clone(),
+            old: old.get(key).cloned(),
+            new: new.get(key).cloned(),
+        })
+        .collect()
+}
+
+/// Whether a key is watched by a subscriber: `WrongPass.*` (or `WrongPass`) watches the
+/// whole section, `PacketLog*` every key starting with `PacketLog`.
+fn is_watched(pattern: &str, key: &str) -> bool {
+    let pattern = pattern.to_lowercase();
+    let key = key.to_lowercase();
+    match pattern.strip_suffix('*') {
+        Some(prefix) => key.starts_with(prefix),
+        None => key == pattern || key.starts_with(&format!("{}.", pattern)),
+    }
 }
 
 impl Config {
@@ -39,65 +215,41 @@ impl Config {
     /// conf_dir - Configuration directory or file.
     /// default_source - A function or closure that accepts the config builder.
     ///                  In that function is possible to configure the default configuration values.
+    ///                  It is called again on every reload.
     pub fn load_config_with_default<S, D, E>(conf_dir: S, default_source: D) -> Result<Config>
     where
         S: AsRef<str>,
         E: std::error::Error + Send + Sync + 'static,
-        D: Fn(&mut ConfigBuilder<DefaultState>) -> std::result::Result<(), E>,
+        D: Fn(&mut ConfigBuilder<DefaultState>) -> std::result::Result<(), E>
+            + Send
+            + Sync
+            + 'static,
     {
-        let mut builder = config::Config::builder();
-        default_source(&mut builder).map_err(|e| Error::new(e))?;
-
-        let conf_dir_meta = fs::metadata(conf_dir.as_ref())?;
-        let sources = if conf_dir_meta.is_dir() {
-            let mut path_buf = PathBuf::new();
-            path_buf.push(conf_dir.as_ref());
-            path_buf.push("*");
-
-            glob(path_buf.to_string_lossy().as_ref())
-                .unwrap()
-                .map(|path| File::from(path.unwrap()))
-                .collect::<Vec<_>>()
-        } else {
-            let mut path_buf = PathBuf::new();
-            path_buf.push(conf_dir.as_ref());
-            let is_conf = path_buf.extension().is_some_and(|ext| ext == "conf");
-
-            let mut file = File::from(path_buf);
-            if is_conf {
-                file = file.format(FileFormat::Ini);
-            }
-
-            vec![file]
-        };
-
-        let config = Box::new(
-            builder
-                .add_source(sources)
-                .add_source(
-                    Environment::with_prefix("app")
-                        .convert_case(Case::Pascal)
-                        .prefix_separator("_")
-                        .separator("__"),
-                )
-                .build()?,
-        );
+        let default_source: DefaultSource =
+            Box::new(move |builder| default_source(builder).map_err(Error::new));
+        let conf_dir = conf_dir.as_ref().to_string();
+        let config = build(&conf_dir, &default_source)?;
+        let inner = Box::new(ConfigInner {
+            conf_dir,
+            default_source,
+            current: RwLock::new(Arc::new(config)),
+            subscribers: Mutex::default(),
+        });
 
         Ok(Self {
             #[cfg(feature = "ffi_config")]
-            inner: Box::into_raw(config) as *const c_void,
+            inner: Box::into_raw(inner) as *const c_void,
             #[cfg(not(feature = "ffi_config"))]
-            inner: config,
+            inner,
         })
     }
 
     /// # Safety
-    /// Inner is always initialized.
-    fn inner(&self) -> &config::Config {
+    /// The inner state is always initialized.
+    fn inner(&self) -> &ConfigInner {
         #[cfg(feature = "ffi_config")]
         {
-            let b = unsafe { Box::<config::Config>::from_raw(self.inner as *mut _) };
-            Box::leak(b)
+            unsafe { &*(self.inner as *const ConfigInner) }
         }
 
         #[cfg(not(feature = "ffi_config"))]
@@ -106,28 +258,14 @@ impl Config {
         }
     }
 
+    /// The configuration currently loaded, which a reload does not change.
+    fn current(&self) -> Arc<config::Config> {
+        self.inner().current.read().unwrap().clone()
+    }
+
     /// Get all the configuration keys as string.
     pub fn get_keys(&self) -> Vec<String> {
-        let configs = self.inner().collect().unwrap();
-        let mut keys = vec![];
-
-        fn process_map(
-            map: &config::Map<String, Value>,
-            keys: &mut Vec<String>,
-            current_prefix: &str,
-        ) {
-            for (key, value) in map.iter() {
-                match &value.kind {
-                    ValueKind::Table(table) => {
-                        process_map(table, keys, &format!("{}{}.", current_prefix, key))
-                    }
-                    _ => keys.push(format!("{}{}", current_prefix, key)),
-                }
-            }
-        }
-
-        process_map(&configs, &mut keys, "");
-        keys
+        flatten(&self.current()).into_keys().collect()
     }
 
     /// Gets a configuration value by key.
@@ -136,7 +274,7 @@ impl Config {
     where
         T: Deserialize<'de>,
     {
-        self.inner().get(key).or_else(|e| {
+        self.current().get(key).or_else(|e| {
             if let Some(def) = default {
                 Ok(def)
             } else {
@@ -145,6 +283,83 @@ impl Config {
         })
     }
 
+    /// Gets a configuration section as a struct, e.g. `config.section::<WrongPass>("WrongPass")`.
+    /// The keys are matched to the fields ignoring the case and the underscores, `MaxCount`
+    /// sets `max_count`. A missing section is read as an empty one, so that the struct needs
+    /// `#[serde(default)]` for its optional fields.
+    pub fn section<T>(&self, name: &str) -> Result<T>
+    where
+        T: DeserializeOwned,
+    {
+        let value = match self.current().get::<Value>(name) {
+            Ok(value) => value,
+            Err(ConfigError::NotFound(_)) => Value::new(None, ValueKind::Table(Map::new())),
+            Err(e) => return Err(Error::new(e)),
+        };
+
+        T::deserialize(Section(value)).with_context(|| format!("Invalid {} section", name))
+    }
+
+    /// Gets all the values by their (lowercase) dotted keys.
+    pub(crate) fn get_values(&self) -> BTreeMap<String, Value> {
+        flatten_values(&self.current())
+    }
+
+    /// Reads the configuration again, from the same files and environment, then notifies the
+    /// subscribers of the changed keys. The configuration is unchanged if it cannot be read.
+    pub fn reload(&self) -> Result<Vec<ConfigChange>> {
+        let inner = self.inner();
+        let config = Arc::new(build(&inner.conf_dir, &inner.default_source)?);
+        let old = std::mem::replace(&mut *inner.current.write().unwrap(), config.clone());
+        let changes = diff(&flatten(&old), &flatten(&config));
+
+        info!(target: "server::loading", "Configuration reloaded, {} changed keys.", changes.len());
+        for change in &changes {
+            debug!(target: "server::loading", "{}: {:?} -> {:?}", change.key, change.old, change.new);
+        }
+
+        let subscribers = inner.subscribers.lock().unwrap().clone();
+        for (patterns, callback) in subscribers {
+            let watched: Vec<_> = changes
+                .iter()
+                .filter(|change| {
+                    patterns
+                        .iter()
+                        .any(|pattern| is_watched(pattern, &change.key))
+                })
+                .cloned()
+                .collect();
+            if !watched.is_empty() {
+                callback(self, &watched);
+            }
+        }
+
+        Ok(changes)
+    }
+
+    /// Calls `callback` with the reloaded configuration when keys matching `pattern` change.
+    /// `WrongPass.*` watches a section, `PacketLog*` the keys with this prefix.
+    pub fn subscribe<F>(&self, pattern: &str, callback: F)
+    where
+        F: Fn(&Config, &[ConfigChange]) + Send + Sync + 'static,
+    {
+        self.subscribe_all(&[pattern], callback);
+    }
+
+    /// Like [`Config::subscribe`], `callback` being called once per reload for the changed keys
+    /// matching any of the patterns.
+    pub fn subscribe_all<F>(&self, patterns: &[&str], callback: F)
+    where
+        F: Fn(&Config, &[ConfigChange]) + Send + Sync + 'static,
+    {
+        let patterns = patterns.iter().map(|pattern| pattern.to_string()).collect();
+        self.inner()
+            .subscribers
+            .lock()
+            .unwrap()
+            .push((patterns, Arc::new(callback)));
+    }
+
     // C/C++ interface
     // The following methods serves only as interface for C code
 
@@ -170,7 +385,7 @@ impl Config {
                 let conf_error = ManuallyDrop::new(CString::new(error_message).unwrap());
                 *load_error = conf_error.as_ref().as_ptr() as *mut _;
 
-                std::mem::transmute::<_, Config>(0_usize)
+                std::mem::transmute::<usize, Config>(0_usize)
             }
         }
     }
@@ -216,7 +431,7 @@ impl Config {
         default: *const c_char,
     ) -> *const c_char {
         let name = CStr::from_ptr(name).to_str().unwrap();
-        let value = if let Ok(value) = self.inner().get_string(name) {
+        let value = if let Ok(value) = self.current().get_string(name) {
             ManuallyDrop::new(CString::new(value).unwrap())
         } else {
             let default = CStr::from_ptr(default).to_str().unwrap();
@@ -238,7 +453,7 @@ impl Config {
         default: bool,
     ) -> bool {
         let name = CStr::from_ptr(name).to_str().unwrap();
-        if let Ok(value) = self.inner().get_bool(name) {
+        if let Ok(value) = self.current().get_bool(name) {
             value
         } else {
             default
@@ -253,7 +468,7 @@ impl Config {
     #[cfg(feature = "ffi_config")]
     pub unsafe extern "C" fn Config_GetIntDefault(&self, name: *const c_char, default: i64) -> i64 {
         let name = CStr::from_ptr(name).to_str().unwrap();
-        if let Ok(value) = self.inner().get_int(name) {
+        if let Ok(value) = self.current().get_int(name) {
             value
         } else {
             default
@@ -272,13 +487,28 @@ impl Config {
         default: f64,
     ) -> f64 {
         let name = CStr::from_ptr(name).to_str().unwrap();
-        if let Ok(value) = self.inner().get_float(name) {
+        if let Ok(value) = self.current().get_float(name) {
             value
         } else {
             default
         }
     }
 
+    /// Reloads the configuration, see [`Config::reload`].
+    /// If reload error is not NULL, the configuration could not be reloaded and is unchanged.
+    ///
+    /// # Safety
+    /// Unsafe: reload error must be freed after use.
+    #[no_mangle]
+    #[cfg(feature = "ffi_config")]
+    pub unsafe extern "C" fn Config_Reload(&self, reload_error: *mut *mut c_char) {
+        *reload_error = null_mut();
+        if let Err(err) = self.reload() {
+            let conf_error = ManuallyDrop::new(CString::new(err.to_string()).unwrap());
+            *reload_error = conf_error.as_ref().as_ptr() as *mut _;
+        }
+    }
+
     /// De-allocates the current configuration, freeing memory
     #[no_mangle]
     #[cfg(feature = "ffi_config")]
@@ -291,9 +521,114 @@ impl Config {
 impl Drop for Config {
     fn drop(&mut self) {
         if !self.inner.is_null() {
-            unsafe {
-                ptr::drop_in_place(self.inner as *mut c_void);
-            }
+            drop(unsafe { Box::from_raw(self.inner as *mut ConfigInner) });
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::config::{Config, ConfigChange};
+    use serde::Deserialize;
+    use std::fs;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Debug, Default, PartialEq, Eq, Deserialize)]
+    #[serde(default)]
+    struct WrongPass {
+        max_count: u32,
+        ban_time: u64,
+        logging: bool,
+    }
+
+    #[test]
+    pub fn sections_are_typed() {
+        let path =
+            std::env::temp_dir().join(format!("enturion-section-{}.yaml", std::process::id()));
+        fs::write(
+            &path,
+            "WrongPass:\n    MaxCount: 3\n    Logging: true\nPort: x\n",
+        )
+        .unwrap();
+        let config = Config::load_config(path.to_string_lossy()).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(
+            config.section::<WrongPass>("WrongPass").unwrap(),
+            WrongPass {
+                max_count: 3,
+                ban_time: 0,
+                logging: true,
+            }
+        );
+        assert_eq!(
+            config.section::<WrongPass>("Missing").unwrap(),
+            WrongPass::default()
+        );
+        assert!(config.section::<WrongPass>("Port").is_err());
+    }
+
+    #[test]
+    pub fn reloads_are_notified() {
+        let directory =
+            std::env::temp_dir().join(format!("enturion-config-{}", std::process::id()));
+        fs::create_dir_all(&directory).unwrap();
+        let path = directory.join("authserver.yaml");
+        fs::write(
+            &path,
+            "WrongPass:\n    MaxCount: 3\n    Logging: false\nPort: 1\n",
+        )
+        .unwrap();
+
+        let config = Config::load_config(path.to_string_lossy()).unwrap();
+        let notified = Arc::new(Mutex::new(vec![]));
+        let notified_ref = notified.clone();
+        config.subscribe("WrongPass.*", move |config, changes| {
+            let max_count = config.get::<u32>("WrongPass.MaxCount", None).unwrap();
+            notified_ref
+                .lock()
+                .unwrap()
+                .push((max_count, changes.to_vec()));
+        });
+        config.subscribe("Realm*", |_, _| panic!("Nothing changed in this section"));
+        let calls = Arc::new(Mutex::new(vec![]));
+        let calls_ref = calls.clone();
+        config.subscribe_all(&["WrongPass.*", "Port"], move |_, changes| {
+            calls_ref.lock().unwrap().push(changes.len());
+        });
+
+        fs::write(
+            &path,
+            "WrongPass:\n    MaxCount: 5\n    Logging: false\nPort: 2\nExtra: x\n",
+        )
+        .unwrap();
+        let changes = config.reload().unwrap();
+        assert_eq!(config.get::<u32>("Port", None).unwrap(), 2);
+        // Keys are lowercased by the configuration library
+        assert_eq!(
+            changes
+                .iter()
+                .map(|change| &change.key[..])
+                .collect::<Vec<_>>(),
+            ["extra", "port", "wrongpass.maxcount"]
+        );
+        assert_eq!(
+            notified.lock().unwrap()[..],
+            [(
+                5,
+                vec![ConfigChange {
+                    key: "wrongpass.maxcount".to_string(),
+                    old: Some("3".to_string()),
+                    new: Some("5".to_string()),
+                }]
+            )]
+        );
+        assert_eq!(calls.lock().unwrap()[..], [2]);
+
+        // A configuration which cannot be read is not applied
+        fs::remove_file(&path).unwrap();
+        assert!(config.reload().is_err());
+        assert_eq!(config.get::<u32>("Port", None).unwrap(), 2);
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
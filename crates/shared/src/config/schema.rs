@@ -0,0 +1,447 @@
+//! The known configuration keys, with their type, default value and description: to report the
+//! misspelled keys and the invalid values at startup, and to print a documented configuration.
+
+use crate::config::{normalize, Config};
+use config::{Value, ValueKind};
+use log::warn;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_value::Value as DefaultValue;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter, Write};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueType {
+    Boolean,
+    Integer,
+    Float,
+    String,
+    List,
+    /// Any value, including a whole section of free-form keys (like the log appenders).
+    Any,
+}
+
+impl ValueType {
+    /// Whether a value can be read as this type. The environment variables are strings, which
+    /// are parsed when read.
+    fn accepts(self, value: &Value) -> bool {
+        match (&value.kind, self) {
+            (_, ValueType::Any) | (ValueKind::Nil, _) => true,
+            (ValueKind::Array(_), ValueType::List) => true,
+            (ValueKind::Array(_) | ValueKind::Table(_), _) => false,
+            (_, ValueType::String) => true,
+            (ValueKind::Boolean(_), ValueType::Boolean) => true,
+            // The C++ code reads the 0 and 1 flags as booleans
+            (ValueKind::I64(0 | 1) | ValueKind::U64(0 | 1), ValueType::Boolean) => true,
+            (
+                ValueKind::I64(_) | ValueKind::I128(_) | ValueKind::U64(_) | ValueKind::U128(_),
+                ValueType::Integer | ValueType::Float,
+            ) => true,
+            (ValueKind::Float(_), ValueType::Float) => true,
+            (ValueKind::String(_), ValueType::Boolean) => value.clone().into_bool().is_ok(),
+            (ValueKind::String(_), ValueType::Integer) => value.clone().into_int().is_ok(),
+            (ValueKind::String(_), ValueType::Float) => value.clone().into_float().is_ok(),
+            _ => false,
+        }
+    }
+}
+
+impl ValueType {
+    /// The type of a default value, `None` for the values which do not tell it.
+    fn of(value: &DefaultValue) -> Option<Self> {
+        match value {
+            DefaultValue::Bool(_) => Some(ValueType::Boolean),
+            DefaultValue::U8(_)
+            | DefaultValue::U16(_)
+            | DefaultValue::U32(_)
+            | DefaultValue::U64(_)
+            | DefaultValue::I8(_)
+            | DefaultValue::I16(_)
+            | DefaultValue::I32(_)
+            | DefaultValue::I64(_) => Some(ValueType::Integer),
+            DefaultValue::F32(_) | DefaultValue::F64(_) => Some(ValueType::Float),
+            DefaultValue::Char(_) | DefaultValue::String(_) => Some(ValueType::String),
+            DefaultValue::Seq(_) => Some(ValueType::List),
+            DefaultValue::Option(Some(value)) | DefaultValue::Newtype(value) => Self::of(value),
+            _ => None,
+        }
+    }
+}
+
+/// Writes a default value in YAML.
+fn to_yaml(value: &DefaultValue) -> String {
+    match value {
+        DefaultValue::Bool(value) => value.to_string(),
+        DefaultValue::U8(value) => value.to_string(),
+        DefaultValue::U16(value) => value.to_string(),
+        DefaultValue::U32(value) => value.to_string(),
+        DefaultValue::U64(value) => value.to_string(),
+        DefaultValue::I8(value) => value.to_string(),
+        DefaultValue::I16(value) => value.to_string(),
+        DefaultValue::I32(value) => value.to_string(),
+        DefaultValue::I64(value) => value.to_string(),
+        DefaultValue::F32(value) => value.to_string(),
+        DefaultValue::F64(value) => value.to_string(),
+        DefaultValue::Char(value) => to_yaml(&DefaultValue::String(value.to_string())),
+        DefaultValue::String(value) => format!("'{}'", value.replace('\'', "''")),
+        DefaultValue::Seq(values) => format!(
+            "[{}]",
+            values.iter().map(to_yaml).collect::<Vec<_>>().join(", ")
+        ),
+        DefaultValue::Option(Some(value)) | DefaultValue::Newtype(value) => to_yaml(value),
+        _ => "~".to_string(),
+    }
+}
+
+impl Display for ValueType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValueType::Boolean => write!(f, "a boolean"),
+            ValueType::Integer => write!(f, "an integer"),
+            ValueType::Float => write!(f, "a number"),
+            ValueType::String => write!(f, "a string"),
+            ValueType::List => write!(f, "a list"),
+            ValueType::Any => write!(f, "any value"),
+        }
+    }
+}
+
+/// A known key, `Section.Key` for the keys of a section.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigKey {
+    pub key: String,
+    pub value_type: ValueType,
+    /// The default value, in YAML.
+    pub default: String,
+    pub doc: String,
+}
+
+/// A problem of the configuration found by [`ConfigSchema::validate`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchemaError {
+    /// A key which is not in the schema, often misspelled.
+    UnknownKey(String),
+    WrongType {
+        key: String,
+        expected: ValueType,
+        value: String,
+    },
+    /// A section whose keys have the right type, but which cannot be read, e.g. with an unknown
+    /// choice.
+    InvalidSection(String),
+}
+
+impl Display for SchemaError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SchemaError::UnknownKey(key) => write!(f, "Unknown configuration key {}", key),
+            SchemaError::WrongType {
+                key,
+                expected,
+                value,
+            } => write!(f, "{} ({}) must be {}", key, value, expected),
+            SchemaError::InvalidSection(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+/// Reads a typed section of the configuration, to check its values.
+type ReadSection = fn(&Config, &str) -> anyhow::Result<()>;
+
+/// The known keys of a configuration, in the order they are described.
+#[derive(Debug, Default, Clone)]
+pub struct ConfigSchema {
+    keys: Vec<ConfigKey>,
+    /// The typed sections, with the function reading them.
+    sections: Vec<(String, ReadSection)>,
+}
+
+impl ConfigSchema {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a key. `default` is written as is in the described configuration, a multi-line
+    /// default being the content of a section of [`ValueType::Any`].
+    pub fn key(mut self, key: &str, value_type: ValueType, default: &str, doc: &str) -> Self {
+        self.keys.push(ConfigKey {
+            key: key.to_string(),
+            value_type,
+            default: default.to_string(),
+            doc: doc.to_string(),
+        });
+
+        self
+    }
+
+    /// Adds the keys of a section read with [`Config::section`]: their type and default value
+    /// come from the default of `T`, `docs` gives their name and description, in order.
+    /// The fields without a default value (`None`) are left out, to be added with
+    /// [`ConfigSchema::key`].
+    ///
+    /// # Panics
+    /// If `docs` and the fields of `T` do not match.
+    pub fn section<T>(mut self, name: &str, docs: &[(&str, &str)]) -> Self
+    where
+        T: Serialize + DeserializeOwned + Default,
+    {
+        self.sections.push((name.to_string(), |config, name| {
+            config.section::<T>(name).map(|_| ())
+        }));
+        let DefaultValue::Map(defaults) = serde_value::to_value(T::default())
+            .unwrap_or_else(|e| panic!("Invalid default {} section: {}", name, e))
+        else {
+            panic!("The {} section is not a struct", name);
+        };
+        let mut fields: BTreeMap<String, DefaultValue> = defaults
+            .into_iter()
+            .filter_map(|(field, value)| match (field, value) {
+                (_, DefaultValue::Option(None) | DefaultValue::Unit) => None,
+                (DefaultValue::String(field), value) => Some((normalize(&field), value)),
+                _ => None,
+            })
+            .collect();
+
+        for (key, doc) in docs {
+            let default = fields
+                .remove(&normalize(key))
+                .unwrap_or_else(|| panic!("{}.{} is not a field with a default", name, key));
+            let value_type =
+                ValueType::of(&default).unwrap_or_else(|| panic!("{}.{} has no type", name, key));
+            self = self.key(
+                &format!("{}.{}", name, key),
+                value_type,
+                &to_yaml(&default),
+                doc,
+            );
+        }
+        if let Some(field) = fields.keys().next() {
+            panic!(
+                "The {} field of the {} section is not documented",
+                field, name
+            );
+        }
+
+        self
+    }
+
+    pub fn keys(&self) -> &[ConfigKey] {
+        &self.keys
+    }
+
+    /// Gets the schema of a configuration key, the keys below a key of [`ValueType::Any`]
+    /// belonging to it.
+    pub fn get(&self, key: &str) -> Option<&ConfigKey> {
+        let key = key.to_lowercase();
+        self.keys.iter().find(|known| {
+            let known_key = known.key.to_lowercase();
+            key == known_key
+                || (known.value_type == ValueType::Any
+                    && key.starts_with(&format!("{}.", known_key)))
+        })
+    }
+
+    /// Checks every key of the configuration, then reads the typed sections whose keys have the
+    /// right type.
+    pub fn validate(&self, config: &Config) -> Vec<SchemaError> {
+        let mut errors: Vec<_> = config
+            .get_values()
+            .into_iter()
+            .filter_map(|(key, value)| match self.get(&key) {
+                None => Some(SchemaError::UnknownKey(key)),
+                Some(known) if !known.value_type.accepts(&value) => Some(SchemaError::WrongType {
+                    key,
+                    expected: known.value_type,
+                    value: value.to_string(),
+                }),
+                Some(_) => None,
+            })
+            .collect();
+
+        for (name, read) in &self.sections {
+            let prefix = format!("{}.", name.to_lowercase());
+            let wrong_type = errors.iter().any(|error| {
+                matches!(error, SchemaError::WrongType { key, .. } if key.starts_with(&prefix))
+            });
+            if !wrong_type {
+                if let Err(e) = read(config, name) {
+                    errors.push(SchemaError::InvalidSection(format!("{:#}", e)));
+                }
+            }
+        }
+
+        errors
+    }
+
+    /// Logs the problems of the configuration as warnings and returns their count.
+    pub fn report(&self, config: &Config, target: &str) -> usize {
+        let errors = self.validate(config);
+        for error in &errors {
+            warn!(target: target, "{}", error);
+        }
+
+        errors.len()
+    }
+
+    /// Writes the configuration with the default values, documented by comments.
+    pub fn describe(&self) -> String {
+        let mut description = String::new();
+        let mut current_sections: Vec<&str> = vec![];
+        for known in &self.keys {
+            let mut path: Vec<_> = known.key.split('.').collect();
+            let name = path.pop().unwrap_or_default();
+            let common = current_sections
+                .iter()
+                .zip(&path)
+                .take_while(|(current, section)| current == section)
+                .count();
+            // The top level keys and sections are separated by a blank line
+            if common == 0 && !description.is_empty() {
+                description.push('\n');
+            }
+            for (depth, section) in path.iter().enumerate().skip(common) {
+                let _ = writeln!(description, "{}{}:", "    ".repeat(depth), section);
+            }
+            current_sections = path;
+
+            let indent = "    ".repeat(current_sections.len());
+            for line in known.doc.lines() {
+                let _ = writeln!(description, "{}# {}", indent, line);
+            }
+            if known.default.contains('\n') {
+                let _ = writeln!(description, "{}{}:", indent, name);
+                for line in known.default.lines() {
+                    let _ = writeln!(description, "{}    {}", indent, line);
+                }
+            } else {
+                let _ = writeln!(description, "{}{}: {}", indent, name, known.default);
+            }
+        }
+
+        description
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::schema::{ConfigSchema, SchemaError, ValueType};
+    use crate::config::Config;
+    use serde::{Deserialize, Serialize};
+    use std::fs;
+
+    #[derive(Debug, Default, Serialize, Deserialize)]
+    #[serde(default)]
+    struct WrongPass {
+        max_count: u32,
+        logging: bool,
+        ban_type: Option<u32>,
+    }
+
+    fn schema() -> ConfigSchema {
+        ConfigSchema::new()
+            .key("RealmServerPort", ValueType::Integer, "3724", "TCP port.")
+            .key("PidFile", ValueType::String, "~", "PID file.")
+            .section::<WrongPass>(
+                "WrongPass",
+                &[("MaxCount", "Attempts."), ("Logging", "Log.\nAttempts.")],
+            )
+            .key(
+                "PlayerSave.Stats.MinLevel",
+                ValueType::Integer,
+                "~",
+                "Level.",
+            )
+            .key(
+                "Appender",
+                ValueType::Any,
+                "Console:\n    type: console",
+                "Appenders.",
+            )
+    }
+
+    #[test]
+    pub fn unknown_keys_and_wrong_types_are_reported() {
+        let path =
+            std::env::temp_dir().join(format!("enturion-schema-{}.yaml", std::process::id()));
+        fs::write(
+            &path,
+            "RealmServerPort: '3725'\nPidFile: ~\nWrongPass:\n    MaxCont: 3\n    Logging: 2\n\
+             PlayerSave:\n    Stats:\n        MinLevel: 10\n\
+             Appender:\n    Auth:\n        type: file\n",
+        )
+        .unwrap();
+        let config = Config::load_config(path.to_string_lossy()).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(
+            schema().validate(&config),
+            [
+                SchemaError::WrongType {
+                    key: "wrongpass.logging".to_string(),
+                    expected: ValueType::Boolean,
+                    value: "2".to_string(),
+                },
+                SchemaError::UnknownKey("wrongpass.maxcont".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    pub fn schemas_are_described() {
+        assert_eq!(
+            schema().describe(),
+            "# TCP port.\nRealmServerPort: 3724\n\n# PID file.\nPidFile: ~\n\n\
+             WrongPass:\n    # Attempts.\n    MaxCount: 0\n    # Log.\n    # Attempts.\n    Logging: false\n\n\
+             PlayerSave:\n    Stats:\n        # Level.\n        MinLevel: ~\n\n\
+             # Appenders.\nAppender:\n    Console:\n        type: console\n"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "The logging field of the WrongPass section is not documented")]
+    pub fn section_fields_are_documented() {
+        ConfigSchema::new().section::<WrongPass>("WrongPass", &[("MaxCount", "Attempts.")]);
+    }
+
+    #[test]
+    pub fn booleans_are_flags() {
+        let boolean = |kind| ValueType::Boolean.accepts(&config::Value::new(None, kind));
+        assert!(boolean(config::ValueKind::I64(0)));
+        assert!(boolean(config::ValueKind::U64(1)));
+        assert!(!boolean(config::ValueKind::I64(2)));
+        assert!(!boolean(config::ValueKind::I64(-1)));
+    }
+
+    #[derive(Debug, Default, Serialize, Deserialize)]
+    #[serde(rename_all = "lowercase")]
+    enum Overrun {
+        #[default]
+        Skip,
+        Delay,
+    }
+
+    #[derive(Debug, Default, Serialize, Deserialize)]
+    #[serde(default)]
+    struct Tick {
+        overrun: Overrun,
+    }
+
+    #[test]
+    pub fn invalid_choices_are_reported() {
+        let path =
+            std::env::temp_dir().join(format!("enturion-choices-{}.yaml", std::process::id()));
+        fs::write(&path, "Tick:\n    Overrun: often\n").unwrap();
+        let config = Config::load_config(path.to_string_lossy()).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        let schema = ConfigSchema::new().section::<Tick>("Tick", &[("Overrun", "Overrun.")]);
+        assert_eq!(schema.keys()[0].default, "'skip'");
+        assert_eq!(
+            schema.validate(&config),
+            [SchemaError::InvalidSection(
+                "Invalid Tick section: enum Overrun does not have variant constructor often"
+                    .to_string()
+            )]
+        );
+    }
+}
@@ -8,10 +8,13 @@ pub mod banner;
 pub mod config;
 pub mod dbc;
 pub mod error;
+pub mod lifecycle;
 pub mod log;
 pub mod net;
 pub mod signals;
+pub mod tick;
 pub mod timer;
+pub mod watchdog;
 #[cfg(windows)]
 pub mod win_service;
 
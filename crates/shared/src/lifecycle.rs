@@ -0,0 +1,518 @@
+//! Stop requests of the server: delayed shutdowns and restarts with their countdown, and the
+//! exit code of the process.
+
+use crate::config::Config;
+use crate::signals::Signal;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+use tokio::sync::watch;
+use tokio::time::Instant;
+
+static LIFECYCLE: OnceLock<Lifecycle> = OnceLock::new();
+
+const MINUTE: u64 = 60;
+const HOUR: u64 = 60 * MINUTE;
+
+/// Exit code of the process, used by the restarter scripts.
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExitCode {
+    Shutdown = 0,
+    Error = 1,
+    Restart = 2,
+}
+
+impl From<u8> for ExitCode {
+    fn from(code: u8) -> Self {
+        match code {
+            0 => ExitCode::Shutdown,
+            2 => ExitCode::Restart,
+            _ => ExitCode::Error,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StopKind {
+    Shutdown,
+    Restart,
+}
+
+impl StopKind {
+    fn exit_code(self) -> ExitCode {
+        match self {
+            StopKind::Shutdown => ExitCode::Shutdown,
+            StopKind::Restart => ExitCode::Restart,
+        }
+    }
+}
+
+/// Announcement of a planned stop, to warn the players.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Announcement {
+    /// The server stops in `remaining` seconds.
+    Countdown {
+        kind: StopKind,
+        remaining: u64,
+        reason: String,
+    },
+    Cancelled {
+        kind: StopKind,
+    },
+}
+
+impl Display for Announcement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let action = |kind: &StopKind| match kind {
+            StopKind::Shutdown => "shutdown",
+            StopKind::Restart => "restart",
+        };
+
+        match self {
+            Announcement::Countdown {
+                kind,
+                remaining,
+                reason,
+            } => {
+                write!(f, "Server {} in {} s", action(kind), remaining)?;
+                if !reason.is_empty() {
+                    write!(f, " - {}", reason)?;
+                }
+
+                Ok(())
+            }
+            Announcement::Cancelled { kind } => write!(f, "Server {} cancelled.", action(kind)),
+        }
+    }
+}
+
+type AnnouncementCallback = Box<dyn Fn(&Announcement) + Send + Sync>;
+
+/// A stop to plan.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StopRequest {
+    pub kind: StopKind,
+    pub delay: Duration,
+    pub reason: String,
+    /// The exit code of the process, the one of `kind` if `None`.
+    pub exit_code: Option<ExitCode>,
+    /// Whether the stop waits for the server to be idle. The countdown of an idle stop is not
+    /// announced.
+    pub idle: bool,
+}
+
+impl StopRequest {
+    pub fn new(kind: StopKind, delay: Duration) -> Self {
+        Self {
+            kind,
+            delay,
+            reason: String::new(),
+            exit_code: None,
+            idle: false,
+        }
+    }
+}
+
+/// The state of a planned stop.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Planned {
+    pub kind: StopKind,
+    pub remaining: Duration,
+    pub idle: bool,
+}
+
+struct PlannedStop {
+    kind: StopKind,
+    deadline: Instant,
+    reason: String,
+    exit_code: ExitCode,
+    idle: bool,
+    last_announced: u64,
+}
+
+/// Whether the countdown is announced with the given remaining seconds: every 12 hours, then
+/// every hour, 5 minutes, minute and finally every 15 seconds.
+fn is_announced(remaining: u64) -> bool {
+    (remaining < 5 * MINUTE && remaining.is_multiple_of(15))
+        || (remaining < 15 * MINUTE && remaining.is_multiple_of(MINUTE))
+        || (remaining < 30 * MINUTE && remaining.is_multiple_of(5 * MINUTE))
+        || (remaining < 12 * HOUR && remaining.is_multiple_of(HOUR))
+        || (remaining > 12 * HOUR && remaining.is_multiple_of(12 * HOUR))
+}
+
+/// Plans the stop of the server. The planned stop happens on the first [`Lifecycle::update`]
+/// after its deadline.
+pub struct Lifecycle {
+    planned: Mutex<Option<PlannedStop>>,
+    callbacks: Mutex<Vec<AnnouncementCallback>>,
+    exit_code: watch::Sender<Option<ExitCode>>,
+}
+
+impl Default for Lifecycle {
+    fn default() -> Self {
+        Self {
+            planned: Mutex::default(),
+            callbacks: Mutex::default(),
+            exit_code: watch::channel(None).0,
+        }
+    }
+}
+
+impl Lifecycle {
+    /// Gets the lifecycle of the server.
+    pub fn global() -> &'static Lifecycle {
+        LIFECYCLE.get_or_init(Lifecycle::default)
+    }
+
+    /// Shuts the server down after `delay`, replacing any planned stop.
+    pub fn shutdown(&self, delay: Duration, reason: impl Into<String>) {
+        self.plan(StopRequest {
+            reason: reason.into(),
+            ..StopRequest::new(StopKind::Shutdown, delay)
+        });
+    }
+
+    /// Restarts the server after `delay`, replacing any planned stop.
+    pub fn restart(&self, delay: Duration) {
+        self.plan(StopRequest::new(StopKind::Restart, delay));
+    }
+
+    /// Plans a stop, replacing any planned one.
+    pub fn plan(&self, request: StopRequest) {
+        if self.is_stopped() {
+            return;
+        }
+
+        let exit_code = request
+            .exit_code
+            .unwrap_or_else(|| request.kind.exit_code());
+        if request.delay.is_zero() && !request.idle {
+            self.stop_now(exit_code);
+            return;
+        }
+
+        let remaining = request.delay.as_secs();
+        let announcement = Announcement::Countdown {
+            kind: request.kind,
+            remaining,
+            reason: request.reason.clone(),
+        };
+        *self.planned.lock().unwrap() = Some(PlannedStop {
+            kind: request.kind,
+            deadline: Instant::now() + request.delay,
+            reason: request.reason,
+            exit_code,
+            idle: request.idle,
+            last_announced: remaining,
+        });
+        if !request.idle {
+            self.announce(&announcement);
+        }
+    }
+
+    /// Cancels the planned stop, returning the time it had left. Nothing is cancelled once the
+    /// server is stopped.
+    pub fn cancel(&self) -> Option<Duration> {
+        if self.is_stopped() {
+            return None;
+        }
+
+        let planned = self.planned.lock().unwrap().take()?;
+        self.announce(&Announcement::Cancelled { kind: planned.kind });
+
+        Some(planned.deadline.saturating_duration_since(Instant::now()))
+    }
+
+    /// The planned stop, if any.
+    pub fn planned(&self) -> Option<Planned> {
+        let planned = self.planned.lock().unwrap();
+        planned.as_ref().map(|planned| Planned {
+            kind: planned.kind,
+            remaining: planned.deadline.saturating_duration_since(Instant::now()),
+            idle: planned.idle,
+        })
+    }
+
+    /// Stops the server on its next tick. The first exit code is kept.
+    pub fn stop_now(&self, exit_code: ExitCode) {
+        self.exit_code.send_if_modified(|current| {
+            let stopping = current.is_none();
+            if stopping {
+                *current = Some(exit_code);
+            }
+
+            stopping
+        });
+    }
+
+    pub fn is_stopped(&self) -> bool {
+        self.exit_code.borrow().is_some()
+    }
+
+    /// The exit code of the process, once stopped.
+    pub fn exit_code(&self) -> Option<ExitCode> {
+        *self.exit_code.borrow()
+    }
+
+    /// Waits until the server is stopped.
+    pub async fn stopped(&self) -> ExitCode {
+        let mut exit_code = self.exit_code.subscribe();
+        let exit_code = exit_code.wait_for(Option::is_some).await;
+        exit_code
+            .map(|code| code.unwrap())
+            .unwrap_or(ExitCode::Error)
+    }
+
+    /// Calls `callback` on every countdown step and cancellation of a planned stop.
+    pub fn on_announcement<F>(&self, callback: F)
+    where
+        F: Fn(&Announcement) + Send + Sync + 'static,
+    {
+        self.callbacks.lock().unwrap().push(Box::new(callback));
+    }
+
+    /// Announces the countdown of the planned stop, and stops the server once it is overdue.
+    /// Called on every tick.
+    pub fn update(&self) {
+        self.update_when_idle(|| true);
+    }
+
+    /// Like [`Lifecycle::update`], an overdue idle stop only happening once `is_idle` is true.
+    pub fn update_when_idle(&self, is_idle: impl FnOnce() -> bool) {
+        let announcement = {
+            let mut planned = self.planned.lock().unwrap();
+            let Some(stop) = planned.as_mut() else {
+                return;
+            };
+
+            let remaining = stop.deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                if stop.idle && !is_idle() {
+                    return;
+                }
+
+                let exit_code = stop.exit_code;
+                *planned = None;
+                drop(planned);
+                self.stop_now(exit_code);
+                return;
+            }
+
+            // Rounded up, so that the countdown does not skip its first second
+            let remaining = remaining.as_secs() + u64::from(remaining.subsec_nanos() > 0);
+            if stop.idle || remaining == stop.last_announced || !is_announced(remaining) {
+                return;
+            }
+
+            stop.last_announced = remaining;
+            Announcement::Countdown {
+                kind: stop.kind,
+                remaining,
+                reason: stop.reason.clone(),
+            }
+        };
+
+        self.announce(&announcement);
+    }
+
+    fn announce(&self, announcement: &Announcement) {
+        for callback in self.callbacks.lock().unwrap().iter() {
+            callback(announcement);
+        }
+    }
+}
+
+/// What the server does when it receives a signal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignalAction {
+    Ignore,
+    Abort,
+    Reload,
+    /// Logs diagnostics.
+    Dump,
+    Shutdown(Duration),
+    Restart(Duration),
+}
+
+/// The action of `SIGUSR1` or `SIGUSR2` in the configuration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UserSignalAction {
+    None,
+    /// Reloads the configuration, like `SIGHUP`.
+    Reload,
+    Shutdown,
+    Restart,
+}
+
+impl UserSignalAction {
+    /// The action of the signal, the stops being delayed by `delay`.
+    pub fn with_delay(self, delay: Duration) -> SignalAction {
+        match self {
+            UserSignalAction::None => SignalAction::Ignore,
+            UserSignalAction::Reload => SignalAction::Reload,
+            UserSignalAction::Shutdown => SignalAction::Shutdown(delay),
+            UserSignalAction::Restart => SignalAction::Restart(delay),
+        }
+    }
+}
+
+/// The `Signals` config section.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SignalsSection {
+    pub user1: UserSignalAction,
+    pub user2: UserSignalAction,
+    /// In seconds.
+    pub stop_delay: u64,
+}
+
+impl Default for SignalsSection {
+    fn default() -> Self {
+        Self {
+            user1: UserSignalAction::None,
+            user2: UserSignalAction::None,
+            stop_delay: 0,
+        }
+    }
+}
+
+/// The actions of the signals: `SIGHUP` reloads the configuration and the actions of `SIGUSR1`
+/// and `SIGUSR2` are configured with `Signals.User1` and `Signals.User2`. The shutdowns and
+/// restarts they request are delayed by `Signals.StopDelay` seconds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SignalActions {
+    pub user1: SignalAction,
+    pub user2: SignalAction,
+}
+
+impl Default for SignalActions {
+    fn default() -> Self {
+        Self {
+            user1: SignalAction::Ignore,
+            user2: SignalAction::Ignore,
+        }
+    }
+}
+
+impl SignalActions {
+    pub fn from_config(config: &Config) -> Result<Self> {
+        let section: SignalsSection = config.section("Signals")?;
+        let delay = Duration::from_secs(section.stop_delay);
+
+        Ok(Self {
+            user1: section.user1.with_delay(delay),
+            user2: section.user2.with_delay(delay),
+        })
+    }
+
+    pub fn action(&self, signal: Signal) -> SignalAction {
+        match signal {
+            Signal::Terminate | Signal::Interruption => SignalAction::Shutdown(Duration::ZERO),
+            Signal::Abort => SignalAction::Abort,
+            Signal::Reload => SignalAction::Reload,
+            Signal::Quit => SignalAction::Dump,
+            Signal::User1 => self.user1,
+            Signal::User2 => self.user2,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lifecycle::{Announcement, ExitCode, Lifecycle, Planned, StopKind, StopRequest};
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+    use tokio::time::advance;
+
+    #[tokio::test(start_paused = true)]
+    pub async fn countdowns_are_announced() {
+        let lifecycle = Lifecycle::default();
+        let announcements = Arc::new(Mutex::new(vec![]));
+        let announcements_ref = announcements.clone();
+        lifecycle.on_announcement(move |announcement| {
+            announcements_ref.lock().unwrap().push(announcement.clone())
+        });
+
+        lifecycle.shutdown(Duration::from_secs(100), "Maintenance");
+        for _ in 0..100 {
+            lifecycle.update();
+            advance(Duration::from_millis(500)).await;
+        }
+        assert_eq!(
+            lifecycle.planned(),
+            Some(Planned {
+                kind: StopKind::Shutdown,
+                remaining: Duration::from_secs(50),
+                idle: false,
+            })
+        );
+        let remaining: Vec<_> = announcements
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|announcement| match announcement {
+                Announcement::Countdown { remaining, .. } => *remaining,
+                Announcement::Cancelled { .. } => 0,
+            })
+            .collect();
+        assert_eq!(remaining, [100, 90, 75, 60]);
+
+        assert_eq!(lifecycle.cancel(), Some(Duration::from_secs(50)));
+        assert_eq!(
+            announcements.lock().unwrap().last(),
+            Some(&Announcement::Cancelled {
+                kind: StopKind::Shutdown
+            })
+        );
+        lifecycle.update();
+        assert_eq!(lifecycle.exit_code(), None);
+    }
+
+    #[tokio::test(start_paused = true)]
+    pub async fn stops_set_the_exit_code() {
+        let lifecycle = Lifecycle::default();
+        lifecycle.restart(Duration::from_secs(10));
+        lifecycle.update();
+        assert!(!lifecycle.is_stopped());
+
+        advance(Duration::from_secs(10)).await;
+        lifecycle.update();
+        assert_eq!(lifecycle.stopped().await, ExitCode::Restart);
+        assert_eq!(lifecycle.planned(), None);
+
+        // The first exit code is kept
+        lifecycle.stop_now(ExitCode::Error);
+        lifecycle.shutdown(Duration::ZERO, "");
+        assert_eq!(lifecycle.exit_code(), Some(ExitCode::Restart));
+    }
+
+    #[tokio::test(start_paused = true)]
+    pub async fn idle_stops_wait_for_the_server_to_be_idle() {
+        let lifecycle = Lifecycle::default();
+        let announcements = Arc::new(Mutex::new(vec![]));
+        let announcements_ref = announcements.clone();
+        lifecycle.on_announcement(move |announcement| {
+            announcements_ref.lock().unwrap().push(announcement.clone())
+        });
+
+        lifecycle.plan(StopRequest {
+            exit_code: Some(ExitCode::Error),
+            idle: true,
+            ..StopRequest::new(StopKind::Shutdown, Duration::ZERO)
+        });
+        lifecycle.update_when_idle(|| false);
+        advance(Duration::from_secs(30)).await;
+        lifecycle.update_when_idle(|| false);
+        assert!(!lifecycle.is_stopped());
+        assert!(announcements.lock().unwrap().is_empty());
+
+        lifecycle.update_when_idle(|| true);
+        assert_eq!(lifecycle.exit_code(), Some(ExitCode::Error));
+        assert_eq!(lifecycle.planned(), None);
+        assert_eq!(lifecycle.cancel(), None);
+    }
+}
@@ -13,13 +13,29 @@ use log4rs::append::Append;
 use log4rs::config::{Appender, Logger, Root};
 use std::ffi::{c_char, CStr};
 use std::ops::Deref;
+use std::sync::RwLock;
 
 const LEVELS: [&str; 6] = ["OFF", "ERROR", "WARN", "INFO", "DEBUG", "TRACE"];
 
-pub struct LogMgr(log4rs::Logger);
+/// The logger of the servers. The `Appender.*` and `Logger.*` settings are applied again when
+/// they are reloaded.
+pub struct LogMgr(RwLock<log4rs::Logger>);
 
 impl LogMgr {
     pub fn new(config: &Config) -> Result<Self> {
+        Ok(Self(RwLock::new(Self::build(config)?)))
+    }
+
+    /// Replaces the appenders and loggers with the ones of `config`.
+    /// The current ones are kept if the new ones cannot be built.
+    pub fn reload(&self, config: &Config) -> Result<()> {
+        let logger = Self::build(config)?;
+        *self.0.write().unwrap() = logger;
+
+        Ok(())
+    }
+
+    fn build(config: &Config) -> Result<log4rs::Logger> {
         let appenders_config = config.get::<Map<String, Value>>("appender", None)?;
         let appenders = appenders_config.iter().filter_map(|(name, value)| {
             let appender: Result<Box<dyn Append>> = match &value.kind {
@@ -39,7 +55,7 @@ impl LogMgr {
         let mut appender_list = vec![];
         for (name, appender) in appenders {
             if let Err(error) = appender {
-                eprintln!("Error in appender config {}: {}", name, error.to_string());
+                eprintln!("Error in appender config {}: {}", name, error);
                 continue;
             }
 
@@ -64,7 +80,7 @@ impl LogMgr {
         let mut root = None;
         for (name, logger) in loggers {
             if let Err(error) = logger {
-                eprintln!("Error in logger config {}: {}", name, error.to_string());
+                eprintln!("Error in logger config {}: {}", name, error);
                 continue;
             }
 
@@ -85,7 +101,7 @@ impl LogMgr {
             .loggers(logger_list)
             .build(root.unwrap_or_else(|| Root::builder().build(LevelFilter::Warn)))?;
 
-        Ok(Self(log4rs::Logger::new(config)))
+        Ok(log4rs::Logger::new(config))
     }
 
     /// Write a log entry
@@ -138,17 +154,17 @@ impl LogMgr {
 impl Log for LogMgr {
     #[inline]
     fn enabled(&self, metadata: &Metadata) -> bool {
-        self.0.enabled(metadata)
+        self.0.read().unwrap().enabled(metadata)
     }
 
     #[inline]
     fn log(&self, record: &Record) {
-        self.0.log(record)
+        self.0.read().unwrap().log(record)
     }
 
     #[inline]
     fn flush(&self) {
-        Log::flush(&self.0)
+        Log::flush(&*self.0.read().unwrap())
     }
 }
 
@@ -157,7 +173,9 @@ impl Log for LogMgr {
 pub extern "C" fn LogMgr_Initialize(config: &Config) -> *const LogMgr {
     let mgr = LogMgr::new(config);
     let Ok(log_mgr) = mgr else {
-        eprintln!("Cannot initialize log manager: {}", unsafe { mgr.unwrap_err_unchecked() }.to_string());
+        eprintln!("Cannot initialize log manager: {}", unsafe {
+            mgr.unwrap_err_unchecked()
+        });
         return std::ptr::null();
     };
 
@@ -165,10 +183,14 @@ pub extern "C" fn LogMgr_Initialize(config: &Config) -> *const LogMgr {
     let pointer = Box::leak(boxed);
     let res = ::log::set_logger(pointer);
     if let Err(e) = res {
-        eprintln!(
-            "Cannot set log manager as default logger: {}",
-            e.to_string()
-        );
+        eprintln!("Cannot set log manager as default logger: {}", e);
+    } else {
+        let log_mgr: &'static LogMgr = pointer;
+        config.subscribe_all(&["Appender.*", "Logger.*"], move |config, _| {
+            if let Err(e) = log_mgr.reload(config) {
+                ::log::error!(target: "server::loading", "Cannot apply the log settings: {:#}", e);
+            }
+        });
     }
 
     ::log::set_max_level(LevelFilter::Trace);
@@ -0,0 +1,230 @@
+//! Encoding of the packet fields bincode cannot express,
+//! used by the `#[wow(...)]` field attributes of the packet macros.
+
+use bincode::de::read::Reader;
+use bincode::de::Decoder;
+use bincode::enc::write::Writer;
+use bincode::enc::Encoder;
+use bincode::error::{DecodeError, EncodeError};
+use bincode::{Decode, Encode};
+
+/// A field stored as raw bytes: `String` or `Vec<u8>`.
+pub trait FieldBytes: Sized {
+    fn field_bytes(&self) -> &[u8];
+    fn from_field_bytes(bytes: Vec<u8>) -> Result<Self, DecodeError>;
+}
+
+impl FieldBytes for String {
+    fn field_bytes(&self) -> &[u8] {
+        self.as_bytes()
+    }
+
+    fn from_field_bytes(bytes: Vec<u8>) -> Result<Self, DecodeError> {
+        String::from_utf8(bytes).map_err(|e| DecodeError::Utf8 {
+            inner: e.utf8_error(),
+        })
+    }
+}
+
+impl FieldBytes for Vec<u8> {
+    fn field_bytes(&self) -> &[u8] {
+        self
+    }
+
+    fn from_field_bytes(bytes: Vec<u8>) -> Result<Self, DecodeError> {
+        Ok(bytes)
+    }
+}
+
+/// Bytes read at once by [`read_bytes`].
+const READ_CHUNK: usize = 256;
+
+/// Reads `length` bytes. The length comes from the client, so the bytes are read by chunks:
+/// the buffer only grows with the data actually received.
+fn read_bytes<D: Decoder>(decoder: &mut D, length: usize) -> Result<Vec<u8>, DecodeError> {
+    decoder.claim_bytes_read(length)?;
+    let mut bytes = Vec::with_capacity(length.min(READ_CHUNK));
+    while bytes.len() < length {
+        let start = bytes.len();
+        bytes.resize(start + (length - start).min(READ_CHUNK), 0);
+        decoder.reader().read(&mut bytes[start..])?;
+    }
+
+    Ok(bytes)
+}
+
+/// Writes a NUL terminated string.
+pub fn encode_c_string<T: FieldBytes, E: Encoder>(
+    value: &T,
+    encoder: &mut E,
+) -> Result<(), EncodeError> {
+    let bytes = value.field_bytes();
+    if bytes.contains(&0) {
+        return Err(EncodeError::Other("C string containing a NUL byte"));
+    }
+
+    encoder.writer().write(bytes)?;
+    encoder.writer().write(&[0])
+}
+
+/// Reads a NUL terminated string.
+pub fn decode_c_string<T: FieldBytes, D: Decoder>(decoder: &mut D) -> Result<T, DecodeError> {
+    let mut bytes = vec![];
+    loop {
+        let byte = read_bytes(decoder, 1)?[0];
+        if byte == 0 {
+            break;
+        }
+
+        bytes.push(byte);
+    }
+
+    T::from_field_bytes(bytes)
+}
+
+/// Writes the length of the value as a `L` integer, followed by the value.
+pub fn encode_prefixed<L, T, E>(value: &T, encoder: &mut E) -> Result<(), EncodeError>
+where
+    L: Encode + TryFrom<usize>,
+    T: FieldBytes,
+    E: Encoder,
+{
+    let bytes = value.field_bytes();
+    L::try_from(bytes.len())
+        .map_err(|_| EncodeError::Other("field too long for its length prefix"))?
+        .encode(encoder)?;
+
+    encoder.writer().write(bytes)
+}
+
+/// Reads a `L` length, followed by the value.
+pub fn decode_prefixed<L, T, D>(decoder: &mut D) -> Result<T, DecodeError>
+where
+    L: Decode + Into<u64>,
+    T: FieldBytes,
+    D: Decoder,
+{
+    let length = usize::try_from(L::decode(decoder)?.into())
+        .map_err(|_| DecodeError::Other("field length overflow"))?;
+
+    T::from_field_bytes(read_bytes(decoder, length)?)
+}
+
+/// Writes the value padded with NUL bytes to `length` bytes.
+pub fn encode_fixed<T: FieldBytes, E: Encoder>(
+    value: &T,
+    length: usize,
+    encoder: &mut E,
+) -> Result<(), EncodeError> {
+    let bytes = value.field_bytes();
+    if bytes.len() > length {
+        return Err(EncodeError::Other("field too long for its fixed size"));
+    }
+
+    encoder.writer().write(bytes)?;
+    encoder.writer().write(&vec![0; length - bytes.len()])
+}
+
+/// Reads `length` bytes, without the trailing NUL bytes.
+pub fn decode_fixed<T: FieldBytes, D: Decoder>(
+    decoder: &mut D,
+    length: usize,
+) -> Result<T, DecodeError> {
+    let mut bytes = read_bytes(decoder, length)?;
+    while bytes.last() == Some(&0) {
+        bytes.pop();
+    }
+
+    T::from_field_bytes(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::net::fields::{
+        decode_c_string, decode_fixed, decode_prefixed, encode_c_string, encode_fixed,
+        encode_prefixed,
+    };
+    use bincode::config::{self, Configuration, Fixint, LittleEndian};
+    use bincode::de::read::SliceReader;
+    use bincode::de::DecoderImpl;
+    use bincode::enc::write::Writer;
+    use bincode::enc::EncoderImpl;
+    use bincode::error::{DecodeError, EncodeError};
+
+    type TestConfiguration = Configuration<LittleEndian, Fixint>;
+    const CONFIGURATION: TestConfiguration = config::standard()
+        .with_little_endian()
+        .with_fixed_int_encoding();
+
+    struct VecWriter(Vec<u8>);
+
+    impl Writer for VecWriter {
+        fn write(&mut self, bytes: &[u8]) -> Result<(), EncodeError> {
+            self.0.extend_from_slice(bytes);
+            Ok(())
+        }
+    }
+
+    fn encode<F>(f: F) -> Result<Vec<u8>, EncodeError>
+    where
+        F: FnOnce(&mut EncoderImpl<VecWriter, TestConfiguration>) -> Result<(), EncodeError>,
+    {
+        let mut encoder = EncoderImpl::new(VecWriter(vec![]), CONFIGURATION);
+        f(&mut encoder)?;
+        Ok(encoder.into_writer().0)
+    }
+
+    fn decode<T, F>(bytes: &[u8], f: F) -> Result<T, DecodeError>
+    where
+        F: FnOnce(&mut DecoderImpl<SliceReader, TestConfiguration>) -> Result<T, DecodeError>,
+    {
+        f(&mut DecoderImpl::new(
+            SliceReader::new(bytes),
+            CONFIGURATION,
+        ))
+    }
+
+    #[test]
+    pub fn fields_round_trip() {
+        let name = "PLAYER".to_string();
+        let c_string = encode(|e| encode_c_string(&name, e)).unwrap();
+        assert_eq!(c_string, b"PLAYER\0");
+        assert_eq!(
+            decode(&c_string, |d| decode_c_string::<String, _>(d)).unwrap(),
+            name
+        );
+        assert!(encode(|e| encode_c_string(&"A\0B".to_string(), e)).is_err());
+
+        let prefixed = encode(|e| encode_prefixed::<u16, _, _>(&name, e)).unwrap();
+        assert_eq!(prefixed, b"\x06\x00PLAYER");
+        assert_eq!(
+            decode(&prefixed, |d| decode_prefixed::<u16, Vec<u8>, _>(d)).unwrap(),
+            b"PLAYER"
+        );
+        assert!(decode(&prefixed[..4], |d| decode_prefixed::<u16, String, _>(d)).is_err());
+        assert!(encode(|e| encode_prefixed::<u8, _, _>(&vec![0_u8; 256], e)).is_err());
+
+        let fixed = encode(|e| encode_fixed(&"WoW".to_string(), 4, e)).unwrap();
+        assert_eq!(fixed, b"WoW\0");
+        assert_eq!(
+            decode(&fixed, |d| decode_fixed::<String, _>(d, 4)).unwrap(),
+            "WoW"
+        );
+        assert!(encode(|e| encode_fixed(&"WoWo!".to_string(), 4, e)).is_err());
+    }
+
+    #[test]
+    pub fn lengths_are_bounded_by_the_data() {
+        let mut prefixed = (u64::MAX >> 1).to_le_bytes().to_vec();
+        prefixed.extend_from_slice(&[0; 1000]);
+        assert!(matches!(
+            decode(&prefixed, |d| decode_prefixed::<u64, Vec<u8>, _>(d)),
+            Err(DecodeError::UnexpectedEnd { .. })
+        ));
+
+        let mut long = 600_u16.to_le_bytes().to_vec();
+        long.extend_from_slice(&[b'x'; 600]);
+        let decoded = decode(&long, |d| decode_prefixed::<u16, String, _>(d)).unwrap();
+        assert_eq!(decoded, "x".repeat(600));
+    }
+}
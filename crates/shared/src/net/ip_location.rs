@@ -0,0 +1,323 @@
+use crate::timer::create_timer;
+use anyhow::{bail, Error, Result};
+use log::{error, info};
+use std::convert::Infallible;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::net::IpAddr;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock, RwLock};
+use std::time::{Duration, SystemTime};
+use tokio::sync::watch;
+
+static STORE: OnceLock<IpLocationStore> = OnceLock::new();
+
+/// Country an IP address is located in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IpLocation {
+    /// Lowercase ISO 3166 country code, `-` for reserved ranges.
+    pub country_code: String,
+    pub country_name: String,
+}
+
+/// A range of addresses, bounds included.
+#[derive(Debug, Clone, Copy)]
+struct IpRange<T> {
+    from: T,
+    to: T,
+    location: u16,
+}
+
+/// Finds the range containing the given address in a sorted range list.
+fn find_range<T: Ord + Copy>(ranges: &[IpRange<T>], ip: T) -> Option<u16> {
+    let index = ranges.partition_point(|range| range.to < ip);
+    ranges
+        .get(index)
+        .filter(|range| range.from <= ip)
+        .map(|range| range.location)
+}
+
+/// Sorts the ranges and checks they don't overlap.
+fn sort_ranges<T: Ord + Copy>(ranges: &mut [IpRange<T>]) -> Result<()> {
+    ranges.sort_unstable_by_key(|range| range.from);
+    if ranges.windows(2).any(|pair| pair[0].to >= pair[1].from) {
+        bail!("Overlapping IP ranges detected in database file");
+    }
+
+    Ok(())
+}
+
+/// The IPv4 mapped IPv6 addresses, `::ffff:0:0/96`.
+const IPV4_MAPPED_START: u128 = 0xFFFF_0000_0000;
+const IPV4_MAPPED_END: u128 = 0xFFFF_FFFF_FFFF;
+
+/// An IP2Location country database.
+/// Locations are stored once, ranges only keep the index of their location.
+#[derive(Debug, Default)]
+pub struct IpLocationTable {
+    locations: Vec<IpLocation>,
+    v4: Vec<IpRange<u32>>,
+    v6: Vec<IpRange<u128>>,
+}
+
+impl IpLocationTable {
+    /// Parses an IP2Location DB1 CSV file, either the IPv4 or the IPv6 one.
+    /// Lines are `"ip_from","ip_to","country_code","country_name"`, with addresses as integers.
+    pub fn parse<R: BufRead>(reader: R) -> Result<Self> {
+        let mut table = Self::default();
+        for (number, line) in reader.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let invalid_line = || Error::msg(format!("Invalid IP location at line {}", number + 1));
+            let mut fields = line
+                .splitn(4, ',')
+                .map(|field| field.trim().replace('"', ""));
+            let (Some(from), Some(to), Some(country_code), Some(country_name)) =
+                (fields.next(), fields.next(), fields.next(), fields.next())
+            else {
+                return Err(invalid_line());
+            };
+
+            let from = from.parse::<u128>().map_err(|_| invalid_line())?;
+            let to = to.parse::<u128>().map_err(|_| invalid_line())?;
+            if from > to {
+                return Err(invalid_line());
+            }
+
+            let location = table.location_index(IpLocation {
+                country_code: country_code.to_lowercase(),
+                country_name,
+            })?;
+
+            if to <= u32::MAX as u128 {
+                table.v4.push(IpRange {
+                    from: from as u32,
+                    to: to as u32,
+                    location,
+                });
+            } else if from >= IPV4_MAPPED_START && to <= IPV4_MAPPED_END {
+                // IPv4 ranges of the IPv6 database
+                table.v4.push(IpRange {
+                    from: (from - IPV4_MAPPED_START) as u32,
+                    to: (to - IPV4_MAPPED_START) as u32,
+                    location,
+                });
+            } else {
+                table.v6.push(IpRange { from, to, location });
+            }
+        }
+
+        sort_ranges(&mut table.v4)?;
+        sort_ranges(&mut table.v6)?;
+        Ok(table)
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let file = File::open(path)
+            .map_err(|e| Error::msg(format!("Cannot open {}: {}", path.display(), e)))?;
+
+        Self::parse(BufReader::new(file))
+    }
+
+    fn location_index(&mut self, location: IpLocation) -> Result<u16> {
+        let index = match self.locations.iter().position(|known| *known == location) {
+            Some(index) => index,
+            None => {
+                self.locations.push(location);
+                self.locations.len() - 1
+            }
+        };
+
+        u16::try_from(index).map_err(|_| Error::msg("Too many IP locations"))
+    }
+
+    /// Number of address ranges.
+    pub fn len(&self) -> usize {
+        self.v4.len() + self.v6.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn lookup(&self, ip: IpAddr) -> Option<&IpLocation> {
+        let location = match ip.to_canonical() {
+            IpAddr::V4(ip) => find_range(&self.v4, u32::from(ip)),
+            IpAddr::V6(ip) => find_range(&self.v6, u128::from(ip)),
+        };
+
+        location.map(|index| &self.locations[index as usize])
+    }
+}
+
+/// Whether an account locked to `lock_country` can log in from `country`.
+/// Accounts locked to no country (empty or `00`) and unknown locations are always allowed.
+pub fn is_country_allowed(country: Option<&IpLocation>, lock_country: &str) -> bool {
+    if lock_country.is_empty() || lock_country == "00" {
+        return true;
+    }
+
+    country.is_none_or(|country| country.country_code.eq_ignore_ascii_case(lock_country))
+}
+
+/// The IP location database, reloaded when its file changes.
+pub struct IpLocationStore {
+    table: RwLock<Arc<IpLocationTable>>,
+    modified: Mutex<Option<SystemTime>>,
+    loaded: watch::Sender<bool>,
+}
+
+impl Default for IpLocationStore {
+    fn default() -> Self {
+        Self {
+            table: RwLock::default(),
+            modified: Mutex::default(),
+            loaded: watch::channel(false).0,
+        }
+    }
+}
+
+impl IpLocationStore {
+    /// Gets the IP location database of the server.
+    pub fn global() -> &'static IpLocationStore {
+        STORE.get_or_init(IpLocationStore::default)
+    }
+
+    /// Gets the current table.
+    pub fn table(&self) -> Arc<IpLocationTable> {
+        self.table.read().unwrap().clone()
+    }
+
+    /// Replaces the current table.
+    pub fn set_table(&self, table: IpLocationTable) {
+        *self.table.write().unwrap() = Arc::new(table);
+        self.loaded.send_replace(true);
+    }
+
+    /// Loads the database file, if it changed since the last load.
+    /// The current table is kept if the file cannot be loaded.
+    pub async fn reload(&self, path: &Path) -> Result<()> {
+        let modified = {
+            let path = path.to_path_buf();
+            tokio::task::spawn_blocking(move || std::fs::metadata(path)?.modified()).await?
+        };
+
+        let result = match modified {
+            Ok(modified) if *self.modified.lock().unwrap() == Some(modified) => return Ok(()),
+            Ok(modified) => {
+                let path = path.to_path_buf();
+                let table =
+                    tokio::task::spawn_blocking(move || IpLocationTable::load(&path)).await?;
+                table.map(|table| (table, modified))
+            }
+            Err(e) => Err(Error::msg(format!("Cannot open {}: {}", path.display(), e))),
+        };
+
+        match result {
+            Ok((table, modified)) => {
+                info!(target: "server::loading", ">> Loaded {} ip location entries.", table.len());
+                *self.modified.lock().unwrap() = Some(modified);
+                self.set_table(table);
+                Ok(())
+            }
+            Err(e) => {
+                // Do not make lookups wait forever on a missing file
+                self.loaded.send_replace(true);
+                Err(e)
+            }
+        }
+    }
+
+    /// Loads the database file and checks it for changes every `interval`.
+    /// Without file, the database stays empty.
+    pub fn watch(&'static self, path: Option<PathBuf>, interval: Duration) {
+        let Some(path) = path else {
+            self.set_table(IpLocationTable::default());
+            return;
+        };
+
+        create_timer(interval, move || {
+            let path = path.clone();
+            async move {
+                if let Err(e) = self.reload(&path).await {
+                    error!(target: "server::loading", "IPLocation: {}", e);
+                }
+
+                Ok::<(), Infallible>(())
+            }
+        });
+    }
+
+    /// Gets the location of an address, waiting for the database to be loaded.
+    pub async fn location(&self, ip: IpAddr) -> Option<IpLocation> {
+        let _ = self.loaded.subscribe().wait_for(|loaded| *loaded).await;
+        self.table().lookup(ip).cloned()
+    }
+
+    /// Whether an account locked to `lock_country` can log in from the given address.
+    pub async fn check_country_lock(&self, ip: IpAddr, lock_country: &str) -> bool {
+        is_country_allowed(self.location(ip).await.as_ref(), lock_country)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::net::ip_location::{is_country_allowed, IpLocationStore, IpLocationTable};
+    use std::net::IpAddr;
+
+    const DATABASE: &str = r#""0","16777215","-","-"
+"16777472","16778239","CN","China"
+"16777216","16777471","US","United States of America"
+"281470698586368","281470698586623","AU","Australia"
+"42540528726795050063891204319802818560","42540528806023212578155541913346768895","JP","Japan"
+"55827987809411540836515382960316219392","55827987888639703350779720553860169727","DE","Germany"
+"#;
+
+    fn lookup(table: &IpLocationTable, ip: &str) -> Option<String> {
+        table
+            .lookup(ip.parse::<IpAddr>().unwrap())
+            .map(|location| location.country_code.clone())
+    }
+
+    #[test]
+    pub fn ranges_are_looked_up() {
+        let table = IpLocationTable::parse(DATABASE.as_bytes()).unwrap();
+        assert_eq!(table.len(), 6);
+        assert_eq!(lookup(&table, "1.0.0.0").as_deref(), Some("us"));
+        assert_eq!(lookup(&table, "1.0.0.255").as_deref(), Some("us"));
+        assert_eq!(lookup(&table, "1.0.1.0").as_deref(), Some("cn"));
+        assert_eq!(lookup(&table, "::ffff:1.0.1.0").as_deref(), Some("cn"));
+        // Mapped range of the IPv6 database
+        assert_eq!(lookup(&table, "1.1.1.1").as_deref(), Some("au"));
+        assert_eq!(lookup(&table, "1.2.0.0"), None);
+        assert_eq!(lookup(&table, "2001:200::1").as_deref(), Some("jp"));
+        assert_eq!(lookup(&table, "2a00:1450:4001::1").as_deref(), Some("de"));
+        assert_eq!(lookup(&table, "2a00:1451::"), None);
+
+        let overlapping = "\"16777216\",\"16777471\",\"US\",\"United States of America\"\n\
+                           \"16777400\",\"16777500\",\"CN\",\"China\"\n";
+        assert!(IpLocationTable::parse(overlapping.as_bytes()).is_err());
+        assert!(IpLocationTable::parse("\"1\",\"x\",\"US\",\"\"".as_bytes()).is_err());
+    }
+
+    #[tokio::test]
+    pub async fn country_lock() {
+        let store = IpLocationStore::default();
+        store.set_table(IpLocationTable::parse(DATABASE.as_bytes()).unwrap());
+
+        let ip = "1.0.1.1".parse().unwrap();
+        assert!(store.check_country_lock(ip, "cn").await);
+        assert!(store.check_country_lock(ip, "CN").await);
+        assert!(!store.check_country_lock(ip, "us").await);
+        assert!(store.check_country_lock(ip, "00").await);
+        assert!(
+            store
+                .check_country_lock("8.8.8.8".parse().unwrap(), "us")
+                .await
+        );
+        assert!(is_country_allowed(None, "fr"));
+    }
+}
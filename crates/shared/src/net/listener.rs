@@ -0,0 +1,236 @@
+use anyhow::{Context, Error, Result};
+use log::{error, info};
+use socket2::{Domain, Protocol, Socket, Type};
+use std::net::{IpAddr, SocketAddr};
+use std::time::Duration;
+use tokio::net::{lookup_host, TcpListener, TcpStream};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+/// Backlog of the listening sockets.
+const LISTEN_BACKLOG: i32 = 1024;
+
+/// Resolves a list of listen endpoints.
+/// Every entry can be an address (`0.0.0.0`, `::`), an address with port (`0.0.0.0:3724`,
+/// `[::]:3724`) or a hostname with optional port. `default_port` is used when no port is given.
+pub async fn resolve_endpoints<S>(endpoints: &[S], default_port: u16) -> Result<Vec<SocketAddr>>
+where
+    S: AsRef<str>,
+{
+    let mut addresses = vec![];
+    for endpoint in endpoints {
+        let endpoint = endpoint.as_ref().trim();
+        for address in resolve_endpoint(endpoint, default_port).await? {
+            if !addresses.contains(&address) {
+                addresses.push(address);
+            }
+        }
+    }
+
+    Ok(addresses)
+}
+
+async fn resolve_endpoint(endpoint: &str, default_port: u16) -> Result<Vec<SocketAddr>> {
+    if let Ok(address) = endpoint.parse::<SocketAddr>() {
+        return Ok(vec![address]);
+    }
+
+    if let Ok(ip) = endpoint.trim_matches(['[', ']']).parse::<IpAddr>() {
+        return Ok(vec![SocketAddr::new(ip, default_port)]);
+    }
+
+    let host = match endpoint.rsplit_once(':') {
+        Some((_, port)) if port.parse::<u16>().is_ok() => endpoint.to_string(),
+        _ => format!("{}:{}", endpoint, default_port),
+    };
+
+    let addresses = lookup_host(&host)
+        .await
+        .with_context(|| format!("Cannot resolve listen address '{}'", endpoint))?
+        .collect::<Vec<_>>();
+
+    if addresses.is_empty() {
+        return Err(Error::msg(format!(
+            "Listen address '{}' did not resolve to any address",
+            endpoint
+        )));
+    }
+
+    Ok(addresses)
+}
+
+/// Binds a TCP listener on the given address.
+/// IPv6 listeners are IPv6 only, so that the same port can be bound on both `0.0.0.0` and `::`.
+pub fn bind(address: SocketAddr) -> Result<TcpListener> {
+    let bind = || -> std::io::Result<TcpListener> {
+        let socket = Socket::new(
+            Domain::for_address(address),
+            Type::STREAM,
+            Some(Protocol::TCP),
+        )?;
+        if address.is_ipv6() {
+            socket.set_only_v6(true)?;
+        }
+
+        #[cfg(unix)]
+        socket.set_reuse_address(true)?;
+        socket.set_nonblocking(true)?;
+        socket.bind(&address.into())?;
+        socket.listen(LISTEN_BACKLOG)?;
+
+        TcpListener::from_std(socket.into())
+    };
+
+    bind().with_context(|| format!("Cannot bind to {}", address))
+}
+
+/// A connection accepted by one of the [`Listeners`].
+pub struct Accepted {
+    pub stream: TcpStream,
+    pub peer_address: SocketAddr,
+    pub local_address: SocketAddr,
+}
+
+/// A set of TCP listeners feeding a single accept queue.
+pub struct Listeners {
+    receiver: mpsc::Receiver<Accepted>,
+    addresses: Vec<SocketAddr>,
+    tasks: Vec<JoinHandle<()>>,
+}
+
+impl Listeners {
+    /// Binds all the given addresses. Fails if any of them cannot be bound.
+    /// `target` is the log target used to report listener events.
+    pub fn bind(addresses: &[SocketAddr], target: &'static str) -> Result<Self> {
+        if addresses.is_empty() {
+            return Err(Error::msg("No listen address configured"));
+        }
+
+        let listeners = addresses
+            .iter()
+            .map(|address| bind(*address))
+            .collect::<Result<Vec<_>>>()?;
+
+        let (sender, receiver) = mpsc::channel(LISTEN_BACKLOG as usize);
+        let mut tasks = vec![];
+        let mut bound = vec![];
+        for listener in listeners {
+            let local_address = listener.local_addr()?;
+            info!(target: target, "Listening on {}", local_address);
+
+            let sender = sender.clone();
+            bound.push(local_address);
+            tasks.push(tokio::spawn(async move {
+                loop {
+                    match listener.accept().await {
+                        Ok((stream, peer_address)) => {
+                            let accepted = Accepted {
+                                stream,
+                                peer_address,
+                                local_address,
+                            };
+
+                            if sender.send(accepted).await.is_err() {
+                                break;
+                            }
+                        }
+                        Err(e) => {
+                            // Usually caused by running out of file descriptors: give it some time
+                            error!(target: target, "Failed to accept connection on {}: {}", local_address, e);
+                            tokio::time::sleep(Duration::from_millis(100)).await;
+                        }
+                    }
+                }
+            }));
+        }
+
+        Ok(Self {
+            receiver,
+            addresses: bound,
+            tasks,
+        })
+    }
+
+    /// The local addresses of the listeners.
+    pub fn addresses(&self) -> &[SocketAddr] {
+        &self.addresses
+    }
+
+    /// Waits for the next connection on any of the listeners.
+    pub async fn accept(&mut self) -> Option<Accepted> {
+        self.receiver.recv().await
+    }
+}
+
+impl Drop for Listeners {
+    fn drop(&mut self) {
+        for task in self.tasks.drain(..) {
+            task.abort();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::net::listener::{resolve_endpoints, Listeners};
+    use anyhow::Result;
+    use std::net::SocketAddr;
+    use tokio::net::TcpStream;
+
+    #[tokio::test]
+    pub async fn endpoints_are_resolved() -> Result<()> {
+        let addresses = resolve_endpoints(
+            &[
+                "0.0.0.0",
+                "[::]:3725",
+                "::1",
+                "127.0.0.1:3724",
+                "0.0.0.0:3724",
+            ],
+            3724,
+        )
+        .await?;
+
+        assert_eq!(
+            addresses,
+            vec![
+                "0.0.0.0:3724".parse::<SocketAddr>()?,
+                "[::]:3725".parse()?,
+                "[::1]:3724".parse()?,
+                "127.0.0.1:3724".parse()?,
+            ]
+        );
+
+        let addresses = resolve_endpoints(&["localhost"], 3724).await?;
+        assert!(addresses.iter().all(|address| address.port() == 3724));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn listeners_share_accept_queue() -> Result<()> {
+        let mut listeners =
+            Listeners::bind(&["127.0.0.1:0".parse()?, "127.0.0.1:0".parse()?], "network")?;
+        let addresses = listeners.addresses().to_vec();
+        assert_eq!(addresses.len(), 2);
+
+        for address in addresses {
+            let _client = TcpStream::connect(address).await?;
+            let accepted = listeners.accept().await.unwrap();
+            assert_eq!(accepted.local_address, address);
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn bind_failure_is_reported() -> Result<()> {
+        let listeners = Listeners::bind(&["127.0.0.1:0".parse()?], "network")?;
+        let address = listeners.addresses()[0];
+
+        let error = Listeners::bind(&[address], "network").err().unwrap();
+        assert_eq!(error.to_string(), format!("Cannot bind to {}", address));
+
+        Ok(())
+    }
+}
@@ -1,9 +1,15 @@
 use crate::AsyncResult;
 use bincode::{Decode, Encode};
 
+pub mod fields;
+pub mod ip_location;
+pub mod listener;
+pub mod notifier;
+pub mod proxy_protocol;
+
 pub trait Session {
     /// Sends a WoW packet to the client.
-    fn send_packet<'a, T: WoWPacket + Send + 'a>(&'a mut self, pkt: T) -> AsyncResult<()>;
+    fn send_packet<'a, T: WoWPacket + Send + 'a>(&'a mut self, pkt: T) -> AsyncResult<'a, ()>;
 }
 
 pub trait WoWPacket: Encode + Decode {}
@@ -0,0 +1,47 @@
+use std::sync::Arc;
+use tokio::sync::Notify;
+
+/// Wakes a task up to process pending C++ work, e.g. when a database query completed.
+/// C++ gets handles on it through the FFI of the task owner, and can use them from any thread.
+#[derive(Default)]
+pub struct Notifier(Notify);
+
+impl Notifier {
+    pub fn new() -> Arc<Self> {
+        Arc::default()
+    }
+
+    /// Waits for a notification. A notification sent while nobody waits wakes the next wait.
+    pub async fn notified(&self) {
+        self.0.notified().await
+    }
+
+    pub fn notify(&self) {
+        self.0.notify_one();
+    }
+
+    /// Gets a new handle for C++, to be freed with `Notifier_Free`.
+    pub fn to_raw(self: &Arc<Self>) -> *const Notifier {
+        Arc::into_raw(self.clone())
+    }
+}
+
+/// Wakes the task of the notifier up. Can be called from any thread.
+///
+/// # Safety
+/// Unsafe: notifier must be a handle that is not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn Notifier_Notify(notifier: *const Notifier) {
+    (*notifier).notify();
+}
+
+/// Frees a notifier handle.
+///
+/// # Safety
+/// Unsafe: notifier must be a handle that is not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn Notifier_Free(notifier: *const Notifier) {
+    if !notifier.is_null() {
+        drop(Arc::from_raw(notifier));
+    }
+}
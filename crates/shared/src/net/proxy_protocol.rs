@@ -0,0 +1,365 @@
+use crate::config::Config;
+use anyhow::{anyhow, bail, Error, Result};
+use ipnet::IpNet;
+use serde::{Deserialize, Serialize};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::str::FromStr;
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncReadExt};
+use tokio::time;
+
+/// PROXY protocol v2 signature.
+const V2_SIGNATURE: [u8; 12] = *b"\r\n\r\n\0\r\nQUIT\n";
+/// PROXY protocol v1 prefix.
+const V1_PREFIX: &[u8] = b"PROXY ";
+/// Maximum length of a v1 header, including the trailing CRLF.
+const V1_MAX_LENGTH: usize = 107;
+
+/// The `ProxyProtocol` config section.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProxyProtocolSection {
+    pub enabled: bool,
+    pub trusted_proxies: Vec<String>,
+    /// In seconds.
+    pub header_timeout: u64,
+}
+
+impl Default for ProxyProtocolSection {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            trusted_proxies: vec![],
+            header_timeout: 5,
+        }
+    }
+}
+
+/// PROXY protocol settings, read from the `ProxyProtocol` config section.
+#[derive(Debug, Default, Clone)]
+pub struct ProxyProtocolConfig {
+    pub enabled: bool,
+    pub trusted_proxies: Vec<IpNet>,
+    pub header_timeout: Duration,
+}
+
+impl ProxyProtocolConfig {
+    pub fn from_config(config: &Config) -> Result<Self> {
+        let section: ProxyProtocolSection = config.section("ProxyProtocol")?;
+        let trusted_proxies = section
+            .trusted_proxies
+            .iter()
+            .map(|network| parse_network(network))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            enabled: section.enabled,
+            trusted_proxies,
+            header_timeout: Duration::from_secs(section.header_timeout),
+        })
+    }
+
+    /// Whether a PROXY header is expected from the given peer.
+    pub fn is_trusted(&self, ip: IpAddr) -> bool {
+        let ip = ip.to_canonical();
+        self.enabled && self.trusted_proxies.iter().any(|net| net.contains(&ip))
+    }
+
+    /// Gets the real address of a client and the address of its proxy, reading the PROXY
+    /// header if the peer is a trusted proxy. The peer is the client otherwise.
+    pub async fn resolve<R>(
+        &self,
+        reader: &mut R,
+        peer_address: SocketAddr,
+    ) -> Result<(SocketAddr, Option<SocketAddr>)>
+    where
+        R: AsyncRead + Unpin,
+    {
+        if !self.is_trusted(peer_address.ip()) {
+            return Ok((peer_address, None));
+        }
+
+        let header = time::timeout(self.header_timeout, read_proxy_header(reader))
+            .await
+            .map_err(|_| anyhow!("No PROXY header received in time"))??;
+
+        Ok((header.source.unwrap_or(peer_address), Some(peer_address)))
+    }
+}
+
+/// Parses a network in CIDR notation. A plain address is treated as a single host.
+pub fn parse_network(network: &str) -> Result<IpNet> {
+    IpNet::from_str(network)
+        .or_else(|_| IpAddr::from_str(network).map(IpNet::from))
+        .map_err(|_| Error::msg(format!("Invalid network '{}'", network)))
+}
+
+/// Addresses carried by a PROXY header.
+/// Both are `None` when the proxy sent a `LOCAL`/`UNKNOWN` header (e.g. health checks).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProxyHeader {
+    pub source: Option<SocketAddr>,
+    pub destination: Option<SocketAddr>,
+}
+
+impl ProxyHeader {
+    const LOCAL: ProxyHeader = ProxyHeader {
+        source: None,
+        destination: None,
+    };
+}
+
+/// Reads a PROXY protocol (v1 or v2) header from the stream.
+/// No byte after the header is consumed.
+pub async fn read_proxy_header<R>(reader: &mut R) -> Result<ProxyHeader>
+where
+    R: AsyncRead + Unpin,
+{
+    // The shortest v1 header ("PROXY UNKNOWN\r\n") is longer than the v2 signature
+    let mut buf = vec![0_u8; V2_SIGNATURE.len()];
+    reader.read_exact(&mut buf).await?;
+
+    if buf == V2_SIGNATURE {
+        let mut header = [0_u8; 4];
+        reader.read_exact(&mut header).await?;
+        buf.extend_from_slice(&header);
+
+        let length = u16::from_be_bytes([header[2], header[3]]) as usize;
+        let start = buf.len();
+        buf.resize(start + length, 0);
+        reader.read_exact(&mut buf[start..]).await?;
+
+        parse_v2(&buf)
+    } else if buf.starts_with(V1_PREFIX) {
+        while !buf.ends_with(b"\r\n") {
+            if buf.len() >= V1_MAX_LENGTH {
+                bail!("PROXY v1 header too long");
+            }
+
+            buf.push(reader.read_u8().await?);
+        }
+
+        parse_v1(&buf)
+    } else {
+        bail!("Missing PROXY protocol header")
+    }
+}
+
+/// Parses a PROXY protocol v1 header (including the trailing CRLF).
+pub fn parse_v1(header: &[u8]) -> Result<ProxyHeader> {
+    let Some(line) = header
+        .strip_prefix(V1_PREFIX)
+        .and_then(|line| line.strip_suffix(b"\r\n"))
+    else {
+        bail!("Malformed PROXY v1 header");
+    };
+
+    let line = std::str::from_utf8(line)?;
+    let mut parts = line.split(' ');
+    match parts.next() {
+        Some("UNKNOWN") => return Ok(ProxyHeader::LOCAL),
+        Some("TCP4") | Some("TCP6") => {}
+        _ => bail!("Unsupported PROXY v1 protocol"),
+    }
+
+    let (Some(source), Some(destination), Some(source_port), Some(destination_port), None) = (
+        parts.next(),
+        parts.next(),
+        parts.next(),
+        parts.next(),
+        parts.next(),
+    ) else {
+        bail!("Malformed PROXY v1 header");
+    };
+
+    Ok(ProxyHeader {
+        source: Some(SocketAddr::new(source.parse()?, source_port.parse()?)),
+        destination: Some(SocketAddr::new(
+            destination.parse()?,
+            destination_port.parse()?,
+        )),
+    })
+}
+
+/// Parses a PROXY protocol v2 header (signature included).
+pub fn parse_v2(header: &[u8]) -> Result<ProxyHeader> {
+    if header.len() < 16 || header[..12] != V2_SIGNATURE {
+        bail!("Malformed PROXY v2 header");
+    }
+
+    let version = header[12] >> 4;
+    let command = header[12] & 0x0F;
+    let family = header[13];
+    let length = u16::from_be_bytes([header[14], header[15]]) as usize;
+    let addresses = &header[16..];
+
+    if version != 2 {
+        bail!("Unsupported PROXY protocol version {}", version);
+    }
+
+    if addresses.len() < length {
+        bail!("Truncated PROXY v2 header");
+    }
+
+    match command {
+        // LOCAL: connection established by the proxy itself
+        0x0 => return Ok(ProxyHeader::LOCAL),
+        0x1 => {}
+        _ => bail!("Unsupported PROXY v2 command {}", command),
+    }
+
+    let (source, destination) = match family >> 4 {
+        // AF_INET
+        0x1 if length >= 12 => {
+            let ip = |offset: usize| {
+                let octets: [u8; 4] = addresses[offset..offset + 4].try_into().unwrap();
+                IpAddr::from(Ipv4Addr::from(octets))
+            };
+
+            (ip(0), ip(4))
+        }
+        // AF_INET6
+        0x2 if length >= 36 => {
+            let ip = |offset: usize| {
+                let octets: [u8; 16] = addresses[offset..offset + 16].try_into().unwrap();
+                IpAddr::from(Ipv6Addr::from(octets))
+            };
+
+            (ip(0), ip(16))
+        }
+        // AF_UNSPEC or AF_UNIX: no usable address
+        0x0 | 0x3 => return Ok(ProxyHeader::LOCAL),
+        _ => bail!("Malformed PROXY v2 address block"),
+    };
+
+    let ports = match source {
+        IpAddr::V4(_) => &addresses[8..12],
+        IpAddr::V6(_) => &addresses[32..36],
+    };
+
+    Ok(ProxyHeader {
+        source: Some(SocketAddr::new(
+            source,
+            u16::from_be_bytes([ports[0], ports[1]]),
+        )),
+        destination: Some(SocketAddr::new(
+            destination,
+            u16::from_be_bytes([ports[2], ports[3]]),
+        )),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::net::proxy_protocol::{
+        parse_v1, parse_v2, read_proxy_header, ProxyHeader, ProxyProtocolConfig,
+    };
+    use anyhow::Result;
+    use std::net::SocketAddr;
+    use std::time::Duration;
+
+    #[test]
+    pub fn v1_header() -> Result<()> {
+        let header = parse_v1(b"PROXY TCP4 192.168.0.1 192.168.0.11 56324 3724\r\n")?;
+        assert_eq!(
+            header.source,
+            Some("192.168.0.1:56324".parse::<SocketAddr>()?)
+        );
+        assert_eq!(
+            header.destination,
+            Some("192.168.0.11:3724".parse::<SocketAddr>()?)
+        );
+
+        let header = parse_v1(b"PROXY TCP6 ::1 ::2 1 2\r\n")?;
+        assert_eq!(header.source, Some("[::1]:1".parse::<SocketAddr>()?));
+
+        assert_eq!(
+            parse_v1(b"PROXY UNKNOWN ffff::1 ffff::2 1 2\r\n")?,
+            ProxyHeader {
+                source: None,
+                destination: None
+            }
+        );
+        assert!(parse_v1(b"PROXY TCP4 192.168.0.1 192.168.0.11 56324\r\n").is_err());
+        assert!(parse_v1(b"PROXY UDP4 192.168.0.1 192.168.0.11 1 2\r\n").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn v2_header() -> Result<()> {
+        let mut header = b"\r\n\r\n\0\r\nQUIT\n\x21\x11\x00\x0C".to_vec();
+        header.extend_from_slice(&[10, 0, 0, 1, 10, 0, 0, 2, 0xDC, 0x04, 0x0E, 0x8C]);
+
+        let parsed = parse_v2(&header)?;
+        assert_eq!(parsed.source, Some("10.0.0.1:56324".parse::<SocketAddr>()?));
+        assert_eq!(
+            parsed.destination,
+            Some("10.0.0.2:3724".parse::<SocketAddr>()?)
+        );
+
+        // LOCAL command
+        let local = b"\r\n\r\n\0\r\nQUIT\n\x20\x00\x00\x00";
+        assert_eq!(parse_v2(local)?.source, None);
+
+        // Truncated address block
+        assert!(parse_v2(&header[..20]).is_err());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn read_leaves_payload_untouched() -> Result<()> {
+        let mut stream: &[u8] = b"PROXY TCP4 1.2.3.4 5.6.7.8 1000 3724\r\n\x00\x08";
+        let header = read_proxy_header(&mut stream).await?;
+        assert_eq!(header.source, Some("1.2.3.4:1000".parse::<SocketAddr>()?));
+        assert_eq!(stream, b"\x00\x08");
+
+        let mut stream: &[u8] = b"\x00\x08\x26\x00WoW\x00";
+        assert!(read_proxy_header(&mut stream).await.is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn trusted_proxies() -> Result<()> {
+        let config = ProxyProtocolConfig {
+            enabled: true,
+            trusted_proxies: vec!["10.0.0.0/8".parse()?, "::1/128".parse()?],
+            ..Default::default()
+        };
+
+        assert!(config.is_trusted("10.1.2.3".parse()?));
+        assert!(config.is_trusted("::ffff:10.1.2.3".parse()?));
+        assert!(config.is_trusted("::1".parse()?));
+        assert!(!config.is_trusted("192.168.0.1".parse()?));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn addresses_are_resolved() -> Result<()> {
+        let config = ProxyProtocolConfig {
+            enabled: true,
+            trusted_proxies: vec!["10.0.0.0/8".parse()?],
+            header_timeout: Duration::from_secs(1),
+        };
+        let proxy = "10.0.0.1:4000".parse::<SocketAddr>()?;
+        let client = "1.2.3.4:1000".parse::<SocketAddr>()?;
+
+        let mut stream: &[u8] = b"PROXY TCP4 1.2.3.4 5.6.7.8 1000 8085\r\n\x00";
+        assert_eq!(
+            config.resolve(&mut stream, proxy).await?,
+            (client, Some(proxy))
+        );
+        assert_eq!(stream, b"\x00");
+
+        // Untrusted peers are the clients, their stream is not read
+        let header: &[u8] = b"PROXY TCP4 9.9.9.9 5.6.7.8 1000 8085\r\n";
+        let mut stream = header;
+        assert_eq!(config.resolve(&mut stream, client).await?, (client, None));
+        assert_eq!(stream, header);
+
+        Ok(())
+    }
+}
@@ -1,4 +1,6 @@
+use futures::Stream;
 use std::future::Future;
+use std::io;
 use std::pin::Pin;
 use std::task::{Context, Poll};
 #[cfg(unix)]
@@ -15,56 +17,149 @@ pub enum Signal {
     Terminate = 0,
     Interruption = 1,
     Abort = 2,
+    /// SIGHUP, to reload the configuration.
+    Reload = 3,
+    User1 = 4,
+    User2 = 5,
+    /// SIGQUIT, to dump diagnostics.
+    Quit = 6,
 }
 
+impl Signal {
+    pub const ALL: [Signal; 7] = [
+        Signal::Terminate,
+        Signal::Interruption,
+        Signal::Abort,
+        Signal::Reload,
+        Signal::User1,
+        Signal::User2,
+        Signal::Quit,
+    ];
+}
+
+#[cfg(unix)]
+type Listener = tokio_signal;
+
+#[cfg(unix)]
+fn listeners(signal_kind: Signal) -> io::Result<Vec<Listener>> {
+    let kind = match signal_kind {
+        Signal::Terminate => SignalKind::terminate(),
+        Signal::Interruption => SignalKind::interrupt(),
+        Signal::Abort => SignalKind::from_raw(libc::SIGABRT),
+        Signal::Reload => SignalKind::hangup(),
+        Signal::User1 => SignalKind::user_defined1(),
+        Signal::User2 => SignalKind::user_defined2(),
+        Signal::Quit => SignalKind::quit(),
+    };
+
+    Ok(vec![signal(kind)?])
+}
+
+/// The console events of Windows, which only has the termination and interruption signals.
+#[cfg(windows)]
+enum Listener {
+    CtrlBreak(CtrlBreak),
+    CtrlC(CtrlC),
+    CtrlClose(CtrlClose),
+    CtrlShutdown(CtrlShutdown),
+    CtrlLogoff(CtrlLogoff),
+}
+
+#[cfg(windows)]
+impl Listener {
+    fn poll_recv(&mut self, cx: &mut Context<'_>) -> Poll<Option<()>> {
+        match self {
+            Listener::CtrlBreak(listener) => listener.poll_recv(cx),
+            Listener::CtrlC(listener) => listener.poll_recv(cx),
+            Listener::CtrlClose(listener) => listener.poll_recv(cx),
+            Listener::CtrlShutdown(listener) => listener.poll_recv(cx),
+            Listener::CtrlLogoff(listener) => listener.poll_recv(cx),
+        }
+    }
+}
+
+#[cfg(windows)]
+fn listeners(signal_kind: Signal) -> io::Result<Vec<Listener>> {
+    Ok(match signal_kind {
+        Signal::Interruption => vec![
+            Listener::CtrlBreak(ctrl_break()?),
+            Listener::CtrlC(ctrl_c()?),
+        ],
+        Signal::Terminate => vec![
+            Listener::CtrlShutdown(ctrl_shutdown()?),
+            Listener::CtrlLogoff(ctrl_logoff()?),
+            Listener::CtrlClose(ctrl_close()?),
+        ],
+        _ => vec![],
+    })
+}
+
+/// Chooses the signals to listen for.
+#[derive(Debug, Default)]
+pub struct SignalsBuilder {
+    signals: Vec<Signal>,
+}
+
+impl SignalsBuilder {
+    pub fn signal(mut self, signal_kind: Signal) -> Self {
+        if !self.signals.contains(&signal_kind) {
+            self.signals.push(signal_kind);
+        }
+
+        self
+    }
+
+    pub fn signals(self, signals: impl IntoIterator<Item = Signal>) -> Self {
+        signals.into_iter().fold(self, SignalsBuilder::signal)
+    }
+
+    /// Starts listening. The signals which do not exist on this platform are ignored.
+    pub fn build(self) -> io::Result<Signals> {
+        let mut listeners_by_signal = vec![];
+        for signal_kind in self.signals {
+            for listener in listeners(signal_kind)? {
+                listeners_by_signal.push((signal_kind, listener));
+            }
+        }
+
+        Ok(Signals {
+            listeners: listeners_by_signal,
+            next: 0,
+        })
+    }
+}
+
+/// Listens for signals, as a future of the next signal or as a stream.
 pub struct Signals {
-    #[cfg(unix)]
-    sigterm: tokio_signal,
-    #[cfg(unix)]
-    sigint: tokio_signal,
-    #[cfg(unix)]
-    sigabrt: tokio_signal,
-    #[cfg(windows)]
-    ctrl_break: CtrlBreak,
-    #[cfg(windows)]
-    ctrl_c: CtrlC,
-    #[cfg(windows)]
-    ctrl_close: CtrlClose,
-    #[cfg(windows)]
-    ctrl_shutdown: CtrlShutdown,
-    #[cfg(windows)]
-    ctrl_logoff: CtrlLogoff,
+    listeners: Vec<(Signal, Listener)>,
+    /// The listener polled first, so that a signal received repeatedly does not hide the others.
+    next: usize,
 }
 
-impl Default for Signals {
-    #[cfg(unix)]
-    fn default() -> Self {
-        let sigterm = signal(SignalKind::terminate()).unwrap();
-        let sigint = signal(SignalKind::interrupt()).unwrap();
-        let sigabrt = signal(SignalKind::from_raw(libc::SIGABRT)).unwrap();
-
-        Self {
-            sigterm,
-            sigint,
-            sigabrt,
+impl Signals {
+    pub fn builder() -> SignalsBuilder {
+        SignalsBuilder::default()
+    }
+
+    fn poll_signal(&mut self, cx: &mut Context<'_>) -> Poll<Option<Signal>> {
+        let count = self.listeners.len();
+        for offset in 0..count {
+            let index = (self.next + offset) % count;
+            let (signal_kind, listener) = &mut self.listeners[index];
+            if let Poll::Ready(received) = listener.poll_recv(cx) {
+                self.next = (index + 1) % count;
+                return Poll::Ready(received.map(|_| *signal_kind));
+            }
         }
+
+        Poll::Pending
     }
+}
 
-    #[cfg(windows)]
+/// Listens for all the signals.
+impl Default for Signals {
     fn default() -> Self {
-        let ctrl_break = ctrl_break().unwrap();
-        let ctrl_c = ctrl_c().unwrap();
-        let ctrl_shutdown = ctrl_shutdown().unwrap();
-        let ctrl_logoff = ctrl_logoff().unwrap();
-        let ctrl_close = ctrl_close().unwrap();
-
-        Self {
-            ctrl_break,
-            ctrl_c,
-            ctrl_shutdown,
-            ctrl_logoff,
-            ctrl_close,
-        }
+        Signals::builder().signals(Signal::ALL).build().unwrap()
     }
 }
 
@@ -77,43 +172,45 @@ impl AsMut<Signals> for Signals {
 impl Future for &mut Signals {
     type Output = Option<Signal>;
 
-    #[cfg(unix)]
     fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
-        let sigint = self.sigint.poll_recv(cx);
-        let sigterm = self.sigterm.poll_recv(cx);
-        let sigabrt = self.sigabrt.poll_recv(cx);
-
-        if let Poll::Ready(opt) = sigint {
-            Poll::Ready(opt.map(|_| Signal::Interruption))
-        } else if let Poll::Ready(opt) = sigterm {
-            Poll::Ready(opt.map(|_| Signal::Terminate))
-        } else if let Poll::Ready(opt) = sigabrt {
-            Poll::Ready(opt.map(|_| Signal::Abort))
-        } else {
-            Poll::Pending
-        }
+        self.poll_signal(cx)
     }
+}
 
-    #[cfg(windows)]
-    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
-        let ctrl_break = self.ctrl_break.poll_recv(cx);
-        let ctrl_c = self.ctrl_c.poll_recv(cx);
-        let ctrl_shutdown = self.ctrl_shutdown.poll_recv(cx);
-        let ctrl_logoff = self.ctrl_logoff.poll_recv(cx);
-        let ctrl_close = self.ctrl_close.poll_recv(cx);
-
-        if let Poll::Ready(opt) = ctrl_break {
-            Poll::Ready(opt.map(|_| Signal::Interruption))
-        } else if let Poll::Ready(opt) = ctrl_c {
-            Poll::Ready(opt.map(|_| Signal::Interruption))
-        } else if let Poll::Ready(opt) = ctrl_shutdown {
-            Poll::Ready(opt.map(|_| Signal::Terminate))
-        } else if let Poll::Ready(opt) = ctrl_logoff {
-            Poll::Ready(opt.map(|_| Signal::Terminate))
-        } else if let Poll::Ready(opt) = ctrl_close {
-            Poll::Ready(opt.map(|_| Signal::Terminate))
-        } else {
-            Poll::Pending
+impl Stream for Signals {
+    type Item = Signal;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.poll_signal(cx)
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use crate::signals::{Signal, Signals};
+    use futures::StreamExt;
+
+    #[tokio::test]
+    pub async fn signals_are_received() {
+        let mut signals = Signals::builder()
+            .signal(Signal::User1)
+            .signal(Signal::User2)
+            .signal(Signal::User1)
+            .build()
+            .unwrap();
+        assert_eq!(signals.listeners.len(), 2);
+
+        unsafe {
+            libc::raise(libc::SIGUSR1);
+        }
+        assert_eq!(signals.as_mut().await, Some(Signal::User1));
+
+        // The next poll starts after the listener which received the last signal
+        unsafe {
+            libc::raise(libc::SIGUSR1);
+            libc::raise(libc::SIGUSR2);
         }
+        assert_eq!(signals.next().await, Some(Signal::User2));
+        assert_eq!(signals.next().await, Some(Signal::User1));
     }
 }
@@ -0,0 +1,350 @@
+//! Fixed timestep scheduler of the C++ update loops.
+
+use crate::config::Config;
+use crate::watchdog::{runtime_summary, Heartbeat};
+use anyhow::Result;
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::runtime::Handle;
+use tokio::time::{self, Instant, Interval, MissedTickBehavior};
+
+/// Upper bounds (in milliseconds) of the buckets of the tick histogram, the last bucket holds
+/// the longer ticks.
+const BUCKET_BOUNDS: [u64; 10] = [1, 2, 5, 10, 20, 50, 100, 200, 500, 1000];
+const REPORT_INTERVAL: Duration = Duration::from_secs(60);
+
+/// What to do when a tick took longer than the interval.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Overrun {
+    /// Drop the missed ticks and keep ticking on the original schedule, the tick after a late
+    /// one still waiting for the minimum interval.
+    Skip,
+    /// Tick right away, then schedule the next ticks from now.
+    Delay,
+}
+
+impl From<Overrun> for MissedTickBehavior {
+    fn from(overrun: Overrun) -> Self {
+        match overrun {
+            Overrun::Skip => MissedTickBehavior::Skip,
+            Overrun::Delay => MissedTickBehavior::Delay,
+        }
+    }
+}
+
+/// The `Tick` config section, the minimum interval being read from a key of each server.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TickSection {
+    pub overrun: Overrun,
+    /// In milliseconds, 0 to disable.
+    pub slow_threshold: u64,
+}
+
+impl Default for TickSection {
+    fn default() -> Self {
+        Self {
+            overrun: Overrun::Skip,
+            slow_threshold: 100,
+        }
+    }
+}
+
+/// Tick settings: the minimum time between two ticks, the overrun behaviour (`Tick.Overrun`)
+/// and the duration above which a tick is logged (`Tick.SlowThreshold`, 0 to disable).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TickConfig {
+    pub min_interval: Duration,
+    pub overrun: Overrun,
+    pub slow_threshold: Option<Duration>,
+}
+
+impl Default for TickConfig {
+    fn default() -> Self {
+        Self {
+            min_interval: Duration::from_millis(5),
+            overrun: Overrun::Skip,
+            slow_threshold: Some(Duration::from_millis(100)),
+        }
+    }
+}
+
+impl TickConfig {
+    /// Reads the settings, the minimum interval (in milliseconds) being read from
+    /// `min_interval_key`.
+    pub fn from_config(
+        config: &Config,
+        min_interval_key: &str,
+        default_min_interval: u64,
+    ) -> Result<Self> {
+        let section: TickSection = config.section("Tick")?;
+        // A zero period would make the ticks spin
+        let min_interval = config
+            .get(min_interval_key, Some(default_min_interval))?
+            .max(1);
+        let slow_threshold = section.slow_threshold;
+
+        Ok(Self {
+            min_interval: Duration::from_millis(min_interval),
+            overrun: section.overrun,
+            slow_threshold: (slow_threshold > 0).then(|| Duration::from_millis(slow_threshold)),
+        })
+    }
+}
+
+/// Drives an update loop: waits for the next tick, then runs the update with the time elapsed
+/// since the previous one.
+pub struct TickScheduler {
+    config: TickConfig,
+    target: &'static str,
+    interval: Interval,
+    last_tick: Instant,
+    /// The earliest time of the next tick, `min_interval` after the previous one.
+    next_tick: Instant,
+    heartbeat: Arc<Heartbeat>,
+    histogram: TickHistogram,
+    last_report: Instant,
+    /// The ticks since the last report.
+    recent: TickHistogram,
+}
+
+impl TickScheduler {
+    /// Creates the scheduler, `target` being the log target of the slow ticks and reports.
+    pub fn new(config: TickConfig, target: &'static str) -> Self {
+        let now = Instant::now();
+        let mut interval = time::interval(config.min_interval);
+        interval.set_missed_tick_behavior(config.overrun.into());
+
+        Self {
+            config,
+            target,
+            interval,
+            last_tick: now,
+            next_tick: now,
+            heartbeat: Arc::default(),
+            histogram: TickHistogram::default(),
+            last_report: now,
+            recent: TickHistogram::default(),
+        }
+    }
+
+    /// Waits for the next tick and returns the time elapsed since the previous one.
+    pub async fn tick(&mut self) -> Duration {
+        self.interval.tick().await;
+        if Instant::now() < self.next_tick {
+            // A skipped tick can fall right after a late one, the schedule restarts from the
+            // minimum interval
+            time::sleep_until(self.next_tick).await;
+            self.interval.reset();
+        }
+
+        // The deadline of a late tick is in the past, the update needs the real elapsed time
+        let now = Instant::now();
+        let diff = now.saturating_duration_since(self.last_tick);
+        self.last_tick = now;
+        self.next_tick = now + self.config.min_interval;
+
+        diff
+    }
+
+    /// Runs an update, recording how long it took.
+    pub fn run<R>(&mut self, diff: Duration, update: impl FnOnce() -> R) -> R {
+        let start = Instant::now();
+        let result = update();
+        self.heartbeat.beat();
+        self.record(diff, start.elapsed());
+
+        result
+    }
+
+    /// Logs the state of the updates and of the runtime, on demand.
+    pub fn log_diagnostics(&self) {
+        let last_tick = self.heartbeat.since_last_beat().unwrap_or_default();
+        info!(
+            target: self.target,
+            "Last tick {} ms ago, {} ticks, {}; runtime: {}",
+            last_tick.as_millis(),
+            self.heartbeat.beats(),
+            self.histogram,
+            runtime_summary(&Handle::current())
+        );
+    }
+
+    /// The heartbeat of the updates, to watch for freezes.
+    pub fn heartbeat(&self) -> Arc<Heartbeat> {
+        self.heartbeat.clone()
+    }
+
+    pub fn histogram(&self) -> &TickHistogram {
+        &self.histogram
+    }
+
+    fn record(&mut self, diff: Duration, duration: Duration) {
+        self.histogram.record(duration);
+        self.recent.record(duration);
+        if self
+            .config
+            .slow_threshold
+            .is_some_and(|threshold| duration > threshold)
+        {
+            warn!(target: self.target, "Slow tick: update took {} ms, {} ms after the previous one", duration.as_millis(), diff.as_millis());
+        }
+
+        let now = Instant::now();
+        if now.duration_since(self.last_report) >= REPORT_INTERVAL {
+            info!(target: self.target, "Last minute: {}", std::mem::take(&mut self.recent));
+            self.last_report = now;
+        }
+    }
+}
+
+/// Counts of the tick durations.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct TickHistogram {
+    buckets: [u64; BUCKET_BOUNDS.len() + 1],
+    total: Duration,
+    max: Duration,
+}
+
+impl TickHistogram {
+    pub fn record(&mut self, duration: Duration) {
+        let millis = duration.as_millis() as u64;
+        let bucket = BUCKET_BOUNDS
+            .iter()
+            .position(|&bound| millis <= bound)
+            .unwrap_or(BUCKET_BOUNDS.len());
+        self.buckets[bucket] += 1;
+        self.total += duration;
+        self.max = self.max.max(duration);
+    }
+
+    pub fn count(&self) -> u64 {
+        self.buckets.iter().sum()
+    }
+
+    /// Mean duration of the ticks, `None` before any.
+    pub fn mean(&self) -> Option<Duration> {
+        let count = self.count();
+        (count > 0).then(|| Duration::from_nanos((self.total.as_nanos() / count as u128) as u64))
+    }
+
+    /// Upper bound of the duration of the given fraction of the ticks, the longest tick for
+    /// the ticks above the last bucket.
+    pub fn percentile(&self, fraction: f64) -> Option<Duration> {
+        let count = self.count();
+        if count == 0 {
+            return None;
+        }
+
+        let rank = ((count as f64 * fraction).ceil() as u64).clamp(1, count);
+        let mut seen = 0;
+        for (bucket, &ticks) in self.buckets.iter().enumerate() {
+            seen += ticks;
+            if seen >= rank {
+                return Some(match BUCKET_BOUNDS.get(bucket) {
+                    Some(&bound) => Duration::from_millis(bound).min(self.max),
+                    None => self.max,
+                });
+            }
+        }
+
+        Some(self.max)
+    }
+
+    pub fn max(&self) -> Duration {
+        self.max
+    }
+}
+
+impl Display for TickHistogram {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let millis =
+            |duration: Option<Duration>| duration.unwrap_or_default().as_secs_f64() * 1000.0;
+        write!(
+            f,
+            "{} ticks, mean {:.2} ms, p50 <= {:.0} ms, p99 <= {:.0} ms, max {:.0} ms",
+            self.count(),
+            millis(self.mean()),
+            millis(self.percentile(0.5)),
+            millis(self.percentile(0.99)),
+            millis(Some(self.max))
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tick::{Overrun, TickConfig, TickHistogram, TickScheduler};
+    use std::time::Duration;
+    use tokio::time::{advance, sleep};
+
+    fn config(overrun: Overrun) -> TickConfig {
+        TickConfig {
+            min_interval: Duration::from_millis(10),
+            overrun,
+            slow_threshold: None,
+        }
+    }
+
+    #[test]
+    pub fn histogram() {
+        let mut histogram = TickHistogram::default();
+        assert_eq!(histogram.mean(), None);
+        assert_eq!(histogram.percentile(0.5), None);
+
+        for millis in [1, 1, 3, 4, 7, 8, 40, 45, 60, 2000] {
+            histogram.record(Duration::from_millis(millis));
+        }
+        histogram.record(Duration::from_millis(15));
+
+        assert_eq!(histogram.count(), 11);
+        assert_eq!(histogram.mean(), Some(Duration::from_nanos(198_545_454)));
+        assert_eq!(histogram.percentile(0.5), Some(Duration::from_millis(10)));
+        assert_eq!(histogram.percentile(0.8), Some(Duration::from_millis(50)));
+        assert_eq!(histogram.percentile(1.0), Some(Duration::from_millis(2000)));
+        assert_eq!(histogram.max(), Duration::from_millis(2000));
+    }
+
+    #[test]
+    pub fn histogram_mean_of_many_ticks() {
+        let mut histogram = TickHistogram::default();
+        histogram.buckets[0] = 1 << 32;
+        histogram.total = Duration::from_millis(1 << 32);
+        assert_eq!(histogram.mean(), Some(Duration::from_millis(1)));
+    }
+
+    #[tokio::test(start_paused = true)]
+    pub async fn overrun_ticks_are_skipped() {
+        let mut scheduler = TickScheduler::new(config(Overrun::Skip), "server");
+        scheduler.tick().await;
+
+        assert_eq!(scheduler.tick().await, Duration::from_millis(10));
+        // The update overruns, the next tick is still at least 10 ms after the late one
+        advance(Duration::from_millis(25)).await;
+        assert_eq!(scheduler.tick().await, Duration::from_millis(25));
+        assert_eq!(scheduler.tick().await, Duration::from_millis(10));
+        assert_eq!(scheduler.tick().await, Duration::from_millis(10));
+    }
+
+    #[tokio::test(start_paused = true)]
+    pub async fn overrun_ticks_are_delayed() {
+        let mut scheduler = TickScheduler::new(config(Overrun::Delay), "server");
+        scheduler.tick().await;
+
+        advance(Duration::from_millis(25)).await;
+        assert_eq!(scheduler.tick().await, Duration::from_millis(25));
+        // The next tick is scheduled from the late one
+        let result = scheduler.run(Duration::from_millis(25), || 1);
+        assert_eq!(result, 1);
+        assert_eq!(scheduler.tick().await, Duration::from_millis(10));
+        assert_eq!(scheduler.histogram().count(), 1);
+        assert_eq!(scheduler.heartbeat().beats(), 1);
+
+        sleep(Duration::from_millis(3)).await;
+        assert_eq!(scheduler.tick().await, Duration::from_millis(10));
+    }
+}
@@ -27,10 +27,9 @@ where
     timers.push(handle);
 }
 
-#[allow(clippy::await_holding_lock)]
 pub async fn stop_timers() {
-    let mut timers = TIMERS.get_or_init(Mutex::default).lock().unwrap();
-    for timer in timers.drain(..) {
+    let timers = std::mem::take(&mut *TIMERS.get_or_init(Mutex::default).lock().unwrap());
+    for timer in timers {
         timer.abort();
         let _ = timer.await;
     }
@@ -0,0 +1,206 @@
+//! Freeze detector of the update loops, running on its own thread so that it still runs when
+//! the runtime is stuck.
+
+use crate::config::Config;
+use anyhow::Result;
+use log::{error, info};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+use tokio::runtime::Handle;
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Describes the state of a runtime, to investigate freezes.
+pub fn runtime_summary(runtime: &Handle) -> String {
+    let metrics = runtime.metrics();
+    format!(
+        "{} workers, {} alive tasks, {} queued tasks",
+        metrics.num_workers(),
+        metrics.num_alive_tasks(),
+        metrics.global_queue_depth()
+    )
+}
+
+/// Counter bumped by every tick of an update loop.
+#[derive(Debug)]
+pub struct Heartbeat {
+    origin: Instant,
+    beats: AtomicU64,
+    /// Time of the last beat, in milliseconds since `origin`.
+    last_beat: AtomicU64,
+}
+
+impl Heartbeat {
+    pub fn new() -> Self {
+        Self {
+            origin: Instant::now(),
+            beats: AtomicU64::new(0),
+            last_beat: AtomicU64::new(0),
+        }
+    }
+
+    pub fn beat(&self) {
+        self.last_beat
+            .store(self.origin.elapsed().as_millis() as u64, Ordering::Relaxed);
+        self.beats.fetch_add(1, Ordering::Release);
+    }
+
+    pub fn beats(&self) -> u64 {
+        self.beats.load(Ordering::Acquire)
+    }
+
+    /// Time elapsed since the last beat, `None` before the first one.
+    pub fn since_last_beat(&self) -> Option<Duration> {
+        (self.beats() > 0).then(|| {
+            let last_beat = Duration::from_millis(self.last_beat.load(Ordering::Relaxed));
+            self.origin.elapsed().saturating_sub(last_beat)
+        })
+    }
+}
+
+impl Default for Heartbeat {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Watchdog settings: the time (`MaxCoreStuckTime`, in seconds) without any tick after which
+/// the server is aborted. A zero time disables the watchdog.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WatchdogConfig {
+    pub max_stuck_time: Option<Duration>,
+}
+
+impl WatchdogConfig {
+    pub fn from_config(config: &Config) -> Result<Self> {
+        let max_stuck_time = config.get("MaxCoreStuckTime", Some(60_u64))?;
+
+        Ok(Self {
+            max_stuck_time: (max_stuck_time > 0).then(|| Duration::from_secs(max_stuck_time)),
+        })
+    }
+}
+
+/// Calls `on_freeze` once the heartbeat it watches stopped for longer than the configured time.
+pub struct Watchdog {
+    stop: Sender<()>,
+    thread: JoinHandle<()>,
+}
+
+impl Watchdog {
+    /// Starts the watchdog thread, or returns `None` if it is disabled. `runtime` is the runtime
+    /// whose metrics are logged on freeze, `target` the log target.
+    pub fn start<F>(
+        config: WatchdogConfig,
+        heartbeat: Arc<Heartbeat>,
+        runtime: Handle,
+        target: &'static str,
+        on_freeze: F,
+    ) -> Result<Option<Self>>
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        let Some(max_stuck_time) = config.max_stuck_time else {
+            return Ok(None);
+        };
+
+        let (stop, stopped) = mpsc::channel();
+        let thread = thread::Builder::new()
+            .name("watchdog".to_string())
+            .spawn(move || {
+                let mut last_beats = heartbeat.beats();
+                let mut last_change = Instant::now();
+
+                loop {
+                    match stopped.recv_timeout(CHECK_INTERVAL.min(max_stuck_time / 4)) {
+                        Err(RecvTimeoutError::Timeout) => {}
+                        _ => return,
+                    }
+
+                    let beats = heartbeat.beats();
+                    if beats != last_beats {
+                        last_beats = beats;
+                        last_change = Instant::now();
+                        continue;
+                    }
+
+                    let stuck_time = last_change.elapsed();
+                    if stuck_time > max_stuck_time {
+                        let last_tick = match heartbeat.since_last_beat() {
+                            Some(since) => format!("{} ms ago", since.as_millis()),
+                            None => "never".to_string(),
+                        };
+                        error!(
+                            target: target,
+                            "Update loop hangs for {} ms, forcing a crash! Last tick: {} ({} ticks), runtime: {}",
+                            stuck_time.as_millis(),
+                            last_tick,
+                            beats,
+                            runtime_summary(&runtime)
+                        );
+                        ::log::logger().flush();
+                        on_freeze();
+                        return;
+                    }
+                }
+            })?;
+
+        info!(target: target, "Starting up anti-freeze thread ({} seconds max stuck time)...", max_stuck_time.as_secs());
+        Ok(Some(Self { stop, thread }))
+    }
+
+    /// Stops watching, before the update loop stops ticking.
+    pub fn stop(self) {
+        let _ = self.stop.send(());
+        let _ = self.thread.join();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::watchdog::{Heartbeat, Watchdog, WatchdogConfig};
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+    use std::thread::sleep;
+    use std::time::Duration;
+    use tokio::runtime::Handle;
+
+    #[tokio::test]
+    pub async fn freezes_are_detected() {
+        let config = WatchdogConfig {
+            max_stuck_time: Some(Duration::from_millis(100)),
+        };
+        let heartbeat = Arc::new(Heartbeat::new());
+        let frozen = Arc::new(AtomicBool::new(false));
+        let frozen_ref = frozen.clone();
+        let watchdog = Watchdog::start(
+            config,
+            heartbeat.clone(),
+            Handle::current(),
+            "server",
+            move || frozen_ref.store(true, Ordering::SeqCst),
+        )
+        .unwrap()
+        .unwrap();
+
+        for _ in 0..20 {
+            heartbeat.beat();
+            sleep(Duration::from_millis(20));
+        }
+        assert!(!frozen.load(Ordering::SeqCst));
+        assert!(heartbeat.since_last_beat().unwrap() < Duration::from_millis(100));
+
+        sleep(Duration::from_millis(300));
+        assert!(frozen.load(Ordering::SeqCst));
+        watchdog.stop();
+
+        let disabled = WatchdogConfig {
+            max_stuck_time: None,
+        };
+        let watchdog = Watchdog::start(disabled, heartbeat, Handle::current(), "server", || {});
+        assert!(watchdog.unwrap().is_none());
+    }
+}
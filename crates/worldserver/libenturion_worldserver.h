@@ -6,7 +6,98 @@
 #include <stdint.h>
 #include <stdlib.h>
 
-typedef void (*TickCallback)(void);
+/**
+ * Size of the client headers: a big endian `u16` size then a little endian `u32` opcode.
+ */
+#define CLIENT_HEADER_SIZE 6
+
+/**
+ * The current state of the session of a socket, checked against the `SessionStatus` of the
+ * client packets. C++ sets it with `WorldSocketControl_SetSessionState`.
+ */
+enum SessionState
+#ifdef __cplusplus
+  : uint8_t
+#endif // __cplusplus
+ {
+  /**
+   * `CMSG_AUTH_SESSION` has not been handled yet.
+   */
+  SESSION_STATE_UNAUTHENTICATED = 0,
+  /**
+   * Authenticated, no player in game.
+   */
+  SESSION_STATE_AUTHED = 1,
+  /**
+   * The player is in world.
+   */
+  SESSION_STATE_LOGGED_IN = 2,
+  /**
+   * The player is not in world: loading, or transferring to another map.
+   */
+  SESSION_STATE_TRANSFER = 3,
+  /**
+   * The player logged out, until the character list is requested again.
+   */
+  SESSION_STATE_RECENTLY_LOGGED_OUT = 4,
+};
+#ifndef __cplusplus
+typedef uint8_t SessionState;
+#endif // __cplusplus
+
+/**
+ * Handle on a world socket, to queue work for it from any thread.
+ * C++ gets handles through `WorldSocket_GetControl`: they outlive the socket, and queued work
+ * is dropped once the socket is closed.
+ */
+typedef struct WorldSocketControl WorldSocketControl;
+
+/**
+ * A stop planned by the world, see [`WorldLifecycle_GetPlannedStop`].
+ */
+typedef struct WorldPlannedStop {
+  bool restart;
+  bool idle;
+  /**
+   * Rounded up, at least 1 while the stop is planned.
+   */
+  uint32_t remaining_seconds;
+} WorldPlannedStop;
+
+/**
+ * Runs an update of the C++ server, with the time elapsed (in milliseconds) since the previous one.
+ */
+typedef void (*TickCallback)(uint32_t diff);
+
+/**
+ * `SMSG_AUTH_RESPONSE` rejecting the authentication, with a `ResponseCodes` value.
+ */
+typedef struct AuthResponseError {
+  uint8_t code;
+} AuthResponseError;
+
+/**
+ * The content of `CMSG_AUTH_SESSION`, for C++ to check it against the account.
+ * The pointers are only valid during the call to `WorldSocket_HandleAuthSession`.
+ */
+typedef struct WorldAuthSession {
+  uint32_t build;
+  uint32_t login_server_id;
+  const char *account;
+  uint32_t login_server_type;
+  uint8_t local_challenge[4];
+  uint32_t region_id;
+  uint32_t battlegroup_id;
+  uint32_t realm_id;
+  uint64_t dos_response;
+  uint8_t digest[20];
+  /**
+   * The seed sent in `SMSG_AUTH_CHALLENGE`.
+   */
+  uint8_t auth_seed[4];
+  const uint8_t *addon_info;
+  uintptr_t addon_info_size;
+} WorldAuthSession;
 
 #ifdef __cplusplus
 extern "C" {
@@ -14,11 +105,172 @@ extern "C" {
 
 extern void AbortHandler(void);
 
+extern const Config *ConfigGetInstance(void);
+
 extern int32_t World_IsStopped(void);
 
+extern bool World_IsIdle(void);
+
+extern uint8_t World_GetExitCode(void);
+
+extern void World_ReloadConfig(void);
+
+extern void World_AnnounceStop(bool restart, uint32_t remaining_seconds, const char *reason);
+
+/**
+ * Plans a stop of the server, replacing any planned one: the `.server shutdown` and
+ * `.server restart` commands. An idle stop is not announced and waits for the world to have
+ * no sessions.
+ *
+ * # Safety
+ * Unsafe: reason must be a valid C string.
+ */
+void WorldLifecycle_PlanStop(bool restart,
+                             uint32_t delay_seconds,
+                             const char *reason,
+                             uint8_t exit_code,
+                             bool idle);
+
+/**
+ * Cancels the planned stop, returning the seconds it had left, 0 if there was none.
+ */
+uint32_t WorldLifecycle_CancelStop(void);
+
+/**
+ * Gets the planned stop, returns false if there is none.
+ *
+ * # Safety
+ * Unsafe: planned must be a valid pointer.
+ */
+bool WorldLifecycle_GetPlannedStop(struct WorldPlannedStop *planned);
+
 void WorldServerRsInit(void);
 
-void WorldServerRsMain(TickCallback tick_callback);
+/**
+ * Prints the known configuration keys with their default value and description, as a
+ * configuration file.
+ */
+void WorldServerRsPrintConfig(void);
+
+/**
+ * Runs the world server until it is stopped.
+ * Returns the exit code of the process, the error one if the network could not be started.
+ */
+uint8_t WorldServerRsMain(TickCallback tick_callback);
+
+struct AuthResponseError AuthResponseError_New(uint8_t code);
+
+void AuthResponseError_Send(struct AuthResponseError self, const void *session);
+
+/**
+ * Starts or stops the packet log. Returns false if it could not be started.
+ */
+bool WorldPacketLog_SetEnabled(bool enabled);
+
+bool WorldPacketLog_IsEnabled(void);
+
+extern void WorldSocket_Free(void *world_socket);
+
+extern bool WorldSocket_HandleAuthSession(const void *world_socket,
+                                          const struct WorldAuthSession *auth_session);
+
+extern bool WorldSocket_HandlePacket(const void *world_socket,
+                                     uint32_t opcode,
+                                     const uint8_t *data,
+                                     uintptr_t size);
+
+extern void *WorldSocket_New(void *rs_world_socket);
+
+extern void WorldSocket_Start(const void *world_socket);
+
+extern void WorldSocket_Update(const void *world_socket);
+
+const char *WorldSocket_GetRemoteIpAddress(const void *this_);
+
+uint16_t WorldSocket_GetRemotePort(const void *this_);
+
+/**
+ * Gets a new handle on the control of the socket, to queue work from any thread.
+ *
+ * # Safety
+ * Unsafe: the handle must be freed with `WorldSocketControl_Free`.
+ */
+const struct WorldSocketControl *WorldSocket_GetControl(const void *this_);
+
+/**
+ * Gets a new handle on the notifier of the socket, to have `WorldSocket_Update` run from
+ * any thread.
+ *
+ * # Safety
+ * Unsafe: the handle must be freed with `Notifier_Free`.
+ */
+const Notifier *WorldSocket_GetNotifier(const void *this_);
+
+/**
+ * Queues a packet. Its header is built and encrypted by the socket task.
+ *
+ * # Safety
+ * Unsafe: control must be a live handle, data must be `size` bytes long.
+ */
+void WorldSocketControl_SendPacket(const struct WorldSocketControl *control,
+                                   uint16_t opcode,
+                                   const uint8_t *data,
+                                   uintptr_t size);
+
+/**
+ * # Safety
+ * Unsafe: control must be a live handle.
+ */
+void WorldSocketControl_SendAuthChallenge(const struct WorldSocketControl *control);
+
+/**
+ * Encrypts the headers of the packets queued from now on, and of the received ones.
+ *
+ * # Safety
+ * Unsafe: control must be a live handle, session_key must be `size` bytes long.
+ */
+void WorldSocketControl_InitEncryption(const struct WorldSocketControl *control,
+                                       const uint8_t *session_key,
+                                       uintptr_t size);
+
+/**
+ * # Safety
+ * Unsafe: control must be a live handle.
+ */
+void WorldSocketControl_ResumeReading(const struct WorldSocketControl *control);
+
+/**
+ * # Safety
+ * Unsafe: control must be a live handle.
+ */
+void WorldSocketControl_DelayedClose(const struct WorldSocketControl *control);
+
+/**
+ * # Safety
+ * Unsafe: control must be a live handle.
+ */
+void WorldSocketControl_Close(const struct WorldSocketControl *control);
+
+/**
+ * Sets the state of the session the client packets are checked against.
+ * `state` is a `SessionState` value.
+ *
+ * # Safety
+ * Unsafe: control must be a live handle.
+ */
+void WorldSocketControl_SetSessionState(const struct WorldSocketControl *control, uint8_t state);
+
+/**
+ * # Safety
+ * Unsafe: control must be a live handle.
+ */
+bool WorldSocketControl_IsOpen(const struct WorldSocketControl *control);
+
+/**
+ * # Safety
+ * Unsafe: control must be a live handle, it is freed by the call.
+ */
+void WorldSocketControl_Free(const struct WorldSocketControl *control);
 
 #ifdef __cplusplus
 } // extern "C"
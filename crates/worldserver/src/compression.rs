@@ -0,0 +1,263 @@
+//! zlib compression of the large server packets which have a compressed form.
+
+use crate::opcodes::Opcode;
+use anyhow::Result;
+use enturion_shared::config::Config;
+use flate2::{Compress, Compression, FlushCompress, Status};
+use log::error;
+use std::fmt::{Display, Formatter};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Compression totals of all the world sockets.
+pub static COMPRESSION_STATS: CompressionStats = CompressionStats::new();
+
+/// Compression settings: `Compression` is the zlib level, and only payloads larger than
+/// `Network.CompressionThreshold` bytes are compressed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CompressionConfig {
+    pub level: u32,
+    pub threshold: usize,
+}
+
+impl Default for CompressionConfig {
+    fn default() -> Self {
+        Self {
+            level: 1,
+            threshold: 100,
+        }
+    }
+}
+
+impl CompressionConfig {
+    pub fn from_config(config: &Config) -> Result<Self> {
+        let default = Self::default();
+        let mut level = config.get("Compression", Some(default.level))?;
+        if !(1..=9).contains(&level) {
+            error!(target: "server::loading", "Compression level ({}) must be in range 1..9. Using default compression level ({}).", level, default.level);
+            level = default.level;
+        }
+
+        Ok(Self {
+            level,
+            threshold: config.get("Network.CompressionThreshold", Some(default.threshold))?,
+        })
+    }
+}
+
+/// The compressed form of a server packet, and the size of the header of the packet which is
+/// written as is: the compressed payload is the size of the rest of the packet then its zlib
+/// stream. `SMSG_ADDON_INFO` has no compressed form in this version of the protocol.
+fn compressed_form(opcode: u16) -> Option<(Opcode, usize)> {
+    match Opcode::try_from(opcode as u32) {
+        Ok(Opcode::SMSG_UPDATE_OBJECT) => Some((Opcode::SMSG_COMPRESSED_UPDATE_OBJECT, 0)),
+        // The uncompressed moves already start with the size of the moves
+        Ok(Opcode::SMSG_MULTIPLE_MOVES) => Some((Opcode::SMSG_COMPRESSED_MOVES, 4)),
+        _ => None,
+    }
+}
+
+/// Compresses the packets of a socket, reusing its deflate context.
+pub struct PacketCompressor {
+    config: CompressionConfig,
+    deflate: Compress,
+}
+
+impl PacketCompressor {
+    pub fn new(config: CompressionConfig) -> Self {
+        Self {
+            config,
+            deflate: Compress::new(Compression::new(config.level), true),
+        }
+    }
+
+    /// Returns the compressed form of a packet, or `None` if it has to be sent as is: it has no
+    /// compressed form, is too small or would not get smaller.
+    pub fn compress(&mut self, opcode: u16, payload: &[u8]) -> Option<(u16, Vec<u8>)> {
+        let (compressed_opcode, header_size) = compressed_form(opcode)?;
+        if payload.len() <= self.config.threshold || payload.len() <= header_size {
+            return None;
+        }
+
+        let data = &payload[header_size..];
+        // The compressed packet has to be smaller than the original one
+        let mut compressed = Vec::with_capacity(payload.len() - 1);
+        compressed.extend_from_slice(&(data.len() as u32).to_le_bytes());
+
+        self.deflate.reset();
+        let deflated = match self
+            .deflate
+            .compress_vec(data, &mut compressed, FlushCompress::Finish)
+        {
+            Ok(Status::StreamEnd) => true,
+            Ok(_) => false,
+            Err(e) => {
+                error!(target: "network", "Cannot compress packet {:#06X}: {}", opcode, e);
+                false
+            }
+        };
+
+        COMPRESSION_STATS.record(payload.len(), deflated.then_some(compressed.len()));
+        deflated.then_some((compressed_opcode as u16, compressed))
+    }
+}
+
+/// Counters of the compressed packets, to follow the compression ratio.
+#[derive(Debug)]
+pub struct CompressionStats {
+    packets: AtomicU64,
+    /// Packets sent as is because they would not get smaller.
+    incompressible: AtomicU64,
+    uncompressed_bytes: AtomicU64,
+    compressed_bytes: AtomicU64,
+}
+
+impl CompressionStats {
+    pub const fn new() -> Self {
+        Self {
+            packets: AtomicU64::new(0),
+            incompressible: AtomicU64::new(0),
+            uncompressed_bytes: AtomicU64::new(0),
+            compressed_bytes: AtomicU64::new(0),
+        }
+    }
+
+    fn record(&self, uncompressed_size: usize, compressed_size: Option<usize>) {
+        let Some(compressed_size) = compressed_size else {
+            self.incompressible.fetch_add(1, Ordering::Relaxed);
+            return;
+        };
+
+        self.packets.fetch_add(1, Ordering::Relaxed);
+        self.uncompressed_bytes
+            .fetch_add(uncompressed_size as u64, Ordering::Relaxed);
+        self.compressed_bytes
+            .fetch_add(compressed_size as u64, Ordering::Relaxed);
+    }
+
+    pub fn snapshot(&self) -> CompressionSnapshot {
+        CompressionSnapshot {
+            packets: self.packets.load(Ordering::Relaxed),
+            incompressible: self.incompressible.load(Ordering::Relaxed),
+            uncompressed_bytes: self.uncompressed_bytes.load(Ordering::Relaxed),
+            compressed_bytes: self.compressed_bytes.load(Ordering::Relaxed),
+        }
+    }
+}
+
+impl Default for CompressionStats {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The compression counters at a point in time.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CompressionSnapshot {
+    pub packets: u64,
+    pub incompressible: u64,
+    pub uncompressed_bytes: u64,
+    pub compressed_bytes: u64,
+}
+
+impl CompressionSnapshot {
+    /// Compressed size over uncompressed size of the compressed packets, `None` before any.
+    pub fn ratio(&self) -> Option<f64> {
+        (self.uncompressed_bytes > 0)
+            .then(|| self.compressed_bytes as f64 / self.uncompressed_bytes as f64)
+    }
+
+    /// The counters since an earlier snapshot.
+    pub fn since(&self, earlier: &CompressionSnapshot) -> CompressionSnapshot {
+        CompressionSnapshot {
+            packets: self.packets - earlier.packets,
+            incompressible: self.incompressible - earlier.incompressible,
+            uncompressed_bytes: self.uncompressed_bytes - earlier.uncompressed_bytes,
+            compressed_bytes: self.compressed_bytes - earlier.compressed_bytes,
+        }
+    }
+}
+
+impl Display for CompressionSnapshot {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} packets compressed from {} to {} bytes (ratio {:.2}), {} sent uncompressed",
+            self.packets,
+            self.uncompressed_bytes,
+            self.compressed_bytes,
+            self.ratio().unwrap_or(1.0),
+            self.incompressible
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::compression::{CompressionConfig, CompressionSnapshot, PacketCompressor};
+    use crate::opcodes::Opcode;
+    use flate2::{Decompress, FlushDecompress};
+
+    fn inflate(data: &[u8], size: usize) -> Vec<u8> {
+        let mut output = Vec::with_capacity(size);
+        Decompress::new(true)
+            .decompress_vec(data, &mut output, FlushDecompress::Finish)
+            .unwrap();
+        output
+    }
+
+    #[test]
+    pub fn large_packets_are_compressed() {
+        let mut compressor = PacketCompressor::new(CompressionConfig::default());
+        let update = [0x2A_u8; 500];
+
+        for _ in 0..2 {
+            let (opcode, payload) = compressor
+                .compress(Opcode::SMSG_UPDATE_OBJECT as u16, &update)
+                .unwrap();
+            assert_eq!(opcode, Opcode::SMSG_COMPRESSED_UPDATE_OBJECT as u16);
+            assert_eq!(payload[..4], 500_u32.to_le_bytes());
+            assert!(payload.len() < 100);
+            assert_eq!(inflate(&payload[4..], 500), update);
+        }
+
+        // Moves keep their size header out of the compressed stream
+        let mut moves = 496_u32.to_le_bytes().to_vec();
+        moves.extend_from_slice(&[7; 496]);
+        let (opcode, payload) = compressor
+            .compress(Opcode::SMSG_MULTIPLE_MOVES as u16, &moves)
+            .unwrap();
+        assert_eq!(opcode, Opcode::SMSG_COMPRESSED_MOVES as u16);
+        assert_eq!(payload[..4], 496_u32.to_le_bytes());
+        assert_eq!(inflate(&payload[4..], 496), moves[4..]);
+
+        // Small packets, packets without a compressed form and random data are sent as is
+        let update_opcode = Opcode::SMSG_UPDATE_OBJECT as u16;
+        assert_eq!(compressor.compress(update_opcode, &[0x2A; 100]), None);
+        assert_eq!(
+            compressor.compress(Opcode::SMSG_ADDON_INFO as u16, &update),
+            None
+        );
+        let random: Vec<u8> = (0..500).map(|_| rand::random()).collect();
+        assert_eq!(compressor.compress(update_opcode, &random), None);
+    }
+
+    #[test]
+    pub fn ratios() {
+        let earlier = CompressionSnapshot {
+            packets: 1,
+            incompressible: 0,
+            uncompressed_bytes: 100,
+            compressed_bytes: 50,
+        };
+        let now = CompressionSnapshot {
+            packets: 3,
+            incompressible: 1,
+            uncompressed_bytes: 1100,
+            compressed_bytes: 300,
+        };
+
+        assert_eq!(CompressionSnapshot::default().ratio(), None);
+        assert_eq!(now.since(&earlier).ratio(), Some(0.25));
+        assert_eq!(now.since(&earlier).packets, 2);
+    }
+}
@@ -0,0 +1,2839 @@
+use enturion_shared::config::schema::{ConfigSchema, ValueType};
+use enturion_shared::lifecycle::SignalsSection;
+use enturion_shared::net::proxy_protocol::ProxyProtocolSection;
+use enturion_shared::tick::TickSection;
+
+const APPENDERS: &str = "\
+Console:
+    # Appender type: could be \"console\" or \"file\"
+    type: console
+    # Minimum log level
+    # One of \"trace\", \"debug\", \"info\", \"warn\" or \"error\"
+    level: debug
+    # Optional: when type == \"console\" this maps every level to a given color/style.
+    # color_map: { error: 9, warn: 11, info: 12, debug: 5, trace: 2 }
+Server:
+    type: file
+    level: debug
+    filename: Server.log
+    append: true
+GM:
+    type: file
+    level: debug
+    filename: GM.log
+    append: true
+DBErrors:
+    type: file
+    level: debug
+    filename: DBErrors.log
+    append: true";
+
+const LOGGERS: &str = "\
+# The root logger is the logger used if no other logger can be used.
+root:
+    # Minimum log level
+    level: error
+    # Appenders of the log
+    appenders:
+        - Console
+        - Server
+server:
+    level: info
+    appenders:
+        - Console
+        - Server
+commands::gm:
+    level: info
+    appenders:
+        - Console
+        - Server
+scripts::hotswap:
+    level: info
+    appenders:
+        - Console
+        - Server
+sql::sql:
+    level: error
+    appenders:
+        - Console
+        - DBErrors
+sql::updates:
+    level: info
+    appenders:
+        - Console
+        - Server
+mmaps:
+    level: info
+    appenders:
+        - Server
+#    achievement:
+#        level: info
+#        appenders:
+#            - Console
+#            - Server
+#    addon:
+#        level: info
+#        appenders:
+#            - Console
+#            - Server
+#    ahbot:
+#        level: info
+#        appenders:
+#            - Console
+#            - Server
+#    auctionHouse:
+#        level: info
+#        appenders:
+#            - Console
+#            - Server
+#    bg::arena:
+#        level: info
+#        appenders:
+#            - Console
+#            - Server
+#    bg::battlefield:
+#        level: info
+#        appenders:
+#            - Console
+#            - Server
+#    bg::battleground:
+#        level: info
+#        appenders:
+#            - Console
+#            - Server
+#    bg::reportpvpafk:
+#        level: info
+#        appenders:
+#            - Console
+#            - Server
+#    chat::log:
+#        level: info
+#        appenders:
+#            - Console
+#            - Server
+#    chat::system:
+#        level: info
+#        appenders:
+#            - Console
+#            - Server
+#    calender:
+#        level: info
+#        appenders:
+#            - Console
+#            - Server
+#    cheat:
+#        level: info
+#        appenders:
+#            - Console
+#            - Server
+#    commands::ra:
+#        level: info
+#        appenders:
+#            - Console
+#            - Server
+#    condition:
+#        level: info
+#        appenders:
+#            - Console
+#            - Server
+#    entities::faction:
+#        level: info
+#        appenders:
+#            - Console
+#            - Server
+#    entities::gameobject:
+#        level: info
+#        appenders:
+#            - Console
+#            - Server
+#    entities::pet:
+#        level: info
+#        appenders:
+#            - Console
+#            - Server
+#    entities::player:
+#        level: info
+#        appenders:
+#            - Console
+#            - Server
+#    entities::player::character:
+#        level: info
+#        appenders:
+#            - Console
+#            - Server
+#    entities::player::dump:
+#        level: info
+#        appenders:
+#            - Console
+#            - Server
+#    entities::player::items:
+#        level: info
+#        appenders:
+#            - Console
+#            - Server
+#    entities::player::loading:
+#        level: info
+#        appenders:
+#            - Console
+#            - Server
+#    entities::player::skills:
+#        level: info
+#        appenders:
+#            - Console
+#            - Server
+#    entities::unit:
+#        level: info
+#        appenders:
+#            - Console
+#            - Server
+#    entities::unit::ai:
+#        level: info
+#        appenders:
+#            - Console
+#            - Server
+#    entities::vehicle:
+#        level: info
+#        appenders:
+#            - Console
+#            - Server
+#    gameevent:
+#        level: info
+#        appenders:
+#            - Console
+#            - Server
+#    guild:
+#        level: info
+#        appenders:
+#            - Console
+#            - Server
+#    lfg:
+#        level: info
+#        appenders:
+#            - Console
+#            - Server
+#    loot:
+#        level: info
+#        appenders:
+#            - Console
+#            - Server
+#    maps:
+#        level: info
+#        appenders:
+#            - Console
+#            - Server
+#    maps::script:
+#        level: info
+#        appenders:
+#            - Console
+#            - Server
+#    misc:
+#        level: info
+#        appenders:
+#            - Console
+#            - Server
+#    mmaps::tiles:
+#        level: info
+#        appenders:
+#            - Console
+#            - Server
+#    movement::flightpath:
+#        level: info
+#        appenders:
+#            - Console
+#            - Server
+#    movement::motionmaster:
+#        level: info
+#        appenders:
+#            - Console
+#            - Server
+#    movement::splinechain:
+#        level: info
+#        appenders:
+#            - Console
+#            - Server
+#    network:
+#        level: info
+#        appenders:
+#            - Console
+#            - Server
+#    network::kick:
+#        level: info
+#        appenders:
+#            - Console
+#            - Server
+#    network::opcode:
+#        level: info
+#        appenders:
+#            - Console
+#            - Server
+#    network::soap:
+#        level: info
+#        appenders:
+#            - Console
+#            - Server
+#    outdoorpvp:
+#        level: info
+#        appenders:
+#            - Console
+#            - Server
+#    pool:
+#        level: info
+#        appenders:
+#            - Console
+#            - Server
+#    rbac:
+#        level: info
+#        appenders:
+#            - Console
+#            - Server
+#    scripts:
+#        level: info
+#        appenders:
+#            - Console
+#            - Server
+#    scripts::ai:
+#        level: info
+#        appenders:
+#            - Console
+#            - Server
+#    scripts::ai::escortai:
+#        level: info
+#        appenders:
+#            - Console
+#            - Server
+#    scripts::ai::followerai:
+#        level: info
+#        appenders:
+#            - Console
+#            - Server
+#    scripts::ai::petai:
+#        level: info
+#        appenders:
+#            - Console
+#            - Server
+#    scripts::ai::sai:
+#        level: info
+#        appenders:
+#            - Console
+#            - Server
+#    scripts::cos:
+#        level: info
+#        appenders:
+#            - Console
+#            - Server
+#    server::authserver:
+#        level: info
+#        appenders:
+#            - Console
+#            - Server
+#    spells:
+#        level: info
+#        appenders:
+#            - Console
+#            - Server
+#    spells::aura::effect:
+#        level: info
+#        appenders:
+#            - Console
+#            - Server
+#    spells::aura::effect::nospell:
+#        level: info
+#        appenders:
+#            - Console
+#            - Server
+#    spells::effect:
+#        level: info
+#        appenders:
+#            - Console
+#            - Server
+#    spells::effect::nospell:
+#        level: info
+#        appenders:
+#            - Console
+#            - Server
+#    sql::dev:
+#        level: info
+#        appenders:
+#            - Console
+#            - Server
+#    sql::driver:
+#        level: info
+#        appenders:
+#            - Console
+#            - Server
+#    warden:
+#        level: info
+#        appenders:
+#            - Console
+#            - Server";
+
+/// The keys of the world server configuration, read by the Rust and C++ code.
+pub fn schema() -> ConfigSchema {
+    use ValueType::*;
+
+    ConfigSchema::new()
+        .key("RealmID", Integer, "1", "ID of the Realm using this config.")
+        .key("DataDir", String, "\".\"", "Data directory setting.")
+        .key("LogsDir", String, "\"\"", "Logs directory setting.")
+        .key("PidFile", String, "~", "World daemon PID file.")
+        .key(
+            "PacketLogFile",
+            String,
+            "~",
+            "Binary packet logging file for the world server.\n\
+             Filename extension must be .pkt to be parsable with WowPacketParser.\n\
+             The log can be switched at runtime with .debug packetlog.",
+        )
+        .key(
+            "PacketLogMaxSize",
+            Integer,
+            "0",
+            "Size in MB after which the packet log is moved to <name>.<index>.pkt and a new one started.\n\
+             0 - Never rotate the packet log.",
+        )
+        .key(
+            "LoginDatabaseInfo",
+            String,
+            "\"127.0.0.1;3306;Kitron;Kitron;auth\"",
+            "LoginDatabaseInfo\n\
+             WorldDatabaseInfo\n\
+             CharacterDatabaseInfo\n\
+             \x20   Description: Database connection settings for the world server.\n\
+             \x20   Example:     \"hostname;port;username;password;database;ssl\"\n\
+             \x20                \".;some_number;username;password;database\" - (Use named pipes on Windows\n\
+             \x20                                                            \"enable-named-pipe\" to [mysqld]\n\
+             \x20                                                            section my.ini)\n\
+             \x20                \".;/path/to/unix_socket;username;password;database\" - (use Unix sockets on\n\
+             \x20                                                                      Unix/Linux)\n\
+             \x20   Default:     \"127.0.0.1;3306;Kitron;Kitron;auth\"       - (LoginDatabaseInfo)\n\
+             \x20                \"127.0.0.1;3306;Kitron;Kitron;world\"      - (WorldDatabaseInfo)\n\
+             \x20                \"127.0.0.1;3306;Kitron;Kitron;characters\" - (CharacterDatabaseInfo)\n\
+             \n\
+             Don't change hostname unless you are hosting MySQL on a different machine, if you need help\n\
+             with configuration allowing to connect from different machine than the one running server\n\
+             search for TCE00016 on forum.\n\
+             Don't open port on firewall to external connections (it belongs to MySQL, not to wow server).\n\
+             The username you choose must have permissions to create/alter/rename tables.\n\
+             The SSL option will enable TLS when connecting to the specified database. If not provided or\n\
+             any value other than 'ssl' is set, TLS will not be used.",
+        )
+        .key("WorldDatabaseInfo", String, "\"127.0.0.1;3306;Kitron;Kitron;world\"", "")
+        .key("CharacterDatabaseInfo", String, "\"127.0.0.1;3306;Kitron;Kitron;characters\"", "")
+        .key("LoginDatabase.WorkerThreads", Integer, "1", "")
+        .key("LoginDatabase.SynchThreads", Integer, "1", "")
+        .key("WorldDatabase.WorkerThreads", Integer, "1", "")
+        .key("WorldDatabase.SynchThreads", Integer, "1", "")
+        .key("CharacterDatabase.WorkerThreads", Integer, "1", "")
+        .key("CharacterDatabase.SynchThreads", Integer, "2", "")
+        .key("WorldServerPort", Integer, "8085", "TCP port to reach the world server.")
+        .key("BindIP", String, "'0.0.0.0'", "Bind world server to IP/hostname.")
+        .key("IPLocationFile", String, "\"\"", "The path to your IP2Location database CSV file.")
+        .key("ThreadPool", Integer, "2", "")
+        .key(
+            "UseProcessors",
+            Integer,
+            "~",
+            "Processors mask for Windows and Linux based multi-processor systems.",
+        )
+        .key(
+            "ProcessPriority",
+            Integer,
+            "0",
+            "Process priority setting for Windows and Linux based systems. 0 - Normal, 1 - High",
+        )
+        .key(
+            "Compression",
+            Integer,
+            "1",
+            "Compression level (1-9) of the large update object and move packets.",
+        )
+        .key(
+            "PlayerLimit",
+            Integer,
+            "0",
+            "Maximum number of players in the world. Excluding Mods, GMs and Admins.",
+        )
+        .key(
+            "MaxOverspeedPings",
+            Integer,
+            "2",
+            "Maximum overspeed ping count before character is disconnected.",
+        )
+        .key(
+            "GridUnload",
+            Boolean,
+            "true",
+            "Unload grids to save memory. Can be disabled if enough memory is available\n\
+             to speed up moving players to new grids.",
+        )
+        .key(
+            "BaseMapLoadAllGrids",
+            Boolean,
+            "false",
+            "Load all grids for base maps upon load. Requires GridUnload to be 0.\n\
+             This will take around 5GB of ram upon server load, and will take some time\n\
+             to initially load the server.",
+        )
+        .key(
+            "InstanceMapLoadAllGrids",
+            Boolean,
+            "false",
+            "Load all grids for instance maps upon load. Requires GridUnload to be 0.\n\
+             Upon loading an instance map, all creatures/objects in the map will be pre-loaded",
+        )
+        .key("MaxPingTime", Integer, "30", "Time (in minutes) between database pings.")
+        .key(
+            "SocketTimeOutTime",
+            Integer,
+            "900000",
+            "Time (in milliseconds) after which a connection being idle on the character selection screen is disconnected.",
+        )
+        .key(
+            "SocketTimeOutTimeActive",
+            Integer,
+            "60000",
+            "Time (in milliseconds) after which an idle connection is dropped while logged into the world.\n\
+             The client sends keepalive packets every 30 seconds. Values <= 30s are not recommended.",
+        )
+        .key(
+            "SessionAddDelay",
+            Integer,
+            "10000",
+            "Time (in microseconds) that a network thread will sleep after authentication\n\
+             protocol handling before adding a connection to the world session map.",
+        )
+        .key("GridCleanUpDelay", Integer, "300000", "Time (in milliseconds) grid clean up delay.")
+        .key(
+            "MinWorldUpdateTime",
+            Integer,
+            "5",
+            "Minimum time (milliseconds) between world update ticks (for mostly idle servers).",
+        )
+        .section::<TickSection>(
+            "Tick",
+            &[
+                (
+                    "Overrun",
+                    "What to do when a world update takes longer than MinWorldUpdateTime:\n\
+                     'skip' drops the missed ticks and keeps the original schedule,\n\
+                     'delay' runs the next tick right away and schedules the following ones from it.",
+                ),
+                (
+                    "SlowThreshold",
+                    "World updates taking longer than this time (in milliseconds) are logged. (0 = disabled)",
+                ),
+            ],
+        )
+        .section::<SignalsSection>(
+            "Signals",
+            &[
+                (
+                    "User1",
+                    "Action on SIGUSR1 and SIGUSR2: 'none', 'reload' (the configuration, like SIGHUP),\n\
+                     'shutdown' or 'restart'. The exit code of a restart is 2, for the restarter scripts.\n\
+                     SIGHUP reloads the configuration and SIGQUIT logs the state of the update loop.",
+                ),
+                ("User2", ""),
+                (
+                    "StopDelay",
+                    "Time (in seconds) before the shutdowns and restarts requested by a signal.",
+                ),
+            ],
+        )
+        .key("MapUpdateInterval", Integer, "10", "Time (milliseconds) for map update interval.")
+        .key(
+            "ChangeWeatherInterval",
+            Integer,
+            "600000",
+            "Time (in milliseconds) for weather update interval.",
+        )
+        .key(
+            "DisconnectToleranceInterval",
+            Integer,
+            "0",
+            "Tolerance (in seconds) for disconnected players before reentering the queue.",
+        )
+        .key("UpdateUptimeInterval", Integer, "10", "Update realm uptime period (in minutes).")
+        .key(
+            "MaxCoreStuckTime",
+            Integer,
+            "60",
+            "Time (in seconds) before the server is forced to crash if it is frozen. (0 = disabled)",
+        )
+        .key(
+            "MailDeliveryDelay",
+            Integer,
+            "3600",
+            "Time (in seconds) mail delivery is delayed when sending items.",
+        )
+        .key(
+            "PlayerSave.Interval",
+            Integer,
+            "90000",
+            "Time (in milliseconds) for player save interval.",
+        )
+        .key(
+            "PlayerSave.Stats.MinLevel",
+            Integer,
+            "~",
+            "Minimum level for saving character stats in the database for external usage.",
+        )
+        .key(
+            "PlayerSave.Stats.SaveOnlyOnLogout",
+            Boolean,
+            "true",
+            "Save player stats only on logout.",
+        )
+        .key(
+            "mmap.enablePathFinding",
+            Boolean,
+            "true",
+            "Enable/Disable pathfinding using mmaps - recommended.",
+        )
+        .key("vmap.enableLOS", Boolean, "true", "Enable line of sight")
+        .key("vmap.enableHeight", Boolean, "true", "Enable height")
+        .key(
+            "vmap.enableIndoorCheck",
+            Boolean,
+            "true",
+            "VMap based indoor check to remove outdoor-only auras (mounts etc.).",
+        )
+        .key(
+            "DetectPosCollision",
+            Boolean,
+            "true",
+            "Check final move position, summon position, etc for visible collision with\n\
+             other objects or walls (walls only if vmaps are enabled).",
+        )
+        .key(
+            "CheckGameObjectLoS",
+            Boolean,
+            "true",
+            "Include dynamic game objects (doors, chests etc.) in line of sight checks.\n\
+             This increases CPU usage somewhat.\n\
+             Disabling it could break some boss encounters.",
+        )
+        .key(
+            "AddonChannel",
+            Boolean,
+            "true",
+            "Configure the use of the addon channel through the server (some client side\n\
+             addons will not work correctly with disabled addon channel)",
+        )
+        .key("MapUpdate.Threads", Integer, "1", "Number of threads to update maps.")
+        .key(
+            "CleanCharacterDB",
+            Boolean,
+            "false",
+            "Clean out deprecated achievements, skills, spells and talents from the db.",
+        )
+        .key(
+            "PersistentCharacterCleanFlags",
+            Integer,
+            "0",
+            "Determines the character clean flags that remain set after cleanups.\n\
+             This is a bitmask value, check /doc/CharacterDBCleanup.txt for more information.\n\
+             14 - (Cleaning up skills, talents and spells will remain enabled after the next cleanup)\n\
+             0  - (All cleanup methods will be disabled after the next cleanup)",
+        )
+        .key(
+            "Auction.GetAllScanDelay",
+            Integer,
+            "900",
+            "Sets the minimum time in seconds, a single player character can perform a GetAll scan.\n\
+             The value is only held in memory so a server restart will clear it.\n\
+             Setting this to zero, will disable GetAll functions completely.",
+        )
+        .key(
+            "Auction.SearchDelay",
+            Integer,
+            "300",
+            "Sets the minimum time in milliseconds (seconds x 1000), that the client must wait between\n\
+             auction search operations. This can be increased if somehow Auction House activity is causing\n\
+             too much load.",
+        )
+        .key(
+            "GameType",
+            Integer,
+            "0",
+            "Server realm type. 0: NORMAL, 1 PVP, 4 NORMAL, 6 RP, 8 RPPVP, 16 FFA_PVP - Free for all PvP mode like arena PvP in all zones except rest activated places and sanctuaries)",
+        )
+        .key(
+            "RealmZone",
+            Integer,
+            "1",
+            "Server realm zone. Set allowed alphabet in character, etc. names.\n\
+             \x201  - (Development   - any language)\n\
+             \x202  - (United States - extended-Latin)\n\
+             \x203  - (Oceanic       - extended-Latin)\n\
+             \x204  - (Latin America - extended-Latin)\n\
+             \x205  - (Tournament    - basic-Latin at create, any at login)\n\
+             \x206  - (Korea         - East-Asian)\n\
+             \x207  - (Tournament    - basic-Latin at create, any at login)\n\
+             \x208  - (English       - extended-Latin)\n\
+             \x209  - (German        - extended-Latin)\n\
+             \x2010 - (French        - extended-Latin)\n\
+             \x2011 - (Spanish       - extended-Latin)\n\
+             \x2012 - (Russian       - Cyrillic)\n\
+             \x2013 - (Tournament    - basic-Latin at create, any at login)\n\
+             \x2014 - (Taiwan        - East-Asian)\n\
+             \x2015 - (Tournament    - basic-Latin at create, any at login)\n\
+             \x2016 - (China         - East-Asian)\n\
+             \x2017 - (CN1           - basic-Latin at create, any at login)\n\
+             \x2018 - (CN2           - basic-Latin at create, any at login)\n\
+             \x2019 - (CN3           - basic-Latin at create, any at login)\n\
+             \x2020 - (CN4           - basic-Latin at create, any at login)\n\
+             \x2021 - (CN5           - basic-Latin at create, any at login)\n\
+             \x2022 - (CN6           - basic-Latin at create, any at login)\n\
+             \x2023 - (CN7           - basic-Latin at create, any at login)\n\
+             \x2024 - (CN8           - basic-Latin at create, any at login)\n\
+             \x2025 - (Tournament    - basic-Latin at create, any at login)\n\
+             \x2026 - (Test Server   - any language)\n\
+             \x2027 - (Tournament    - basic-Latin at create, any at login)\n\
+             \x2028 - (QA Server     - any language)\n\
+             \x2029 - (CN9           - basic-Latin at create, any at login)",
+        )
+        .key(
+            "StrictPlayerNames",
+            Integer,
+            "0",
+            "Limit player name to language specific symbol set. Prevents character\n\
+             creation and forces rename request if not allowed symbols are used\n\
+             \x20 0 - (Disable, Limited server timezone dependent client check)\n\
+             \x20 1 - (Enabled, Strictly basic Latin characters)\n\
+             \x20 2 - (Enabled, Strictly realm zone specific, See RealmZone setting,\n\
+             \x20     Note: Client needs to have the appropriate fonts installed which support\n\
+             \x20     the charset. For non-official localization, custom fonts need to be\n\
+             \x20     placed in clientdir/Fonts.\n\
+             \x20 3 - (Enabled, Basic Latin characters + server timezone specific)",
+        )
+        .key(
+            "StrictCharterNames",
+            Integer,
+            "0",
+            "Limit guild/arena team charter names to language specific symbol set.\n\
+             Prevents charter creation if not allowed symbols are used.\n\
+             \x20 0 - (Disable, Limited server timezone dependent client check)\n\
+             \x20 1 - (Enabled, Strictly basic Latin characters)\n\
+             \x20 2 - (Enabled, Strictly realm zone specific, See RealmZone setting,\n\
+             \x20     Note: Client needs to have the appropriate fonts installed which support\n\
+             \x20     the charset. For non-official localization, custom fonts need to be\n\
+             \x20     placed in clientdir/Fonts.\n\
+             \x20 3 - (Enabled, Basic Latin characters + server timezone specific)",
+        )
+        .key(
+            "StrictPetNames",
+            Integer,
+            "0",
+            "Limit pet names to language specific symbol set.\n\
+             Prevents pet naming if not allowed symbols are used.\n\
+             \x20 0 - (Disable, Limited server timezone dependent client check)\n\
+             \x20 1 - (Enabled, Strictly basic Latin characters)\n\
+             \x20 2 - (Enabled, Strictly realm zone specific, See RealmZone setting,\n\
+             \x20     Note: Client needs to have the appropriate fonts installed which support\n\
+             \x20     the charset. For non-official localization, custom fonts need to be\n\
+             \x20     placed in clientdir/Fonts.\n\
+             \x20 3 - (Enabled, Basic Latin characters + server timezone specific)",
+        )
+        .key(
+            "DBC.Locale",
+            Integer,
+            "255",
+            "DBC language settings.\n\
+             \x20 255 - (Auto Detect)\n\
+             \x20 0   - (English)\n\
+             \x20 1   - (Korean)\n\
+             \x20 2   - (French)\n\
+             \x20 3   - (German)\n\
+             \x20 4   - (Chinese)\n\
+             \x20 5   - (Taiwanese)\n\
+             \x20 6   - (Spanish)\n\
+             \x20 7   - (Spanish Mexico)\n\
+             \x20 8   - (Russian)",
+        )
+        .key(
+            "DBC.EnforceItemAttributes",
+            Boolean,
+            "true",
+            "Disallow overriding item attributes stored in DBC files with values from the database.",
+        )
+        .key(
+            "DeclinedNames",
+            Boolean,
+            "false",
+            "Allow Russian clients to set and use declined names.",
+        )
+        .key(
+            "Expansion",
+            Integer,
+            "2",
+            "Allow server to use content from expansions. Checks for expansion-related\n\
+             map files, client compatibility and class/race character creation.",
+        )
+        .key("MinPlayerName", Integer, "2", "Minimal player name length. (Range: 1-12)")
+        .key("MinCharterName", Integer, "2", "Minimal charter name length. (Range: 1-24)")
+        .key("MinPetName", Integer, "2", "Minimal pet name length. (Range: 1-12)")
+        .key(
+            "ArenaTeam.CharterCost.2v2",
+            Integer,
+            "800000",
+            "Amount of money (in Copper) the petitions costs.\n\
+             \x20 1000 - (10 Silver)\n\
+             \x20 800000 - (80 Gold)\n\
+             \x20 1200000 - (120 Gold)\n\
+             \x20 2000000 - (200 Gold)",
+        )
+        .key("ArenaTeam.CharterCost.3v3", Integer, "1200000", "")
+        .key("ArenaTeam.CharterCost.5v5", Integer, "2000000", "")
+        .key(
+            "MaxWhoListReturns",
+            Integer,
+            "49",
+            "Set the max number of players returned in the /who list and interface.",
+        )
+        .key(
+            "CharacterCreating.Disabled.FactionMask",
+            Integer,
+            "0",
+            "Disable character creation for players based on faction.\n\
+             \x20 0 - (Enabled, All factions are allowed)\n\
+             \x20 1 - (Disabled, Alliance)\n\
+             \x20 2 - (Disabled, Horde)\n\
+             \x20 3 - (Disabled, Both factions)",
+        )
+        .key(
+            "CharacterCreating.Disabled.RaceMask",
+            Integer,
+            "0",
+            "Mask of races which cannot be created by players.\n\
+             \x20 0    - (Enabled, All races are allowed)\n\
+             \x20 1    - (Disabled, Human)\n\
+             \x20 2    - (Disabled, Orc)\n\
+             \x20 4    - (Disabled, Dwarf)\n\
+             \x20 8    - (Disabled, Night Elf)\n\
+             \x20 16   - (Disabled, Undead)\n\
+             \x20 32   - (Disabled, Tauren)\n\
+             \x20 64   - (Disabled, Gnome)\n\
+             \x20 128  - (Disabled, Troll)\n\
+             \x20 512  - (Disabled, Blood Elf)\n\
+             \x20 1024 - (Disabled, Draenei)",
+        )
+        .key(
+            "CharacterCreating.Disabled.ClassMask",
+            Integer,
+            "0",
+            "Mask of classes which cannot be created by players.\n\
+             \x20  0    - (Enabled, All classes are allowed)\n\
+             \x20  1    - (Disabled, Warrior)\n\
+             \x20  2    - (Disabled, Paladin)\n\
+             \x20  4    - (Disabled, Hunter)\n\
+             \x20  8    - (Disabled, Rogue)\n\
+             \x20  16   - (Disabled, Priest)\n\
+             \x20  32   - (Disabled, Death Knight)\n\
+             \x20  64   - (Disabled, Shaman)\n\
+             \x20  128  - (Disabled, Mage)\n\
+             \x20  256  - (Disabled, Warlock)\n\
+             \x20  1024 - (Disabled, Druid)",
+        )
+        .key(
+            "CharacterCreating.MinLevelForDeathKnight",
+            Integer,
+            "55",
+            "Limit creating death knights only for account with another\n\
+             character of specific level (ignored for GM accounts).",
+        )
+        .key(
+            "CharactersPerAccount",
+            Integer,
+            "50",
+            "Limit number of characters per account on all realms on this realmlist.\n\
+             \x20Important: Number must be >= CharactersPerRealm",
+        )
+        .key(
+            "CharactersPerRealm",
+            Integer,
+            "10",
+            "Limit number of characters per account on this realm. (10 = Client limit)",
+        )
+        .key(
+            "DeathKnightsPerRealm",
+            Integer,
+            "1",
+            "Limit number of death knight characters per account on this realm.",
+        )
+        .key(
+            "SkipCinematics",
+            Integer,
+            "0",
+            "Disable cinematic intro at first login after character creation.\n\
+             Prevents buggy intros in case of custom start location coordinates.",
+        )
+        .key("MaxPlayerLevel", Integer, "80", "Maximum level that can be reached by players.")
+        .key("MinDualSpecLevel", Integer, "40", "Level requirement for Dual Talent Specialization.")
+        .key("StartPlayerLevel", Integer, "1", "Starting level for characters after creation.")
+        .key(
+            "StartDeathKnightPlayerLevel",
+            Integer,
+            "55",
+            "Starting level for death knight characters after creation.",
+        )
+        .key(
+            "StartPlayerMoney",
+            Integer,
+            "0",
+            "Amount of money (in Copper) that a character has after creation.",
+        )
+        .key("MaxHonorPoints", Integer, "75000", "Maximum honor points a character can have.")
+        .key(
+            "StartHonorPoints",
+            Integer,
+            "0",
+            "Amount of honor points that characters have after creation.",
+        )
+        .key("MaxArenaPoints", Integer, "10000", "Maximum arena points a character can have.")
+        .key(
+            "StartArenaPoints",
+            Integer,
+            "0",
+            "Amount of arena points that characters has after creation.",
+        )
+        .key(
+            "RecruitAFriend.MaxLevel",
+            Integer,
+            "60",
+            "Highest level up to which a character can benefit from the Recruit-A-Friend\n\
+             experience multiplier.",
+        )
+        .key(
+            "RecruitAFriend.MaxDifference",
+            Integer,
+            "4",
+            "Highest level difference between linked Recruiter and Friend benefit from\n\
+             the Recruit-A-Friend experience multiplier.",
+        )
+        .key(
+            "DisableWaterBreath",
+            Integer,
+            "2",
+            "Required security level for water breathing.\n\
+             \x20 4  - (Disabled)\n\
+             \x20 0  - (Enabled, Everyone)\n\
+             \x20 1  - (Enabled, Mods/GMs/Admins)\n\
+             \x20 2  - (Enabled, GMs/Admins)\n\
+             \x20 3  - (Enabled, Admins)",
+        )
+        .key(
+            "AllFlightPaths",
+            Boolean,
+            "false",
+            "Character knows all flight paths (of both factions) after creation.",
+        )
+        .key(
+            "InstantFlightPaths",
+            Boolean,
+            "false",
+            "Flight paths will take players to their destination instantly instead\n\
+             of making them wait while flying.",
+        )
+        .key(
+            "AlwaysMaxSkillForLevel",
+            Boolean,
+            "false",
+            "Players will automatically gain max skill level when logging in or leveling up.",
+        )
+        .key("ActivateWeather", Boolean, "true", "Activate the weather system.")
+        .key(
+            "CastUnstuck",
+            Boolean,
+            "true",
+            "Allow casting the Unstuck spell using .start or unstuck button in client help options.",
+        )
+        .key(
+            "Instance.IgnoreLevel",
+            Boolean,
+            "false",
+            "Ignore level requirement when entering instances.",
+        )
+        .key(
+            "Instance.IgnoreRaid",
+            Boolean,
+            "false",
+            "Ignore raid group requirement when entering instances.",
+        )
+        .key(
+            "Instance.ResetTimeHour",
+            Integer,
+            "4",
+            "Hour of the day when the global instance reset occurs.",
+        )
+        .key(
+            "Instance.UnloadDelay",
+            Integer,
+            "1800000",
+            "Time (in milliseconds) before instance maps are unloaded from memory if no characters are inside.",
+        )
+        .key(
+            "InstancesResetAnnounce",
+            Boolean,
+            "false",
+            "Announce the reset of one instance to whole party.",
+        )
+        .key(
+            "Quests.EnableQuestTracker",
+            Boolean,
+            "false",
+            "Store data in the database about quest completion and abandonment to help finding bugged quests.",
+        )
+        .key(
+            "Quests.LowLevelHideDiff",
+            Integer,
+            "10",
+            "Level difference between player and quest level at which quests are considered low-level and are not shown via\n\
+             exclamation mark (!) at quest givers. (-1 to disable)",
+        )
+        .key(
+            "Quests.HighLevelHideDiff",
+            Integer,
+            "7",
+            "Level difference between player and quest level at which quests are considered high-level and are not shown via\n\
+             exclamation mark (!) at quest givers. (-1 to disable)",
+        )
+        .key(
+            "Quests.IgnoreRaid",
+            Boolean,
+            "false",
+            "Allow non-raid quests to be completed while in a raid group.",
+        )
+        .key(
+            "Quests.IgnoreAutoAccept",
+            Boolean,
+            "false",
+            "Ignore auto accept flag. Clients will have to manually accept all quests.",
+        )
+        .key(
+            "Quests.IgnoreAutoComplete",
+            Boolean,
+            "0",
+            "Ignore auto complete flag. Clients will have to manually complete all quests.",
+        )
+        .key(
+            "Quests.DailyResetTime",
+            Integer,
+            "3",
+            "Hour of the day when daily quest reset occurs.",
+        )
+        .key(
+            "Quests.WeeklyResetWDay",
+            Integer,
+            "3",
+            "Day of the week when weekly quest reset occurs.",
+        )
+        .key(
+            "Calendar.DeleteOldEventsHour",
+            Integer,
+            "6",
+            "Hour of the day when the daily deletion of old calendar events occurs.",
+        )
+        .key("Guild.CharterCost", Integer, "1000", "")
+        .key(
+            "Guild.EventLogRecordsCount",
+            Integer,
+            "100",
+            "Number of log entries for guild events that are stored per guild. Old entries\n\
+             will be overwritten if the number of log entries exceed the configured value.\n\
+             High numbers prevent this behavior but may have performance impacts.",
+        )
+        .key("Guild.ResetHour", Integer, "6", "Hour of the day when the daily cap resets occur.")
+        .key(
+            "Guild.BankEventLogRecordsCount",
+            Integer,
+            "25",
+            "Number of log entries for guild bank events that are stored per guild. Old entries will be overwritten\n\
+             if the number of log entries exceed the configured value. High numbers prevent this behavior but\n\
+             may have performance impacts.",
+        )
+        .key(
+            "Guild.AllowMultipleGuildMaster",
+            Boolean,
+            "false",
+            "Allow more than one guild master. Additional Guild Masters must be set using the \".guild rank\" command.",
+        )
+        .key(
+            "MaxPrimaryTradeSkill",
+            Integer,
+            "2",
+            "Maximum number of primary professions a character can learn.",
+        )
+        .key(
+            "MinPetitionSigns",
+            Integer,
+            "9",
+            "Number of required signatures on charters to create a guild.",
+        )
+        .key(
+            "MaxGroupXPDistance",
+            Float,
+            "74",
+            "Max distance to creature for group member to get experience at creature death.",
+        )
+        .key(
+            "MaxRecruitAFriendBonusDistance",
+            Float,
+            "100",
+            "Max distance between character and group to gain the Recruit-A-Friend XP multiplier.",
+        )
+        .key(
+            "MinQuestScaledXPRatio",
+            Integer,
+            "0",
+            "Min ratio of experience that a quest can grant when player level scaling is factored.",
+        )
+        .key(
+            "MinCreatureScaledXPRatio",
+            Integer,
+            "0",
+            "Min ratio of experience that a creature kill can grant when player level scaling is factored. This\n\
+             will also allow spell procs to trigger, such as Drain Soul, if > 0 and exp is grantable.\n\
+             \x20 Example:  50 (No less than 50% experience granted from a lower leveled creature kill)\n\
+             \x20          100 (Creature kills always grant full experience upon kill)\n\
+             \x20            0 (Creatures too low may grant no experience)",
+        )
+        .key(
+            "MinDiscoveredScaledXPRatio",
+            Integer,
+            "0",
+            "Min ratio of experience that an area discovery event will grant when player level scaling is factored.\n\
+             \x20 Example:  50 (No less than 50% experience granted from discovering a new section of map)\n\
+             \x20          100 (Map exploration always grant full experience upon discovery)\n\
+             \x20            0 (No experience granted when discovered area is too low level)",
+        )
+        .key(
+            "CleanOldMailTime",
+            Integer,
+            "4",
+            "The hour at which old mails will be returned or deleted by the server.\n\
+             This can be any integer number from 0 to 23. 20 will be 8pm server time.",
+        )
+        .key("SkillChance.Prospecting", Boolean, "false", "Allow skill increase from prospecting.")
+        .key("SkillChance.Milling", Boolean, "false", "Allow skill increase from milling.")
+        .key(
+            "SkillChance.Orange",
+            Integer,
+            "100",
+            "Chance (in percent) to increase a skill from an orange recipe.",
+        )
+        .key(
+            "SkillChance.Yellow",
+            Integer,
+            "75",
+            "Chance (in percent) to increase a skill from a yellow recipe.",
+        )
+        .key(
+            "SkillChance.Green",
+            Integer,
+            "25",
+            "Chance (in percent) to increase a skill from a green recipe.",
+        )
+        .key(
+            "SkillChance.Grey",
+            Integer,
+            "0",
+            "Chance (in percent) to increase a skill from a grey recipe.",
+        )
+        .key(
+            "SkillChance.MiningSteps",
+            Integer,
+            "75",
+            "Mining skill steps after which the skill increase chance drops.",
+        )
+        .key(
+            "SkillChance.SkinningSteps",
+            Integer,
+            "75",
+            "Skinning skill steps after which the skill increase chance drops.",
+        )
+        .key(
+            "OffhandCheckAtSpellUnlearn",
+            Boolean,
+            "true",
+            "Unlearning certain spells can change offhand weapon restrictions for equip slots.",
+        )
+        .key(
+            "ClientCacheVersion",
+            Integer,
+            "0",
+            "Client cache version for client cache data reset. Use any value different\n\
+             from DB and not recently been used to trigger client side cache reset.",
+        )
+        .key("Event.Announce", Boolean, "false", "Announce events.")
+        .key("BeepAtStart", Boolean, "false", "Beep when the world server finished starting.")
+        .key(
+            "FlashAtStart",
+            Boolean,
+            "false",
+            "Flashes in taskbar when the world server finished starting. (Works on Windows only)",
+        )
+        .key(
+            "Motd",
+            String,
+            "\"Welcome to a Kitron server.\"",
+            "Message of the Day, displayed at login. Use '@' for a newline.",
+        )
+        .key("Server.LoginInfo", Boolean, "false", "Display core version (.server info) on login.")
+        .key(
+            "Command.LookupMaxResults",
+            Integer,
+            "0",
+            "Number of results being displayed using a .lookup command. 0 = unlimited.",
+        )
+        .key("AllowTickets", Boolean, "true", "Allow/disallow sending new tickets.")
+        .key(
+            "DeletedCharacterTicketTrace",
+            Boolean,
+            "false",
+            "Keep trace of tickets opened by deleted characters gm_ticket.playerGuid will be 0, old GUID and character name\n\
+             will be included in gm_ticket.comment",
+        )
+        .key(
+            "DungeonFinder.OptionsMask",
+            Integer,
+            "1",
+            "\n\
+             \x20   DungeonFinder.OptionsMask\n\
+             \x20      Description:\n\
+             \x20      Default:     1\n\
+             Dungeon and raid finder system.\n\
+             Value is a bitmask consisting of:\n\
+             \x20LFG_OPTION_ENABLE_DUNGEON_FINDER = 1,     Enable the dungeon finder browser\n\
+             \x20LFG_OPTION_ENABLE_RAID_BROWSER   = 2,     Enable the raid browser",
+        )
+        .key(
+            "AccountInstancesPerHour",
+            Integer,
+            "5",
+            "Controls the max amount of different instances player can enter within hour.",
+        )
+        .key(
+            "Account.PasswordChangeSecurity",
+            Integer,
+            "0",
+            "Controls how secure the password changes are.\n\
+             \x200 - None (Old and new password)\n\
+             \x201 - Email (Email confirmation necessary)\n\
+             \x202 - RBAC (RBAC enable or disables email confirmation per group)",
+        )
+        .key(
+            "BirthdayTime",
+            Integer,
+            "1685404800",
+            "Set to date of project's birth in UNIX time. By default, the date when KitronCore was started (Tue May 30, 2023)",
+        )
+        .key(
+            "CacheDataQueries",
+            Boolean,
+            "1",
+            "Server caches data queries at startup. Can be disabled if not enough memory is available.",
+        )
+        .key(
+            "AllowLoggingIPAddressesInDatabase",
+            Boolean,
+            "true",
+            "Specifies if IP addresses can be logged to the database",
+        )
+        .key(
+            "TOTPMasterSecret",
+            String,
+            "~",
+            "The key used by authserver to decrypt TOTP secrets from database storage.\n\
+             You only need to set this here if you plan to use the in-game 2FA\n\
+             management commands (.account 2fa), otherwise this can be left blank.\n\
+             \n\
+             The server will auto-detect if this does not match your authserver setting,\n\
+             in which case any commands reliant on the secret will be disabled.",
+        )
+        .key(
+            "Updates.EnableDatabases",
+            Integer,
+            "7",
+            "A mask that describes which databases shall be updated.\n\
+             Following flags are available\n\
+             \x20  DATABASE_LOGIN     = 1, // Auth database\n\
+             \x20  DATABASE_CHARACTER = 2, // Character database\n\
+             \x20  DATABASE_WORLD     = 4, // World database",
+        )
+        .key("Updates.AutoSetup", Boolean, "true", "Auto-populate empty databases.")
+        .key(
+            "Updates.Redundancy",
+            Boolean,
+            "true",
+            "Perform data redundancy checks through hashing to detect changes on sql updates and reapply it.",
+        )
+        .key(
+            "Updates.ArchivedRedundancy",
+            Boolean,
+            "false",
+            "Check hashes of archived updates (slows down startup).",
+        )
+        .key(
+            "Updates.AllowRehash",
+            Boolean,
+            "true",
+            "Inserts the current file hash in the database if it is left empty.\n\
+             Useful if you want to mark a file as applied, but you don't know its hash.",
+        )
+        .key(
+            "Updates.CleanDeadRefMaxCount",
+            Integer,
+            "3",
+            "Cleans dead/orphaned references that occur if an update was removed or renamed and edited in one step.\n\
+             It only starts the cleanup if the count of the missing updates is below or equal the Updates.CleanDeadRefMaxCount value.\n\
+             This way prevents erasing of the update history due to wrong source directory state (maybe wrong branch or bad revision).\n\
+             Disable this if you want to know if the database is in a possible \"dirty state\".\n\
+             \x20  3 - (Enabled)\n\
+             \x20  0 - (Disabled)\n\
+             \x20  -1 - (Enabled - unlimited)",
+        )
+        .key("Warden.Enabled", Boolean, "false", "Enable Warden anti-cheat system.")
+        .key(
+            "Warden.NumInjectionChecks",
+            Integer,
+            "9",
+            "Number of Warden checks of the respective category that are sent per cycle.",
+        )
+        .key("Warden.NumLuaSandboxChecks", Integer, "1", "")
+        .key("Warden.NumClientModChecks", Integer, "1", "")
+        .key(
+            "Warden.ClientResponseDelay",
+            Integer,
+            "600",
+            "Time (in seconds) before client is getting disconnecting for not responding.",
+        )
+        .key(
+            "Warden.ClientCheckHoldOff",
+            Integer,
+            "30",
+            "Time (in seconds) to wait before sending the next check request to the client.\n\
+             A low number increases traffic and load on client and server side.",
+        )
+        .key(
+            "Warden.ClientCheckFailAction",
+            Integer,
+            "0",
+            "Default action being taken if a client check failed. Actions can be\n\
+             overwritten for each single check via warden_action table in characters database.\n\
+             \x20 0 - (Disabled, Logging only)\n\
+             \x20 1 - (Kick)\n\
+             \x20 2 - (Ban)",
+        )
+        .key(
+            "Warden.BanDuration",
+            Integer,
+            "86400",
+            "Time (in seconds) an account will be banned if ClientCheckFailAction is set to ban.",
+        )
+        .key(
+            "AllowTwoSide.Interaction.Calendar",
+            Boolean,
+            "false",
+            "Allow calendar invites between factions.",
+        )
+        .key(
+            "AllowTwoSide.Interaction.Channel",
+            Boolean,
+            "false",
+            "Allow channel chat between factions.",
+        )
+        .key(
+            "AllowTwoSide.Interaction.Group",
+            Boolean,
+            "false",
+            "Allow group joining between factions.",
+        )
+        .key(
+            "AllowTwoSide.Interaction.Guild",
+            Boolean,
+            "false",
+            "Allow guild joining between factions.",
+        )
+        .key(
+            "AllowTwoSide.Interaction.Auction",
+            Boolean,
+            "false",
+            "Allow auctions between factions.\n\
+             This results in all auctions being handled as if placed in the neutral\n\
+             auction house and will intentionally lock acces to the Horde and Alliance AH.\n\
+             Deposit fee and auction cut values will be taken from neutral auction house.\n\
+             If used in combination with the AH-Bot, settings should be set for neutral\n\
+             auction house only.\n\
+             \n\
+             Warning: changing this in production results in players loosing access to their\n\
+             already placed auctions in the faction houses - these auctions will expire after\n\
+             their regular timings.",
+        )
+        .key("AllowTwoSide.Trade", Boolean, "false", "Allow trading between factions.")
+        .key(
+            "TalentsInspecting",
+            Integer,
+            "1",
+            "Allow/disallow inspecting other characters' talents. Doesn't affect game master accounts.\n\
+             \x20 2 - (Enabled for all characters)\n\
+             \x20 1 - (Enabled for characters of the same faction)\n\
+             \x20 0 - (Talent inspecting is disabled)",
+        )
+        .key(
+            "ThreatRadius",
+            Float,
+            "60",
+            "Distance for creatures to evade after being pulled away from the combat\n\
+             starting point. If ThreatRadius is less than creature aggro radius then aggro\n\
+             radius will be used.",
+        )
+        .key(
+            "CreatureFamilyFleeAssistanceRadius",
+            Float,
+            "30",
+            "Distance for fleeing creatures seeking assistance from other creatures.",
+        )
+        .key(
+            "CreatureFamilyAssistanceRadius",
+            Float,
+            "10",
+            "Distance for creatures calling for assistance from other creatures without moving.",
+        )
+        .key(
+            "CreatureFamilyAssistanceDelay",
+            Integer,
+            "1500",
+            "Time (in milliseconds) before creature assistance call.",
+        )
+        .key(
+            "CreatureFamilyFleeDelay",
+            Integer,
+            "7000",
+            "Time (in milliseconds) during which creature can flee if no assistance was found.",
+        )
+        .key("WorldBossLevelDiff", Integer, "3", "World boss level difference.")
+        .key(
+            "Corpse.Decay.NORMAL",
+            Integer,
+            "300",
+            "Time (in seconds) until creature corpse will decay if not looted or skinned.",
+        )
+        .key("Corpse.Decay.RARE", Integer, "300", "")
+        .key("Corpse.Decay.ELITE", Integer, "300", "")
+        .key("Corpse.Decay.RAREELITE", Integer, "300", "")
+        .key("Corpse.Decay.WORLDBOSS", Integer, "3600", "")
+        .key(
+            "Creature.PickPocketRefillDelay",
+            Integer,
+            "600",
+            "Time in seconds that the server will wait before refilling the pickpocket loot for a creature",
+        )
+        .key(
+            "Creature.MovingStopTimeForPlayer",
+            Integer,
+            "180000",
+            "Time (in milliseconds) during which creature will not move after interaction with player.",
+        )
+        .key(
+            "Creature.RegenHPCannotReachTargetInRaid",
+            Boolean,
+            "true",
+            "Regenerates HP for Creatures in Raids if they cannot reach the target.",
+        )
+        .key(
+            "ListenRange.Say",
+            Float,
+            "40",
+            "Distance in which players can read say messages from creatures or gameobjects.",
+        )
+        .key(
+            "ListenRange.TextEmote",
+            Float,
+            "40",
+            "Distance in which players can read emotes from creatures or gameobjects.",
+        )
+        .key(
+            "ListenRange.Yell",
+            Float,
+            "300",
+            "Distance in which players can read yell messages from creatures or gameobjects.",
+        )
+        .key(
+            "MonsterSight",
+            Float,
+            "50.000000",
+            "The maximum distance in yards that a \"monster\" creature can see regardless of level difference\n\
+             (through CreatureAI::IsVisible). Increases CONFIG_SIGHT_MONSTER to 50 yards. Used to be 20 yards.",
+        )
+        .key(
+            "Respawn.MinCheckIntervalMS",
+            Integer,
+            "5000",
+            "Minimum time that needs to pass between respawn checks for any given map.",
+        )
+        .key(
+            "Respawn.GuidWarnLevel",
+            Integer,
+            "12000000",
+            "The point at which the highest guid for creatures or gameobjects in any map must reach\n\
+             before the warning logic is enabled. A restart will then be queued at the next quiet time\n\
+             The maximum guid per map is 16,777,216. So, it must be less than this value.",
+        )
+        .key(
+            "Respawn.WarningMessage",
+            String,
+            "\"There will be an unscheduled server restart at 03:00. The server will be available again shortly after.\"",
+            "This message will be periodically shown (Frequency specified by Respawn.WarningFrequency) to\n\
+             all users of the server, once the Respawn.GuidWarnLevel has been passed, and a restart scheduled.\n\
+             It's used to warn users that there will be an out of schedule server restart soon.",
+        )
+        .key(
+            "Respawn.WarningFrequency",
+            Integer,
+            "1800",
+            "The frequency (in seconds) that the warning message will be sent to users after a quiet time restart is triggered.\n\
+             The message will repeat each time this many seconds passed until the server is restarted.\n\
+             If set to 0, no warnings will be sent.",
+        )
+        .key(
+            "Respawn.GuidAlertLevel",
+            Integer,
+            "16000000",
+            "The point at which the highest guid for creatures or gameobjects in any map must reach\n\
+             before the alert logic is enabled. A restart will then be triggered for 30 min. from that\n\
+             point. The maximum guid per map is 16,777,216. So, it must be less than this value.",
+        )
+        .key(
+            "Respawn.AlertRestartReason",
+            String,
+            "\"Urgent Maintenance\"",
+            "The shutdown reason given when the alert level is reached. The server will use a fixed time of 5 minutes\n\
+             and the reason for shutdown will be this message",
+        )
+        .key(
+            "Respawn.RestartQuietTime",
+            Integer,
+            "3",
+            "The hour at which the server will be restarted after the Respawn.GuidWarnLevel\n\
+             threshold has been reached. This can be between 0 and 23. 20 will be 8pm server time",
+        )
+        .key(
+            "Respawn.DynamicMode",
+            Integer,
+            "0",
+            "Select which mode (if any) should be used to adjust respawn of creatures.\n\
+             This will only affect creatures that have dynamic spawn rate scaling enabled in\n\
+             the spawn group table (by default, gathering nodes and quest targets with respawn time <30min\n\
+             \x20  1 - Use number of players in zone\n\
+             \x20  0 - No dynamic respawn function",
+        )
+        .key(
+            "Respawn.DynamicEscortNPC",
+            Boolean,
+            "false",
+            "This switch controls the dynamic respawn system for escort NPCs not in instanceable maps (base maps only).\n\
+             This will cause the respawn timer to begin when an escort event begins, and potentially\n\
+             allow multiple instances of the NPC to be alive at the same time (when combined with Respawn.DynamicMode > 0)",
+        )
+        .key(
+            "Respawn.DynamicRateCreature",
+            Float,
+            "10",
+            "The rate at which the respawn time is adjusted for high player counts in a zone (for creatures).\n\
+             Up to this number of players, the respawn rate is unchanged.\n\
+             At double this number in players, you get twice as many respawns, at three times this number, three times the respawns, and so forth.",
+        )
+        .key(
+            "Respawn.DynamicMinimumCreature",
+            Integer,
+            "10",
+            "The minimum respawn time (in seconds) for a creature under dynamic scaling.",
+        )
+        .key(
+            "Respawn.DynamicRateGameObject",
+            Float,
+            "10",
+            "The rate at which the respawn time is adjusted for high player counts in a zone (for gameobjects).\n\
+             Up to this number of players, the respawn rate is unchanged.\n\
+             At double this number in players, you get twice as many respawns, at three times this number, three times the respawns, and so forth.",
+        )
+        .key(
+            "Respawn.DynamicMinimumGameObject",
+            Integer,
+            "10",
+            "The minimum respawn time for a GameObject under dynamic scaling.",
+        )
+        .key(
+            "ChatFakeMessagePreventing",
+            Boolean,
+            "true",
+            "Additional protection from creating fake chat messages using spaces.\n\
+             Collapses multiple subsequent whitespaces into a single whitespace.\n\
+             Not applied to the addon language, but may break old addons that use\n\
+             \"normal\" chat messages for sending data to other clients.",
+        )
+        .key(
+            "ChatStrictLinkChecking.Severity",
+            Integer,
+            "0",
+            "Check chat messages for in-game links to spells, items, quests, etc.\n\
+             \x20 -1 - (Only verify validity of link data, but permit use of custom colors)\n\
+             \x20  0 - (Only verify that link data and color are valid without checking text)\n\
+             \x20  1 - (Additionally verifies that the link text matches the provided data)\n\
+             \n\
+             \x20  Note: If this is set to '1', you must additionally provide .dbc files for all\n\
+             \x20        client locales that are in use on your server.\n\
+             \x20        If any files are missing, messages with links from clients using those\n\
+             \x20        locales will likely be blocked by the server.",
+        )
+        .key(
+            "ChatStrictLinkChecking.Kick",
+            Boolean,
+            "false",
+            "Defines if the user should be kicked if a message containing invalid control characters is received.\n\
+             If false, simply ignore the message.",
+        )
+        .key(
+            "ChatFlood.MessageCount",
+            Integer,
+            "10",
+            "Chat flood protection, number of messages before player gets muted.",
+        )
+        .key(
+            "ChatFlood.MessageDelay",
+            Integer,
+            "1",
+            "Time (in seconds) between messages to be counted into ChatFlood.MessageCount.",
+        )
+        .key(
+            "ChatFlood.AddonMessageCount",
+            Integer,
+            "100",
+            "Chat flood protection, number of addon messages before player gets muted.",
+        )
+        .key(
+            "ChatFlood.AddonMessageDelay",
+            Integer,
+            "1",
+            "Time (in seconds) between addon messages to be counted into ChatFlood.AddonMessageCount.",
+        )
+        .key(
+            "ChatFlood.MuteTime",
+            Integer,
+            "10",
+            "Time (in seconds) characters get muted for violating ChatFlood.MessageCount / ChatFlood.AddonMessageCount.",
+        )
+        .key(
+            "Channel.RestrictedLfg",
+            Boolean,
+            "true",
+            "Restrict LookupForGroup channel to characters registered in the LFG tool.",
+        )
+        .key(
+            "ChatLevelReq.Channel",
+            Integer,
+            "1",
+            "Level requirement for characters to be able to use chats.",
+        )
+        .key("ChatLevelReq.Whisper", Integer, "1", "")
+        .key("ChatLevelReq.Emote", Integer, "1", "")
+        .key("ChatLevelReq.Say", Integer, "1", "")
+        .key("ChatLevelReq.Yell", Integer, "1", "")
+        .key(
+            "PartyLevelReq",
+            Integer,
+            "1",
+            "Minimum level at which players can invite to group, even if they aren't on\n\
+             the invitee friends list. (Players who are on that friend list can always\n\
+             invite despite having lower level)",
+        )
+        .key(
+            "PreserveCustomChannels",
+            Boolean,
+            "true",
+            "Store custom chat channel settings like password, automatic ownership handout or ban list in the database.\n\
+             Needs to be enabled to save custom world/trade/etc. channels that have automatic ownership handout disabled.\n\
+             (.channel set ownership $channel off)",
+        )
+        .key(
+            "PreserveCustomChannelInterval",
+            Integer,
+            "5",
+            "Interval (in minutes) at which custom channel data is saved to the database",
+        )
+        .key(
+            "PreserveCustomChannelDuration",
+            Integer,
+            "14",
+            "Time (in days) that needs to pass before the customs chat channels get cleaned up from the database.\n\
+             Only channels with ownership handout enabled (default behavior) will be cleaned.",
+        )
+        .key(
+            "PartyRaidWarnings",
+            Boolean,
+            "false",
+            "Allow any user to use raid warnings when in a 5-man party.",
+        )
+        .key(
+            "GM.LoginState",
+            Integer,
+            "2",
+            "GM mode at login: 2 - last saved state, 0 - disabled, 1 - enabled",
+        )
+        .key(
+            "GM.Visible",
+            Integer,
+            "2",
+            "GM visibility at login: 2 - last saved state, 0 - disabled, 1 - enabled",
+        )
+        .key(
+            "GM.Chat",
+            Integer,
+            "2",
+            "GM chat mode at login: 2 - last saved state, 0 - disabled, 1 - enabled",
+        )
+        .key(
+            "GM.WhisperingTo",
+            Integer,
+            "2",
+            "Is GM accepting whispers from player by default or not: 2 - last saved state, 0 - disabled, 1 - enabled",
+        )
+        .key(
+            "GM.FreezeAuraDuration",
+            Integer,
+            "0",
+            "Allows to set a default duration (in seconds) to the Freeze Aura applied on players when using the .freeze command",
+        )
+        .key(
+            "GM.InGMList.Level",
+            Integer,
+            "3",
+            "Maximum GM level shown in GM list (if enabled) in non-GM state (.gm off). 3 = Anyone",
+        )
+        .key("GM.InWhoList.Level", Integer, "3", "Max GM level showed in who list (if visible).")
+        .key("GM.StartLevel", Integer, "1", "GM character starting level.")
+        .key("GM.AllowInvite", Boolean, "false", "Allow players to invite GM characters.")
+        .key(
+            "GM.LowerSecurity",
+            Boolean,
+            "false",
+            "Allow lower security levels to use commands on higher security level characters.",
+        )
+        .key(
+            "GM.TicketSystem.ChanceOfGMSurvey",
+            Float,
+            "50",
+            "Chance of sending a GM survey after ticket completion.",
+        )
+        .key(
+            "GM.ForceShutdownThreshold",
+            Integer,
+            "30",
+            "Minimum shutdown time in seconds before 'force' is required if other players are connected.",
+        )
+        .key(
+            "Visibility.GroupMode",
+            Integer,
+            "1",
+            "Group visibility modes. Defines which groups can always detect invisible characters of the same\n\
+             raid (1), group (0), faction (2) or none (3).",
+        )
+        .key(
+            "Visibility.Distance.Continents",
+            Float,
+            "100",
+            "Visibility distance (in yards) on the continents.",
+        )
+        .key(
+            "Visibility.Distance.Instances",
+            Float,
+            "170",
+            "Visibility distance (in yards) in the instances.",
+        )
+        .key(
+            "Visibility.Distance.BG",
+            Float,
+            "533",
+            "Visibility distance (in yards) in the battlegrounds.",
+        )
+        .key(
+            "Visibility.Distance.Arenas",
+            Float,
+            "533",
+            "Visibility distance (in yards) in the arenas.",
+        )
+        .key(
+            "Visibility.Notify.Period.OnContinents",
+            Integer,
+            "1000",
+            "Time (in milliseconds) between the visibility updates on the continents.",
+        )
+        .key(
+            "Visibility.Notify.Period.InInstances",
+            Integer,
+            "1000",
+            "Time (in milliseconds) between the visibility updates in the instances.",
+        )
+        .key(
+            "Visibility.Notify.Period.InBG",
+            Integer,
+            "1000",
+            "Time (in milliseconds) between the visibility updates in the battlegrounds.",
+        )
+        .key(
+            "Visibility.Notify.Period.InArenas",
+            Integer,
+            "1000",
+            "Time (in milliseconds) between the visibility updates in the arenas.",
+        )
+        .key(
+            "Distance.Continents",
+            Integer,
+            "100",
+            "Visibility distance to see other players or gameobjects.\n\
+             Visibility on continents on retail ~100 yards. In BG/Arenas ~533.\n\
+             For instances default ~170.\n\
+             Max limited by grid size: 533.33333\n\
+             Min limit is max aggro radius (45) * Rate.Creature.Aggro",
+        )
+        .key("Distance.Instances", Integer, "170", "")
+        .key("Distance.BG", Integer, "533", "")
+        .key("Distance.Arenas", Integer, "533", "")
+        .key(
+            "Distance.Notify.Period.OnContinents",
+            Integer,
+            "1000",
+            "Time (in milliseconds) for visibility update period. Lower values may have performance impact.",
+        )
+        .key("Distance.Notify.Period.InInstances", Integer, "1000", "")
+        .key("Distance.Notify.Period.InBG", Integer, "1000", "")
+        .key("Distance.Notify.Period.InArenas", Integer, "1000", "")
+        .key("Rate.Creature.Aggro", Float, "1", "Aggro radius percentage.")
+        .key("Rate.Creature.Normal.Damage", Float, "1", "Multiplier for creature melee damage.")
+        .key(
+            "Rate.Creature.Normal.SpellDamage",
+            Float,
+            "1",
+            "Multiplier for creature spell damage.",
+        )
+        .key("Rate.Creature.Normal.HP", Float, "1", "Multiplier for creature health.")
+        .key("Rate.Creature.Elite.Elite.Damage", Float, "1", "")
+        .key("Rate.Creature.Elite.Elite.SpellDamage", Float, "1", "")
+        .key("Rate.Creature.Elite.Elite.HP", Float, "1", "")
+        .key("Rate.Creature.Elite.RARE.Damage", Float, "1", "")
+        .key("Rate.Creature.Elite.RARE.SpellDamage", Float, "1", "")
+        .key("Rate.Creature.Elite.RARE.HP", Float, "1", "")
+        .key("Rate.Creature.Elite.RAREELITE.Damage", Float, "1", "")
+        .key("Rate.Creature.Elite.RAREELITE.SpellDamage", Float, "1", "")
+        .key("Rate.Creature.Elite.RAREELITE.HP", Float, "1", "")
+        .key("Rate.Creature.Elite.WORLDBOSS.Damage", Float, "1", "")
+        .key("Rate.Creature.Elite.WORLDBOSS.SpellDamage", Float, "1", "")
+        .key("Rate.Creature.Elite.WORLDBOSS.HP", Float, "1", "")
+        .key(
+            "Rate.Corpse.Decay.Looted",
+            Float,
+            "0.5",
+            "Multiplier for Corpse.Decay.* to configure how long creature corpses stay after they have been looted.",
+        )
+        .key("Rate.Health", Float, "1", "")
+        .key("Rate.Mana", Float, "1", "")
+        .key("Rate.Rage.Income", Float, "1", "")
+        .key("Rate.Rage.Loss", Float, "1", "")
+        .key("Rate.RunicPower.Income", Float, "1", "")
+        .key("Rate.RunicPower.Loss", Float, "1", "")
+        .key("Rate.Focus", Float, "1", "")
+        .key("Rate.Energy", Float, "1", "")
+        .key("Rate.Loyalty", Integer, "1", "")
+        .key("Rate.Skill.Discovery", Float, "1", "")
+        .key("Rate.Drop.Item.ReferencedAmount", Float, "1", "")
+        .key("Rate.Drop.Item.Poor", Float, "1", "")
+        .key("Rate.Drop.Item.Normal", Float, "1", "")
+        .key("Rate.Drop.Item.Uncommon", Float, "1", "")
+        .key("Rate.Drop.Item.Rare", Float, "1", "")
+        .key("Rate.Drop.Item.Epic", Float, "1", "")
+        .key("Rate.Drop.Item.Legendary", Float, "1", "")
+        .key("Rate.Drop.Item.Artifact", Float, "1", "")
+        .key("Rate.Drop.Item.Referenced", Float, "1", "")
+        .key("Rate.Drop.Money", Float, "1", "")
+        .key("Rate.XP.Kill", Float, "1", "")
+        .key("Rate.XP.Quest", Float, "1", "")
+        .key("Rate.XP.Explore", Float, "1", "")
+        .key("Rate.XP.BattlegroundKill", Float, "1", "")
+        .key("Rate.Quest.Money.Reward", Float, "1", "")
+        .key("Rate.Quest.Money.Max.Level.Reward", Float, "1", "")
+        .key("Rate.RepairCost", Float, "1", "")
+        .key("Rate.Rest.InGame", Float, "1", "")
+        .key("Rate.Rest.Offline.InTavernOrCity", Float, "1", "")
+        .key("Rate.Rest.Offline.InWilderness", Float, "1", "")
+        .key("Rate.Damage.Fall", Float, "1", "")
+        .key("Rate.Auction.Time", Float, "1", "")
+        .key("Rate.Auction.Deposit", Float, "1", "")
+        .key("Rate.Auction.Cut", Float, "1", "")
+        .key("Rate.Honor", Float, "1", "")
+        .key("Rate.ArenaPoints", Float, "1", "")
+        .key("Rate.Talent", Float, "1", "")
+        .key("Rate.Reputation.Gain", Float, "1", "")
+        .key("Rate.Reputation.LowLevel.Kill", Float, "1", "")
+        .key("Rate.Reputation.LowLevel.Quest", Float, "1", "")
+        .key("Rate.Reputation.RecruitAFriendBonus", Float, "0.1", "")
+        .key("Rate.MoveSpeed", Float, "1", "")
+        .key("Rate.InstanceResetTime", Float, "1", "")
+        .key("SkillGain.Crafting", Integer, "1", "")
+        .key("SkillGain.Defense", Integer, "1", "")
+        .key("SkillGain.Gathering", Integer, "1", "")
+        .key("SkillGain.Weapon", Integer, "1", "")
+        .key("SkillGain.Orange", Integer, "100", "")
+        .key("SkillGain.Yellow", Integer, "75", "")
+        .key("SkillGain.Green", Integer, "25", "")
+        .key("SkillGain.Grey", Integer, "0", "")
+        .key("SkillGain.MiningSteps", Integer, "0", "")
+        .key("SkillGain.SkinningSteps", Integer, "0", "")
+        .key("DurabilityLoss.InPvP", Boolean, "false", "Durability loss on death during PvP.")
+        .key("DurabilityLoss.OnDeath", Float, "10", "Durability loss percentage on death.")
+        .key(
+            "DurabilityLossChance.Damage",
+            Float,
+            "0.5",
+            "Chance to lose durability on one equipped item from damage.",
+        )
+        .key(
+            "DurabilityLossChance.Absorb",
+            Float,
+            "0.5",
+            "Chance to lose durability on one equipped armor item when absorbing damage.",
+        )
+        .key(
+            "DurabilityLossChance.Parry",
+            Float,
+            "0.05",
+            "Chance to lose durability on main weapon when parrying attacks.",
+        )
+        .key(
+            "DurabilityLossChance.Block",
+            Float,
+            "0.05",
+            "Chance to lose durability on shield when blocking attacks.",
+        )
+        .key("Death.SicknessLevel", Integer, "11", "Starting level for resurrection sickness.")
+        .key(
+            "Death.CorpseReclaimDelay.PvP",
+            Boolean,
+            "true",
+            "Increase corpse reclaim delay at PvP/PvE deaths.",
+        )
+        .key("Death.CorpseReclaimDelay.PvE", Boolean, "false", "")
+        .key(
+            "Death.Bones.World",
+            Boolean,
+            "true",
+            "Create bones instead of corpses at resurrection in normal zones, instances, battleground or arenas.",
+        )
+        .key("Death.Bones.BattlegroundOrArena", Boolean, "true", "")
+        .key(
+            "Die.Command.Mode",
+            Boolean,
+            "true",
+            "Do not trigger things like loot from .die command.",
+        )
+        .key("Stats.Limits.Enable", Boolean, "false", "Enable or disable stats system.")
+        .key("Stats.Limits.Dodge", Float, "95.0", "")
+        .key("Stats.Limits.Parry", Float, "95.0", "")
+        .key("Stats.Limits.Block", Float, "95.0", "")
+        .key("Stats.Limits.Crit", Float, "95.0", "")
+        .key("AutoBroadcast.On", Boolean, "false", "Enable auto broadcast.")
+        .key(
+            "AutoBroadcast.Center",
+            Integer,
+            "0",
+            "Auto broadcasting display method. 0 - (Announce), 1 - (Notify), 2 - (Both)",
+        )
+        .key(
+            "AutoBroadcast.Timer",
+            Integer,
+            "600000",
+            "Timer (in milliseconds) for auto broadcasts.",
+        )
+        .key(
+            "Battleground.CastDeserter",
+            Boolean,
+            "true",
+            "Cast Deserter spell at players who leave battlegrounds in progress.",
+        )
+        .key(
+            "Battleground.QueueAnnouncer.Enable",
+            Boolean,
+            "false",
+            "Announce battleground queue status to chat.",
+        )
+        .key(
+            "Battleground.QueueAnnouncer.PlayerOnly",
+            Boolean,
+            "false",
+            "Battleground queue announcement type.",
+        )
+        .key(
+            "Battleground.StoreStatistics.Enable",
+            Boolean,
+            "false",
+            "Store Battleground scores in the database.",
+        )
+        .key(
+            "Battleground.TrackDeserters.Enable",
+            Boolean,
+            "false",
+            "Track deserters of Battlegrounds.",
+        )
+        .key(
+            "Battleground.InvitationType",
+            Integer,
+            "0",
+            "Set Battleground invitation type.\n\
+             \x20 0 - (Normal, Invite as many players to battlegrounds as queued, Don't bother with balance)\n\
+             \x20 1 - (Experimental, Don't allow to invite much more players of one faction)\n\
+             \x20 2 - (Experimental, Try to have even teams)",
+        )
+        .key(
+            "Battleground.PrematureFinishTimer",
+            Integer,
+            "300000",
+            "Time (in milliseconds) before battleground will end prematurely if there are\n\
+             not enough players on one team. (Values defined in battleground template)",
+        )
+        .key(
+            "Battleground.PremadeGroupWaitForMatch",
+            Integer,
+            "1800000",
+            "Time (in milliseconds) a pre-made group has to wait for matching group of the other faction.",
+        )
+        .key(
+            "Battleground.GiveXPForKills",
+            Boolean,
+            "false",
+            "Give experience for honorable kills in battlegrounds, the rate can be changed in the Rate.XP.BattlegroundKill setting.",
+        )
+        .key(
+            "Battleground.Random.ResetHour",
+            Integer,
+            "6",
+            "Hour of the day when the global instance resets occur.",
+        )
+        .key("Battleground.RewardWinnerHonorFirst", Integer, "30", "")
+        .key("Battleground.RewardWinnerArenaFirst", Integer, "25", "")
+        .key("Battleground.RewardWinnerHonorLast", Integer, "15", "")
+        .key("Battleground.RewardWinnerArenaLast", Integer, "0", "")
+        .key("Battleground.RewardLoserHonorFirst", Integer, "5", "")
+        .key("Battleground.RewardLoserHonorLast", Integer, "5", "")
+        .key(
+            "Battleground.ReportAFK",
+            Integer,
+            "3",
+            "Number of reports needed to kick someone AFK from Battleground.",
+        )
+        .key("Wintergrasp.Enable", Boolean, "false", "Enable the Wintergrasp battlefield.")
+        .key(
+            "Wintergrasp.PlayerMax",
+            Integer,
+            "100",
+            " Maximum number of players allowed in Wintergrasp.",
+        )
+        .key(
+            "Wintergrasp.PlayerMin",
+            Integer,
+            "0",
+            "Minimum number of players required for Wintergrasp.",
+        )
+        .key(
+            "Wintergrasp.PlayerMinLvl",
+            Integer,
+            "77",
+            "Required character level for the Wintergrasp battle.",
+        )
+        .key(
+            "Wintergrasp.BattleTimer",
+            Integer,
+            "30",
+            "Time (in minutes) for the Wintergrasp battle to last.",
+        )
+        .key(
+            "Wintergrasp.NoBattleTimer",
+            Integer,
+            "150",
+            "Time (in minutes) between Wintergrasp battles.",
+        )
+        .key(
+            "Wintergrasp.CrashRestartTimer",
+            Integer,
+            "10",
+            "Time (in minutes) to delay the restart of Wintergrasp if the world server crashed during a running battle.",
+        )
+        .key(
+            "Arena.MaxRatingDifference",
+            Integer,
+            "150",
+            "Maximum rating difference between two teams in rated matches.",
+        )
+        .key(
+            "Arena.RatingDiscardTimer",
+            Integer,
+            "600000",
+            "Time (in milliseconds) after which rating differences are ignored when setting up matches.",
+        )
+        .key(
+            "Arena.PreviousOpponentsDiscardTimer",
+            Integer,
+            "120000",
+            "Time (in milliseconds) after which the previous opponents will be ignored. (2 minutes - Blizzlike)",
+        )
+        .key(
+            "Arena.RatedUpdateTimer",
+            Integer,
+            "5000",
+            "Time (in milliseconds) between checks for match-ups in rated arena.",
+        )
+        .key(
+            "Arena.AutoDistributePoints",
+            Boolean,
+            "false",
+            "Automatically distribute arena points.",
+        )
+        .key(
+            "Arena.AutoDistributeInterval",
+            Integer,
+            "7",
+            "Time (in days) how often arena points should be distributed if automatic distribution is enabled.",
+        )
+        .key(
+            "Arena.QueueAnnouncer.Enable",
+            Boolean,
+            "false",
+            "Announce arena queue status to chat.",
+        )
+        .key("Arena.ArenaSeason.ID", Integer, "8", "Current arena season id shown in clients.")
+        .key("Arena.ArenaSeason.InProgress", Boolean, "true", "State of current arena season.")
+        .key("Arena.ArenaStartRating", Integer, "0", "Start rating for new arena teams.")
+        .key(
+            "Arena.ArenaStartPersonalRating",
+            Integer,
+            "0",
+            "Start personal rating when joining a team.",
+        )
+        .key(
+            "Arena.ArenaStartMatchmakerRating",
+            Integer,
+            "1500",
+            "Start matchmaker rating for players.",
+        )
+        .key(
+            "Arena.ArenaWinRatingModifier1",
+            Float,
+            "48",
+            "Modifier of rating addition when winner team rating is less than 1300\n\
+             be aware that from 1000 to 1300 it gradually decreases automatically down to the half of it\n\
+             (increasing this value will give more rating)",
+        )
+        .key(
+            "Arena.ArenaWinRatingModifier2",
+            Float,
+            "24",
+            "Modifier of rating addition when winner team rating is equal or more than 1300\n\
+             (increasing this value will give more rating)",
+        )
+        .key(
+            "Arena.ArenaLoseRatingModifier",
+            Float,
+            "24",
+            "Modifier of rating subtraction for loser team (increasing this value will subtract more rating)",
+        )
+        .key("Arena.ArenaMatchmakerRatingModifier", Float, "24", "Modifier of matchmaker rating")
+        .key(
+            "ArenaLog.ExtendedInfo",
+            Boolean,
+            "false",
+            "Include extended info to ArenaLogFile for each player after rated arena\n\
+             matches (guid, name, team, IP, healing/damage done, killing blows).",
+        )
+        .key(
+            "Network.OutKBuff",
+            Integer,
+            "-1",
+            "Amount of memory (in bytes) used for the output kernel buffer (see SO_SNDBUF socket option, TCP manual).",
+        )
+        .key("Network.TcpNodelay", Boolean, "true", "TCP Nagle algorithm setting.")
+        .key(
+            "Network.CompressionThreshold",
+            Integer,
+            "100",
+            "Packets with a compressed form are compressed when larger than this amount of bytes.",
+        )
+        .section::<ProxyProtocolSection>(
+            "ProxyProtocol",
+            &[
+                (
+                    "Enabled",
+                    "Expect a PROXY protocol (v1 or v2) header from trusted proxies, to get the real client address.",
+                ),
+                (
+                    "TrustedProxies",
+                    "Networks (CIDR notation) of the proxies allowed to send a PROXY header.\n\
+                     Connections from other addresses are handled as direct client connections.",
+                ),
+                (
+                    "HeaderTimeout",
+                    "Time (in seconds) a trusted proxy has to send the PROXY header.",
+                ),
+            ],
+        )
+        .key("Console.Enable", Boolean, "true", "")
+        .key("Ra.Enable", Boolean, "false", "Remote administration")
+        .key("Ra.IP", String, "\"0.0.0.0\"", "")
+        .key("Ra.Port", Integer, "3443", "")
+        .key("Ra.MinLevel", Integer, "3", "")
+        .key("SOAP.Enabled", Boolean, "false", "")
+        .key("SOAP.IP", String, "\"127.0.0.1\"", "")
+        .key("SOAP.Port", Integer, "7878", "")
+        .key(
+            "CharDelete.Method",
+            Integer,
+            "0",
+            "Character deletion behavior. 0 = delete from DB, 1 = soft-delete, free up the name.",
+        )
+        .key(
+            "CharDelete.MinLevel",
+            Integer,
+            "0",
+            "Required level to use the unlinking method if enabled for non-heroic classes.",
+        )
+        .key(
+            "CharDelete.DeathKnight.MinLevel",
+            Integer,
+            "0",
+            "Required level to use the unlinking method if enabled for death knights.",
+        )
+        .key(
+            "CharDelete.KeepDays",
+            Integer,
+            "30",
+            "Time (in days) before unlinked characters will be removed from the database. 0 = never delete.",
+        )
+        .key(
+            "AllowTrackBothResources",
+            Boolean,
+            "false",
+            "Allows players to track herbs and minerals at the same time (if they have the skills)",
+        )
+        .key(
+            "PlayerStart.AllReputation",
+            Boolean,
+            "false",
+            "Players will start with most of the high level reputations that are needed for items, mounts etc.",
+        )
+        .key(
+            "PlayerStart.AllSpells",
+            Boolean,
+            "false",
+            "If enabled, players will start with all their class spells (not talents).\n\
+             You must populate playercreateinfo_spell_custom table with the spells you\n\
+             want, or this will not work! The table has data for all classes / races up\n\
+             to WoTLK expansion.",
+        )
+        .key(
+            "PlayerStart.MapsExplored",
+            Boolean,
+            "false",
+            "Characters start with all maps explored.",
+        )
+        .key(
+            "PlayerStart.String",
+            String,
+            "~",
+            "String to be displayed at first login of newly created characters.",
+        )
+        .key(
+            "XP.Boost.Daymask",
+            Integer,
+            "0",
+            "Enables experience boost during the defined days. This field is a bitmask.\n\
+             \x20 0 - (Disabled)\n\
+             \x20 1 - (Sunday)\n\
+             \x20 2 - (Monday)\n\
+             \x20 4 - (Tuesday)\n\
+             \x20 8 - (Wednesday)\n\
+             \x20 16 - (Thursday)\n\
+             \x20 32 - (Friday)\n\
+             \x20 64 - (Saturday)\n\
+             \x20 65 - (Weekend only)\n\
+             \x20 127 - (Always active)",
+        )
+        .key(
+            "XP.Boost.Rate",
+            Float,
+            "2.0",
+            "The boost multiplier for experience gain during XP boosted days.",
+        )
+        .key(
+            "HonorPointsAfterDuel",
+            Integer,
+            "0",
+            "Amount of honor points the duel winner will get after a duel.",
+        )
+        .key(
+            "ResetDuelCooldowns",
+            Boolean,
+            "false",
+            "Reset all cooldowns before duel starts and restore them when duel ends.",
+        )
+        .key(
+            "ResetDuelHealthMana",
+            Boolean,
+            "false",
+            "Reset health and mana before duel starts and restore them when duel ends.",
+        )
+        .key(
+            "AlwaysMaxWeaponSkill",
+            Boolean,
+            "false",
+            "Players will automatically gain max weapon/defense skill when logging in, or leveling.",
+        )
+        .key(
+            "PvPToken.Enable",
+            Boolean,
+            "false",
+            "Character will receive a token after defeating another character that yields honor.",
+        )
+        .key(
+            "PvPToken.MapAllowType",
+            Integer,
+            "4",
+            "Define where characters can receive tokens.\n\
+             \x20 4 - (All maps)\n\
+             \x20 3 - (Battlegrounds)\n\
+             \x20 2 - (FFA areas only like Gurubashi arena)\n\
+             \x20 1 - (Battlegrounds and FFA areas)",
+        )
+        .key(
+            "PvPToken.ItemID",
+            Integer,
+            "29434",
+            "Item characters will receive after defeating another character if PvP Token system is enabled.",
+        )
+        .key("PvPToken.ItemCount", Integer, "1", "Number of tokens a character will receive.")
+        .key(
+            "NoResetTalentsCost",
+            Boolean,
+            "false",
+            "\n\
+             \x20  NoResetTalentsCost\n\
+             \x20      Description: Resetting talents doesn't cost anything.\n\
+             \x20      Default:     0 - (Disabled)\n\
+             \x20                   1 - (Enabled)",
+        )
+        .key(
+            "ShowKickInWorld",
+            Boolean,
+            "false",
+            "Determines whether a message is broadcast to the entire server when a player gets kicked.",
+        )
+        .key(
+            "ShowMuteInWorld",
+            Boolean,
+            "false",
+            "Determines whether a message is broadcast to the entire server when a player gets muted.",
+        )
+        .key(
+            "ShowBanInWorld",
+            Boolean,
+            "false",
+            "Determines whether a message is broadcast to the entire server when a player gets banned.",
+        )
+        .key(
+            "LevelReq.Trade",
+            Integer,
+            "1",
+            "Level requirement for characters to be able to initiate a trade.",
+        )
+        .key(
+            "LevelReq.Ticket",
+            Integer,
+            "1",
+            "Level requirement for characters to be able to write tickets.",
+        )
+        .key(
+            "LevelReq.Auction",
+            Integer,
+            "1",
+            "Level requirement for characters to be able to use the auction house.",
+        )
+        .key(
+            "LevelReq.Mail",
+            Integer,
+            "1",
+            "Level requirement for characters to be able to send and receive mails.",
+        )
+        .key(
+            "PlayerDump.DisallowPaths",
+            Boolean,
+            "true",
+            "Disallow using paths in PlayerDump output files",
+        )
+        .key(
+            "PlayerDump.DisallowOverwrite",
+            Boolean,
+            "true",
+            "Disallow overwriting existing files with PlayerDump",
+        )
+        .key(
+            "Calculate.Creature.Zone.Area.Data",
+            Boolean,
+            "false",
+            "Calculate at loading creature zoneId / areaId and save in creature table (WARNING: SLOW WORLD SERVER STARTUP)",
+        )
+        .key(
+            "Calculate.Gameoject.Zone.Area.Data",
+            Boolean,
+            "false",
+            "Calculate at loading gameobject zoneId / areaId and save in gameobject table (WARNING: SLOW WORLD SERVER STARTUP)",
+        )
+        .key(
+            "NoGrayAggro.Above",
+            Integer,
+            "0",
+            "Gray mobs will not aggro players above/below some levels\n\
+             NoGrayAggro.Above: If player is at this level or above, gray mobs will not attack\n\
+             NoGrayAggro.Below: If player is at this level or below, gray mobs will not attack\n\
+             You can for example make players free from gray until they reach level 30.\n\
+             Then gray will start to attack them, until they reach max level (80 for example):\n\
+             NoGrayAggro.Above = 80\n\
+             NoGrayAggro.Below = 29",
+        )
+        .key("NoGrayAggro.Below", Integer, "0", "")
+        .key(
+            "PreventRenameCharacterOnCustomization",
+            Boolean,
+            "false",
+            "If option is set to true, player can not rename the character in character customization.\n\
+             Applies to all character customization commands.",
+        )
+        .key(
+            "AuctionHouseBot.Account",
+            Integer,
+            "0",
+            "Account ID for AHBot characters. If non-zero, all auctions and bids associated\n\
+             with the AHBot will randomly be assigned one of this account's characters.",
+        )
+        .key(
+            "AuctionHouseBot.Update.Interval",
+            Integer,
+            "20",
+            "Interval in seconds for AHBot to get updated",
+        )
+        .key(
+            "AuctionHouseBot.Seller.Enabled",
+            Boolean,
+            "false",
+            "General enable or disable AuctionHouseBot Seller functionality",
+        )
+        .key("AuctionHouseBot.Alliance.Items.Amount.Ratio", Integer, "100", "")
+        .key(
+            "AuctionHouseBot.Alliance.Price.Ratio",
+            Integer,
+            "100",
+            "Percentage by which the price of items sold on Alliance Auction House is incremented / decreased",
+        )
+        .key("AuctionHouseBot.Horde.Items.Amount.Ratio", Integer, "100", "")
+        .key("AuctionHouseBot.Horde.Price.Ratio", Integer, "100", "")
+        .key("AuctionHouseBot.Neutral.Items.Amount.Ratio", Integer, "100", "")
+        .key("AuctionHouseBot.Neutral.Price.Ratio", Integer, "100", "")
+        .key("AuctionHouseBot.MinTime", Integer, "1", "Minimum time for the new auction in hours")
+        .key("AuctionHouseBot.MaxTime", Integer, "72", " Maximum time for the new auction in hours")
+        .key(
+            "AuctionHouseBot.Class.Consumable.Allow.Zero",
+            Boolean,
+            "false",
+            "\n\
+             \x20  Class.CLASS.Allow.Zero = 0\n\
+             \x20     Description:\n\
+             \x20     Default:     0 - (Disabled)\n\
+             \x20                  1 - (Enabled)\n\
+             Include items without a sell or buy price.",
+        )
+        .key(
+            "AuctionHouseBot.Class.Consumable.Price.Ratio",
+            Integer,
+            "100",
+            "Percentage by which the price of items sold of each class is incremented / decreased (for all houses)",
+        )
+        .key("AuctionHouseBot.Class.Consumable.Priority", Integer, "6", "Range: 0 - 10")
+        .key(
+            "AuctionHouseBot.Class.Consumable.RandomStackRatio",
+            Integer,
+            "20",
+            "Used to determine how often a stack of the class will be single or randomly-size stacked when posted\n\
+             Value needs to be between 0 and 100, no decimal.  Anything higher than 100 will be treated as 100",
+        )
+        .key("AuctionHouseBot.Class.Container.Allow.Zero", Boolean, "false", "")
+        .key("AuctionHouseBot.Class.Container.Price.Ratio", Integer, "100", "")
+        .key("AuctionHouseBot.Class.Container.Priority", Integer, "4", "")
+        .key("AuctionHouseBot.Class.Container.ItemLevel.Min", Integer, "0", "")
+        .key("AuctionHouseBot.Class.Container.ItemLevel.Max", Integer, "0", "")
+        .key("AuctionHouseBot.Class.Container.RandomStackRatio", Integer, "0", "")
+        .key("AuctionHouseBot.Class.Weapon.Allow.Zero", Boolean, "false", "")
+        .key("AuctionHouseBot.Class.Weapon.Price.Ratio", Integer, "100", "")
+        .key("AuctionHouseBot.Class.Weapon.Priority", Integer, "8", "")
+        .key("AuctionHouseBot.Class.Weapon.RandomStackRatio", Integer, "0", "")
+        .key("AuctionHouseBot.Class.Gem.Allow.Zero", Boolean, "false", "")
+        .key("AuctionHouseBot.Class.Gem.Price.Ratio", Integer, "100", "")
+        .key("AuctionHouseBot.Class.Gem.Priority", Integer, "3", "")
+        .key("AuctionHouseBot.Class.Gem.RandomStackRatio", Integer, "20", "")
+        .key("AuctionHouseBot.Class.Armor.Allow.Zero", Boolean, "false", "")
+        .key("AuctionHouseBot.Class.Armor.Price.Ratio", Integer, "100", "")
+        .key("AuctionHouseBot.Class.Armor.Priority", Integer, "8", "")
+        .key("AuctionHouseBot.Class.Armor.RandomStackRatio", Integer, "0", "")
+        .key("AuctionHouseBot.Class.Reagent.Allow.Zero", Boolean, "false", "")
+        .key("AuctionHouseBot.Class.Reagent.Price.Ratio", Integer, "100", "")
+        .key("AuctionHouseBot.Class.Reagent.Priority", Integer, "1", "")
+        .key("AuctionHouseBot.Class.Reagent.RandomStackRatio", Integer, "100", "")
+        .key("AuctionHouseBot.Class.Projectile.Allow.Zero", Boolean, "false", "")
+        .key("AuctionHouseBot.Class.Projectile.Price.Ratio", Integer, "100", "")
+        .key("AuctionHouseBot.Class.Projectile.Priority", Integer, "2", "")
+        .key("AuctionHouseBot.Class.Projectile.RandomStackRatio", Integer, "100", "")
+        .key("AuctionHouseBot.Class.TradeGood.Allow.Zero", Boolean, "false", "")
+        .key("AuctionHouseBot.Class.TradeGood.Price.Ratio", Integer, "100", "")
+        .key("AuctionHouseBot.Class.TradeGood.Priority", Integer, "10", "")
+        .key("AuctionHouseBot.Class.TradeGood.ItemLevel.Min", Integer, "0", "")
+        .key("AuctionHouseBot.Class.TradeGood.ItemLevel.Max", Integer, "0", "")
+        .key("AuctionHouseBot.Class.TradeGood.RandomStackRatio", Integer, "50", "")
+        .key("AuctionHouseBot.Class.Recipe.Allow.Zero", Boolean, "false", "")
+        .key("AuctionHouseBot.Class.Recipe.Price.Ratio", Integer, "100", "")
+        .key("AuctionHouseBot.Class.Recipe.Priority", Integer, "6", "")
+        .key("AuctionHouseBot.Class.Recipe.RandomStackRatio", Integer, "0", "")
+        .key("AuctionHouseBot.Class.Quiver.Allow.Zero", Boolean, "false", "")
+        .key("AuctionHouseBot.Class.Quiver.Price.Ratio", Integer, "100", "")
+        .key("AuctionHouseBot.Class.Quiver.Priority", Integer, "1", "")
+        .key("AuctionHouseBot.Class.Quiver.RandomStackRatio", Integer, "0", "")
+        .key("AuctionHouseBot.Class.Quest.Allow.Zero", Boolean, "false", "")
+        .key("AuctionHouseBot.Class.Quest.Price.Ratio", Integer, "100", "")
+        .key("AuctionHouseBot.Class.Quest.Priority", Integer, "1", "")
+        .key("AuctionHouseBot.Class.Quest.RandomStackRatio", Integer, "100", "")
+        .key("AuctionHouseBot.Class.Key.Allow.Zero", Boolean, "false", "")
+        .key("AuctionHouseBot.Class.Key.Price.Ratio", Integer, "100", "")
+        .key("AuctionHouseBot.Class.Key.Priority", Integer, "1", "")
+        .key("AuctionHouseBot.Class.Key.RandomStackRatio", Integer, "100", "")
+        .key("AuctionHouseBot.Class.Misc.Allow.Zero", Boolean, "false", "")
+        .key("AuctionHouseBot.Class.Misc.Price.Ratio", Integer, "100", "")
+        .key("AuctionHouseBot.Class.Misc.Priority", Integer, "1", "")
+        .key("AuctionHouseBot.Class.Misc.Mount.ReqLevel.Min", Integer, "0", "")
+        .key("AuctionHouseBot.Class.Misc.Mount.ReqLevel.Max", Integer, "0", "")
+        .key("AuctionHouseBot.Class.Misc.Mount.ReqSkill.Min", Integer, "0", "")
+        .key("AuctionHouseBot.Class.Misc.Mount.ReqSkill.Max", Integer, "0", "")
+        .key("AuctionHouseBot.Class.Misc.RandomStackRatio", Integer, "100", "")
+        .key("AuctionHouseBot.Class.Glyph.Allow.Zero", Boolean, "false", "")
+        .key("AuctionHouseBot.Class.Glyph.Price.Ratio", Integer, "100", "")
+        .key("AuctionHouseBot.Class.Glyph.Priority", Integer, "3", "")
+        .key("AuctionHouseBot.Class.Glyph.ReqLevel.Min", Integer, "0", "")
+        .key("AuctionHouseBot.Class.Glyph.ReqLevel.Max", Integer, "0", "")
+        .key("AuctionHouseBot.Class.Glyph.ItemLevel.Min", Integer, "0", "")
+        .key("AuctionHouseBot.Class.Glyph.ItemLevel.Max", Integer, "0", "")
+        .key("AuctionHouseBot.Class.Glyph.RandomStackRatio", Integer, "0", "")
+        .key(
+            "AuctionHouseBot.Items.Vendor",
+            Boolean,
+            "false",
+            "Include items that can be bought from vendors.",
+        )
+        .key(
+            "AuctionHouseBot.Items.Loot",
+            Boolean,
+            "true",
+            "Include items that can be looted or fished for.",
+        )
+        .key("AuctionHouseBot.Items.Misc", Boolean, "false", "Include misc. items.")
+        .key(
+            "AuctionHouseBot.Items.ItemLevel.Min",
+            Integer,
+            "0",
+            "Prevent seller from listing items below/above this item level",
+        )
+        .key("AuctionHouseBot.Items.ItemLevel.Max", Integer, "0", "")
+        .key(
+            "AuctionHouseBot.Items.ReqLevel.Min",
+            Integer,
+            "0",
+            "Prevent seller from listing items below/above this required level",
+        )
+        .key("AuctionHouseBot.Items.ReqLevel.Max", Integer, "0", "")
+        .key(
+            "AuctionHouseBot.Items.ReqSkill.Min",
+            Integer,
+            "0",
+            "Prevent seller from listing items below/above this skill level",
+        )
+        .key("AuctionHouseBot.Items.ReqSkill.Max", Integer, "0", "")
+        .key("AuctionHouseBot.Items.Gray.Amount", Integer, "0", "")
+        .key(
+            "AuctionHouseBot.Items.Gray.Price.Ratio",
+            Integer,
+            "100",
+            "Percentage by which the price of items sold of each quality is incremented / decreased (for all houses)",
+        )
+        .key("AuctionHouseBot.Items.White.Amount", Integer, "2000", "")
+        .key("AuctionHouseBot.Items.White.Price.Ratio", Integer, "100", "")
+        .key("AuctionHouseBot.Items.Green.Amount", Integer, "2500", "")
+        .key("AuctionHouseBot.Items.Green.Price.Ratio", Integer, "100", "")
+        .key("AuctionHouseBot.Items.Blue.Amount", Integer, "1500", "")
+        .key("AuctionHouseBot.Items.Blue.Price.Ratio", Integer, "100", "")
+        .key("AuctionHouseBot.Items.Purple.Purple", Integer, "1000", "")
+        .key("AuctionHouseBot.Items.Purple.Price.Ratio", Integer, "100", "")
+        .key("AuctionHouseBot.Items.Orange.Amount", Integer, "0", "")
+        .key("AuctionHouseBot.Items.Orange.Price.Ratio", Integer, "100", "")
+        .key("AuctionHouseBot.Items.Yellow.Amount", Integer, "0", "")
+        .key("AuctionHouseBot.Items.Yellow.Price.Ratio", Integer, "100", "")
+        .key(
+            "AuctionHouseBot.Bind.No",
+            Boolean,
+            "true",
+            "Indicates which bonding types to allow the bot to put up for auction\n\
+             \x20 No     - Items that don't bind            Default 1 (Allowed)\n\
+             \x20 Pickup - Items that bind on pickup        Default 0 (Not Allowed)\n\
+             \x20 Equip  - Items that bind on equip         Default 1 (Allowed)\n\
+             \x20 Use    - Items that bind on use           Default 1 (Allowed)\n\
+             \x20 Quest  - Quest Items                      Default 0 (Not Allowed)",
+        )
+        .key("AuctionHouseBot.Bind.Pickup", Boolean, "false", "")
+        .key("AuctionHouseBot.Bind.Equip", Boolean, "true", "")
+        .key("AuctionHouseBot.Bind.Use", Boolean, "true", "")
+        .key("AuctionHouseBot.Bind.Quest", Boolean, "false", "")
+        .key(
+            "AuctionHouseBot.LockBox.Enabled",
+            Boolean,
+            "false",
+            "Enable or disable lockboxes in the AuctionHouse.",
+        )
+        .key(
+            "AuctionHouseBot.ItemsPerCycle.Boost",
+            Integer,
+            "1000",
+            "This value is used to fill AH faster than normal when there is more than this value on missed items (not auctioned items).\n\
+             Usually this value is only used once on server start with empty auction table.",
+        )
+        .key(
+            "AuctionHouseBot.ItemsPerCycle.Normal",
+            Integer,
+            "20",
+            "This value is used to fill AH for sold and expired items. A high value will be more resource intensive\n\
+             Usually this value is used always when auction table is already initialised.",
+        )
+        .key(
+            "AuctionHouseBot.BuyPrice.Seller",
+            Integer,
+            "0",
+            "Should the Seller use the items BuyPrice or SellPrice to determine Bid Prices\n\
+             0 - (use BuyPrice - results in higher prices)\n\
+             1 - (use SellPrice - results in lower prices)",
+        )
+        .key(
+            "AuctionHouseBot.BidPrice.Min",
+            Float,
+            "0.6",
+            "These values determine the range that the Bid Price will fall into, as a percentage of the Buy Price",
+        )
+        .key("AuctionHouseBot.BidPrice.Max", Float, "0.9", "")
+        .key(
+            "AuctionHouseBot.ForceIncludeItems",
+            String,
+            "\"\"",
+            "Include these items and ignore ALL filters. List of ids with delimiter ','",
+        )
+        .key(
+            "AuctionHouseBot.ForceExcludeItems",
+            String,
+            "\"6343,6345,6376,7192\"",
+            "Exclude these items even if they would pass the filters. List of ids with delimiter ','",
+        )
+        .key(
+            "AuctionHouseBot.Buyer.Enabled",
+            Boolean,
+            "false",
+            "General enable or disable AuctionHouseBot Buyer functionality",
+        )
+        .key(
+            "AuctionHouseBot.Buyer.Alliance.Enabled",
+            Boolean,
+            "false",
+            "\n\
+             \x20  Buyer.FACTION.Enabled\n\
+             \x20     Description: Enable or disable buyer independently by faction\n\
+             \x20     Default:     0 - (Disabled)\n\
+             \x20                  1 - (Enabled)",
+        )
+        .key("AuctionHouseBot.Buyer.Horde.Enabled", Boolean, "false", "")
+        .key("AuctionHouseBot.Buyer.Neutral.Enabled", Boolean, "false", "")
+        .key(
+            "AuctionHouseBot.Buyer.ChanceFactor",
+            Integer,
+            "2",
+            "k value in the formula used for the chance to buy an item \"100^(1 + (1 - (AuctionBid / ItemPrice)) / k)\"\n\
+             It must be a decimal number in the range of (0, +infinity). The higher the number the higher chance to buy overpriced auctions",
+        )
+        .key(
+            "AuctionHouseBot.Buyer.Baseprice.Gray",
+            Integer,
+            "3504",
+            "Base sell prices in copper for non priced items for each quality.\n\
+             The default values are based on average item prices of each quality.",
+        )
+        .key("AuctionHouseBot.Buyer.Baseprice.White", Integer, "5429", "")
+        .key("AuctionHouseBot.Buyer.Baseprice.Green", Integer, "21752", "")
+        .key("AuctionHouseBot.Buyer.Baseprice.Blue", Integer, "36463", "")
+        .key("AuctionHouseBot.Buyer.Baseprice.Purple", Integer, "87124", "")
+        .key("AuctionHouseBot.Buyer.Baseprice.Orange", Integer, "214347", "")
+        .key("AuctionHouseBot.Buyer.Baseprice.Yellow", Integer, "407406", "")
+        .key(
+            "AuctionHouseBot.Buyer.ChanceMultiplier.Gray",
+            Integer,
+            "100",
+            "Multipliers for the buy/bid chances for each quality. 100 means the chance is 100% of the original,\n\
+             1 would mean 1 % of the original and 200 would mean 200% of the original chance.",
+        )
+        .key("AuctionHouseBot.Buyer.ChanceMultiplier.White", Integer, "100", "")
+        .key("AuctionHouseBot.Buyer.ChanceMultiplier.Green", Integer, "100", "")
+        .key("AuctionHouseBot.Buyer.ChanceMultiplier.Blue", Integer, "100", "")
+        .key("AuctionHouseBot.Buyer.ChanceMultiplier.Purple", Integer, "100", "")
+        .key("AuctionHouseBot.Buyer.ChanceMultiplier.Orange", Integer, "100", "")
+        .key("AuctionHouseBot.Buyer.ChanceMultiplier.Yellow", Integer, "100", "")
+        .key(
+            "AuctionHouseBot.Buyer.Recheck.Interval",
+            Integer,
+            "20",
+            "This specifies the time interval (in minutes) between two evaluations of the same sold item.\n\
+             The smaller this value is, the more chances you give for an item to be bought by AHBot.",
+        )
+        .key(
+            "Appender",
+            Any,
+            APPENDERS,
+            "",
+        )
+        .key(
+            "Logger",
+            Any,
+            LOGGERS,
+            "",
+        )
+        .key(
+            "PacketSpoof.Policy",
+            Integer,
+            "1",
+            "Determines the course of action when packet spoofing is detected. 1 - (Log + kick), 0 - (Log only 'network'), 2 - (Log + kick + ban)",
+        )
+        .key(
+            "PacketSpoof.BanMode",
+            Integer,
+            "0",
+            "If PacketSpoof.Policy equals 2, this will determine the ban mode. 0 - Ban Account, 2 - Ban IP",
+        )
+        .key(
+            "PacketSpoof.BanDuration",
+            Integer,
+            "86400",
+            "Duration of the ban in seconds. Only valid if PacketSpoof.Policy is set to 2. Set to 0 for permanent ban.",
+        )
+        .key(
+            "AntiCheat.PendingMoveChangesTimeoutTime",
+            Integer,
+            "0",
+            "Time each client has to acknowledge a movement change until they are kicked.\n\
+             \x20Note: A lower time will give cheaters less advantage when using a very specific\n\
+             \x20      type of hack. However, the cost is that innocent but laggy players might\n\
+             \x20      sometimes get kicked. Disabled if 0.",
+        )
+        .key("Metric.Enable", Boolean, "false", "Enables statistics sent to the metric database.")
+        .key(
+            "Metric.Interval",
+            Integer,
+            "1",
+            "Interval between every batch of data sent in seconds",
+        )
+        .key(
+            "Metric.ConnectionInfo",
+            String,
+            "\"127.0.0.1;8086;worldserver\"",
+            "Connection settings for metric database (currently InfluxDB).",
+        )
+        .key(
+            "Metric.OverallStatusInterval",
+            Integer,
+            "1",
+            "Interval between every gathering of overall worldserver status data in seconds",
+        )
+        .key(
+            "LogDB.Opt.ClearInterval",
+            Integer,
+            "10",
+            "Time (in minutes) between the cleanups of the old database logs.",
+        )
+        .key(
+            "LogDB.Opt.ClearTime",
+            Integer,
+            "1209600",
+            "Age (in seconds) of the database logs removed by the cleanups.",
+        )
+        .key(
+            "Allow.IP.Based.Action.Logging",
+            Boolean,
+            "false",
+            "Log the actions of the accounts with their IP address.",
+        )
+        .key("HotSwap.Enabled", Boolean, "true", "Reload the script modules when they change.")
+        .key("HotSwap.ScriptDir", String, "'scripts'", "Directory of the script modules.")
+        .key("HotSwap.EnableReCompiler", Boolean, "true", "Recompile the changed script sources.")
+        .key(
+            "HotSwap.EnableEarlyTermination",
+            Boolean,
+            "true",
+            "Stop the running build when a source changes again.",
+        )
+        .key(
+            "HotSwap.EnableBuildFileRecreation",
+            Boolean,
+            "true",
+            "Recreate the build files of the new script modules.",
+        )
+        .key("HotSwap.EnableInstall", Boolean, "true", "Install the rebuilt script modules.")
+        .key(
+            "HotSwap.EnablePrefixCorrection",
+            Boolean,
+            "true",
+            "Correct the prefix of the script module names.",
+        )
+        .key(
+            "HotSwap.ReCompilerBuildType",
+            String,
+            "''",
+            "Build type of the recompiled script modules, the one of the server if empty.",
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config_schema::schema;
+    use std::fs;
+
+    #[test]
+    pub fn config_file_is_known() {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../../src/server/worldserver/worldserver.yaml"
+        );
+        let config = enturion_shared::config::Config::load_config(path).unwrap();
+        assert_eq!(schema().validate(&config), []);
+
+        // The described configuration is a valid one
+        let described =
+            std::env::temp_dir().join(format!("enturion-described-{}.yaml", std::process::id()));
+        fs::write(&described, schema().describe()).unwrap();
+        let config = enturion_shared::config::Config::load_config(described.to_string_lossy());
+        fs::remove_file(&described).unwrap();
+        assert_eq!(schema().validate(&config.unwrap()), []);
+    }
+}
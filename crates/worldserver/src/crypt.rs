@@ -0,0 +1,126 @@
+//! Header encryption of the world protocol.
+
+use hmac::{Hmac, Mac};
+use sha1::Sha1;
+
+/// HMAC key of the headers sent by the server.
+const SERVER_ENCRYPTION_KEY: [u8; 16] = [
+    0xCC, 0x98, 0xAE, 0x04, 0xE8, 0x97, 0xEA, 0xCA, 0x12, 0xDD, 0xC0, 0x93, 0x42, 0x91, 0x53, 0x57,
+];
+/// HMAC key of the headers sent by the client.
+const SERVER_DECRYPTION_KEY: [u8; 16] = [
+    0xC2, 0xB3, 0x72, 0x3C, 0xC6, 0xAE, 0xD9, 0xB5, 0x34, 0x3C, 0x53, 0xEE, 0x2F, 0x43, 0x67, 0xCE,
+];
+/// Length of the keystream dropped before use.
+const DROP_LENGTH: usize = 1024;
+
+/// The ARC4 stream cipher.
+struct Arc4 {
+    state: [u8; 256],
+    i: u8,
+    j: u8,
+}
+
+impl Arc4 {
+    fn new(key: &[u8]) -> Self {
+        let mut state = [0; 256];
+        for (i, value) in state.iter_mut().enumerate() {
+            *value = i as u8;
+        }
+
+        let mut j = 0_u8;
+        for i in 0..256 {
+            j = j.wrapping_add(state[i]).wrapping_add(key[i % key.len()]);
+            state.swap(i, j as usize);
+        }
+
+        Self { state, i: 0, j: 0 }
+    }
+
+    /// Encrypts or decrypts the data in place.
+    fn apply(&mut self, data: &mut [u8]) {
+        for byte in data {
+            self.i = self.i.wrapping_add(1);
+            self.j = self.j.wrapping_add(self.state[self.i as usize]);
+            self.state.swap(self.i as usize, self.j as usize);
+            let index = self.state[self.i as usize].wrapping_add(self.state[self.j as usize]);
+            *byte ^= self.state[index as usize];
+        }
+    }
+
+    /// ARC4-drop1024: ARC4 keyed with `HMAC-SHA1(seed, session key)`, without the start of the
+    /// keystream.
+    fn drop1024(seed: &[u8], session_key: &[u8]) -> Self {
+        let mut hmac = Hmac::<Sha1>::new_from_slice(seed).unwrap();
+        hmac.update(session_key);
+        let mut arc4 = Self::new(&hmac.finalize().into_bytes());
+        arc4.apply(&mut [0; DROP_LENGTH]);
+        arc4
+    }
+}
+
+/// Encrypts the headers of the packets once the client is authenticated.
+/// The payloads are sent in clear.
+pub struct AuthCrypt {
+    decrypt: Arc4,
+    encrypt: Arc4,
+}
+
+impl AuthCrypt {
+    /// The server side of the connection, keyed by the session key of the account.
+    pub fn server(session_key: &[u8]) -> Self {
+        Self {
+            decrypt: Arc4::drop1024(&SERVER_DECRYPTION_KEY, session_key),
+            encrypt: Arc4::drop1024(&SERVER_ENCRYPTION_KEY, session_key),
+        }
+    }
+
+    /// The client side of the connection.
+    #[cfg(test)]
+    pub fn client(session_key: &[u8]) -> Self {
+        Self {
+            decrypt: Arc4::drop1024(&SERVER_ENCRYPTION_KEY, session_key),
+            encrypt: Arc4::drop1024(&SERVER_DECRYPTION_KEY, session_key),
+        }
+    }
+
+    pub fn decrypt_recv(&mut self, header: &mut [u8]) {
+        self.decrypt.apply(header);
+    }
+
+    pub fn encrypt_send(&mut self, header: &mut [u8]) {
+        self.encrypt.apply(header);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::crypt::{Arc4, AuthCrypt};
+
+    #[test]
+    pub fn arc4_test_vector() {
+        let mut data = *b"Plaintext";
+        Arc4::new(b"Key").apply(&mut data);
+        assert_eq!(data, [0xBB, 0xF3, 0x16, 0xE8, 0xD9, 0x40, 0xAF, 0x0A, 0xD3]);
+    }
+
+    #[test]
+    pub fn headers_are_decrypted_by_the_other_side() {
+        let key = [0x5A_u8; 40];
+        let mut server = AuthCrypt::server(&key);
+        let mut client = AuthCrypt::client(&key);
+
+        for _ in 0..3 {
+            let mut header = [0x00, 0x0C, 0xED, 0x01, 0x00, 0x00];
+            client.encrypt_send(&mut header);
+            assert_ne!(header, [0x00, 0x0C, 0xED, 0x01, 0x00, 0x00]);
+            server.decrypt_recv(&mut header);
+            assert_eq!(header, [0x00, 0x0C, 0xED, 0x01, 0x00, 0x00]);
+
+            let mut header = [0x00, 0x03, 0xEE, 0x01];
+            server.encrypt_send(&mut header);
+            client.decrypt_recv(&mut header);
+            assert_eq!(header, [0x00, 0x03, 0xEE, 0x01]);
+        }
+    }
+}
@@ -1,43 +1,341 @@
-use enturion_shared::signals::{Signal, Signals};
+extern crate self as enturion_worldserver;
+
+mod compression;
+mod config_schema;
+mod crypt;
+pub mod opcodes;
+pub mod packet;
+mod packet_log;
+mod socket_registry;
+mod world_socket;
+
+use crate::compression::{CompressionConfig, CompressionSnapshot, COMPRESSION_STATS};
+use crate::packet_log::PacketLog;
+use crate::socket_registry::SocketRegistry;
+use crate::world_socket::WorldSocket;
+use anyhow::{Context, Result};
+use enturion_shared::config::Config;
+use enturion_shared::lifecycle::{
+    Announcement, ExitCode, Lifecycle, SignalAction, SignalActions, StopKind, StopRequest,
+};
+use enturion_shared::net::listener::{resolve_endpoints, Accepted, Listeners};
+use enturion_shared::net::proxy_protocol::ProxyProtocolConfig;
+use enturion_shared::signals::Signals;
+use enturion_shared::tick::{TickConfig, TickScheduler};
+use enturion_shared::timer::{create_timer, stop_timers};
+use enturion_shared::watchdog::{Watchdog, WatchdogConfig};
 use enturion_shared::RUNTIME;
+use log::{debug, error, info, trace};
+use socket2::SockRef;
+use std::convert::Infallible;
+use std::ffi::{c_char, CStr, CString};
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
-use tokio::runtime::Runtime;
-use tokio::time;
+use tokio::net::TcpStream;
+use tokio::runtime::{Handle, Runtime};
 
-type TickCallback = unsafe extern "C" fn();
+/// Runs an update of the C++ server, with the time elapsed (in milliseconds) since the previous one.
+type TickCallback = unsafe extern "C" fn(diff: u32);
 extern "C" {
     pub fn AbortHandler();
+    pub fn ConfigGetInstance() -> &'static Config;
     pub fn World_IsStopped() -> i32;
+    pub fn World_IsIdle() -> bool;
+    pub fn World_GetExitCode() -> u8;
+    pub fn World_ReloadConfig();
+    pub fn World_AnnounceStop(restart: bool, remaining_seconds: u32, reason: *const c_char);
+}
+
+/// Options of the accepted connections.
+#[derive(Debug, Clone, Copy)]
+struct SocketOptions {
+    tcp_nodelay: bool,
+    /// Size of the kernel send buffer, the system default if `None`.
+    send_buffer_size: Option<usize>,
+}
+
+impl SocketOptions {
+    fn from_config(config: &Config) -> Result<Self> {
+        let send_buffer_size = config.get("Network.OutKBuff", Some(-1_i64))?;
+        Ok(Self {
+            tcp_nodelay: config.get("Network.TcpNodelay", Some(true))?,
+            send_buffer_size: usize::try_from(send_buffer_size).ok(),
+        })
+    }
+
+    fn apply(&self, stream: &TcpStream) -> std::io::Result<()> {
+        let socket = SockRef::from(stream);
+        socket.set_nodelay(self.tcp_nodelay)?;
+        if let Some(size) = self.send_buffer_size {
+            socket.set_send_buffer_size(size)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Gets the addresses the world server listens on.
+/// `BindIP` can either be a single endpoint or a list of endpoints,
+/// `WorldServerPort` is used for the endpoints without an explicit port.
+async fn get_listen_addresses() -> Result<Vec<SocketAddr>> {
+    let (endpoints, port) = {
+        let config = unsafe { ConfigGetInstance() };
+        let endpoints = config.get::<Vec<String>>("BindIP", None).or_else(|_| {
+            config
+                .get("BindIP", Some("0.0.0.0".to_string()))
+                .map(|endpoint| vec![endpoint])
+        })?;
+
+        (endpoints, config.get("WorldServerPort", Some(8085_u16))?)
+    };
+
+    resolve_endpoints(&endpoints, port).await
+}
+
+/// Periodically logs the compression ratio of the packets sent since the last report.
+fn report_compression() {
+    let last_report = Mutex::new(CompressionSnapshot::default());
+    create_timer(Duration::from_secs(60), move || {
+        let snapshot = COMPRESSION_STATS.snapshot();
+        let previous = std::mem::replace(&mut *last_report.lock().unwrap(), snapshot);
+        let compressed = snapshot.since(&previous);
+        if compressed != CompressionSnapshot::default() {
+            info!(target: "network", "Last minute: {}", compressed);
+        }
+
+        async { Ok::<(), Infallible>(()) }
+    });
+}
+
+/// Resolves the real client address (if behind a trusted proxy) and runs the world socket.
+async fn handle_connection(
+    accepted: Accepted,
+    options: SocketOptions,
+    compression: CompressionConfig,
+    proxy_protocol: Arc<ProxyProtocolConfig>,
+    sockets: Arc<SocketRegistry>,
+) {
+    let Accepted {
+        mut stream,
+        peer_address,
+        local_address,
+    } = accepted;
+
+    if let Err(e) = options.apply(&stream) {
+        error!(target: "network", "Cannot set the socket options of {}: {}", peer_address, e);
+        return;
+    }
+
+    let (address, proxy_address) = match proxy_protocol.resolve(&mut stream, peer_address).await {
+        Ok(addresses) => addresses,
+        Err(e) => {
+            debug!(target: "network", "Invalid PROXY header from {}: {:#}", peer_address, e);
+            return;
+        }
+    };
+
+    match proxy_address {
+        Some(proxy) => {
+            trace!(target: "network", "Accepting incoming connection from {} on {} (via {})", address, local_address, proxy)
+        }
+        None => {
+            trace!(target: "network", "Accepting incoming connection from {} on {}", address, local_address)
+        }
+    }
+
+    let mut socket = WorldSocket::new(stream, address, compression, &sockets);
+    if let Err(e) = socket.start().await {
+        debug!(target: "network", "World socket of {} closed: {}", address, e);
+    }
 }
 
-async fn async_main(tick_callback: TickCallback) -> Result<(), Box<dyn std::error::Error>> {
-    let mut interval = time::interval(Duration::from_millis(5));
+/// Sends the countdown of a planned stop to the players, a zero countdown being a cancellation.
+fn announce_stop(announcement: &Announcement) {
+    let (kind, remaining, reason) = match announcement {
+        Announcement::Countdown {
+            kind,
+            remaining,
+            reason,
+        } => (kind, *remaining, reason.as_str()),
+        Announcement::Cancelled { kind } => (kind, 0, ""),
+    };
+
+    let reason = CString::new(reason).unwrap_or_default();
+    unsafe {
+        World_AnnounceStop(
+            *kind == StopKind::Restart,
+            remaining.try_into().unwrap_or(u32::MAX),
+            reason.as_ptr(),
+        );
+    }
+}
+
+/// A stop planned by the world, see [`WorldLifecycle_GetPlannedStop`].
+#[repr(C)]
+pub struct WorldPlannedStop {
+    pub restart: bool,
+    pub idle: bool,
+    /// Rounded up, at least 1 while the stop is planned.
+    pub remaining_seconds: u32,
+}
+
+fn whole_seconds(duration: Duration) -> u32 {
+    let seconds = duration.as_secs() + u64::from(duration.subsec_nanos() > 0);
+    seconds.try_into().unwrap_or(u32::MAX)
+}
+
+/// Plans a stop of the server, replacing any planned one: the `.server shutdown` and
+/// `.server restart` commands. An idle stop is not announced and waits for the world to have
+/// no sessions.
+///
+/// # Safety
+/// Unsafe: reason must be a valid C string.
+#[no_mangle]
+pub unsafe extern "C" fn WorldLifecycle_PlanStop(
+    restart: bool,
+    delay_seconds: u32,
+    reason: *const c_char,
+    exit_code: u8,
+    idle: bool,
+) {
+    let kind = if restart {
+        StopKind::Restart
+    } else {
+        StopKind::Shutdown
+    };
+    Lifecycle::global().plan(StopRequest {
+        reason: CStr::from_ptr(reason).to_string_lossy().into_owned(),
+        exit_code: Some(ExitCode::from(exit_code)),
+        idle,
+        ..StopRequest::new(kind, Duration::from_secs(delay_seconds.into()))
+    });
+}
+
+/// Cancels the planned stop, returning the seconds it had left, 0 if there was none.
+#[no_mangle]
+pub extern "C" fn WorldLifecycle_CancelStop() -> u32 {
+    Lifecycle::global()
+        .cancel()
+        .map_or(0, |remaining| whole_seconds(remaining).max(1))
+}
+
+/// Gets the planned stop, returns false if there is none.
+///
+/// # Safety
+/// Unsafe: planned must be a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn WorldLifecycle_GetPlannedStop(planned: *mut WorldPlannedStop) -> bool {
+    let Some(stop) = Lifecycle::global().planned() else {
+        return false;
+    };
+
+    *planned = WorldPlannedStop {
+        restart: stop.kind == StopKind::Restart,
+        idle: stop.idle,
+        remaining_seconds: whole_seconds(stop.remaining).max(1),
+    };
+    true
+}
+
+async fn async_main(tick_callback: TickCallback) -> Result<ExitCode> {
+    let schema = config_schema::schema();
+    schema.report(unsafe { ConfigGetInstance() }, "server::loading");
+    // The configuration is reloaded by the world, check it again when it changes
+    unsafe { ConfigGetInstance() }.subscribe("*", move |config, _| {
+        schema.report(config, "server::loading");
+    });
+    let options = SocketOptions::from_config(unsafe { ConfigGetInstance() })?;
+    let compression = CompressionConfig::from_config(unsafe { ConfigGetInstance() })?;
+    let proxy_protocol = Arc::new(ProxyProtocolConfig::from_config(unsafe {
+        ConfigGetInstance()
+    })?);
+    PacketLog::global().configure(unsafe { ConfigGetInstance() })?;
+    unsafe { ConfigGetInstance() }.subscribe("PacketLog*", |config, _| {
+        if let Err(e) = PacketLog::global().configure(config) {
+            error!(target: "server::worldserver", "Cannot apply the packet log settings: {:#}", e);
+        }
+    });
+    let sockets = SocketRegistry::new();
+    let mut listeners =
+        async { Listeners::bind(&get_listen_addresses().await?, "server::worldserver") }
+            .await
+            .context("Failed to initialize network")?;
+    report_compression();
+
+    let tick_config =
+        TickConfig::from_config(unsafe { ConfigGetInstance() }, "MinWorldUpdateTime", 1)?;
+    let mut scheduler = TickScheduler::new(tick_config, "server::worldserver");
+    let watchdog = Watchdog::start(
+        WatchdogConfig::from_config(unsafe { ConfigGetInstance() })?,
+        scheduler.heartbeat(),
+        Handle::current(),
+        "server::worldserver",
+        || unsafe { AbortHandler() },
+    )?;
+    let signal_actions = SignalActions::from_config(unsafe { ConfigGetInstance() })?;
     let mut signals = Signals::default();
+    let lifecycle = Lifecycle::global();
+    lifecycle.on_announcement(announce_stop);
 
-    loop {
+    let exit_code = loop {
         tokio::select! {
+            Some(accepted) = listeners.accept() => {
+                tokio::spawn(handle_connection(
+                    accepted,
+                    options,
+                    compression,
+                    proxy_protocol.clone(),
+                    sockets.clone(),
+                ));
+            },
             signal = signals.as_mut() => {
-                match signal {
-                    Some(Signal::Abort) => {
+                match signal.map(|signal| signal_actions.action(signal)) {
+                    Some(SignalAction::Abort) => {
                         unsafe { AbortHandler(); }
                     },
-                    _ => {
-                        break;
+                    Some(SignalAction::Reload) => {
+                        unsafe { World_ReloadConfig(); }
                     },
+                    Some(SignalAction::Dump) => scheduler.log_diagnostics(),
+                    Some(SignalAction::Shutdown(delay)) => lifecycle.shutdown(delay, ""),
+                    Some(SignalAction::Restart(delay)) => lifecycle.restart(delay),
+                    Some(SignalAction::Ignore) => {},
+                    None => lifecycle.stop_now(ExitCode::Shutdown),
                 }
             },
-            _ = interval.tick() => {
-                unsafe { tick_callback(); }
+            exit_code = lifecycle.stopped() => {
+                break exit_code;
+            },
+            diff = scheduler.tick() => {
+                scheduler.run(diff, || unsafe { tick_callback(diff.as_millis() as u32) });
                 ::log::logger().flush();
 
+                // The world can also be stopped by its own shutdown commands
                 if unsafe { World_IsStopped() } > 0 {
-                    break;
+                    lifecycle.stop_now(ExitCode::from(unsafe { World_GetExitCode() }));
                 }
+                lifecycle.update_when_idle(|| unsafe { World_IsIdle() });
             }
         }
+    };
+
+    // The sockets call into the world, they have to be gone before it is stopped
+    drop(listeners);
+    sockets.close_all();
+    if !sockets.wait_until_empty(Duration::from_secs(5)).await {
+        error!(
+            target: "server::worldserver",
+            "{} world sockets could not be closed",
+            sockets.len()
+        );
     }
 
-    Ok(())
+    if let Some(watchdog) = watchdog {
+        watchdog.stop();
+    }
+    stop_timers().await;
+    Ok(exit_code)
 }
 
 #[no_mangle]
@@ -46,12 +344,27 @@ pub extern "C" fn WorldServerRsInit() {
     let _ = RUNTIME.set(Runtime::new().unwrap());
 }
 
+/// Prints the known configuration keys with their default value and description, as a
+/// configuration file.
+#[no_mangle]
+pub extern "C" fn WorldServerRsPrintConfig() {
+    print!("{}", config_schema::schema().describe());
+}
+
+/// Runs the world server until it is stopped.
+/// Returns the exit code of the process, the error one if the network could not be started.
 #[no_mangle]
-pub extern "C" fn WorldServerRsMain(tick_callback: TickCallback) {
+pub extern "C" fn WorldServerRsMain(tick_callback: TickCallback) -> u8 {
     let rt = RUNTIME.get().unwrap();
     let main_handle = rt.spawn(async move {
-        async_main(tick_callback).await.unwrap();
+        let result = async_main(tick_callback).await;
+        if let Err(e) = &result {
+            error!(target: "server::worldserver", "{:#}", e);
+            ::log::logger().flush();
+        }
+
+        result.unwrap_or(ExitCode::Error)
     });
 
-    let _ = rt.block_on(main_handle);
+    rt.block_on(main_handle).unwrap_or(ExitCode::Error) as u8
 }
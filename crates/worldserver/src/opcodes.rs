@@ -0,0 +1,1595 @@
+//! The opcodes of the world protocol: who sends them, and in which session state and on which
+//! thread the client packets are handled. Mirrors the opcode table of `Opcodes.cpp`.
+
+use std::fmt::{Display, Formatter};
+
+/// Who sends the packets of an opcode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Client,
+    Server,
+    Both,
+}
+
+/// The state a session must be in for a client packet to be handled, `SessionStatus` in C++.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionStatus {
+    /// Authenticated, no player in game.
+    Authed,
+    LoggedIn,
+    /// The player is transferring to another map.
+    Transfer,
+    LoggedInOrRecentlyLogout,
+    /// Not accepted from the client: deprecated, server side only or handled by the socket.
+    Never,
+    Unhandled,
+}
+
+/// The current state of the session of a socket, checked against the `SessionStatus` of the
+/// client packets. C++ sets it with `WorldSocketControl_SetSessionState`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum SessionState {
+    /// `CMSG_AUTH_SESSION` has not been handled yet.
+    Unauthenticated = 0,
+    /// Authenticated, no player in game.
+    Authed = 1,
+    /// The player is in world.
+    LoggedIn = 2,
+    /// The player is not in world: loading, or transferring to another map.
+    Transfer = 3,
+    /// The player logged out, until the character list is requested again.
+    RecentlyLoggedOut = 4,
+}
+
+impl TryFrom<u8> for SessionState {
+    type Error = u8;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Ok(match value {
+            0 => Self::Unauthenticated,
+            1 => Self::Authed,
+            2 => Self::LoggedIn,
+            3 => Self::Transfer,
+            4 => Self::RecentlyLoggedOut,
+            _ => return Err(value),
+        })
+    }
+}
+
+/// Where a client packet is handled, `PacketProcessing` in C++.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PacketProcessing {
+    /// As soon as it is received.
+    InPlace,
+    /// In `World::UpdateSessions`.
+    ThreadUnsafe,
+    /// In `Map::Update`.
+    ThreadSafe,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OpcodeInfo {
+    pub opcode: Opcode,
+    pub name: &'static str,
+    pub direction: Direction,
+    pub status: SessionStatus,
+    pub processing: PacketProcessing,
+}
+
+/// What the socket does with a client packet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Admission {
+    Handle,
+    /// Dropped, the session would not handle it.
+    Ignore,
+    /// The client is misbehaving, the connection is closed.
+    Close,
+}
+
+impl OpcodeInfo {
+    /// Whether a packet received on a socket is handled in the current state of its session.
+    /// The packets handled by the socket itself have the `Never` status. Like
+    /// `WorldSession::Update`, `LoggedIn` packets received before the player is in world are
+    /// handled, C++ queues them until it is.
+    pub fn admission(&self, state: SessionState) -> Admission {
+        match (self.opcode, state) {
+            (Opcode::CMSG_AUTH_SESSION, SessionState::Unauthenticated) => Admission::Handle,
+            (_, SessionState::Unauthenticated) | (Opcode::CMSG_AUTH_SESSION, _) => Admission::Close,
+            (Opcode::CMSG_PING | Opcode::CMSG_KEEP_ALIVE, _) => Admission::Handle,
+            _ => match (self.status, state) {
+                (SessionStatus::Authed, _)
+                | (SessionStatus::LoggedIn, SessionState::Authed | SessionState::LoggedIn)
+                | (SessionStatus::Transfer, SessionState::Transfer)
+                | (
+                    SessionStatus::LoggedInOrRecentlyLogout,
+                    SessionState::LoggedIn
+                    | SessionState::Transfer
+                    | SessionState::RecentlyLoggedOut,
+                ) => Admission::Handle,
+                _ => Admission::Ignore,
+            },
+        }
+    }
+}
+
+macro_rules! opcodes {
+    ($($value:literal $name:ident $direction:ident $status:ident $processing:ident,)*) => {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        #[repr(u16)]
+        #[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+        pub enum Opcode {
+            $($name = $value,)*
+        }
+
+        /// The opcodes, by value.
+        pub static OPCODES: &[OpcodeInfo] = &[
+            $(OpcodeInfo {
+                opcode: Opcode::$name,
+                name: stringify!($name),
+                direction: Direction::$direction,
+                status: SessionStatus::$status,
+                processing: PacketProcessing::$processing,
+            },)*
+        ];
+    };
+}
+
+impl Opcode {
+    pub fn info(self) -> &'static OpcodeInfo {
+        let index = OPCODES.binary_search_by_key(&(self as u16), |info| info.opcode as u16);
+        &OPCODES[index.unwrap()]
+    }
+
+    pub fn name(self) -> &'static str {
+        self.info().name
+    }
+}
+
+impl TryFrom<u32> for Opcode {
+    type Error = u32;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        OPCODES
+            .binary_search_by_key(&value, |info| info.opcode as u32)
+            .map(|index| OPCODES[index].opcode)
+            .map_err(|_| value)
+    }
+}
+
+/// Formats like `GetOpcodeNameForLogging`: `[CMSG_PING 0x01DC (476)]`.
+impl Display for Opcode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "[{} {:#06X} ({})]",
+            self.name(),
+            *self as u16,
+            *self as u16
+        )
+    }
+}
+
+/// Formats any opcode value like `GetOpcodeNameForLogging`.
+pub fn opcode_name_for_logging(opcode: u32) -> String {
+    match Opcode::try_from(opcode) {
+        Ok(opcode) => opcode.to_string(),
+        Err(opcode) => format!("[UNKNOWN OPCODE {:#06X} ({})]", opcode, opcode),
+    }
+}
+
+#[rustfmt::skip]
+opcodes! {
+    0x001 CMSG_BOOTME                                           Client Never                    InPlace,
+    0x002 CMSG_DBLOOKUP                                         Client Never                    InPlace,
+    0x003 SMSG_DBLOOKUP                                         Server Never                    InPlace,
+    0x004 CMSG_QUERY_OBJECT_POSITION                            Client Never                    InPlace,
+    0x005 SMSG_QUERY_OBJECT_POSITION                            Server Never                    InPlace,
+    0x006 CMSG_QUERY_OBJECT_ROTATION                            Client Never                    InPlace,
+    0x007 SMSG_QUERY_OBJECT_ROTATION                            Server Never                    InPlace,
+    0x008 CMSG_WORLD_TELEPORT                                   Client LoggedIn                 ThreadUnsafe,
+    0x009 CMSG_TELEPORT_TO_UNIT                                 Client LoggedIn                 InPlace,
+    0x00A CMSG_ZONE_MAP                                         Client Never                    InPlace,
+    0x00B SMSG_ZONE_MAP                                         Server Never                    InPlace,
+    0x00C CMSG_DEBUG_CHANGECELLZONE                             Client Never                    InPlace,
+    0x00D CMSG_MOVE_CHARACTER_CHEAT                             Client Never                    InPlace,
+    0x00E SMSG_MOVE_CHARACTER_CHEAT                             Server Never                    InPlace,
+    0x00F CMSG_RECHARGE                                         Client Never                    InPlace,
+    0x010 CMSG_LEARN_SPELL                                      Client Never                    InPlace,
+    0x011 CMSG_CREATEMONSTER                                    Client Never                    InPlace,
+    0x012 CMSG_DESTROYMONSTER                                   Client Never                    InPlace,
+    0x013 CMSG_CREATEITEM                                       Client Never                    InPlace,
+    0x014 CMSG_CREATEGAMEOBJECT                                 Client Never                    InPlace,
+    0x015 SMSG_CHECK_FOR_BOTS                                   Server Never                    InPlace,
+    0x016 CMSG_MAKEMONSTERATTACKGUID                            Client Never                    InPlace,
+    0x017 CMSG_BOT_DETECTED2                                    Client Never                    InPlace,
+    0x018 CMSG_FORCEACTION                                      Client Never                    InPlace,
+    0x019 CMSG_FORCEACTIONONOTHER                               Client Never                    InPlace,
+    0x01A CMSG_FORCEACTIONSHOW                                  Client Never                    InPlace,
+    0x01B SMSG_FORCEACTIONSHOW                                  Server Never                    InPlace,
+    0x01C CMSG_PETGODMODE                                       Client Never                    InPlace,
+    0x01D SMSG_PETGODMODE                                       Server Never                    InPlace,
+    0x01E SMSG_REFER_A_FRIEND_EXPIRED                           Server Never                    InPlace,
+    0x01F CMSG_WEATHER_SPEED_CHEAT                              Client Never                    InPlace,
+    0x020 CMSG_UNDRESSPLAYER                                    Client Never                    InPlace,
+    0x021 CMSG_BEASTMASTER                                      Client Never                    InPlace,
+    0x022 CMSG_GODMODE                                          Client Never                    InPlace,
+    0x023 SMSG_GODMODE                                          Server Never                    InPlace,
+    0x024 CMSG_CHEAT_SETMONEY                                   Client Never                    InPlace,
+    0x025 CMSG_LEVEL_CHEAT                                      Client Never                    InPlace,
+    0x026 CMSG_PET_LEVEL_CHEAT                                  Client Never                    InPlace,
+    0x027 CMSG_SET_WORLDSTATE                                   Client Never                    InPlace,
+    0x028 CMSG_COOLDOWN_CHEAT                                   Client Never                    InPlace,
+    0x029 CMSG_USE_SKILL_CHEAT                                  Client Never                    InPlace,
+    0x02A CMSG_FLAG_QUEST                                       Client Never                    InPlace,
+    0x02B CMSG_FLAG_QUEST_FINISH                                Client Never                    InPlace,
+    0x02C CMSG_CLEAR_QUEST                                      Client Never                    InPlace,
+    0x02D CMSG_SEND_EVENT                                       Client Never                    InPlace,
+    0x02E CMSG_DEBUG_AISTATE                                    Client Never                    InPlace,
+    0x02F SMSG_DEBUG_AISTATE                                    Server Never                    InPlace,
+    0x030 CMSG_DISABLE_PVP_CHEAT                                Client Never                    InPlace,
+    0x031 CMSG_ADVANCE_SPAWN_TIME                               Client Never                    InPlace,
+    0x032 SMSG_DESTRUCTIBLE_BUILDING_DAMAGE                     Server Never                    InPlace,
+    0x033 CMSG_AUTH_SRP6_BEGIN                                  Client Never                    InPlace,
+    0x034 CMSG_AUTH_SRP6_PROOF                                  Client Never                    InPlace,
+    0x035 CMSG_AUTH_SRP6_RECODE                                 Client Never                    InPlace,
+    0x036 CMSG_CHAR_CREATE                                      Client Authed                   ThreadUnsafe,
+    0x037 CMSG_CHAR_ENUM                                        Client Authed                   ThreadUnsafe,
+    0x038 CMSG_CHAR_DELETE                                      Client Authed                   ThreadUnsafe,
+    0x039 SMSG_AUTH_SRP6_RESPONSE                               Server Never                    InPlace,
+    0x03A SMSG_CHAR_CREATE                                      Server Never                    InPlace,
+    0x03B SMSG_CHAR_ENUM                                        Server Never                    InPlace,
+    0x03C SMSG_CHAR_DELETE                                      Server Never                    InPlace,
+    0x03D CMSG_PLAYER_LOGIN                                     Client Authed                   ThreadUnsafe,
+    0x03E SMSG_NEW_WORLD                                        Server Never                    InPlace,
+    0x03F SMSG_TRANSFER_PENDING                                 Server Never                    InPlace,
+    0x040 SMSG_TRANSFER_ABORTED                                 Server Never                    InPlace,
+    0x041 SMSG_CHARACTER_LOGIN_FAILED                           Server Never                    InPlace,
+    0x042 SMSG_LOGIN_SET_TIME_SPEED                             Server Never                    InPlace,
+    0x043 SMSG_GAMETIME_UPDATE                                  Server Never                    InPlace,
+    0x044 CMSG_GAMETIME_SET                                     Client Never                    InPlace,
+    0x045 SMSG_GAMETIME_SET                                     Server Never                    InPlace,
+    0x046 CMSG_GAMESPEED_SET                                    Client Never                    InPlace,
+    0x047 SMSG_GAMESPEED_SET                                    Server Never                    InPlace,
+    0x048 CMSG_SERVERTIME                                       Client Never                    InPlace,
+    0x049 SMSG_SERVERTIME                                       Server Never                    InPlace,
+    0x04A CMSG_PLAYER_LOGOUT                                    Client LoggedIn                 ThreadUnsafe,
+    0x04B CMSG_LOGOUT_REQUEST                                   Client LoggedIn                 ThreadUnsafe,
+    0x04C SMSG_LOGOUT_RESPONSE                                  Server Never                    InPlace,
+    0x04D SMSG_LOGOUT_COMPLETE                                  Server Never                    InPlace,
+    0x04E CMSG_LOGOUT_CANCEL                                    Client LoggedInOrRecentlyLogout ThreadUnsafe,
+    0x04F SMSG_LOGOUT_CANCEL_ACK                                Server Never                    InPlace,
+    0x050 CMSG_NAME_QUERY                                       Client LoggedIn                 InPlace,
+    0x051 SMSG_NAME_QUERY_RESPONSE                              Server Never                    InPlace,
+    0x052 CMSG_PET_NAME_QUERY                                   Client LoggedIn                 InPlace,
+    0x053 SMSG_PET_NAME_QUERY_RESPONSE                          Server Never                    InPlace,
+    0x054 CMSG_GUILD_QUERY                                      Client Authed                   ThreadUnsafe,
+    0x055 SMSG_GUILD_QUERY_RESPONSE                             Server Never                    InPlace,
+    0x056 CMSG_ITEM_QUERY_SINGLE                                Client LoggedIn                 InPlace,
+    0x057 CMSG_ITEM_QUERY_MULTIPLE                              Client Never                    InPlace,
+    0x058 SMSG_ITEM_QUERY_SINGLE_RESPONSE                       Server Never                    InPlace,
+    0x059 SMSG_ITEM_QUERY_MULTIPLE_RESPONSE                     Server Never                    InPlace,
+    0x05A CMSG_PAGE_TEXT_QUERY                                  Client LoggedIn                 InPlace,
+    0x05B SMSG_PAGE_TEXT_QUERY_RESPONSE                         Server Never                    InPlace,
+    0x05C CMSG_QUEST_QUERY                                      Client LoggedIn                 InPlace,
+    0x05D SMSG_QUEST_QUERY_RESPONSE                             Server Never                    InPlace,
+    0x05E CMSG_GAMEOBJECT_QUERY                                 Client LoggedIn                 InPlace,
+    0x05F SMSG_GAMEOBJECT_QUERY_RESPONSE                        Server Never                    InPlace,
+    0x060 CMSG_CREATURE_QUERY                                   Client LoggedIn                 InPlace,
+    0x061 SMSG_CREATURE_QUERY_RESPONSE                          Server Never                    InPlace,
+    0x062 CMSG_WHO                                              Client LoggedIn                 ThreadSafe,
+    0x063 SMSG_WHO                                              Server Never                    InPlace,
+    0x064 CMSG_WHOIS                                            Client LoggedIn                 ThreadUnsafe,
+    0x065 SMSG_WHOIS                                            Server Never                    InPlace,
+    0x066 CMSG_CONTACT_LIST                                     Client LoggedIn                 ThreadUnsafe,
+    0x067 SMSG_CONTACT_LIST                                     Server Never                    InPlace,
+    0x068 SMSG_FRIEND_STATUS                                    Server Never                    InPlace,
+    0x069 CMSG_ADD_FRIEND                                       Client LoggedIn                 ThreadUnsafe,
+    0x06A CMSG_DEL_FRIEND                                       Client LoggedIn                 ThreadUnsafe,
+    0x06B CMSG_SET_CONTACT_NOTES                                Client LoggedIn                 ThreadUnsafe,
+    0x06C CMSG_ADD_IGNORE                                       Client LoggedIn                 ThreadUnsafe,
+    0x06D CMSG_DEL_IGNORE                                       Client LoggedIn                 ThreadUnsafe,
+    0x06E CMSG_GROUP_INVITE                                     Client LoggedIn                 ThreadUnsafe,
+    0x06F SMSG_GROUP_INVITE                                     Server Never                    InPlace,
+    0x070 CMSG_GROUP_CANCEL                                     Client LoggedIn                 InPlace,
+    0x071 SMSG_GROUP_CANCEL                                     Server Never                    InPlace,
+    0x072 CMSG_GROUP_ACCEPT                                     Client LoggedIn                 ThreadUnsafe,
+    0x073 CMSG_GROUP_DECLINE                                    Client LoggedIn                 ThreadUnsafe,
+    0x074 SMSG_GROUP_DECLINE                                    Server Never                    InPlace,
+    0x075 CMSG_GROUP_UNINVITE                                   Client LoggedIn                 ThreadUnsafe,
+    0x076 CMSG_GROUP_UNINVITE_GUID                              Client LoggedIn                 ThreadUnsafe,
+    0x077 SMSG_GROUP_UNINVITE                                   Server Never                    InPlace,
+    0x078 CMSG_GROUP_SET_LEADER                                 Client LoggedIn                 ThreadUnsafe,
+    0x079 SMSG_GROUP_SET_LEADER                                 Server Never                    InPlace,
+    0x07A CMSG_LOOT_METHOD                                      Client LoggedIn                 ThreadUnsafe,
+    0x07B CMSG_GROUP_DISBAND                                    Client LoggedIn                 ThreadUnsafe,
+    0x07C SMSG_GROUP_DESTROYED                                  Server Never                    InPlace,
+    0x07D SMSG_GROUP_LIST                                       Server Never                    InPlace,
+    0x07E SMSG_PARTY_MEMBER_STATS                               Server Never                    InPlace,
+    0x07F SMSG_PARTY_COMMAND_RESULT                             Server Never                    InPlace,
+    0x080 UMSG_UPDATE_GROUP_MEMBERS                             Both   Never                    InPlace,
+    0x081 CMSG_GUILD_CREATE                                     Client LoggedIn                 ThreadUnsafe,
+    0x082 CMSG_GUILD_INVITE                                     Client LoggedIn                 ThreadUnsafe,
+    0x083 SMSG_GUILD_INVITE                                     Server Never                    InPlace,
+    0x084 CMSG_GUILD_ACCEPT                                     Client LoggedIn                 ThreadUnsafe,
+    0x085 CMSG_GUILD_DECLINE                                    Client LoggedIn                 ThreadUnsafe,
+    0x086 SMSG_GUILD_DECLINE                                    Server Never                    InPlace,
+    0x087 CMSG_GUILD_INFO                                       Client LoggedIn                 ThreadUnsafe,
+    0x088 SMSG_GUILD_INFO                                       Server Never                    InPlace,
+    0x089 CMSG_GUILD_ROSTER                                     Client LoggedIn                 ThreadUnsafe,
+    0x08A SMSG_GUILD_ROSTER                                     Server Never                    InPlace,
+    0x08B CMSG_GUILD_PROMOTE                                    Client LoggedIn                 ThreadUnsafe,
+    0x08C CMSG_GUILD_DEMOTE                                     Client LoggedIn                 ThreadUnsafe,
+    0x08D CMSG_GUILD_LEAVE                                      Client LoggedIn                 ThreadUnsafe,
+    0x08E CMSG_GUILD_REMOVE                                     Client LoggedIn                 ThreadUnsafe,
+    0x08F CMSG_GUILD_DISBAND                                    Client LoggedIn                 ThreadUnsafe,
+    0x090 CMSG_GUILD_LEADER                                     Client LoggedIn                 ThreadUnsafe,
+    0x091 CMSG_GUILD_MOTD                                       Client LoggedIn                 ThreadUnsafe,
+    0x092 SMSG_GUILD_EVENT                                      Server Never                    InPlace,
+    0x093 SMSG_GUILD_COMMAND_RESULT                             Server Never                    InPlace,
+    0x094 UMSG_UPDATE_GUILD                                     Both   Never                    InPlace,
+    0x095 CMSG_MESSAGECHAT                                      Client LoggedIn                 ThreadUnsafe,
+    0x096 SMSG_MESSAGECHAT                                      Server Never                    InPlace,
+    0x097 CMSG_JOIN_CHANNEL                                     Client LoggedIn                 ThreadUnsafe,
+    0x098 CMSG_LEAVE_CHANNEL                                    Client LoggedIn                 ThreadUnsafe,
+    0x099 SMSG_CHANNEL_NOTIFY                                   Server Never                    InPlace,
+    0x09A CMSG_CHANNEL_LIST                                     Client LoggedIn                 ThreadUnsafe,
+    0x09B SMSG_CHANNEL_LIST                                     Server Never                    InPlace,
+    0x09C CMSG_CHANNEL_PASSWORD                                 Client LoggedIn                 ThreadUnsafe,
+    0x09D CMSG_CHANNEL_SET_OWNER                                Client LoggedIn                 ThreadUnsafe,
+    0x09E CMSG_CHANNEL_OWNER                                    Client LoggedIn                 ThreadUnsafe,
+    0x09F CMSG_CHANNEL_MODERATOR                                Client LoggedIn                 ThreadUnsafe,
+    0x0A0 CMSG_CHANNEL_UNMODERATOR                              Client LoggedIn                 ThreadUnsafe,
+    0x0A1 CMSG_CHANNEL_MUTE                                     Client LoggedIn                 ThreadUnsafe,
+    0x0A2 CMSG_CHANNEL_UNMUTE                                   Client LoggedIn                 ThreadUnsafe,
+    0x0A3 CMSG_CHANNEL_INVITE                                   Client LoggedIn                 ThreadUnsafe,
+    0x0A4 CMSG_CHANNEL_KICK                                     Client LoggedIn                 ThreadUnsafe,
+    0x0A5 CMSG_CHANNEL_BAN                                      Client LoggedIn                 ThreadUnsafe,
+    0x0A6 CMSG_CHANNEL_UNBAN                                    Client LoggedIn                 ThreadUnsafe,
+    0x0A7 CMSG_CHANNEL_ANNOUNCEMENTS                            Client LoggedIn                 ThreadUnsafe,
+    0x0A8 CMSG_CHANNEL_MODERATE                                 Client LoggedIn                 ThreadUnsafe,
+    0x0A9 SMSG_UPDATE_OBJECT                                    Server Never                    InPlace,
+    0x0AA SMSG_DESTROY_OBJECT                                   Server Never                    InPlace,
+    0x0AB CMSG_USE_ITEM                                         Client LoggedIn                 InPlace,
+    0x0AC CMSG_OPEN_ITEM                                        Client LoggedIn                 InPlace,
+    0x0AD CMSG_READ_ITEM                                        Client LoggedIn                 InPlace,
+    0x0AE SMSG_READ_ITEM_OK                                     Server Never                    InPlace,
+    0x0AF SMSG_READ_ITEM_FAILED                                 Server Never                    InPlace,
+    0x0B0 SMSG_ITEM_COOLDOWN                                    Server Never                    InPlace,
+    0x0B1 CMSG_GAMEOBJ_USE                                      Client LoggedIn                 InPlace,
+    0x0B2 CMSG_DESTROY_ITEMS                                    Client Never                    InPlace,
+    0x0B3 SMSG_GAMEOBJECT_CUSTOM_ANIM                           Server Never                    InPlace,
+    0x0B4 CMSG_AREATRIGGER                                      Client LoggedIn                 InPlace,
+    0x0B5 MSG_MOVE_START_FORWARD                                Both   LoggedIn                 ThreadSafe,
+    0x0B6 MSG_MOVE_START_BACKWARD                               Both   LoggedIn                 ThreadSafe,
+    0x0B7 MSG_MOVE_STOP                                         Both   LoggedIn                 ThreadSafe,
+    0x0B8 MSG_MOVE_START_STRAFE_LEFT                            Both   LoggedIn                 ThreadSafe,
+    0x0B9 MSG_MOVE_START_STRAFE_RIGHT                           Both   LoggedIn                 ThreadSafe,
+    0x0BA MSG_MOVE_STOP_STRAFE                                  Both   LoggedIn                 ThreadSafe,
+    0x0BB MSG_MOVE_JUMP                                         Both   LoggedIn                 ThreadSafe,
+    0x0BC MSG_MOVE_START_TURN_LEFT                              Both   LoggedIn                 ThreadSafe,
+    0x0BD MSG_MOVE_START_TURN_RIGHT                             Both   LoggedIn                 ThreadSafe,
+    0x0BE MSG_MOVE_STOP_TURN                                    Both   LoggedIn                 ThreadSafe,
+    0x0BF MSG_MOVE_START_PITCH_UP                               Both   LoggedIn                 ThreadSafe,
+    0x0C0 MSG_MOVE_START_PITCH_DOWN                             Both   LoggedIn                 ThreadSafe,
+    0x0C1 MSG_MOVE_STOP_PITCH                                   Both   LoggedIn                 ThreadSafe,
+    0x0C2 MSG_MOVE_SET_RUN_MODE                                 Both   LoggedIn                 ThreadSafe,
+    0x0C3 MSG_MOVE_SET_WALK_MODE                                Both   LoggedIn                 ThreadSafe,
+    0x0C4 MSG_MOVE_TOGGLE_LOGGING                               Both   Never                    InPlace,
+    0x0C5 MSG_MOVE_TELEPORT                                     Both   Never                    InPlace,
+    0x0C6 MSG_MOVE_TELEPORT_CHEAT                               Both   Never                    InPlace,
+    0x0C7 MSG_MOVE_TELEPORT_ACK                                 Both   LoggedIn                 ThreadSafe,
+    0x0C8 MSG_MOVE_TOGGLE_FALL_LOGGING                          Both   Never                    InPlace,
+    0x0C9 MSG_MOVE_FALL_LAND                                    Both   LoggedIn                 ThreadSafe,
+    0x0CA MSG_MOVE_START_SWIM                                   Both   LoggedIn                 ThreadSafe,
+    0x0CB MSG_MOVE_STOP_SWIM                                    Both   LoggedIn                 ThreadSafe,
+    0x0CC MSG_MOVE_SET_RUN_SPEED_CHEAT                          Both   Never                    InPlace,
+    0x0CD MSG_MOVE_SET_RUN_SPEED                                Both   Never                    InPlace,
+    0x0CE MSG_MOVE_SET_RUN_BACK_SPEED_CHEAT                     Both   Never                    InPlace,
+    0x0CF MSG_MOVE_SET_RUN_BACK_SPEED                           Both   Never                    InPlace,
+    0x0D0 MSG_MOVE_SET_WALK_SPEED_CHEAT                         Both   Never                    InPlace,
+    0x0D1 MSG_MOVE_SET_WALK_SPEED                               Both   Never                    InPlace,
+    0x0D2 MSG_MOVE_SET_SWIM_SPEED_CHEAT                         Both   Never                    InPlace,
+    0x0D3 MSG_MOVE_SET_SWIM_SPEED                               Both   Never                    InPlace,
+    0x0D4 MSG_MOVE_SET_SWIM_BACK_SPEED_CHEAT                    Both   Never                    InPlace,
+    0x0D5 MSG_MOVE_SET_SWIM_BACK_SPEED                          Both   Never                    InPlace,
+    0x0D6 MSG_MOVE_SET_ALL_SPEED_CHEAT                          Both   Never                    InPlace,
+    0x0D7 MSG_MOVE_SET_TURN_RATE_CHEAT                          Both   Never                    InPlace,
+    0x0D8 MSG_MOVE_SET_TURN_RATE                                Both   Never                    InPlace,
+    0x0D9 MSG_MOVE_TOGGLE_COLLISION_CHEAT                       Both   Never                    InPlace,
+    0x0DA MSG_MOVE_SET_FACING                                   Both   LoggedIn                 ThreadSafe,
+    0x0DB MSG_MOVE_SET_PITCH                                    Both   LoggedIn                 ThreadSafe,
+    0x0DC MSG_MOVE_WORLDPORT_ACK                                Both   Transfer                 ThreadUnsafe,
+    0x0DD SMSG_MONSTER_MOVE                                     Server Never                    InPlace,
+    0x0DE SMSG_MOVE_WATER_WALK                                  Server Never                    InPlace,
+    0x0DF SMSG_MOVE_LAND_WALK                                   Server Never                    InPlace,
+    0x0E0 CMSG_MOVE_CHARM_PORT_CHEAT                            Client Never                    InPlace,
+    0x0E1 CMSG_MOVE_SET_RAW_POSITION                            Client Never                    InPlace,
+    0x0E2 SMSG_FORCE_RUN_SPEED_CHANGE                           Server Never                    InPlace,
+    0x0E3 CMSG_FORCE_RUN_SPEED_CHANGE_ACK                       Client LoggedIn                 ThreadSafe,
+    0x0E4 SMSG_FORCE_RUN_BACK_SPEED_CHANGE                      Server Never                    InPlace,
+    0x0E5 CMSG_FORCE_RUN_BACK_SPEED_CHANGE_ACK                  Client LoggedIn                 ThreadSafe,
+    0x0E6 SMSG_FORCE_SWIM_SPEED_CHANGE                          Server Never                    InPlace,
+    0x0E7 CMSG_FORCE_SWIM_SPEED_CHANGE_ACK                      Client LoggedIn                 ThreadSafe,
+    0x0E8 SMSG_FORCE_MOVE_ROOT                                  Server Never                    InPlace,
+    0x0E9 CMSG_FORCE_MOVE_ROOT_ACK                              Client LoggedIn                 ThreadSafe,
+    0x0EA SMSG_FORCE_MOVE_UNROOT                                Server Never                    InPlace,
+    0x0EB CMSG_FORCE_MOVE_UNROOT_ACK                            Client LoggedIn                 ThreadSafe,
+    0x0EC MSG_MOVE_ROOT                                         Both   Never                    InPlace,
+    0x0ED MSG_MOVE_UNROOT                                       Both   Never                    InPlace,
+    0x0EE MSG_MOVE_HEARTBEAT                                    Both   LoggedIn                 ThreadSafe,
+    0x0EF SMSG_MOVE_KNOCK_BACK                                  Server Never                    InPlace,
+    0x0F0 CMSG_MOVE_KNOCK_BACK_ACK                              Client LoggedIn                 ThreadSafe,
+    0x0F1 MSG_MOVE_KNOCK_BACK                                   Both   Never                    InPlace,
+    0x0F2 SMSG_MOVE_FEATHER_FALL                                Server Never                    InPlace,
+    0x0F3 SMSG_MOVE_NORMAL_FALL                                 Server Never                    InPlace,
+    0x0F4 SMSG_MOVE_SET_HOVER                                   Server Never                    InPlace,
+    0x0F5 SMSG_MOVE_UNSET_HOVER                                 Server Never                    InPlace,
+    0x0F6 CMSG_MOVE_HOVER_ACK                                   Client LoggedIn                 ThreadUnsafe,
+    0x0F7 MSG_MOVE_HOVER                                        Server Never                    InPlace,
+    0x0F8 CMSG_TRIGGER_CINEMATIC_CHEAT                          Client Never                    InPlace,
+    0x0F9 CMSG_OPENING_CINEMATIC                                Client LoggedIn                 ThreadUnsafe,
+    0x0FA SMSG_TRIGGER_CINEMATIC                                Server Never                    InPlace,
+    0x0FB CMSG_NEXT_CINEMATIC_CAMERA                            Client LoggedIn                 ThreadUnsafe,
+    0x0FC CMSG_COMPLETE_CINEMATIC                               Client LoggedIn                 ThreadUnsafe,
+    0x0FD SMSG_TUTORIAL_FLAGS                                   Server Never                    InPlace,
+    0x0FE CMSG_TUTORIAL_FLAG                                    Client LoggedIn                 ThreadUnsafe,
+    0x0FF CMSG_TUTORIAL_CLEAR                                   Client LoggedIn                 ThreadUnsafe,
+    0x100 CMSG_TUTORIAL_RESET                                   Client LoggedIn                 ThreadUnsafe,
+    0x101 CMSG_STANDSTATECHANGE                                 Client LoggedIn                 ThreadUnsafe,
+    0x102 CMSG_EMOTE                                            Client LoggedIn                 InPlace,
+    0x103 SMSG_EMOTE                                            Server Never                    InPlace,
+    0x104 CMSG_TEXT_EMOTE                                       Client LoggedIn                 InPlace,
+    0x105 SMSG_TEXT_EMOTE                                       Server Never                    InPlace,
+    0x106 CMSG_AUTOEQUIP_GROUND_ITEM                            Client Never                    InPlace,
+    0x107 CMSG_AUTOSTORE_GROUND_ITEM                            Client Never                    InPlace,
+    0x108 CMSG_AUTOSTORE_LOOT_ITEM                              Client LoggedIn                 InPlace,
+    0x109 CMSG_STORE_LOOT_IN_SLOT                               Client Never                    InPlace,
+    0x10A CMSG_AUTOEQUIP_ITEM                                   Client LoggedIn                 InPlace,
+    0x10B CMSG_AUTOSTORE_BAG_ITEM                               Client LoggedIn                 InPlace,
+    0x10C CMSG_SWAP_ITEM                                        Client LoggedIn                 InPlace,
+    0x10D CMSG_SWAP_INV_ITEM                                    Client LoggedIn                 InPlace,
+    0x10E CMSG_SPLIT_ITEM                                       Client LoggedIn                 InPlace,
+    0x10F CMSG_AUTOEQUIP_ITEM_SLOT                              Client LoggedIn                 InPlace,
+    0x110 CMSG_UNCLAIM_LICENSE                                  Client Never                    InPlace,
+    0x111 CMSG_DESTROYITEM                                      Client LoggedIn                 InPlace,
+    0x112 SMSG_INVENTORY_CHANGE_FAILURE                         Server Never                    InPlace,
+    0x113 SMSG_OPEN_CONTAINER                                   Server Never                    InPlace,
+    0x114 CMSG_INSPECT                                          Client LoggedIn                 InPlace,
+    0x115 SMSG_INSPECT_RESULTS_UPDATE                           Server Never                    InPlace,
+    0x116 CMSG_INITIATE_TRADE                                   Client LoggedIn                 ThreadUnsafe,
+    0x117 CMSG_BEGIN_TRADE                                      Client LoggedIn                 ThreadUnsafe,
+    0x118 CMSG_BUSY_TRADE                                       Client LoggedIn                 ThreadUnsafe,
+    0x119 CMSG_IGNORE_TRADE                                     Client LoggedIn                 ThreadUnsafe,
+    0x11A CMSG_ACCEPT_TRADE                                     Client LoggedIn                 ThreadUnsafe,
+    0x11B CMSG_UNACCEPT_TRADE                                   Client LoggedIn                 ThreadUnsafe,
+    0x11C CMSG_CANCEL_TRADE                                     Client LoggedInOrRecentlyLogout ThreadUnsafe,
+    0x11D CMSG_SET_TRADE_ITEM                                   Client LoggedIn                 ThreadUnsafe,
+    0x11E CMSG_CLEAR_TRADE_ITEM                                 Client LoggedIn                 ThreadUnsafe,
+    0x11F CMSG_SET_TRADE_GOLD                                   Client LoggedIn                 ThreadUnsafe,
+    0x120 SMSG_TRADE_STATUS                                     Server Never                    InPlace,
+    0x121 SMSG_TRADE_STATUS_EXTENDED                            Server Never                    InPlace,
+    0x122 SMSG_INITIALIZE_FACTIONS                              Server Never                    InPlace,
+    0x123 SMSG_SET_FACTION_VISIBLE                              Server Never                    InPlace,
+    0x124 SMSG_SET_FACTION_STANDING                             Server Never                    InPlace,
+    0x125 CMSG_SET_FACTION_ATWAR                                Client LoggedIn                 ThreadUnsafe,
+    0x126 CMSG_SET_FACTION_CHEAT                                Client LoggedIn                 ThreadUnsafe,
+    0x127 SMSG_SET_PROFICIENCY                                  Server Never                    InPlace,
+    0x128 CMSG_SET_ACTION_BUTTON                                Client LoggedIn                 ThreadUnsafe,
+    0x129 SMSG_ACTION_BUTTONS                                   Server Never                    InPlace,
+    0x12A SMSG_INITIAL_SPELLS                                   Server Never                    InPlace,
+    0x12B SMSG_LEARNED_SPELL                                    Server Never                    InPlace,
+    0x12C SMSG_SUPERCEDED_SPELL                                 Server Never                    InPlace,
+    0x12D CMSG_NEW_SPELL_SLOT                                   Client Never                    InPlace,
+    0x12E CMSG_CAST_SPELL                                       Client LoggedIn                 ThreadSafe,
+    0x12F CMSG_CANCEL_CAST                                      Client LoggedIn                 ThreadSafe,
+    0x130 SMSG_CAST_FAILED                                      Server Never                    InPlace,
+    0x131 SMSG_SPELL_START                                      Server Never                    InPlace,
+    0x132 SMSG_SPELL_GO                                         Server Never                    InPlace,
+    0x133 SMSG_SPELL_FAILURE                                    Server Never                    InPlace,
+    0x134 SMSG_SPELL_COOLDOWN                                   Server Never                    InPlace,
+    0x135 SMSG_COOLDOWN_EVENT                                   Server Never                    InPlace,
+    0x136 CMSG_CANCEL_AURA                                      Client LoggedIn                 InPlace,
+    0x137 SMSG_EQUIPMENT_SET_SAVED                              Server Never                    InPlace,
+    0x138 SMSG_PET_CAST_FAILED                                  Server Never                    InPlace,
+    0x139 MSG_CHANNEL_START                                     Both   Never                    InPlace,
+    0x13A MSG_CHANNEL_UPDATE                                    Both   Never                    InPlace,
+    0x13B CMSG_CANCEL_CHANNELLING                               Client LoggedIn                 InPlace,
+    0x13C SMSG_AI_REACTION                                      Server Never                    InPlace,
+    0x13D CMSG_SET_SELECTION                                    Client LoggedIn                 InPlace,
+    0x13E CMSG_DELETEEQUIPMENT_SET                              Client LoggedIn                 ThreadUnsafe,
+    0x13F CMSG_INSTANCE_LOCK_RESPONSE                           Client LoggedIn                 ThreadUnsafe,
+    0x140 CMSG_DEBUG_PASSIVE_AURA                               Client Never                    InPlace,
+    0x141 CMSG_ATTACK_SWING                                     Client LoggedIn                 InPlace,
+    0x142 CMSG_ATTACK_STOP                                      Client LoggedIn                 InPlace,
+    0x143 SMSG_ATTACK_START                                     Server Never                    InPlace,
+    0x144 SMSG_ATTACK_STOP                                      Server Never                    InPlace,
+    0x145 SMSG_ATTACK_SWING_NOT_IN_RANGE                        Server Never                    InPlace,
+    0x146 SMSG_ATTACK_SWING_BAD_FACING                          Server Never                    InPlace,
+    0x147 SMSG_INSTANCE_LOCK_WARNING_QUERY                      Server Never                    InPlace,
+    0x148 SMSG_ATTACK_SWING_DEAD_TARGET                         Server Never                    InPlace,
+    0x149 SMSG_ATTACK_SWING_CANT_ATTACK                         Server Never                    InPlace,
+    0x14A SMSG_ATTACKERSTATEUPDATE                              Server Never                    InPlace,
+    0x14B SMSG_BATTLEFIELD_PORT_DENIED                          Server Never                    InPlace,
+    0x14C CMSG_PERFORM_ACTION_SET                               Client Never                    InPlace,
+    0x14D SMSG_RESUME_CAST_BAR                                  Server Never                    InPlace,
+    0x14E SMSG_CANCEL_COMBAT                                    Server Never                    InPlace,
+    0x14F SMSG_SPELLBREAKLOG                                    Server Never                    InPlace,
+    0x150 SMSG_SPELLHEALLOG                                     Server Never                    InPlace,
+    0x151 SMSG_SPELLENERGIZELOG                                 Server Never                    InPlace,
+    0x152 SMSG_BREAK_TARGET                                     Server Never                    InPlace,
+    0x153 CMSG_SAVE_PLAYER                                      Client Never                    InPlace,
+    0x154 CMSG_SETDEATHBINDPOINT                                Client Never                    InPlace,
+    0x155 SMSG_BIND_POINT_UPDATE                                Server Never                    InPlace,
+    0x156 CMSG_GETDEATHBINDZONE                                 Client Never                    InPlace,
+    0x157 SMSG_BINDZONEREPLY                                    Server Never                    InPlace,
+    0x158 SMSG_PLAYER_BOUND                                     Server Never                    InPlace,
+    0x159 SMSG_CLIENT_CONTROL_UPDATE                            Server Never                    InPlace,
+    0x15A CMSG_REPOP_REQUEST                                    Client LoggedIn                 ThreadUnsafe,
+    0x15B SMSG_RESURRECT_REQUEST                                Server Never                    InPlace,
+    0x15C CMSG_RESURRECT_RESPONSE                               Client LoggedIn                 ThreadUnsafe,
+    0x15D CMSG_LOOT                                             Client LoggedIn                 ThreadUnsafe,
+    0x15E CMSG_LOOT_MONEY                                       Client LoggedIn                 ThreadUnsafe,
+    0x15F CMSG_LOOT_RELEASE                                     Client LoggedIn                 ThreadUnsafe,
+    0x160 SMSG_LOOT_RESPONSE                                    Server Never                    InPlace,
+    0x161 SMSG_LOOT_RELEASE_RESPONSE                            Server Never                    InPlace,
+    0x162 SMSG_LOOT_REMOVED                                     Server Never                    InPlace,
+    0x163 SMSG_LOOT_MONEY_NOTIFY                                Server Never                    InPlace,
+    0x164 SMSG_LOOT_ITEM_NOTIFY                                 Server Never                    InPlace,
+    0x165 SMSG_LOOT_CLEAR_MONEY                                 Server Never                    InPlace,
+    0x166 SMSG_ITEM_PUSH_RESULT                                 Server Never                    InPlace,
+    0x167 SMSG_DUEL_REQUESTED                                   Server Never                    InPlace,
+    0x168 SMSG_DUEL_OUTOFBOUNDS                                 Server Never                    InPlace,
+    0x169 SMSG_DUEL_INBOUNDS                                    Server Never                    InPlace,
+    0x16A SMSG_DUEL_COMPLETE                                    Server Never                    InPlace,
+    0x16B SMSG_DUEL_WINNER                                      Server Never                    InPlace,
+    0x16C CMSG_DUEL_ACCEPTED                                    Client LoggedIn                 ThreadUnsafe,
+    0x16D CMSG_DUEL_CANCELLED                                   Client LoggedIn                 ThreadUnsafe,
+    0x16E SMSG_MOUNT_RESULT                                     Server Never                    InPlace,
+    0x16F SMSG_DISMOUNTRESULT                                   Server Never                    InPlace,
+    0x170 SMSG_REMOVED_FROM_PVP_QUEUE                           Server Never                    InPlace,
+    0x171 CMSG_MOUNTSPECIAL_ANIM                                Client LoggedIn                 ThreadUnsafe,
+    0x172 SMSG_MOUNTSPECIAL_ANIM                                Server Never                    InPlace,
+    0x173 SMSG_PET_TAME_FAILURE                                 Server Never                    InPlace,
+    0x174 CMSG_PET_SET_ACTION                                   Client LoggedIn                 ThreadUnsafe,
+    0x175 CMSG_PET_ACTION                                       Client LoggedIn                 ThreadUnsafe,
+    0x176 CMSG_PET_ABANDON                                      Client LoggedIn                 ThreadUnsafe,
+    0x177 CMSG_PET_RENAME                                       Client LoggedIn                 ThreadUnsafe,
+    0x178 SMSG_PET_NAME_INVALID                                 Server Never                    InPlace,
+    0x179 SMSG_PET_SPELLS                                       Server Never                    InPlace,
+    0x17A SMSG_PET_MODE                                         Server Never                    InPlace,
+    0x17B CMSG_GOSSIP_HELLO                                     Client LoggedIn                 InPlace,
+    0x17C CMSG_GOSSIP_SELECT_OPTION                             Client LoggedIn                 ThreadUnsafe,
+    0x17D SMSG_GOSSIP_MESSAGE                                   Server Never                    InPlace,
+    0x17E SMSG_GOSSIP_COMPLETE                                  Server Never                    InPlace,
+    0x17F CMSG_NPC_TEXT_QUERY                                   Client LoggedIn                 InPlace,
+    0x180 SMSG_NPC_TEXT_UPDATE                                  Server Never                    InPlace,
+    0x181 SMSG_NPC_WONT_TALK                                    Server Never                    InPlace,
+    0x182 CMSG_QUESTGIVER_STATUS_QUERY                          Client LoggedIn                 InPlace,
+    0x183 SMSG_QUESTGIVER_STATUS                                Server Never                    InPlace,
+    0x184 CMSG_QUESTGIVER_HELLO                                 Client LoggedIn                 InPlace,
+    0x185 SMSG_QUESTGIVER_QUEST_LIST                            Server Never                    InPlace,
+    0x186 CMSG_QUESTGIVER_QUERY_QUEST                           Client LoggedIn                 InPlace,
+    0x187 CMSG_QUESTGIVER_QUEST_AUTOLAUNCH                      Client LoggedIn                 InPlace,
+    0x188 SMSG_QUEST_GIVER_QUEST_DETAILS                        Server Never                    InPlace,
+    0x189 CMSG_QUESTGIVER_ACCEPT_QUEST                          Client LoggedIn                 InPlace,
+    0x18A CMSG_QUESTGIVER_COMPLETE_QUEST                        Client LoggedIn                 InPlace,
+    0x18B SMSG_QUESTGIVER_REQUEST_ITEMS                         Server Never                    InPlace,
+    0x18C CMSG_QUESTGIVER_REQUEST_REWARD                        Client LoggedIn                 InPlace,
+    0x18D SMSG_QUEST_GIVER_OFFER_REWARD_MESSAGE                 Server Never                    InPlace,
+    0x18E CMSG_QUESTGIVER_CHOOSE_REWARD                         Client LoggedIn                 InPlace,
+    0x18F SMSG_QUESTGIVER_QUEST_INVALID                         Server Never                    InPlace,
+    0x190 CMSG_QUESTGIVER_CANCEL                                Client LoggedIn                 InPlace,
+    0x191 SMSG_QUESTGIVER_QUEST_COMPLETE                        Server Never                    InPlace,
+    0x192 SMSG_QUESTGIVER_QUEST_FAILED                          Server Never                    InPlace,
+    0x193 CMSG_QUESTLOG_SWAP_QUEST                              Client LoggedIn                 InPlace,
+    0x194 CMSG_QUESTLOG_REMOVE_QUEST                            Client LoggedIn                 InPlace,
+    0x195 SMSG_QUESTLOG_FULL                                    Server Never                    InPlace,
+    0x196 SMSG_QUESTUPDATE_FAILED                               Server Never                    InPlace,
+    0x197 SMSG_QUESTUPDATE_FAILEDTIMER                          Server Never                    InPlace,
+    0x198 SMSG_QUESTUPDATE_COMPLETE                             Server Never                    InPlace,
+    0x199 SMSG_QUESTUPDATE_ADD_KILL                             Server Never                    InPlace,
+    0x19A SMSG_QUESTUPDATE_ADD_ITEM                             Server Never                    InPlace,
+    0x19B CMSG_QUEST_CONFIRM_ACCEPT                             Client LoggedIn                 ThreadUnsafe,
+    0x19C SMSG_QUEST_CONFIRM_ACCEPT                             Server Never                    InPlace,
+    0x19D CMSG_PUSHQUESTTOPARTY                                 Client LoggedIn                 ThreadUnsafe,
+    0x19E CMSG_LIST_INVENTORY                                   Client LoggedIn                 InPlace,
+    0x19F SMSG_LIST_INVENTORY                                   Server Never                    InPlace,
+    0x1A0 CMSG_SELL_ITEM                                        Client LoggedIn                 InPlace,
+    0x1A1 SMSG_SELL_ITEM                                        Server Never                    InPlace,
+    0x1A2 CMSG_BUY_ITEM                                         Client LoggedIn                 InPlace,
+    0x1A3 CMSG_BUY_ITEM_IN_SLOT                                 Client LoggedIn                 InPlace,
+    0x1A4 SMSG_BUY_ITEM                                         Server Never                    InPlace,
+    0x1A5 SMSG_BUY_FAILED                                       Server Never                    InPlace,
+    0x1A6 CMSG_TAXICLEARALLNODES                                Client Never                    InPlace,
+    0x1A7 CMSG_TAXIENABLEALLNODES                               Client Never                    InPlace,
+    0x1A8 CMSG_TAXISHOWNODES                                    Client Never                    InPlace,
+    0x1A9 SMSG_SHOWTAXINODES                                    Server Never                    InPlace,
+    0x1AA CMSG_TAXINODE_STATUS_QUERY                            Client LoggedIn                 ThreadSafe,
+    0x1AB SMSG_TAXINODE_STATUS                                  Server Never                    InPlace,
+    0x1AC CMSG_TAXIQUERYAVAILABLENODES                          Client LoggedIn                 ThreadSafe,
+    0x1AD CMSG_ACTIVATETAXI                                     Client LoggedIn                 ThreadSafe,
+    0x1AE SMSG_ACTIVATETAXIREPLY                                Server Never                    InPlace,
+    0x1AF SMSG_NEW_TAXI_PATH                                    Server Never                    InPlace,
+    0x1B0 CMSG_TRAINER_LIST                                     Client LoggedIn                 InPlace,
+    0x1B1 SMSG_TRAINER_LIST                                     Server Never                    InPlace,
+    0x1B2 CMSG_TRAINER_BUY_SPELL                                Client LoggedIn                 InPlace,
+    0x1B3 SMSG_TRAINER_BUY_SUCCEEDED                            Server Never                    InPlace,
+    0x1B4 SMSG_TRAINER_BUY_FAILED                               Server Never                    InPlace,
+    0x1B5 CMSG_BINDER_ACTIVATE                                  Client LoggedIn                 InPlace,
+    0x1B6 SMSG_PLAYERBINDERROR                                  Server Never                    InPlace,
+    0x1B7 CMSG_BANKER_ACTIVATE                                  Client LoggedIn                 InPlace,
+    0x1B8 SMSG_SHOW_BANK                                        Server Never                    InPlace,
+    0x1B9 CMSG_BUY_BANK_SLOT                                    Client LoggedIn                 InPlace,
+    0x1BA SMSG_BUY_BANK_SLOT_RESULT                             Server Never                    InPlace,
+    0x1BB CMSG_PETITION_SHOWLIST                                Client LoggedIn                 ThreadUnsafe,
+    0x1BC SMSG_PETITION_SHOWLIST                                Server Never                    InPlace,
+    0x1BD CMSG_PETITION_BUY                                     Client LoggedIn                 ThreadUnsafe,
+    0x1BE CMSG_PETITION_SHOW_SIGNATURES                         Client LoggedIn                 ThreadUnsafe,
+    0x1BF SMSG_PETITION_SHOW_SIGNATURES                         Server Never                    InPlace,
+    0x1C0 CMSG_PETITION_SIGN                                    Client LoggedIn                 ThreadUnsafe,
+    0x1C1 SMSG_PETITION_SIGN_RESULTS                            Server Never                    InPlace,
+    0x1C2 MSG_PETITION_DECLINE                                  Both   LoggedIn                 ThreadUnsafe,
+    0x1C3 CMSG_OFFER_PETITION                                   Client LoggedIn                 ThreadUnsafe,
+    0x1C4 CMSG_TURN_IN_PETITION                                 Client LoggedIn                 ThreadUnsafe,
+    0x1C5 SMSG_TURN_IN_PETITION_RESULTS                         Server Never                    InPlace,
+    0x1C6 CMSG_PETITION_QUERY                                   Client LoggedIn                 ThreadUnsafe,
+    0x1C7 SMSG_PETITION_QUERY_RESPONSE                          Server Never                    InPlace,
+    0x1C8 SMSG_FISH_NOT_HOOKED                                  Server Never                    InPlace,
+    0x1C9 SMSG_FISH_ESCAPED                                     Server Never                    InPlace,
+    0x1CA CMSG_BUG                                              Client LoggedIn                 ThreadUnsafe,
+    0x1CB SMSG_NOTIFICATION                                     Server Never                    InPlace,
+    0x1CC CMSG_PLAYED_TIME                                      Client LoggedIn                 InPlace,
+    0x1CD SMSG_PLAYED_TIME                                      Server Never                    InPlace,
+    0x1CE CMSG_QUERY_TIME                                       Client LoggedIn                 InPlace,
+    0x1CF SMSG_QUERY_TIME_RESPONSE                              Server Never                    InPlace,
+    0x1D0 SMSG_LOG_XPGAIN                                       Server Never                    InPlace,
+    0x1D1 SMSG_AURACASTLOG                                      Server Never                    InPlace,
+    0x1D2 CMSG_RECLAIM_CORPSE                                   Client LoggedIn                 ThreadUnsafe,
+    0x1D3 CMSG_WRAP_ITEM                                        Client LoggedIn                 ThreadUnsafe,
+    0x1D4 SMSG_LEVELUP_INFO                                     Server Never                    InPlace,
+    0x1D5 MSG_MINIMAP_PING                                      Both   LoggedIn                 ThreadUnsafe,
+    0x1D6 SMSG_RESISTLOG                                        Server Never                    InPlace,
+    0x1D7 SMSG_ENCHANTMENTLOG                                   Server Never                    InPlace,
+    0x1D8 CMSG_SET_SKILL_CHEAT                                  Client Never                    InPlace,
+    0x1D9 SMSG_START_MIRROR_TIMER                               Server Never                    InPlace,
+    0x1DA SMSG_PAUSE_MIRROR_TIMER                               Server Never                    InPlace,
+    0x1DB SMSG_STOP_MIRROR_TIMER                                Server Never                    InPlace,
+    0x1DC CMSG_PING                                             Client Never                    InPlace,
+    0x1DD SMSG_PONG                                             Server Never                    InPlace,
+    0x1DE SMSG_CLEAR_COOLDOWN                                   Server Never                    InPlace,
+    0x1DF SMSG_GAMEOBJECT_PAGETEXT                              Server Never                    InPlace,
+    0x1E0 CMSG_SET_SHEATHED                                     Client LoggedIn                 InPlace,
+    0x1E1 SMSG_COOLDOWN_CHEAT                                   Server Never                    InPlace,
+    0x1E2 SMSG_SPELL_DELAYED                                    Server Never                    InPlace,
+    0x1E3 CMSG_QUEST_POI_QUERY                                  Client LoggedIn                 InPlace,
+    0x1E4 SMSG_QUEST_POI_QUERY_RESPONSE                         Server Never                    InPlace,
+    0x1E5 CMSG_GHOST                                            Client Never                    InPlace,
+    0x1E6 CMSG_GM_INVIS                                         Client Never                    InPlace,
+    0x1E7 SMSG_INVALID_PROMOTION_CODE                           Server Never                    InPlace,
+    0x1E8 MSG_GM_BIND_OTHER                                     Both   Never                    InPlace,
+    0x1E9 MSG_GM_SUMMON                                         Both   Never                    InPlace,
+    0x1EA SMSG_ITEM_TIME_UPDATE                                 Server Never                    InPlace,
+    0x1EB SMSG_ITEM_ENCHANT_TIME_UPDATE                         Server Never                    InPlace,
+    0x1EC SMSG_AUTH_CHALLENGE                                   Server Never                    InPlace,
+    0x1ED CMSG_AUTH_SESSION                                     Client Never                    ThreadUnsafe,
+    0x1EE SMSG_AUTH_RESPONSE                                    Server Never                    InPlace,
+    0x1EF MSG_GM_SHOWLABEL                                      Both   Never                    InPlace,
+    0x1F0 CMSG_PET_CAST_SPELL                                   Client LoggedIn                 InPlace,
+    0x1F1 MSG_SAVE_GUILD_EMBLEM                                 Both   LoggedIn                 ThreadUnsafe,
+    0x1F2 MSG_TABARDVENDOR_ACTIVATE                             Both   LoggedIn                 InPlace,
+    0x1F3 SMSG_PLAY_SPELL_VISUAL                                Server Never                    InPlace,
+    0x1F4 CMSG_ZONEUPDATE                                       Client LoggedIn                 InPlace,
+    0x1F5 SMSG_PARTYKILLLOG                                     Server Never                    InPlace,
+    0x1F6 SMSG_COMPRESSED_UPDATE_OBJECT                         Server Never                    InPlace,
+    0x1F7 SMSG_PLAY_SPELL_IMPACT                                Server Never                    InPlace,
+    0x1F8 SMSG_EXPLORATION_EXPERIENCE                           Server Never                    InPlace,
+    0x1F9 CMSG_GM_SET_SECURITY_GROUP                            Client Never                    InPlace,
+    0x1FA CMSG_GM_NUKE                                          Client Never                    InPlace,
+    0x1FB MSG_RANDOM_ROLL                                       Both   LoggedIn                 ThreadUnsafe,
+    0x1FC SMSG_ENVIRONMENTAL_DAMAGE_LOG                         Server Never                    InPlace,
+    0x1FD CMSG_CHANGEPLAYER_DIFFICULTY                          Client Never                    InPlace,
+    0x1FE SMSG_RWHOIS                                           Server Never                    InPlace,
+    0x1FF SMSG_LFG_PLAYER_REWARD                                Server Never                    InPlace,
+    0x200 SMSG_LFG_TELEPORT_DENIED                              Server Never                    InPlace,
+    0x201 CMSG_UNLEARN_SPELL                                    Client Never                    InPlace,
+    0x202 CMSG_UNLEARN_SKILL                                    Client LoggedIn                 InPlace,
+    0x203 SMSG_REMOVED_SPELL                                    Server Never                    InPlace,
+    0x204 CMSG_DECHARGE                                         Client Never                    InPlace,
+    0x205 CMSG_GMTICKET_CREATE                                  Client LoggedIn                 ThreadUnsafe,
+    0x206 SMSG_GMTICKET_CREATE                                  Server Never                    InPlace,
+    0x207 CMSG_GMTICKET_UPDATETEXT                              Client LoggedIn                 ThreadUnsafe,
+    0x208 SMSG_GMTICKET_UPDATETEXT                              Server Never                    InPlace,
+    0x209 SMSG_ACCOUNT_DATA_TIMES                               Server Never                    InPlace,
+    0x20A CMSG_REQUEST_ACCOUNT_DATA                             Client Authed                   ThreadUnsafe,
+    0x20B CMSG_UPDATE_ACCOUNT_DATA                              Client Authed                   ThreadUnsafe,
+    0x20C SMSG_UPDATE_ACCOUNT_DATA                              Server Never                    InPlace,
+    0x20D SMSG_CLEAR_FAR_SIGHT_IMMEDIATE                        Server Never                    InPlace,
+    0x20E SMSG_CHANGEPLAYER_DIFFICULTY_RESULT                   Server Never                    InPlace,
+    0x20F CMSG_GM_TEACH                                         Client Never                    InPlace,
+    0x210 CMSG_GM_CREATE_ITEM_TARGET                            Client Never                    InPlace,
+    0x211 CMSG_GMTICKET_GETTICKET                               Client LoggedIn                 ThreadUnsafe,
+    0x212 SMSG_GMTICKET_GETTICKET                               Server Never                    InPlace,
+    0x213 CMSG_UNLEARN_TALENTS                                  Client Never                    InPlace,
+    0x214 SMSG_UPDATE_INSTANCE_ENCOUNTER_UNIT                   Server Never                    InPlace,
+    0x215 SMSG_GAMEOBJECT_DESPAWN_ANIM                          Server Never                    InPlace,
+    0x216 MSG_CORPSE_QUERY                                      Both   LoggedIn                 ThreadUnsafe,
+    0x217 CMSG_GMTICKET_DELETETICKET                            Client LoggedIn                 ThreadUnsafe,
+    0x218 SMSG_GMTICKET_DELETETICKET                            Server Never                    InPlace,
+    0x219 SMSG_CHAT_WRONG_FACTION                               Server Never                    InPlace,
+    0x21A CMSG_GMTICKET_SYSTEMSTATUS                            Client LoggedIn                 ThreadUnsafe,
+    0x21B SMSG_GMTICKET_SYSTEMSTATUS                            Server Never                    InPlace,
+    0x21C CMSG_SPIRIT_HEALER_ACTIVATE                           Client LoggedIn                 ThreadUnsafe,
+    0x21D CMSG_SET_STAT_CHEAT                                   Client Never                    InPlace,
+    0x21E SMSG_QUEST_FORCE_REMOVE                               Server Never                    InPlace,
+    0x21F CMSG_SKILL_BUY_STEP                                   Client Never                    InPlace,
+    0x220 CMSG_SKILL_BUY_RANK                                   Client Never                    InPlace,
+    0x221 CMSG_XP_CHEAT                                         Client Never                    InPlace,
+    0x222 SMSG_SPIRIT_HEALER_CONFIRM                            Server Never                    InPlace,
+    0x223 CMSG_CHARACTER_POINT_CHEAT                            Client Never                    InPlace,
+    0x224 SMSG_GOSSIP_POI                                       Server Never                    InPlace,
+    0x225 CMSG_CHAT_IGNORED                                     Client LoggedIn                 ThreadUnsafe,
+    0x226 CMSG_GM_VISION                                        Client Never                    InPlace,
+    0x227 CMSG_SERVER_COMMAND                                   Client Never                    InPlace,
+    0x228 CMSG_GM_SILENCE                                       Client Never                    InPlace,
+    0x229 CMSG_GM_REVEALTO                                      Client Never                    InPlace,
+    0x22A CMSG_GM_RESURRECT                                     Client Never                    InPlace,
+    0x22B CMSG_GM_SUMMONMOB                                     Client Never                    InPlace,
+    0x22C CMSG_GM_MOVECORPSE                                    Client Never                    InPlace,
+    0x22D CMSG_GM_FREEZE                                        Client Never                    InPlace,
+    0x22E CMSG_GM_UBERINVIS                                     Client Never                    InPlace,
+    0x22F CMSG_GM_REQUEST_PLAYER_INFO                           Client Never                    InPlace,
+    0x230 SMSG_GM_PLAYER_INFO                                   Server Never                    InPlace,
+    0x231 CMSG_GUILD_RANK                                       Client LoggedIn                 ThreadUnsafe,
+    0x232 CMSG_GUILD_ADD_RANK                                   Client LoggedIn                 ThreadUnsafe,
+    0x233 CMSG_GUILD_DEL_RANK                                   Client LoggedIn                 ThreadUnsafe,
+    0x234 CMSG_GUILD_SET_PUBLIC_NOTE                            Client LoggedIn                 ThreadUnsafe,
+    0x235 CMSG_GUILD_SET_OFFICER_NOTE                           Client LoggedIn                 ThreadUnsafe,
+    0x236 SMSG_LOGIN_VERIFY_WORLD                               Server Never                    InPlace,
+    0x237 CMSG_CLEAR_EXPLORATION                                Client Never                    InPlace,
+    0x238 CMSG_SEND_MAIL                                        Client LoggedIn                 ThreadUnsafe,
+    0x239 SMSG_SEND_MAIL_RESULT                                 Server Never                    InPlace,
+    0x23A CMSG_GET_MAIL_LIST                                    Client LoggedIn                 ThreadUnsafe,
+    0x23B SMSG_MAIL_LIST_RESULT                                 Server Never                    InPlace,
+    0x23C CMSG_BATTLEFIELD_LIST                                 Client LoggedIn                 ThreadUnsafe,
+    0x23D SMSG_BATTLEFIELD_LIST                                 Server Never                    InPlace,
+    0x23E CMSG_BATTLEFIELD_JOIN                                 Client Never                    InPlace,
+    0x23F SMSG_FORCE_SET_VEHICLE_REC_ID                         Server Never                    InPlace,
+    0x240 CMSG_SET_VEHICLE_REC_ID_ACK                           Client Never                    InPlace,
+    0x241 CMSG_TAXICLEARNODE                                    Client Never                    InPlace,
+    0x242 CMSG_TAXIENABLENODE                                   Client Never                    InPlace,
+    0x243 CMSG_ITEM_TEXT_QUERY                                  Client LoggedIn                 InPlace,
+    0x244 SMSG_ITEM_TEXT_QUERY_RESPONSE                         Server Never                    InPlace,
+    0x245 CMSG_MAIL_TAKE_MONEY                                  Client LoggedIn                 ThreadUnsafe,
+    0x246 CMSG_MAIL_TAKE_ITEM                                   Client LoggedIn                 ThreadUnsafe,
+    0x247 CMSG_MAIL_MARK_AS_READ                                Client LoggedIn                 ThreadUnsafe,
+    0x248 CMSG_MAIL_RETURN_TO_SENDER                            Client LoggedIn                 ThreadUnsafe,
+    0x249 CMSG_MAIL_DELETE                                      Client LoggedIn                 ThreadUnsafe,
+    0x24A CMSG_MAIL_CREATE_TEXT_ITEM                            Client LoggedIn                 ThreadUnsafe,
+    0x24B SMSG_SPELLLOGMISS                                     Server Never                    InPlace,
+    0x24C SMSG_SPELLLOGEXECUTE                                  Server Never                    InPlace,
+    0x24D SMSG_DEBUGAURAPROC                                    Server Never                    InPlace,
+    0x24E SMSG_PERIODICAURALOG                                  Server Never                    InPlace,
+    0x24F SMSG_SPELLDAMAGESHIELD                                Server Never                    InPlace,
+    0x250 SMSG_SPELLNONMELEEDAMAGELOG                           Server Never                    InPlace,
+    0x251 CMSG_LEARN_TALENT                                     Client LoggedIn                 InPlace,
+    0x252 SMSG_RESURRECT_FAILED                                 Server Never                    InPlace,
+    0x253 CMSG_TOGGLE_PVP                                       Client LoggedIn                 ThreadUnsafe,
+    0x254 SMSG_ZONE_UNDER_ATTACK                                Server Never                    InPlace,
+    0x255 MSG_AUCTION_HELLO                                     Both   LoggedIn                 ThreadUnsafe,
+    0x256 CMSG_AUCTION_SELL_ITEM                                Client LoggedIn                 ThreadUnsafe,
+    0x257 CMSG_AUCTION_REMOVE_ITEM                              Client LoggedIn                 ThreadUnsafe,
+    0x258 CMSG_AUCTION_LIST_ITEMS                               Client LoggedIn                 ThreadUnsafe,
+    0x259 CMSG_AUCTION_LIST_OWNER_ITEMS                         Client LoggedIn                 ThreadUnsafe,
+    0x25A CMSG_AUCTION_PLACE_BID                                Client LoggedIn                 ThreadUnsafe,
+    0x25B SMSG_AUCTION_COMMAND_RESULT                           Server Never                    InPlace,
+    0x25C SMSG_AUCTION_LIST_RESULT                              Server Never                    InPlace,
+    0x25D SMSG_AUCTION_OWNER_LIST_RESULT                        Server Never                    InPlace,
+    0x25E SMSG_AUCTION_BIDDER_NOTIFICATION                      Server Never                    InPlace,
+    0x25F SMSG_AUCTION_OWNER_NOTIFICATION                       Server Never                    InPlace,
+    0x260 SMSG_PROCRESIST                                       Server Never                    InPlace,
+    0x261 SMSG_COMBAT_EVENT_FAILED                              Server Never                    InPlace,
+    0x262 SMSG_DISPEL_FAILED                                    Server Never                    InPlace,
+    0x263 SMSG_SPELLORDAMAGE_IMMUNE                             Server Never                    InPlace,
+    0x264 CMSG_AUCTION_LIST_BIDDER_ITEMS                        Client LoggedIn                 ThreadUnsafe,
+    0x265 SMSG_AUCTION_BIDDER_LIST_RESULT                       Server Never                    InPlace,
+    0x266 SMSG_SET_FLAT_SPELL_MODIFIER                          Server Never                    InPlace,
+    0x267 SMSG_SET_PCT_SPELL_MODIFIER                           Server Never                    InPlace,
+    0x268 CMSG_SET_AMMO                                         Client LoggedIn                 InPlace,
+    0x269 SMSG_CORPSE_RECLAIM_DELAY                             Server Never                    InPlace,
+    0x26A CMSG_SET_ACTIVE_MOVER                                 Client LoggedIn                 ThreadUnsafe,
+    0x26B CMSG_PET_CANCEL_AURA                                  Client LoggedIn                 InPlace,
+    0x26C CMSG_PLAYER_AI_CHEAT                                  Client Never                    InPlace,
+    0x26D CMSG_CANCEL_AUTO_REPEAT_SPELL                         Client LoggedIn                 InPlace,
+    0x26E MSG_GM_ACCOUNT_ONLINE                                 Both   Never                    InPlace,
+    0x26F MSG_LIST_STABLED_PETS                                 Both   LoggedIn                 ThreadUnsafe,
+    0x270 CMSG_STABLE_PET                                       Client LoggedIn                 ThreadUnsafe,
+    0x271 CMSG_UNSTABLE_PET                                     Client LoggedIn                 ThreadUnsafe,
+    0x272 CMSG_BUY_STABLE_SLOT                                  Client LoggedIn                 ThreadUnsafe,
+    0x273 SMSG_STABLE_RESULT                                    Server Never                    InPlace,
+    0x274 CMSG_STABLE_REVIVE_PET                                Client LoggedIn                 ThreadUnsafe,
+    0x275 CMSG_STABLE_SWAP_PET                                  Client LoggedIn                 ThreadUnsafe,
+    0x276 MSG_QUEST_PUSH_RESULT                                 Both   LoggedIn                 ThreadUnsafe,
+    0x277 SMSG_PLAY_MUSIC                                       Server Never                    InPlace,
+    0x278 SMSG_PLAY_OBJECT_SOUND                                Server Never                    InPlace,
+    0x279 CMSG_REQUEST_PET_INFO                                 Client LoggedIn                 ThreadUnsafe,
+    0x27A CMSG_FAR_SIGHT                                        Client LoggedIn                 ThreadUnsafe,
+    0x27B SMSG_SPELLDISPELLOG                                   Server Never                    InPlace,
+    0x27C SMSG_DAMAGE_CALC_LOG                                  Server Never                    InPlace,
+    0x27D CMSG_ENABLE_DAMAGE_LOG                                Client Never                    InPlace,
+    0x27E CMSG_GROUP_CHANGE_SUB_GROUP                           Client LoggedIn                 ThreadUnsafe,
+    0x27F CMSG_REQUEST_PARTY_MEMBER_STATS                       Client LoggedIn                 ThreadUnsafe,
+    0x280 CMSG_GROUP_SWAP_SUB_GROUP                             Client Unhandled                InPlace,
+    0x281 CMSG_RESET_FACTION_CHEAT                              Client Never                    InPlace,
+    0x282 CMSG_AUTOSTORE_BANK_ITEM                              Client LoggedIn                 InPlace,
+    0x283 CMSG_AUTOBANK_ITEM                                    Client LoggedIn                 InPlace,
+    0x284 MSG_QUERY_NEXT_MAIL_TIME                              Both   LoggedIn                 ThreadUnsafe,
+    0x285 SMSG_RECEIVED_MAIL                                    Server Never                    InPlace,
+    0x286 SMSG_RAID_GROUP_ONLY                                  Server Never                    InPlace,
+    0x287 CMSG_SET_DURABILITY_CHEAT                             Client Never                    InPlace,
+    0x288 CMSG_SET_PVP_RANK_CHEAT                               Client Never                    InPlace,
+    0x289 CMSG_ADD_PVP_MEDAL_CHEAT                              Client Never                    InPlace,
+    0x28A CMSG_DEL_PVP_MEDAL_CHEAT                              Client Never                    InPlace,
+    0x28B CMSG_SET_PVP_TITLE                                    Client Never                    InPlace,
+    0x28C SMSG_PVP_CREDIT                                       Server Never                    InPlace,
+    0x28D SMSG_AUCTION_REMOVED_NOTIFICATION                     Server Never                    InPlace,
+    0x28E CMSG_GROUP_RAID_CONVERT                               Client LoggedIn                 ThreadUnsafe,
+    0x28F CMSG_GROUP_ASSISTANT_LEADER                           Client LoggedIn                 ThreadUnsafe,
+    0x290 CMSG_BUYBACK_ITEM                                     Client LoggedIn                 InPlace,
+    0x291 SMSG_CHAT_SERVER_MESSAGE                              Server Never                    InPlace,
+    0x292 CMSG_SET_SAVED_INSTANCE_EXTEND                        Client LoggedIn                 ThreadUnsafe,
+    0x293 SMSG_LFG_OFFER_CONTINUE                               Server Never                    InPlace,
+    0x294 CMSG_TEST_DROP_RATE                                   Client Never                    InPlace,
+    0x295 SMSG_TEST_DROP_RATE_RESULT                            Server Never                    InPlace,
+    0x296 CMSG_LFG_GET_STATUS                                   Client LoggedIn                 ThreadSafe,
+    0x297 SMSG_SHOW_MAILBOX                                     Server Never                    InPlace,
+    0x298 SMSG_RESET_RANGED_COMBAT_TIMER                        Server Never                    InPlace,
+    0x299 SMSG_CHAT_NOT_IN_PARTY                                Server Never                    InPlace,
+    0x29A CMSG_GMTICKETSYSTEM_TOGGLE                            Server Never                    InPlace,
+    0x29B CMSG_CANCEL_GROWTH_AURA                               Client LoggedIn                 ThreadUnsafe,
+    0x29C SMSG_CANCEL_AUTO_REPEAT                               Server Never                    InPlace,
+    0x29D SMSG_STANDSTATE_UPDATE                                Server Never                    InPlace,
+    0x29E SMSG_LOOT_ALL_PASSED                                  Server Never                    InPlace,
+    0x29F SMSG_LOOT_ROLL_WON                                    Server Never                    InPlace,
+    0x2A0 CMSG_LOOT_ROLL                                        Client LoggedIn                 ThreadUnsafe,
+    0x2A1 SMSG_LOOT_START_ROLL                                  Server Never                    InPlace,
+    0x2A2 SMSG_LOOT_ROLL                                        Server Never                    InPlace,
+    0x2A3 CMSG_LOOT_MASTER_GIVE                                 Client LoggedIn                 ThreadUnsafe,
+    0x2A4 SMSG_LOOT_MASTER_LIST                                 Server Never                    InPlace,
+    0x2A5 SMSG_SET_FORCED_REACTIONS                             Server Never                    InPlace,
+    0x2A6 SMSG_SPELL_FAILED_OTHER                               Server Never                    InPlace,
+    0x2A7 SMSG_GAMEOBJECT_RESET_STATE                           Server Never                    InPlace,
+    0x2A8 CMSG_REPAIR_ITEM                                      Client LoggedIn                 InPlace,
+    0x2A9 SMSG_CHAT_PLAYER_NOT_FOUND                            Server Never                    InPlace,
+    0x2AA MSG_TALENT_WIPE_CONFIRM                               Both   LoggedIn                 InPlace,
+    0x2AB SMSG_SUMMON_REQUEST                                   Server Never                    InPlace,
+    0x2AC CMSG_SUMMON_RESPONSE                                  Client LoggedIn                 ThreadUnsafe,
+    0x2AD MSG_DEV_SHOWLABEL                                     Both   Never                    InPlace,
+    0x2AE SMSG_MONSTER_MOVE_TRANSPORT                           Server Never                    InPlace,
+    0x2AF SMSG_PET_BROKEN                                       Server Never                    InPlace,
+    0x2B0 MSG_MOVE_FEATHER_FALL                                 Both   Never                    InPlace,
+    0x2B1 MSG_MOVE_WATER_WALK                                   Both   Never                    InPlace,
+    0x2B2 CMSG_SERVER_BROADCAST                                 Client Never                    InPlace,
+    0x2B3 CMSG_SELF_RES                                         Client LoggedIn                 ThreadUnsafe,
+    0x2B4 SMSG_FEIGN_DEATH_RESISTED                             Server Never                    InPlace,
+    0x2B5 CMSG_RUN_SCRIPT                                       Client Never                    InPlace,
+    0x2B6 SMSG_SCRIPT_MESSAGE                                   Server Never                    InPlace,
+    0x2B7 SMSG_DUEL_COUNTDOWN                                   Server Never                    InPlace,
+    0x2B8 SMSG_AREA_TRIGGER_MESSAGE                             Server Never                    InPlace,
+    0x2B9 CMSG_SHOWING_HELM                                     Client LoggedIn                 InPlace,
+    0x2BA CMSG_SHOWING_CLOAK                                    Client LoggedIn                 InPlace,
+    0x2BB SMSG_LFG_ROLE_CHOSEN                                  Server Never                    InPlace,
+    0x2BC SMSG_PLAYER_SKINNED                                   Server Never                    InPlace,
+    0x2BD SMSG_DURABILITY_DAMAGE_DEATH                          Server Never                    InPlace,
+    0x2BE CMSG_SET_EXPLORATION                                  Client Never                    InPlace,
+    0x2BF CMSG_SET_ACTIONBAR_TOGGLES                            Client Authed                   ThreadUnsafe,
+    0x2C0 UMSG_DELETE_GUILD_CHARTER                             Both   Never                    InPlace,
+    0x2C1 MSG_PETITION_RENAME                                   Both   LoggedIn                 ThreadUnsafe,
+    0x2C2 SMSG_INIT_WORLD_STATES                                Server Never                    InPlace,
+    0x2C3 SMSG_UPDATE_WORLD_STATE                               Server Never                    InPlace,
+    0x2C4 CMSG_ITEM_NAME_QUERY                                  Client LoggedIn                 InPlace,
+    0x2C5 SMSG_ITEM_NAME_QUERY_RESPONSE                         Server Never                    InPlace,
+    0x2C6 SMSG_PET_ACTION_FEEDBACK                              Server Never                    InPlace,
+    0x2C7 CMSG_CHAR_RENAME                                      Client Authed                   ThreadUnsafe,
+    0x2C8 SMSG_CHAR_RENAME                                      Server Never                    InPlace,
+    0x2C9 CMSG_MOVE_SPLINE_DONE                                 Client LoggedIn                 ThreadSafe,
+    0x2CA CMSG_MOVE_FALL_RESET                                  Client LoggedIn                 ThreadSafe,
+    0x2CB SMSG_INSTANCE_SAVE_CREATED                            Server Never                    InPlace,
+    0x2CC SMSG_RAID_INSTANCE_INFO                               Server Never                    InPlace,
+    0x2CD CMSG_REQUEST_RAID_INFO                                Client LoggedIn                 ThreadUnsafe,
+    0x2CE CMSG_MOVE_TIME_SKIPPED                                Client LoggedIn                 InPlace,
+    0x2CF CMSG_MOVE_FEATHER_FALL_ACK                            Client LoggedIn                 ThreadSafe,
+    0x2D0 CMSG_MOVE_WATER_WALK_ACK                              Client LoggedIn                 ThreadSafe,
+    0x2D1 CMSG_MOVE_NOT_ACTIVE_MOVER                            Client LoggedIn                 ThreadSafe,
+    0x2D2 SMSG_PLAY_SOUND                                       Server Never                    InPlace,
+    0x2D3 CMSG_BATTLEFIELD_STATUS                               Client LoggedIn                 ThreadUnsafe,
+    0x2D4 SMSG_BATTLEFIELD_STATUS                               Server Never                    InPlace,
+    0x2D5 CMSG_BATTLEFIELD_PORT                                 Client LoggedIn                 ThreadUnsafe,
+    0x2D6 MSG_INSPECT_HONOR_STATS                               Both   LoggedIn                 InPlace,
+    0x2D7 CMSG_BATTLEMASTER_HELLO                               Client LoggedIn                 ThreadUnsafe,
+    0x2D8 CMSG_MOVE_START_SWIM_CHEAT                            Client Never                    InPlace,
+    0x2D9 CMSG_MOVE_STOP_SWIM_CHEAT                             Client Never                    InPlace,
+    0x2DA SMSG_FORCE_WALK_SPEED_CHANGE                          Server Never                    InPlace,
+    0x2DB CMSG_FORCE_WALK_SPEED_CHANGE_ACK                      Client LoggedIn                 ThreadSafe,
+    0x2DC SMSG_FORCE_SWIM_BACK_SPEED_CHANGE                     Server Never                    InPlace,
+    0x2DD CMSG_FORCE_SWIM_BACK_SPEED_CHANGE_ACK                 Client LoggedIn                 ThreadSafe,
+    0x2DE SMSG_FORCE_TURN_RATE_CHANGE                           Server Never                    InPlace,
+    0x2DF CMSG_FORCE_TURN_RATE_CHANGE_ACK                       Client LoggedIn                 ThreadSafe,
+    0x2E0 MSG_PVP_LOG_DATA                                      Both   LoggedIn                 ThreadUnsafe,
+    0x2E1 CMSG_LEAVE_BATTLEFIELD                                Client LoggedIn                 ThreadUnsafe,
+    0x2E2 CMSG_AREA_SPIRIT_HEALER_QUERY                         Client LoggedIn                 ThreadUnsafe,
+    0x2E3 CMSG_AREA_SPIRIT_HEALER_QUEUE                         Client LoggedIn                 ThreadUnsafe,
+    0x2E4 SMSG_AREA_SPIRIT_HEALER_TIME                          Server Never                    InPlace,
+    0x2E5 CMSG_GM_UNTEACH                                       Client Never                    InPlace,
+    0x2E6 SMSG_WARDEN_DATA                                      Server Never                    InPlace,
+    0x2E7 CMSG_WARDEN_DATA                                      Client Authed                   ThreadSafe,
+    0x2E8 SMSG_GROUP_JOINED_BATTLEGROUND                        Server Never                    InPlace,
+    0x2E9 MSG_BATTLEGROUND_PLAYER_POSITIONS                     Both   LoggedIn                 ThreadUnsafe,
+    0x2EA CMSG_PET_STOP_ATTACK                                  Client LoggedIn                 InPlace,
+    0x2EB SMSG_BINDER_CONFIRM                                   Server Never                    InPlace,
+    0x2EC SMSG_BATTLEGROUND_PLAYER_JOINED                       Server Never                    InPlace,
+    0x2ED SMSG_BATTLEGROUND_PLAYER_LEFT                         Server Never                    InPlace,
+    0x2EE CMSG_BATTLEMASTER_JOIN                                Client LoggedIn                 ThreadUnsafe,
+    0x2EF SMSG_ADDON_INFO                                       Server Never                    InPlace,
+    0x2F0 CMSG_PET_UNLEARN                                      Client Never                    InPlace,
+    0x2F1 SMSG_PET_UNLEARN_CONFIRM                              Server Never                    InPlace,
+    0x2F2 SMSG_PARTY_MEMBER_STATS_FULL                          Server Never                    InPlace,
+    0x2F3 CMSG_PET_SPELL_AUTOCAST                               Client LoggedIn                 InPlace,
+    0x2F4 SMSG_WEATHER                                          Server Never                    InPlace,
+    0x2F5 SMSG_PLAY_TIME_WARNING                                Server Never                    InPlace,
+    0x2F6 SMSG_MINIGAME_SETUP                                   Server Never                    InPlace,
+    0x2F7 SMSG_MINIGAME_STATE                                   Server Never                    InPlace,
+    0x2F8 CMSG_MINIGAME_MOVE                                    Client Never                    InPlace,
+    0x2F9 SMSG_MINIGAME_MOVE_FAILED                             Server Never                    InPlace,
+    0x2FA SMSG_RAID_INSTANCE_MESSAGE                            Server Never                    InPlace,
+    0x2FB SMSG_COMPRESSED_MOVES                                 Server Never                    InPlace,
+    0x2FC CMSG_GUILD_INFO_TEXT                                  Client LoggedIn                 ThreadUnsafe,
+    0x2FD SMSG_CHAT_RESTRICTED                                  Server Never                    InPlace,
+    0x2FE SMSG_SPLINE_SET_RUN_SPEED                             Server Never                    InPlace,
+    0x2FF SMSG_SPLINE_SET_RUN_BACK_SPEED                        Server Never                    InPlace,
+    0x300 SMSG_SPLINE_SET_SWIM_SPEED                            Server Never                    InPlace,
+    0x301 SMSG_SPLINE_SET_WALK_SPEED                            Server Never                    InPlace,
+    0x302 SMSG_SPLINE_SET_SWIM_BACK_SPEED                       Server Never                    InPlace,
+    0x303 SMSG_SPLINE_SET_TURN_RATE                             Server Never                    InPlace,
+    0x304 SMSG_SPLINE_MOVE_UNROOT                               Server Never                    InPlace,
+    0x305 SMSG_SPLINE_MOVE_FEATHER_FALL                         Server Never                    InPlace,
+    0x306 SMSG_SPLINE_MOVE_NORMAL_FALL                          Server Never                    InPlace,
+    0x307 SMSG_SPLINE_MOVE_SET_HOVER                            Server Never                    InPlace,
+    0x308 SMSG_SPLINE_MOVE_UNSET_HOVER                          Server Never                    InPlace,
+    0x309 SMSG_SPLINE_MOVE_WATER_WALK                           Server Never                    InPlace,
+    0x30A SMSG_SPLINE_MOVE_LAND_WALK                            Server Never                    InPlace,
+    0x30B SMSG_SPLINE_MOVE_START_SWIM                           Server Never                    InPlace,
+    0x30C SMSG_SPLINE_MOVE_STOP_SWIM                            Server Never                    InPlace,
+    0x30D SMSG_SPLINE_MOVE_SET_RUN_MODE                         Server Never                    InPlace,
+    0x30E SMSG_SPLINE_MOVE_SET_WALK_MODE                        Server Never                    InPlace,
+    0x30F CMSG_GM_NUKE_ACCOUNT                                  Client Never                    InPlace,
+    0x310 MSG_GM_DESTROY_CORPSE                                 Both   Never                    InPlace,
+    0x311 CMSG_GM_DESTROY_ONLINE_CORPSE                         Client Never                    InPlace,
+    0x312 CMSG_ACTIVATETAXIEXPRESS                              Client LoggedIn                 ThreadSafe,
+    0x313 SMSG_SET_FACTION_ATWAR                                Server Never                    InPlace,
+    0x314 SMSG_GAMETIMEBIAS_SET                                 Server Never                    InPlace,
+    0x315 CMSG_DEBUG_ACTIONS_START                              Client Never                    InPlace,
+    0x316 CMSG_DEBUG_ACTIONS_STOP                               Client Never                    InPlace,
+    0x317 CMSG_SET_FACTION_INACTIVE                             Client LoggedIn                 ThreadUnsafe,
+    0x318 CMSG_SET_WATCHED_FACTION                              Client LoggedIn                 ThreadUnsafe,
+    0x319 MSG_MOVE_TIME_SKIPPED                                 Both   Never                    InPlace,
+    0x31A SMSG_SPLINE_MOVE_ROOT                                 Server Never                    InPlace,
+    0x31B CMSG_SET_EXPLORATION_ALL                              Client Never                    InPlace,
+    0x31C SMSG_INVALIDATE_PLAYER                                Server Never                    InPlace,
+    0x31D CMSG_RESET_INSTANCES                                  Client LoggedIn                 ThreadUnsafe,
+    0x31E SMSG_INSTANCE_RESET                                   Server Never                    InPlace,
+    0x31F SMSG_INSTANCE_RESET_FAILED                            Server Never                    InPlace,
+    0x320 SMSG_UPDATE_LAST_INSTANCE                             Server Never                    InPlace,
+    0x321 MSG_RAID_TARGET_UPDATE                                Both   LoggedIn                 ThreadUnsafe,
+    0x322 MSG_RAID_READY_CHECK                                  Both   LoggedIn                 ThreadUnsafe,
+    0x323 CMSG_LUA_USAGE                                        Client Never                    InPlace,
+    0x324 SMSG_PET_ACTION_SOUND                                 Server Never                    InPlace,
+    0x325 SMSG_PET_DISMISS_SOUND                                Server Never                    InPlace,
+    0x326 SMSG_GHOSTEE_GONE                                     Server Never                    InPlace,
+    0x327 CMSG_GM_UPDATE_TICKET_STATUS                          Client Never                    InPlace,
+    0x328 SMSG_GM_TICKET_STATUS_UPDATE                          Server Never                    InPlace,
+    0x329 MSG_SET_DUNGEON_DIFFICULTY                            Both   LoggedIn                 ThreadUnsafe,
+    0x32A CMSG_GMSURVEY_SUBMIT                                  Client LoggedIn                 ThreadUnsafe,
+    0x32B SMSG_UPDATE_INSTANCE_OWNERSHIP                        Server Never                    InPlace,
+    0x32C CMSG_IGNORE_KNOCKBACK_CHEAT                           Client Never                    InPlace,
+    0x32D SMSG_CHAT_PLAYER_AMBIGUOUS                            Server Never                    InPlace,
+    0x32E MSG_DELAY_GHOST_TELEPORT                              Both   Never                    InPlace,
+    0x32F SMSG_SPELLINSTAKILLLOG                                Server Never                    InPlace,
+    0x330 SMSG_SPELL_UPDATE_CHAIN_TARGETS                       Server Never                    InPlace,
+    0x331 CMSG_CHAT_FILTERED                                    Client Never                    InPlace,
+    0x332 SMSG_EXPECTED_SPAM_RECORDS                            Server Never                    InPlace,
+    0x333 SMSG_SPELLSTEALLOG                                    Server Never                    InPlace,
+    0x334 CMSG_LOTTERY_QUERY_OBSOLETE                           Client Never                    InPlace,
+    0x335 SMSG_LOTTERY_QUERY_RESULT_OBSOLETE                    Server Never                    InPlace,
+    0x336 CMSG_BUY_LOTTERY_TICKET_OBSOLETE                      Client Never                    InPlace,
+    0x337 SMSG_LOTTERY_RESULT_OBSOLETE                          Server Never                    InPlace,
+    0x338 SMSG_CHARACTER_PROFILE                                Server Never                    InPlace,
+    0x339 SMSG_CHARACTER_PROFILE_REALM_CONNECTED                Server Never                    InPlace,
+    0x33A SMSG_DEFENSE_MESSAGE                                  Server Never                    InPlace,
+    0x33B SMSG_INSTANCE_DIFFICULTY                              Server Never                    InPlace,
+    0x33C MSG_GM_RESETINSTANCELIMIT                             Both   Never                    InPlace,
+    0x33D SMSG_MOTD                                             Server Never                    InPlace,
+    0x33E SMSG_MOVE_SET_CAN_TRANSITION_BETWEEN_SWIM_AND_FLY     Server Never                    InPlace,
+    0x33F SMSG_MOVE_UNSET_CAN_TRANSITION_BETWEEN_SWIM_AND_FLY   Server Never                    InPlace,
+    0x340 CMSG_MOVE_SET_CAN_TRANSITION_BETWEEN_SWIM_AND_FLY_ACK Client LoggedIn                 ThreadSafe,
+    0x341 MSG_MOVE_START_SWIM_CHEAT                             Both   Never                    InPlace,
+    0x342 MSG_MOVE_STOP_SWIM_CHEAT                              Both   Never                    InPlace,
+    0x343 SMSG_MOVE_SET_CAN_FLY                                 Server Never                    InPlace,
+    0x344 SMSG_MOVE_UNSET_CAN_FLY                               Server Never                    InPlace,
+    0x345 CMSG_MOVE_SET_CAN_FLY_ACK                             Client LoggedIn                 ThreadSafe,
+    0x346 CMSG_MOVE_SET_FLY                                     Client LoggedIn                 ThreadSafe,
+    0x347 CMSG_SOCKET_GEMS                                      Client LoggedIn                 InPlace,
+    0x348 CMSG_ARENA_TEAM_CREATE                                Client Never                    InPlace,
+    0x349 SMSG_ARENA_TEAM_COMMAND_RESULT                        Server Never                    InPlace,
+    0x34A MSG_MOVE_UPDATE_CAN_TRANSITION_BETWEEN_SWIM_AND_FLY   Both   Never                    InPlace,
+    0x34B CMSG_ARENA_TEAM_QUERY                                 Client LoggedIn                 ThreadUnsafe,
+    0x34C SMSG_ARENA_TEAM_QUERY_RESPONSE                        Server Never                    InPlace,
+    0x34D CMSG_ARENA_TEAM_ROSTER                                Client LoggedIn                 ThreadUnsafe,
+    0x34E SMSG_ARENA_TEAM_ROSTER                                Server Never                    InPlace,
+    0x34F CMSG_ARENA_TEAM_INVITE                                Client LoggedIn                 ThreadUnsafe,
+    0x350 SMSG_ARENA_TEAM_INVITE                                Server Never                    InPlace,
+    0x351 CMSG_ARENA_TEAM_ACCEPT                                Client LoggedIn                 ThreadUnsafe,
+    0x352 CMSG_ARENA_TEAM_DECLINE                               Client LoggedIn                 ThreadUnsafe,
+    0x353 CMSG_ARENA_TEAM_LEAVE                                 Client LoggedIn                 ThreadUnsafe,
+    0x354 CMSG_ARENA_TEAM_REMOVE                                Client LoggedIn                 ThreadUnsafe,
+    0x355 CMSG_ARENA_TEAM_DISBAND                               Client LoggedIn                 ThreadUnsafe,
+    0x356 CMSG_ARENA_TEAM_LEADER                                Client LoggedIn                 ThreadUnsafe,
+    0x357 SMSG_ARENA_TEAM_EVENT                                 Server Never                    InPlace,
+    0x358 CMSG_BATTLEMASTER_JOIN_ARENA                          Client LoggedIn                 ThreadUnsafe,
+    0x359 MSG_MOVE_START_ASCEND                                 Both   LoggedIn                 ThreadSafe,
+    0x35A MSG_MOVE_STOP_ASCEND                                  Both   LoggedIn                 ThreadSafe,
+    0x35B SMSG_ARENA_TEAM_STATS                                 Server Never                    InPlace,
+    0x35C CMSG_LFG_JOIN                                         Client LoggedIn                 ThreadUnsafe,
+    0x35D CMSG_LFG_LEAVE                                        Client LoggedIn                 ThreadUnsafe,
+    0x35E CMSG_SEARCH_LFG_JOIN                                  Client LoggedIn                 ThreadUnsafe,
+    0x35F CMSG_SEARCH_LFG_LEAVE                                 Client LoggedIn                 ThreadUnsafe,
+    0x360 SMSG_UPDATE_LFG_LIST                                  Server Never                    InPlace,
+    0x361 SMSG_LFG_PROPOSAL_UPDATE                              Server Never                    InPlace,
+    0x362 CMSG_LFG_PROPOSAL_RESULT                              Client LoggedIn                 ThreadUnsafe,
+    0x363 SMSG_LFG_ROLE_CHECK_UPDATE                            Server Never                    InPlace,
+    0x364 SMSG_LFG_JOIN_RESULT                                  Server Never                    InPlace,
+    0x365 SMSG_LFG_QUEUE_STATUS                                 Server Never                    InPlace,
+    0x366 CMSG_SET_LFG_COMMENT                                  Client LoggedIn                 ThreadUnsafe,
+    0x367 SMSG_LFG_UPDATE_PLAYER                                Server Never                    InPlace,
+    0x368 SMSG_LFG_UPDATE_PARTY                                 Server Never                    InPlace,
+    0x369 SMSG_LFG_UPDATE_SEARCH                                Server Never                    InPlace,
+    0x36A CMSG_LFG_SET_ROLES                                    Client LoggedIn                 ThreadUnsafe,
+    0x36B CMSG_LFG_SET_NEEDS                                    Client Never                    InPlace,
+    0x36C CMSG_LFG_SET_BOOT_VOTE                                Client LoggedIn                 ThreadUnsafe,
+    0x36D SMSG_LFG_BOOT_PROPOSAL_UPDATE                         Server Never                    InPlace,
+    0x36E CMSG_LFD_PLAYER_LOCK_INFO_REQUEST                     Client LoggedIn                 ThreadUnsafe,
+    0x36F SMSG_LFG_PLAYER_INFO                                  Server Never                    InPlace,
+    0x370 CMSG_LFG_TELEPORT                                     Client LoggedIn                 ThreadUnsafe,
+    0x371 CMSG_LFD_PARTY_LOCK_INFO_REQUEST                      Client LoggedIn                 ThreadUnsafe,
+    0x372 SMSG_LFG_PARTY_INFO                                   Server Never                    InPlace,
+    0x373 SMSG_TITLE_EARNED                                     Server Never                    InPlace,
+    0x374 CMSG_SET_TITLE                                        Client LoggedIn                 InPlace,
+    0x375 CMSG_CANCEL_MOUNT_AURA                                Client LoggedIn                 InPlace,
+    0x376 SMSG_ARENA_ERROR                                      Server Never                    InPlace,
+    0x377 MSG_INSPECT_ARENA_TEAMS                               Both   LoggedIn                 InPlace,
+    0x378 SMSG_DEATH_RELEASE_LOC                                Server Never                    InPlace,
+    0x379 CMSG_CANCEL_TEMP_ENCHANTMENT                          Client LoggedIn                 InPlace,
+    0x37A SMSG_FORCED_DEATH_UPDATE                              Server Never                    InPlace,
+    0x37B CMSG_CHEAT_SET_HONOR_CURRENCY                         Client Never                    InPlace,
+    0x37C CMSG_CHEAT_SET_ARENA_CURRENCY                         Client Never                    InPlace,
+    0x37D MSG_MOVE_SET_FLIGHT_SPEED_CHEAT                       Both   Never                    InPlace,
+    0x37E MSG_MOVE_SET_FLIGHT_SPEED                             Both   Never                    InPlace,
+    0x37F MSG_MOVE_SET_FLIGHT_BACK_SPEED_CHEAT                  Both   Never                    InPlace,
+    0x380 MSG_MOVE_SET_FLIGHT_BACK_SPEED                        Both   Never                    InPlace,
+    0x381 SMSG_FORCE_FLIGHT_SPEED_CHANGE                        Server Never                    InPlace,
+    0x382 CMSG_FORCE_FLIGHT_SPEED_CHANGE_ACK                    Client LoggedIn                 ThreadSafe,
+    0x383 SMSG_FORCE_FLIGHT_BACK_SPEED_CHANGE                   Server Never                    InPlace,
+    0x384 CMSG_FORCE_FLIGHT_BACK_SPEED_CHANGE_ACK               Client LoggedIn                 ThreadSafe,
+    0x385 SMSG_SPLINE_SET_FLIGHT_SPEED                          Server Never                    InPlace,
+    0x386 SMSG_SPLINE_SET_FLIGHT_BACK_SPEED                     Server Never                    InPlace,
+    0x387 CMSG_MAELSTROM_INVALIDATE_CACHE                       Client Never                    InPlace,
+    0x388 SMSG_FLIGHT_SPLINE_SYNC                               Server Never                    InPlace,
+    0x389 CMSG_SET_TAXI_BENCHMARK_MODE                          Client LoggedIn                 ThreadUnsafe,
+    0x38A SMSG_JOINED_BATTLEGROUND_QUEUE                        Server Never                    InPlace,
+    0x38B SMSG_REALM_SPLIT                                      Server Never                    InPlace,
+    0x38C CMSG_REALM_SPLIT                                      Client Authed                   ThreadUnsafe,
+    0x38D CMSG_MOVE_CHNG_TRANSPORT                              Client LoggedIn                 ThreadSafe,
+    0x38E MSG_PARTY_ASSIGNMENT                                  Both   LoggedIn                 ThreadUnsafe,
+    0x38F SMSG_OFFER_PETITION_ERROR                             Server Never                    InPlace,
+    0x390 SMSG_TIME_SYNC_REQ                                    Server Never                    InPlace,
+    0x391 CMSG_TIME_SYNC_RESP                                   Client LoggedIn                 ThreadSafe,
+    0x392 CMSG_SEND_LOCAL_EVENT                                 Client Never                    InPlace,
+    0x393 CMSG_SEND_GENERAL_TRIGGER                             Client Never                    InPlace,
+    0x394 CMSG_SEND_COMBAT_TRIGGER                              Client Never                    InPlace,
+    0x395 CMSG_MAELSTROM_GM_SENT_MAIL                           Client Never                    InPlace,
+    0x396 SMSG_RESET_FAILED_NOTIFY                              Server Never                    InPlace,
+    0x397 SMSG_REAL_GROUP_UPDATE                                Server Never                    InPlace,
+    0x398 SMSG_LFG_DISABLED                                     Server Never                    InPlace,
+    0x399 CMSG_ACTIVE_PVP_CHEAT                                 Client Never                    InPlace,
+    0x39A CMSG_CHEAT_DUMP_ITEMS_DEBUG_ONLY                      Client Never                    InPlace,
+    0x39B SMSG_CHEAT_DUMP_ITEMS_DEBUG_ONLY_RESPONSE             Server Never                    InPlace,
+    0x39C SMSG_CHEAT_DUMP_ITEMS_DEBUG_ONLY_RESPONSE_WRITE_FILE  Server Never                    InPlace,
+    0x39D SMSG_UPDATE_COMBO_POINTS                              Server Never                    InPlace,
+    0x39E SMSG_VOICE_SESSION_ROSTER_UPDATE                      Server Never                    InPlace,
+    0x39F SMSG_VOICE_SESSION_LEAVE                              Server Never                    InPlace,
+    0x3A0 SMSG_VOICE_SESSION_ADJUST_PRIORITY                    Server Never                    InPlace,
+    0x3A1 CMSG_VOICE_SET_TALKER_MUTED_REQUEST                   Client Never                    InPlace,
+    0x3A2 SMSG_VOICE_SET_TALKER_MUTED                           Server Never                    InPlace,
+    0x3A3 SMSG_INIT_EXTRA_AURA_INFO_OBSOLETE                    Server Never                    InPlace,
+    0x3A4 SMSG_SET_EXTRA_AURA_INFO_OBSOLETE                     Server Never                    InPlace,
+    0x3A5 SMSG_SET_EXTRA_AURA_INFO_NEED_UPDATE_OBSOLETE         Server Never                    InPlace,
+    0x3A6 SMSG_CLEAR_EXTRA_AURA_INFO_OBSOLETE                   Server Never                    InPlace,
+    0x3A7 MSG_MOVE_START_DESCEND                                Both   LoggedIn                 ThreadSafe,
+    0x3A8 CMSG_IGNORE_REQUIREMENTS_CHEAT                        Client Never                    InPlace,
+    0x3A9 SMSG_IGNORE_REQUIREMENTS_CHEAT                        Server Never                    InPlace,
+    0x3AA SMSG_SPELL_CHANCE_PROC_LOG                            Server Never                    InPlace,
+    0x3AB CMSG_MOVE_SET_RUN_SPEED                               Client Never                    InPlace,
+    0x3AC SMSG_DISMOUNT                                         Server Never                    InPlace,
+    0x3AD MSG_MOVE_UPDATE_CAN_FLY                               Both   Never                    InPlace,
+    0x3AE MSG_RAID_READY_CHECK_CONFIRM                          Both   Never                    InPlace,
+    0x3AF CMSG_VOICE_SESSION_ENABLE                             Client Authed                   ThreadUnsafe,
+    0x3B0 SMSG_VOICE_SESSION_ENABLE                             Server Never                    InPlace,
+    0x3B1 SMSG_VOICE_PARENTAL_CONTROLS                          Server Never                    InPlace,
+    0x3B2 CMSG_GM_WHISPER                                       Client Never                    InPlace,
+    0x3B3 SMSG_GM_MESSAGECHAT                                   Server Never                    InPlace,
+    0x3B4 MSG_GM_GEARRATING                                     Both   Never                    InPlace,
+    0x3B5 CMSG_COMMENTATOR_ENABLE                               Client Never                    InPlace,
+    0x3B6 SMSG_COMMENTATOR_STATE_CHANGED                        Server Never                    InPlace,
+    0x3B7 CMSG_COMMENTATOR_GET_MAP_INFO                         Client Never                    InPlace,
+    0x3B8 SMSG_COMMENTATOR_MAP_INFO                             Server Never                    InPlace,
+    0x3B9 CMSG_COMMENTATOR_GET_PLAYER_INFO                      Client Never                    InPlace,
+    0x3BA SMSG_COMMENTATOR_GET_PLAYER_INFO                      Server Never                    InPlace,
+    0x3BB SMSG_COMMENTATOR_PLAYER_INFO                          Server Never                    InPlace,
+    0x3BC CMSG_COMMENTATOR_ENTER_INSTANCE                       Client Never                    InPlace,
+    0x3BD CMSG_COMMENTATOR_EXIT_INSTANCE                        Client Never                    InPlace,
+    0x3BE CMSG_COMMENTATOR_INSTANCE_COMMAND                     Client Never                    InPlace,
+    0x3BF SMSG_CLEAR_TARGET                                     Server Never                    InPlace,
+    0x3C0 CMSG_BOT_DETECTED                                     Client Never                    InPlace,
+    0x3C1 SMSG_CROSSED_INEBRIATION_THRESHOLD                    Server Never                    InPlace,
+    0x3C2 CMSG_CHEAT_PLAYER_LOGIN                               Client Never                    InPlace,
+    0x3C3 CMSG_CHEAT_PLAYER_LOOKUP                              Client Never                    InPlace,
+    0x3C4 SMSG_CHEAT_PLAYER_LOOKUP                              Server Never                    InPlace,
+    0x3C5 SMSG_KICK_REASON                                      Server Never                    InPlace,
+    0x3C6 MSG_RAID_READY_CHECK_FINISHED                         Both   LoggedIn                 ThreadUnsafe,
+    0x3C7 CMSG_COMPLAIN                                         Client LoggedIn                 ThreadUnsafe,
+    0x3C8 SMSG_COMPLAIN_RESULT                                  Server Never                    InPlace,
+    0x3C9 SMSG_FEATURE_SYSTEM_STATUS                            Server Never                    InPlace,
+    0x3CA CMSG_GM_SHOW_COMPLAINTS                               Client Never                    InPlace,
+    0x3CB CMSG_GM_UNSQUELCH                                     Client Never                    InPlace,
+    0x3CC CMSG_CHANNEL_SILENCE_VOICE                            Client Never                    InPlace,
+    0x3CD CMSG_CHANNEL_SILENCE_ALL                              Client Never                    InPlace,
+    0x3CE CMSG_CHANNEL_UNSILENCE_VOICE                          Client Never                    InPlace,
+    0x3CF CMSG_CHANNEL_UNSILENCE_ALL                            Client Never                    InPlace,
+    0x3D0 CMSG_TARGET_CAST                                      Client Never                    InPlace,
+    0x3D1 CMSG_TARGET_SCRIPT_CAST                               Client Never                    InPlace,
+    0x3D2 CMSG_CHANNEL_DISPLAY_LIST                             Client LoggedIn                 ThreadUnsafe,
+    0x3D3 CMSG_SET_ACTIVE_VOICE_CHANNEL                         Client Authed                   ThreadUnsafe,
+    0x3D4 CMSG_GET_CHANNEL_MEMBER_COUNT                         Client LoggedIn                 ThreadUnsafe,
+    0x3D5 SMSG_CHANNEL_MEMBER_COUNT                             Server Never                    InPlace,
+    0x3D6 CMSG_CHANNEL_VOICE_ON                                 Client LoggedIn                 ThreadUnsafe,
+    0x3D7 CMSG_CHANNEL_VOICE_OFF                                Client Never                    InPlace,
+    0x3D8 CMSG_DEBUG_LIST_TARGETS                               Client Never                    InPlace,
+    0x3D9 SMSG_DEBUG_LIST_TARGETS                               Server Never                    InPlace,
+    0x3DA SMSG_AVAILABLE_VOICE_CHANNEL                          Server Never                    InPlace,
+    0x3DB CMSG_ADD_VOICE_IGNORE                                 Client Never                    InPlace,
+    0x3DC CMSG_DEL_VOICE_IGNORE                                 Client Never                    InPlace,
+    0x3DD CMSG_PARTY_SILENCE                                    Client Never                    InPlace,
+    0x3DE CMSG_PARTY_UNSILENCE                                  Client Never                    InPlace,
+    0x3DF MSG_NOTIFY_PARTY_SQUELCH                              Both   Never                    InPlace,
+    0x3E0 SMSG_COMSAT_RECONNECT_TRY                             Server Never                    InPlace,
+    0x3E1 SMSG_COMSAT_DISCONNECT                                Server Never                    InPlace,
+    0x3E2 SMSG_COMSAT_CONNECT_FAIL                              Server Never                    InPlace,
+    0x3E3 SMSG_VOICE_CHAT_STATUS                                Server Never                    InPlace,
+    0x3E4 CMSG_REPORT_PVP_AFK                                   Client LoggedIn                 ThreadUnsafe,
+    0x3E5 SMSG_REPORT_PVP_AFK_RESULT                            Server Never                    InPlace,
+    0x3E6 CMSG_GUILD_BANKER_ACTIVATE                            Client LoggedIn                 ThreadUnsafe,
+    0x3E7 CMSG_GUILD_BANK_QUERY_TAB                             Client LoggedIn                 ThreadUnsafe,
+    0x3E8 SMSG_GUILD_BANK_LIST                                  Server Never                    InPlace,
+    0x3E9 CMSG_GUILD_BANK_SWAP_ITEMS                            Client LoggedIn                 ThreadUnsafe,
+    0x3EA CMSG_GUILD_BANK_BUY_TAB                               Client LoggedIn                 ThreadUnsafe,
+    0x3EB CMSG_GUILD_BANK_UPDATE_TAB                            Client LoggedIn                 ThreadUnsafe,
+    0x3EC CMSG_GUILD_BANK_DEPOSIT_MONEY                         Client LoggedIn                 ThreadUnsafe,
+    0x3ED CMSG_GUILD_BANK_WITHDRAW_MONEY                        Client LoggedIn                 ThreadUnsafe,
+    0x3EE MSG_GUILD_BANK_LOG_QUERY                              Both   LoggedIn                 ThreadUnsafe,
+    0x3EF CMSG_SET_CHANNEL_WATCH                                Client LoggedIn                 ThreadUnsafe,
+    0x3F0 SMSG_USERLIST_ADD                                     Server Never                    InPlace,
+    0x3F1 SMSG_USERLIST_REMOVE                                  Server Never                    InPlace,
+    0x3F2 SMSG_USERLIST_UPDATE                                  Server Never                    InPlace,
+    0x3F3 CMSG_CLEAR_CHANNEL_WATCH                              Client Never                    InPlace,
+    0x3F4 SMSG_INSPECT_TALENT                                   Server Never                    InPlace,
+    0x3F5 SMSG_GOGOGO_OBSOLETE                                  Server Never                    InPlace,
+    0x3F6 SMSG_ECHO_PARTY_SQUELCH                               Server Never                    InPlace,
+    0x3F7 CMSG_SET_TITLE_SUFFIX                                 Client Never                    InPlace,
+    0x3F8 CMSG_SPELLCLICK                                       Client LoggedIn                 InPlace,
+    0x3F9 SMSG_LOOT_LIST                                        Server Never                    InPlace,
+    0x3FA CMSG_GM_CHARACTER_RESTORE                             Client Never                    InPlace,
+    0x3FB CMSG_GM_CHARACTER_SAVE                                Client Never                    InPlace,
+    0x3FC SMSG_VOICESESSION_FULL                                Server Never                    InPlace,
+    0x3FD MSG_GUILD_PERMISSIONS                                 Both   LoggedIn                 ThreadUnsafe,
+    0x3FE MSG_GUILD_BANK_MONEY_WITHDRAWN                        Both   LoggedIn                 ThreadUnsafe,
+    0x3FF MSG_GUILD_EVENT_LOG_QUERY                             Both   LoggedIn                 ThreadUnsafe,
+    0x400 CMSG_MAELSTROM_RENAME_GUILD                           Client Never                    InPlace,
+    0x401 CMSG_GET_MIRRORIMAGE_DATA                             Client LoggedIn                 ThreadUnsafe,
+    0x402 SMSG_MIRRORIMAGE_DATA                                 Server Never                    InPlace,
+    0x403 SMSG_FORCE_DISPLAY_UPDATE                             Server Never                    InPlace,
+    0x404 SMSG_SPELL_CHANCE_RESIST_PUSHBACK                     Server Never                    InPlace,
+    0x405 CMSG_IGNORE_DIMINISHING_RETURNS_CHEAT                 Client Never                    InPlace,
+    0x406 SMSG_IGNORE_DIMINISHING_RETURNS_CHEAT                 Server Never                    InPlace,
+    0x407 CMSG_KEEP_ALIVE                                       Client Never                    ThreadUnsafe,
+    0x408 SMSG_RAID_READY_CHECK_ERROR                           Server Never                    InPlace,
+    0x409 CMSG_OPT_OUT_OF_LOOT                                  Client Authed                   ThreadUnsafe,
+    0x40A MSG_QUERY_GUILD_BANK_TEXT                             Both   LoggedIn                 ThreadUnsafe,
+    0x40B CMSG_SET_GUILD_BANK_TEXT                              Client LoggedIn                 ThreadUnsafe,
+    0x40C CMSG_SET_GRANTABLE_LEVELS                             Client Never                    InPlace,
+    0x40D CMSG_GRANT_LEVEL                                      Client LoggedIn                 ThreadUnsafe,
+    0x40E CMSG_REFER_A_FRIEND                                   Client Never                    InPlace,
+    0x40F MSG_GM_CHANGE_ARENA_RATING                            Both   Never                    InPlace,
+    0x410 CMSG_DECLINE_CHANNEL_INVITE                           Client LoggedIn                 InPlace,
+    0x411 SMSG_GROUPACTION_THROTTLED                            Server Never                    InPlace,
+    0x412 SMSG_OVERRIDE_LIGHT                                   Server Never                    InPlace,
+    0x413 SMSG_TOTEM_CREATED                                    Server Never                    InPlace,
+    0x414 CMSG_TOTEM_DESTROYED                                  Client LoggedIn                 InPlace,
+    0x415 CMSG_EXPIRE_RAID_INSTANCE                             Client Never                    InPlace,
+    0x416 CMSG_NO_SPELL_VARIANCE                                Client Never                    InPlace,
+    0x417 CMSG_QUESTGIVER_STATUS_MULTIPLE_QUERY                 Client LoggedIn                 ThreadUnsafe,
+    0x418 SMSG_QUESTGIVER_STATUS_MULTIPLE                       Server Never                    InPlace,
+    0x419 CMSG_SET_PLAYER_DECLINED_NAMES                        Client Authed                   ThreadUnsafe,
+    0x41A SMSG_SET_PLAYER_DECLINED_NAMES_RESULT                 Server Never                    InPlace,
+    0x41B CMSG_QUERY_SERVER_BUCK_DATA                           Client Never                    InPlace,
+    0x41C CMSG_CLEAR_SERVER_BUCK_DATA                           Client Never                    InPlace,
+    0x41D SMSG_SERVER_BUCK_DATA                                 Server Never                    InPlace,
+    0x41E SMSG_SEND_UNLEARN_SPELLS                              Server Never                    InPlace,
+    0x41F SMSG_PROPOSE_LEVEL_GRANT                              Server Never                    InPlace,
+    0x420 CMSG_ACCEPT_LEVEL_GRANT                               Client LoggedIn                 ThreadUnsafe,
+    0x421 SMSG_REFER_A_FRIEND_FAILURE                           Server Never                    InPlace,
+    0x422 SMSG_SPLINE_MOVE_SET_FLYING                           Server Never                    InPlace,
+    0x423 SMSG_SPLINE_MOVE_UNSET_FLYING                         Server Never                    InPlace,
+    0x424 SMSG_SUMMON_CANCEL                                    Server Never                    InPlace,
+    0x425 CMSG_CHANGE_PERSONAL_ARENA_RATING                     Client Never                    InPlace,
+    0x426 CMSG_ALTER_APPEARANCE                                 Client LoggedIn                 ThreadUnsafe,
+    0x427 SMSG_ENABLE_BARBER_SHOP                               Server Never                    InPlace,
+    0x428 SMSG_BARBER_SHOP_RESULT                               Server Never                    InPlace,
+    0x429 CMSG_CALENDAR_GET_CALENDAR                            Client LoggedIn                 ThreadUnsafe,
+    0x42A CMSG_CALENDAR_GET_EVENT                               Client LoggedIn                 ThreadUnsafe,
+    0x42B CMSG_CALENDAR_GUILD_FILTER                            Client LoggedIn                 ThreadUnsafe,
+    0x42C CMSG_CALENDAR_ARENA_TEAM                              Client LoggedIn                 ThreadUnsafe,
+    0x42D CMSG_CALENDAR_ADD_EVENT                               Client LoggedIn                 ThreadUnsafe,
+    0x42E CMSG_CALENDAR_UPDATE_EVENT                            Client LoggedIn                 ThreadUnsafe,
+    0x42F CMSG_CALENDAR_REMOVE_EVENT                            Client LoggedIn                 ThreadUnsafe,
+    0x430 CMSG_CALENDAR_COPY_EVENT                              Client LoggedIn                 ThreadUnsafe,
+    0x431 CMSG_CALENDAR_EVENT_INVITE                            Client LoggedIn                 ThreadUnsafe,
+    0x432 CMSG_CALENDAR_EVENT_RSVP                              Client LoggedIn                 ThreadUnsafe,
+    0x433 CMSG_CALENDAR_EVENT_REMOVE_INVITE                     Client LoggedIn                 ThreadUnsafe,
+    0x434 CMSG_CALENDAR_EVENT_STATUS                            Client LoggedIn                 ThreadUnsafe,
+    0x435 CMSG_CALENDAR_EVENT_MODERATOR_STATUS                  Client LoggedIn                 ThreadUnsafe,
+    0x436 SMSG_CALENDAR_SEND_CALENDAR                           Server Never                    InPlace,
+    0x437 SMSG_CALENDAR_SEND_EVENT                              Server Never                    InPlace,
+    0x438 SMSG_CALENDAR_FILTER_GUILD                            Server Never                    InPlace,
+    0x439 SMSG_CALENDAR_ARENA_TEAM                              Server Never                    InPlace,
+    0x43A SMSG_CALENDAR_EVENT_INVITE                            Server Never                    InPlace,
+    0x43B SMSG_CALENDAR_EVENT_INVITE_REMOVED                    Server Never                    InPlace,
+    0x43C SMSG_CALENDAR_EVENT_STATUS                            Server Never                    InPlace,
+    0x43D SMSG_CALENDAR_COMMAND_RESULT                          Server Never                    InPlace,
+    0x43E SMSG_CALENDAR_RAID_LOCKOUT_ADDED                      Server Never                    InPlace,
+    0x43F SMSG_CALENDAR_RAID_LOCKOUT_REMOVED                    Server Never                    InPlace,
+    0x440 SMSG_CALENDAR_EVENT_INVITE_ALERT                      Server Never                    InPlace,
+    0x441 SMSG_CALENDAR_EVENT_INVITE_REMOVED_ALERT              Server Never                    InPlace,
+    0x442 SMSG_CALENDAR_EVENT_INVITE_STATUS_ALERT               Server Never                    InPlace,
+    0x443 SMSG_CALENDAR_EVENT_REMOVED_ALERT                     Server Never                    InPlace,
+    0x444 SMSG_CALENDAR_EVENT_UPDATED_ALERT                     Server Never                    InPlace,
+    0x445 SMSG_CALENDAR_EVENT_MODERATOR_STATUS_ALERT            Server Never                    InPlace,
+    0x446 CMSG_CALENDAR_COMPLAIN                                Client LoggedIn                 ThreadUnsafe,
+    0x447 CMSG_CALENDAR_GET_NUM_PENDING                         Client LoggedIn                 ThreadUnsafe,
+    0x448 SMSG_CALENDAR_SEND_NUM_PENDING                        Server Never                    InPlace,
+    0x449 CMSG_SAVE_DANCE                                       Client Never                    InPlace,
+    0x44A SMSG_NOTIFY_DANCE                                     Server Never                    InPlace,
+    0x44B CMSG_PLAY_DANCE                                       Client Never                    InPlace,
+    0x44C SMSG_PLAY_DANCE                                       Server Never                    InPlace,
+    0x44D CMSG_LOAD_DANCES                                      Client Never                    InPlace,
+    0x44E CMSG_STOP_DANCE                                       Client Never                    InPlace,
+    0x44F SMSG_STOP_DANCE                                       Server Never                    InPlace,
+    0x450 CMSG_SYNC_DANCE                                       Client Never                    InPlace,
+    0x451 CMSG_DANCE_QUERY                                      Client Never                    InPlace,
+    0x452 SMSG_DANCE_QUERY_RESPONSE                             Server Never                    InPlace,
+    0x453 SMSG_INVALIDATE_DANCE                                 Server Never                    InPlace,
+    0x454 CMSG_DELETE_DANCE                                     Client Never                    InPlace,
+    0x455 SMSG_LEARNED_DANCE_MOVES                              Server Never                    InPlace,
+    0x456 CMSG_LEARN_DANCE_MOVE                                 Client Never                    InPlace,
+    0x457 CMSG_UNLEARN_DANCE_MOVE                               Client Never                    InPlace,
+    0x458 CMSG_SET_RUNE_COUNT                                   Client Never                    InPlace,
+    0x459 CMSG_SET_RUNE_COOLDOWN                                Client Never                    InPlace,
+    0x45A MSG_MOVE_SET_PITCH_RATE_CHEAT                         Both   Never                    InPlace,
+    0x45B MSG_MOVE_SET_PITCH_RATE                               Both   Never                    InPlace,
+    0x45C SMSG_FORCE_PITCH_RATE_CHANGE                          Server Never                    InPlace,
+    0x45D CMSG_FORCE_PITCH_RATE_CHANGE_ACK                      Client LoggedIn                 ThreadSafe,
+    0x45E SMSG_SPLINE_SET_PITCH_RATE                            Server Never                    InPlace,
+    0x45F CMSG_CALENDAR_EVENT_INVITE_NOTES                      Client Never                    InPlace,
+    0x460 SMSG_CALENDAR_EVENT_INVITE_NOTES                      Server Never                    InPlace,
+    0x461 SMSG_CALENDAR_EVENT_INVITE_NOTES_ALERT                Server Never                    InPlace,
+    0x462 CMSG_UPDATE_MISSILE_TRAJECTORY                        Client LoggedIn                 ThreadUnsafe,
+    0x463 SMSG_UPDATE_ACCOUNT_DATA_COMPLETE                     Server Never                    InPlace,
+    0x464 SMSG_TRIGGER_MOVIE                                    Server Never                    InPlace,
+    0x465 CMSG_COMPLETE_MOVIE                                   Client LoggedIn                 ThreadUnsafe,
+    0x466 CMSG_SET_GLYPH_SLOT                                   Client Never                    InPlace,
+    0x467 CMSG_SET_GLYPH                                        Client Never                    InPlace,
+    0x468 SMSG_ACHIEVEMENT_EARNED                               Server Never                    InPlace,
+    0x469 SMSG_DYNAMIC_DROP_ROLL_RESULT                         Server Never                    InPlace,
+    0x46A SMSG_CRITERIA_UPDATE                                  Server Never                    InPlace,
+    0x46B CMSG_QUERY_INSPECT_ACHIEVEMENTS                       Client LoggedIn                 InPlace,
+    0x46C SMSG_RESPOND_INSPECT_ACHIEVEMENTS                     Server Never                    InPlace,
+    0x46D CMSG_DISMISS_CONTROLLED_VEHICLE                       Client LoggedIn                 InPlace,
+    0x46E CMSG_COMPLETE_ACHIEVEMENT_CHEAT                       Client Never                    InPlace,
+    0x46F SMSG_QUESTUPDATE_ADD_PVP_KILL                         Server Never                    InPlace,
+    0x470 CMSG_SET_CRITERIA_CHEAT                               Client Never                    InPlace,
+    0x471 SMSG_CALENDAR_RAID_LOCKOUT_UPDATED                    Server Never                    InPlace,
+    0x472 CMSG_UNITANIMTIER_CHEAT                               Client Never                    InPlace,
+    0x473 CMSG_CHAR_CUSTOMIZE                                   Client Authed                   ThreadUnsafe,
+    0x474 SMSG_CHAR_CUSTOMIZE                                   Server Never                    InPlace,
+    0x475 SMSG_PET_RENAMEABLE                                   Server Never                    InPlace,
+    0x476 CMSG_REQUEST_VEHICLE_EXIT                             Client LoggedIn                 InPlace,
+    0x477 CMSG_REQUEST_VEHICLE_PREV_SEAT                        Client LoggedIn                 InPlace,
+    0x478 CMSG_REQUEST_VEHICLE_NEXT_SEAT                        Client LoggedIn                 InPlace,
+    0x479 CMSG_REQUEST_VEHICLE_SWITCH_SEAT                      Client LoggedIn                 InPlace,
+    0x47A CMSG_PET_LEARN_TALENT                                 Client LoggedIn                 InPlace,
+    0x47B CMSG_PET_UNLEARN_TALENTS                              Client Never                    InPlace,
+    0x47C SMSG_SET_PHASE_SHIFT                                  Server Never                    InPlace,
+    0x47D SMSG_ALL_ACHIEVEMENT_DATA                             Server Never                    InPlace,
+    0x47E CMSG_FORCE_SAY_CHEAT                                  Client Never                    InPlace,
+    0x47F SMSG_HEALTH_UPDATE                                    Server Never                    InPlace,
+    0x480 SMSG_POWER_UPDATE                                     Server Never                    InPlace,
+    0x481 CMSG_GAMEOBJ_REPORT_USE                               Client LoggedIn                 InPlace,
+    0x482 SMSG_HIGHEST_THREAT_UPDATE                            Server Never                    InPlace,
+    0x483 SMSG_THREAT_UPDATE                                    Server Never                    InPlace,
+    0x484 SMSG_THREAT_REMOVE                                    Server Never                    InPlace,
+    0x485 SMSG_THREAT_CLEAR                                     Server Never                    InPlace,
+    0x486 SMSG_CONVERT_RUNE                                     Server Never                    InPlace,
+    0x487 SMSG_RESYNC_RUNES                                     Server Never                    InPlace,
+    0x488 SMSG_ADD_RUNE_POWER                                   Server Never                    InPlace,
+    0x489 CMSG_START_QUEST                                      Client Never                    InPlace,
+    0x48A CMSG_REMOVE_GLYPH                                     Client LoggedIn                 InPlace,
+    0x48B CMSG_DUMP_OBJECTS                                     Client Never                    InPlace,
+    0x48C SMSG_DUMP_OBJECTS_DATA                                Server Never                    InPlace,
+    0x48D CMSG_DISMISS_CRITTER                                  Client LoggedIn                 ThreadUnsafe,
+    0x48E SMSG_NOTIFY_DEST_LOC_SPELL_CAST                       Server Never                    InPlace,
+    0x48F CMSG_AUCTION_LIST_PENDING_SALES                       Client LoggedIn                 ThreadUnsafe,
+    0x490 SMSG_AUCTION_LIST_PENDING_SALES                       Server Never                    InPlace,
+    0x491 SMSG_MODIFY_COOLDOWN                                  Server Never                    InPlace,
+    0x492 SMSG_PET_UPDATE_COMBO_POINTS                          Server Never                    InPlace,
+    0x493 CMSG_ENABLETAXI                                       Client LoggedIn                 ThreadSafe,
+    0x494 SMSG_PRE_RESURRECT                                    Server Never                    InPlace,
+    0x495 SMSG_AURA_UPDATE_ALL                                  Server Never                    InPlace,
+    0x496 SMSG_AURA_UPDATE                                      Server Never                    InPlace,
+    0x497 CMSG_FLOOD_GRACE_CHEAT                                Client Never                    InPlace,
+    0x498 SMSG_SERVER_FIRST_ACHIEVEMENT                         Server Never                    InPlace,
+    0x499 SMSG_PET_LEARNED_SPELL                                Server Never                    InPlace,
+    0x49A SMSG_PET_UNLEARNED_SPELL                              Server Never                    InPlace,
+    0x49B CMSG_CHANGE_SEATS_ON_CONTROLLED_VEHICLE               Client LoggedIn                 InPlace,
+    0x49C CMSG_HEARTH_AND_RESURRECT                             Client LoggedIn                 ThreadUnsafe,
+    0x49D SMSG_ON_CANCEL_EXPECTED_RIDE_VEHICLE_AURA             Server Never                    InPlace,
+    0x49E SMSG_CRITERIA_DELETED                                 Server Never                    InPlace,
+    0x49F SMSG_ACHIEVEMENT_DELETED                              Server Never                    InPlace,
+    0x4A0 CMSG_SERVER_INFO_QUERY                                Client Never                    InPlace,
+    0x4A1 SMSG_SERVER_INFO_RESPONSE                             Server Never                    InPlace,
+    0x4A2 CMSG_CHECK_LOGIN_CRITERIA                             Client Never                    InPlace,
+    0x4A3 SMSG_SERVER_BUCK_DATA_START                           Server Never                    InPlace,
+    0x4A4 CMSG_SET_BREATH                                       Client Never                    InPlace,
+    0x4A5 CMSG_QUERY_VEHICLE_STATUS                             Client Never                    InPlace,
+    0x4A6 SMSG_BATTLEGROUND_INFO_THROTTLED                      Server Never                    InPlace,
+    0x4A7 SMSG_PLAYER_VEHICLE_DATA                              Server Never                    InPlace,
+    0x4A8 CMSG_PLAYER_VEHICLE_ENTER                             Client LoggedIn                 ThreadUnsafe,
+    0x4A9 CMSG_CONTROLLER_EJECT_PASSENGER                       Client LoggedIn                 ThreadUnsafe,
+    0x4AA SMSG_PET_GUIDS                                        Server Never                    InPlace,
+    0x4AB SMSG_CLIENTCACHE_VERSION                              Server Never                    InPlace,
+    0x4AC CMSG_CHANGE_GDF_ARENA_RATING                          Client Never                    InPlace,
+    0x4AD CMSG_SET_ARENA_TEAM_RATING_BY_INDEX                   Client Never                    InPlace,
+    0x4AE CMSG_SET_ARENA_TEAM_WEEKLY_GAMES                      Client Never                    InPlace,
+    0x4AF CMSG_SET_ARENA_TEAM_SEASON_GAMES                      Client Never                    InPlace,
+    0x4B0 CMSG_SET_ARENA_MEMBER_WEEKLY_GAMES                    Client Never                    InPlace,
+    0x4B1 CMSG_SET_ARENA_MEMBER_SEASON_GAMES                    Client Never                    InPlace,
+    0x4B2 SMSG_ITEM_REFUND_INFO_RESPONSE                        Server Never                    InPlace,
+    0x4B3 CMSG_ITEM_REFUND_INFO                                 Client LoggedIn                 InPlace,
+    0x4B4 CMSG_ITEM_REFUND                                      Client LoggedIn                 InPlace,
+    0x4B5 SMSG_ITEM_REFUND_RESULT                               Server Never                    InPlace,
+    0x4B6 CMSG_CORPSE_MAP_POSITION_QUERY                        Client LoggedIn                 ThreadUnsafe,
+    0x4B7 SMSG_CORPSE_MAP_POSITION_QUERY_RESPONSE               Server Never                    InPlace,
+    0x4B8 CMSG_UNUSED5                                          Client LoggedIn                 ThreadUnsafe,
+    0x4B9 CMSG_UNUSED6                                          Client Never                    InPlace,
+    0x4BA CMSG_CALENDAR_EVENT_SIGNUP                            Client LoggedIn                 ThreadUnsafe,
+    0x4BB SMSG_CALENDAR_CLEAR_PENDING_ACTION                    Server Never                    InPlace,
+    0x4BC SMSG_EQUIPMENT_SET_LIST                               Server Never                    InPlace,
+    0x4BD CMSG_EQUIPMENT_SET_SAVE                               Client LoggedIn                 ThreadUnsafe,
+    0x4BE CMSG_UPDATE_PROJECTILE_POSITION                       Client LoggedIn                 ThreadUnsafe,
+    0x4BF SMSG_SET_PROJECTILE_POSITION                          Server Never                    InPlace,
+    0x4C0 SMSG_TALENTS_INFO                                     Server Never                    InPlace,
+    0x4C1 CMSG_LEARN_PREVIEW_TALENTS                            Client LoggedIn                 InPlace,
+    0x4C2 CMSG_LEARN_PREVIEW_TALENTS_PET                        Client LoggedIn                 InPlace,
+    0x4C3 CMSG_SET_ACTIVE_TALENT_GROUP_OBSOLETE                 Client Never                    InPlace,
+    0x4C4 CMSG_GM_GRANT_ACHIEVEMENT                             Client Never                    InPlace,
+    0x4C5 CMSG_GM_REMOVE_ACHIEVEMENT                            Client Never                    InPlace,
+    0x4C6 CMSG_GM_SET_CRITERIA_FOR_PLAYER                       Client Never                    InPlace,
+    0x4C7 SMSG_ARENA_UNIT_DESTROYED                             Server Never                    InPlace,
+    0x4C8 SMSG_ARENA_TEAM_CHANGE_FAILED_QUEUED                  Server Never                    InPlace,
+    0x4C9 CMSG_PROFILEDATA_REQUEST                              Client Never                    InPlace,
+    0x4CA SMSG_PROFILEDATA_RESPONSE                             Server Never                    InPlace,
+    0x4CB CMSG_START_BATTLEFIELD_CHEAT                          Client Never                    InPlace,
+    0x4CC CMSG_END_BATTLEFIELD_CHEAT                            Client Never                    InPlace,
+    0x4CD SMSG_MULTIPLE_PACKETS                                 Server Never                    InPlace,
+    0x4CE SMSG_MOVE_GRAVITY_DISABLE                             Server Never                    InPlace,
+    0x4CF CMSG_MOVE_GRAVITY_DISABLE_ACK                         Client LoggedIn                 ThreadSafe,
+    0x4D0 SMSG_MOVE_GRAVITY_ENABLE                              Server Never                    InPlace,
+    0x4D1 CMSG_MOVE_GRAVITY_ENABLE_ACK                          Client LoggedIn                 ThreadSafe,
+    0x4D2 MSG_MOVE_GRAVITY_CHNG                                 Server Never                    InPlace,
+    0x4D3 SMSG_SPLINE_MOVE_GRAVITY_DISABLE                      Server Never                    InPlace,
+    0x4D4 SMSG_SPLINE_MOVE_GRAVITY_ENABLE                       Server Never                    InPlace,
+    0x4D5 CMSG_EQUIPMENT_SET_USE                                Client LoggedIn                 InPlace,
+    0x4D6 SMSG_EQUIPMENT_SET_USE_RESULT                         Server Never                    InPlace,
+    0x4D7 CMSG_FORCE_ANIM                                       Client Never                    InPlace,
+    0x4D8 SMSG_FORCE_ANIM                                       Server Never                    InPlace,
+    0x4D9 CMSG_CHAR_FACTION_CHANGE                              Client Authed                   ThreadUnsafe,
+    0x4DA SMSG_CHAR_FACTION_CHANGE                              Server Never                    InPlace,
+    0x4DB CMSG_PVP_QUEUE_STATS_REQUEST                          Client Never                    InPlace,
+    0x4DC SMSG_PVP_QUEUE_STATS                                  Server Never                    InPlace,
+    0x4DD CMSG_SET_PAID_SERVICE_CHEAT                           Client Never                    InPlace,
+    0x4DE SMSG_BATTLEFIELD_MGR_ENTRY_INVITE                     Server Never                    InPlace,
+    0x4DF CMSG_BATTLEFIELD_MGR_ENTRY_INVITE_RESPONSE            Client LoggedIn                 ThreadUnsafe,
+    0x4E0 SMSG_BATTLEFIELD_MGR_ENTERED                          Server Never                    InPlace,
+    0x4E1 SMSG_BATTLEFIELD_MGR_QUEUE_INVITE                     Server Never                    InPlace,
+    0x4E2 CMSG_BATTLEFIELD_MGR_QUEUE_INVITE_RESPONSE            Client LoggedIn                 InPlace,
+    0x4E3 CMSG_BATTLEFIELD_MGR_QUEUE_REQUEST                    Client Never                    InPlace,
+    0x4E4 SMSG_BATTLEFIELD_MGR_QUEUE_REQUEST_RESPONSE           Server Never                    InPlace,
+    0x4E5 SMSG_BATTLEFIELD_MGR_EJECT_PENDING                    Server Never                    InPlace,
+    0x4E6 SMSG_BATTLEFIELD_MGR_EJECTED                          Server Never                    InPlace,
+    0x4E7 CMSG_BATTLEFIELD_MGR_EXIT_REQUEST                     Client LoggedIn                 InPlace,
+    0x4E8 SMSG_BATTLEFIELD_MGR_STATE_CHANGE                     Server Never                    InPlace,
+    0x4E9 CMSG_BATTLEFIELD_MANAGER_ADVANCE_STATE                Client Never                    InPlace,
+    0x4EA CMSG_BATTLEFIELD_MANAGER_SET_NEXT_TRANSITION_TIME     Client Never                    InPlace,
+    0x4EB MSG_SET_RAID_DIFFICULTY                               Both   LoggedIn                 ThreadUnsafe,
+    0x4EC CMSG_TOGGLE_XP_GAIN                                   Client Never                    InPlace,
+    0x4ED SMSG_TOGGLE_XP_GAIN                                   Server Never                    InPlace,
+    0x4EE SMSG_GMRESPONSE_DB_ERROR                              Server Never                    InPlace,
+    0x4EF SMSG_GMRESPONSE_RECEIVED                              Server Never                    InPlace,
+    0x4F0 CMSG_GMRESPONSE_RESOLVE                               Client LoggedIn                 ThreadUnsafe,
+    0x4F1 SMSG_GMRESPONSE_STATUS_UPDATE                         Server Never                    InPlace,
+    0x4F2 SMSG_GMRESPONSE_CREATE_TICKET                         Server Never                    InPlace,
+    0x4F3 CMSG_GMRESPONSE_CREATE_TICKET                         Client Never                    InPlace,
+    0x4F4 CMSG_SERVERINFO                                       Client Never                    InPlace,
+    0x4F5 SMSG_SERVERINFO                                       Server Never                    InPlace,
+    0x4F6 CMSG_WORLD_STATE_UI_TIMER_UPDATE                      Client LoggedIn                 InPlace,
+    0x4F7 SMSG_WORLD_STATE_UI_TIMER_UPDATE                      Server Never                    InPlace,
+    0x4F8 CMSG_CHAR_RACE_CHANGE                                 Client Authed                   ThreadUnsafe,
+    0x4F9 MSG_VIEW_PHASE_SHIFT                                  Both   Never                    InPlace,
+    0x4FA SMSG_TALENTS_INVOLUNTARILY_RESET                      Server Never                    InPlace,
+    0x4FB CMSG_DEBUG_SERVER_GEO                                 Client Never                    InPlace,
+    0x4FC SMSG_DEBUG_SERVER_GEO                                 Server Never                    InPlace,
+    0x4FD SMSG_LOOT_SLOT_CHANGED                                Server Never                    InPlace,
+    0x4FE UMSG_UPDATE_GROUP_INFO                                Both   Never                    InPlace,
+    0x4FF CMSG_READY_FOR_ACCOUNT_DATA_TIMES                     Client Authed                   ThreadUnsafe,
+    0x500 CMSG_QUERY_QUESTS_COMPLETED                           Client LoggedIn                 InPlace,
+    0x501 SMSG_QUERY_QUESTS_COMPLETED_RESPONSE                  Server Never                    InPlace,
+    0x502 CMSG_GM_REPORT_LAG                                    Client LoggedIn                 ThreadUnsafe,
+    0x503 CMSG_AFK_MONITOR_INFO_REQUEST                         Client Never                    InPlace,
+    0x504 SMSG_AFK_MONITOR_INFO_RESPONSE                        Server Never                    InPlace,
+    0x505 CMSG_AFK_MONITOR_INFO_CLEAR                           Client Never                    InPlace,
+    0x506 SMSG_CORPSE_NOT_IN_INSTANCE                           Server Never                    InPlace,
+    0x507 CMSG_GM_NUKE_CHARACTER                                Client Never                    InPlace,
+    0x508 CMSG_SET_ALLOW_LOW_LEVEL_RAID1                        Client Never                    InPlace,
+    0x509 CMSG_SET_ALLOW_LOW_LEVEL_RAID2                        Client Never                    InPlace,
+    0x50A SMSG_CAMERA_SHAKE                                     Server Never                    InPlace,
+    0x50B SMSG_SOCKET_GEMS_RESULT                               Server Never                    InPlace,
+    0x50C CMSG_SET_CHARACTER_MODEL                              Client Never                    InPlace,
+    0x50D SMSG_REDIRECT_CLIENT                                  Server Never                    InPlace,
+    0x50E CMSG_REDIRECTION_FAILED                               Client Never                    InPlace,
+    0x50F SMSG_SUSPEND_COMMS                                    Server Never                    InPlace,
+    0x510 CMSG_SUSPEND_COMMS_ACK                                Client Never                    InPlace,
+    0x511 SMSG_FORCE_SEND_QUEUED_PACKETS                        Server Never                    InPlace,
+    0x512 CMSG_REDIRECTION_AUTH_PROOF                           Client Never                    InPlace,
+    0x513 CMSG_DROP_NEW_CONNECTION                              Client Never                    InPlace,
+    0x514 SMSG_SEND_ALL_COMBAT_LOG                              Server Never                    InPlace,
+    0x515 SMSG_OPEN_LFG_DUNGEON_FINDER                          Server Never                    InPlace,
+    0x516 SMSG_MOVE_SET_COLLISION_HGT                           Server Never                    InPlace,
+    0x517 CMSG_MOVE_SET_COLLISION_HGT_ACK                       Client LoggedIn                 ThreadSafe,
+    0x518 MSG_MOVE_SET_COLLISION_HGT                            Both   Never                    InPlace,
+    0x519 CMSG_CLEAR_RANDOM_BG_WIN_TIME                         Client Never                    InPlace,
+    0x51A CMSG_CLEAR_HOLIDAY_BG_WIN_TIME                        Client Never                    InPlace,
+    0x51B CMSG_COMMENTATOR_SKIRMISH_QUEUE_COMMAND               Client Never                    InPlace,
+    0x51C SMSG_COMMENTATOR_SKIRMISH_QUEUE_RESULT1               Server Never                    InPlace,
+    0x51D SMSG_COMMENTATOR_SKIRMISH_QUEUE_RESULT2               Server Never                    InPlace,
+    0x51E SMSG_MULTIPLE_MOVES                                   Server Never                    InPlace,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::opcodes::{
+        opcode_name_for_logging, Admission, Direction, Opcode, PacketProcessing, SessionState,
+        SessionStatus, OPCODES,
+    };
+
+    #[test]
+    pub fn opcodes_are_looked_up() {
+        assert_eq!(OPCODES.len(), 1310);
+        assert!(OPCODES
+            .windows(2)
+            .all(|pair| (pair[0].opcode as u16) < (pair[1].opcode as u16)));
+
+        let ping = Opcode::try_from(0x1DC).unwrap();
+        assert_eq!(ping, Opcode::CMSG_PING);
+        assert_eq!(ping.name(), "CMSG_PING");
+        assert_eq!(ping.info().direction, Direction::Client);
+        assert_eq!(Opcode::SMSG_PONG.info().direction, Direction::Server);
+        assert_eq!(Opcode::MSG_MOVE_JUMP.info().direction, Direction::Both);
+        assert_eq!(
+            Opcode::MSG_MOVE_JUMP.info().processing,
+            PacketProcessing::ThreadSafe
+        );
+        assert_eq!(Opcode::CMSG_CHAR_ENUM.info().status, SessionStatus::Authed);
+        assert_eq!(Opcode::try_from(0x51F), Err(0x51F));
+        assert_eq!(Opcode::try_from(0), Err(0));
+
+        assert_eq!(ping.to_string(), "[CMSG_PING 0x01DC (476)]");
+        assert_eq!(
+            opcode_name_for_logging(0x600),
+            "[UNKNOWN OPCODE 0x0600 (1536)]"
+        );
+    }
+
+    #[test]
+    pub fn admissions() {
+        use SessionState::{Authed, LoggedIn, RecentlyLoggedOut, Transfer, Unauthenticated};
+        let admission = |opcode: Opcode, state| opcode.info().admission(state);
+
+        assert_eq!(
+            admission(Opcode::CMSG_AUTH_SESSION, Unauthenticated),
+            Admission::Handle
+        );
+        assert_eq!(
+            admission(Opcode::CMSG_PING, Unauthenticated),
+            Admission::Close
+        );
+        assert_eq!(
+            admission(Opcode::CMSG_CHAR_ENUM, Unauthenticated),
+            Admission::Close
+        );
+        assert_eq!(
+            admission(Opcode::CMSG_KEEP_ALIVE, Unauthenticated),
+            Admission::Close
+        );
+
+        assert_eq!(
+            admission(Opcode::CMSG_AUTH_SESSION, Authed),
+            Admission::Close
+        );
+        assert_eq!(
+            admission(Opcode::CMSG_KEEP_ALIVE, Authed),
+            Admission::Handle
+        );
+        assert_eq!(admission(Opcode::CMSG_PING, Transfer), Admission::Handle);
+        assert_eq!(admission(Opcode::CMSG_CHAR_ENUM, Authed), Admission::Handle);
+        assert_eq!(
+            admission(Opcode::CMSG_CHAR_ENUM, LoggedIn),
+            Admission::Handle
+        );
+        assert_eq!(admission(Opcode::SMSG_PONG, Authed), Admission::Ignore);
+        assert_eq!(admission(Opcode::CMSG_BOOTME, LoggedIn), Admission::Ignore);
+
+        // LoggedIn packets wait in C++ for the player to enter the world
+        let logged_in = Opcode::CMSG_NAME_QUERY;
+        assert_eq!(logged_in.info().status, SessionStatus::LoggedIn);
+        assert_eq!(admission(logged_in, Authed), Admission::Handle);
+        assert_eq!(admission(logged_in, LoggedIn), Admission::Handle);
+        assert_eq!(admission(logged_in, Transfer), Admission::Ignore);
+        assert_eq!(admission(logged_in, RecentlyLoggedOut), Admission::Ignore);
+
+        let transfer = Opcode::MSG_MOVE_WORLDPORT_ACK;
+        assert_eq!(transfer.info().status, SessionStatus::Transfer);
+        assert_eq!(admission(transfer, Transfer), Admission::Handle);
+        assert_eq!(admission(transfer, LoggedIn), Admission::Ignore);
+        assert_eq!(admission(transfer, Authed), Admission::Ignore);
+
+        let logout = Opcode::CMSG_CANCEL_TRADE;
+        assert_eq!(
+            logout.info().status,
+            SessionStatus::LoggedInOrRecentlyLogout
+        );
+        assert_eq!(admission(logout, RecentlyLoggedOut), Admission::Handle);
+        assert_eq!(admission(logout, LoggedIn), Admission::Handle);
+        assert_eq!(admission(logout, Authed), Admission::Ignore);
+
+        assert_eq!(SessionState::try_from(3), Ok(Transfer));
+        assert_eq!(SessionState::try_from(5), Err(5));
+    }
+}
@@ -0,0 +1,310 @@
+//! Framing of the world protocol, and the packets of the authentication handshake.
+
+use crate::crypt::AuthCrypt;
+use crate::opcodes::Opcode;
+use anyhow::{bail, Error, Result};
+use bincode::config::{self as bincode_config, Configuration, Fixint, LittleEndian};
+use bytes::{Buf, Bytes, BytesMut};
+use kitros_derive::wow_world_packet;
+
+/// Wire encoding of the world packets: little endian, fixed size integers.
+pub const BINCODE_CONFIGURATION: Configuration<LittleEndian, Fixint> = bincode_config::standard()
+    .with_little_endian()
+    .with_fixed_int_encoding();
+
+/// Client opcodes are below this value.
+const NUM_MSG_TYPES: u32 = 0x51F;
+
+/// A world packet, see `#[wow_world_packet(opcode = <Opcode>)]`.
+pub trait WorldPacket: enturion_shared::net::WoWPacket {
+    const OPCODE: Opcode;
+}
+
+/// Size of the client headers: a big endian `u16` size then a little endian `u32` opcode.
+pub const CLIENT_HEADER_SIZE: usize = 6;
+
+/// The header of a client packet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClientHeader {
+    /// Size of the payload, without the opcode.
+    pub size: usize,
+    pub opcode: u32,
+}
+
+impl ClientHeader {
+    /// Parses a decrypted header, checking its size and opcode.
+    pub fn parse(header: &[u8; CLIENT_HEADER_SIZE]) -> Result<Self> {
+        let size = u16::from_be_bytes([header[0], header[1]]) as usize;
+        let opcode = u32::from_le_bytes([header[2], header[3], header[4], header[5]]);
+        if !(4..10240).contains(&size) || opcode >= NUM_MSG_TYPES {
+            bail!(
+                "Malformed packet header (size: {}, opcode: {:#x})",
+                size,
+                opcode
+            );
+        }
+
+        Ok(Self {
+            size: size - 4,
+            opcode,
+        })
+    }
+}
+
+/// A complete packet received from the client.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientPacket {
+    pub opcode: u32,
+    pub payload: Bytes,
+}
+
+/// Splits the data received from the client into packets.
+#[derive(Debug, Default)]
+pub struct PacketFramer {
+    /// The header of the packet being received, already decrypted.
+    header: Option<ClientHeader>,
+}
+
+impl PacketFramer {
+    /// Takes the next complete packet out of the buffer, decrypting its header with `crypt`
+    /// once the encryption is initialized. Returns `None` until the packet is complete.
+    pub fn next_packet(
+        &mut self,
+        buf: &mut BytesMut,
+        crypt: Option<&mut AuthCrypt>,
+    ) -> Result<Option<ClientPacket>> {
+        let header = match self.header {
+            Some(header) => header,
+            None if buf.len() < CLIENT_HEADER_SIZE => return Ok(None),
+            None => {
+                let mut header: [u8; CLIENT_HEADER_SIZE] =
+                    buf[..CLIENT_HEADER_SIZE].try_into().unwrap();
+                if let Some(crypt) = crypt {
+                    crypt.decrypt_recv(&mut header);
+                }
+
+                buf.advance(CLIENT_HEADER_SIZE);
+                *self.header.insert(ClientHeader::parse(&header)?)
+            }
+        };
+
+        if buf.len() < header.size {
+            return Ok(None);
+        }
+
+        self.header = None;
+        Ok(Some(ClientPacket {
+            opcode: header.opcode,
+            payload: buf.split_to(header.size).freeze(),
+        }))
+    }
+}
+
+/// Builds the header of a server packet: the size of the payload and opcode, on 2 bytes or,
+/// for packets larger than `0x7FFF`, 3 bytes flagged with `0x80`, then the `u16` opcode.
+pub fn server_header(payload_size: usize, opcode: u16) -> Vec<u8> {
+    let size = payload_size + 2;
+    let mut header = Vec::with_capacity(5);
+    if size > 0x7FFF {
+        header.push(0x80 | (size >> 16) as u8);
+    }
+
+    header.extend_from_slice(&[(size >> 8) as u8, size as u8]);
+    header.extend_from_slice(&opcode.to_le_bytes());
+    header
+}
+
+#[wow_world_packet(opcode = SMSG_AUTH_CHALLENGE, no_ffi)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthChallenge {
+    pub unknown: u32,
+    pub auth_seed: [u8; 4],
+    /// Seeds of the encryption, unused by the server.
+    pub seeds: [u8; 32],
+}
+
+impl AuthChallenge {
+    pub fn new(auth_seed: [u8; 4]) -> Self {
+        Self {
+            unknown: 1,
+            auth_seed,
+            seeds: rand::random(),
+        }
+    }
+}
+
+/// `SMSG_AUTH_RESPONSE` rejecting the authentication, with a `ResponseCodes` value.
+#[wow_world_packet(opcode = SMSG_AUTH_RESPONSE)]
+pub struct AuthResponseError {
+    pub code: u8,
+}
+
+/// Reads the little endian fields of a packet.
+struct PacketReader<'a>(&'a [u8]);
+
+impl<'a> PacketReader<'a> {
+    fn bytes(&mut self, length: usize) -> Result<&'a [u8]> {
+        if self.0.len() < length {
+            bail!("Packet too short");
+        }
+
+        let (bytes, rest) = self.0.split_at(length);
+        self.0 = rest;
+        Ok(bytes)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N]> {
+        Ok(self.bytes(N)?.try_into().unwrap())
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        Ok(u32::from_le_bytes(self.array()?))
+    }
+
+    fn c_string(&mut self) -> Result<String> {
+        let length = self
+            .0
+            .iter()
+            .position(|byte| *byte == 0)
+            .ok_or_else(|| Error::msg("Unterminated string"))?;
+        let string = String::from_utf8(self.bytes(length)?.to_vec())?;
+        self.bytes(1)?;
+        Ok(string)
+    }
+}
+
+/// The payload of `CMSG_AUTH_SESSION`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthSessionRequest {
+    pub build: u32,
+    pub login_server_id: u32,
+    pub account: String,
+    pub login_server_type: u32,
+    pub local_challenge: [u8; 4],
+    pub region_id: u32,
+    pub battlegroup_id: u32,
+    pub realm_id: u32,
+    pub dos_response: u64,
+    /// `SHA1(account | 0u32 | local challenge | auth seed | session key)`
+    pub digest: [u8; 20],
+    /// The compressed addon list.
+    pub addon_info: Vec<u8>,
+}
+
+impl AuthSessionRequest {
+    pub fn parse(payload: &[u8]) -> Result<Self> {
+        let mut reader = PacketReader(payload);
+        let request = Self {
+            build: reader.u32()?,
+            login_server_id: reader.u32()?,
+            account: reader.c_string()?,
+            login_server_type: reader.u32()?,
+            local_challenge: reader.array()?,
+            region_id: reader.u32()?,
+            battlegroup_id: reader.u32()?,
+            realm_id: reader.u32()?,
+            dos_response: u64::from_le_bytes(reader.array()?),
+            digest: reader.array()?,
+            addon_info: reader.0.to_vec(),
+        };
+
+        if request.addon_info.is_empty() {
+            bail!("Missing addon info");
+        }
+
+        Ok(request)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::crypt::AuthCrypt;
+    use crate::opcodes::Opcode;
+    use crate::packet::{
+        server_header, AuthChallenge, AuthSessionRequest, ClientHeader, PacketFramer,
+        BINCODE_CONFIGURATION,
+    };
+    use bytes::BytesMut;
+
+    #[test]
+    pub fn auth_challenge_is_encoded() {
+        let challenge = AuthChallenge::new([1, 2, 3, 4]);
+        let payload = bincode::encode_to_vec(&challenge, BINCODE_CONFIGURATION).unwrap();
+        assert_eq!(payload.len(), 40);
+        assert_eq!(payload[..8], [1, 0, 0, 0, 1, 2, 3, 4]);
+        assert_eq!(payload[8..], challenge.seeds);
+    }
+
+    #[test]
+    pub fn server_headers() {
+        assert_eq!(server_header(1, 0x1EE), [0x00, 0x03, 0xEE, 0x01]);
+        assert_eq!(server_header(0x7FFD, 0x1EE), [0x7F, 0xFF, 0xEE, 0x01]);
+        assert_eq!(
+            server_header(0x12345, 0x1EE),
+            [0x81, 0x23, 0x47, 0xEE, 0x01]
+        );
+    }
+
+    #[test]
+    pub fn auth_session_is_parsed() {
+        let mut payload = vec![];
+        payload.extend_from_slice(&12340_u32.to_le_bytes());
+        payload.extend_from_slice(&0_u32.to_le_bytes());
+        payload.extend_from_slice(b"PLAYER\0");
+        payload.extend_from_slice(&0_u32.to_le_bytes());
+        payload.extend_from_slice(&[1, 2, 3, 4]);
+        payload.extend_from_slice(&[0; 12]);
+        payload.extend_from_slice(&[0; 8]);
+        payload.extend_from_slice(&[0xAB; 20]);
+        payload.extend_from_slice(&[0x9E, 0x02, 0x00, 0x00, 0x78, 0x9C]);
+
+        let mut packet = ((payload.len() + 4) as u16).to_be_bytes().to_vec();
+        packet.extend_from_slice(&(Opcode::CMSG_AUTH_SESSION as u32).to_le_bytes());
+        packet.extend_from_slice(&payload);
+
+        let header = ClientHeader::parse(packet[..6].try_into().unwrap()).unwrap();
+        assert_eq!(header.opcode, Opcode::CMSG_AUTH_SESSION as u32);
+        assert_eq!(header.size, payload.len());
+        let request = AuthSessionRequest::parse(&payload).unwrap();
+        assert_eq!(request.build, 12340);
+        assert_eq!(request.account, "PLAYER");
+        assert_eq!(request.local_challenge, [1, 2, 3, 4]);
+        assert_eq!(request.digest, [0xAB; 20]);
+        assert_eq!(request.addon_info.len(), 6);
+
+        assert!(AuthSessionRequest::parse(&payload[..40]).is_err());
+        assert!(ClientHeader::parse(&[0x00, 0x02, 0xED, 0x01, 0x00, 0x00]).is_err());
+        assert!(ClientHeader::parse(&[0x00, 0x08, 0xFF, 0xFF, 0x00, 0x00]).is_err());
+    }
+
+    #[test]
+    pub fn packets_are_framed() {
+        let key = [0x11_u8; 40];
+        let mut client = AuthCrypt::client(&key);
+        let mut server = AuthCrypt::server(&key);
+        let mut framer = PacketFramer::default();
+        let mut buf = BytesMut::new();
+
+        // CMSG_PING, sent in two parts
+        let mut header = [0x00, 0x0C, 0xDC, 0x01, 0x00, 0x00];
+        client.encrypt_send(&mut header);
+        buf.extend_from_slice(&header);
+        buf.extend_from_slice(&[1, 0, 0, 0]);
+        assert_eq!(
+            framer.next_packet(&mut buf, Some(&mut server)).unwrap(),
+            None
+        );
+        buf.extend_from_slice(&[2, 0, 0, 0]);
+        let packet = framer
+            .next_packet(&mut buf, Some(&mut server))
+            .unwrap()
+            .unwrap();
+        assert_eq!(packet.opcode, 0x1DC);
+        assert_eq!(packet.payload.as_ref(), [1, 0, 0, 0, 2, 0, 0, 0]);
+        assert!(buf.is_empty());
+
+        // A header decrypted with the wrong keystream is rejected
+        buf.extend_from_slice(&[0x00, 0x04, 0x37, 0x00, 0x00, 0x00]);
+        assert!(framer.next_packet(&mut buf, Some(&mut server)).is_err());
+    }
+}
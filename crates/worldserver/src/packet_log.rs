@@ -0,0 +1,386 @@
+//! Logging of the world packets in the PKT 3.1 format read by WowPacketParser.
+
+use anyhow::{Error, Result};
+use enturion_shared::config::Config;
+use log::{debug, error};
+use std::fs::File;
+use std::io::Write;
+use std::net::{IpAddr, SocketAddr};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+static PACKET_LOG: OnceLock<PacketLog> = OnceLock::new();
+/// Origin of the tick counts of the log, like `getMSTime`.
+static TICKS_ORIGIN: OnceLock<Instant> = OnceLock::new();
+
+const FORMAT_VERSION: u16 = 0x0301;
+const SNIFFER_ID: u8 = b'T';
+const CLIENT_BUILD: u32 = 12340;
+const LOCALE: [u8; 4] = *b"enUS";
+
+fn ticks() -> u32 {
+    TICKS_ORIGIN.get_or_init(Instant::now).elapsed().as_millis() as u32
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PacketDirection {
+    ClientToServer,
+    ServerToClient,
+}
+
+impl PacketDirection {
+    fn tag(self) -> [u8; 4] {
+        match self {
+            PacketDirection::ClientToServer => *b"CMSG",
+            PacketDirection::ServerToClient => *b"SMSG",
+        }
+    }
+}
+
+/// Packet log settings: the log is written to `PacketLogFile` in `LogsDir`, and rotated once it
+/// gets larger than `PacketLogMaxSize` MB.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct PacketLogConfig {
+    /// No packets are logged without a file.
+    pub path: Option<PathBuf>,
+    /// Size in bytes after which the file is rotated, never if 0.
+    pub max_size: u64,
+}
+
+impl PacketLogConfig {
+    pub fn from_config(config: &Config) -> Result<Self> {
+        let file = config.get("PacketLogFile", Some(String::new()))?;
+        let directory = config.get("LogsDir", Some(String::new()))?;
+        let max_size = config.get("PacketLogMaxSize", Some(0_u64))?;
+
+        Ok(Self {
+            path: (!file.is_empty()).then(|| Path::new(&directory).join(file)),
+            max_size: max_size * 1024 * 1024,
+        })
+    }
+}
+
+/// The header of a log file.
+fn file_header() -> Vec<u8> {
+    let start = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+
+    let mut header = Vec::with_capacity(66);
+    header.extend_from_slice(b"PKT");
+    header.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+    header.push(SNIFFER_ID);
+    header.extend_from_slice(&CLIENT_BUILD.to_le_bytes());
+    header.extend_from_slice(&LOCALE);
+    // The session key is not logged
+    header.extend_from_slice(&[0; 40]);
+    header.extend_from_slice(&(start.as_secs() as u32).to_le_bytes());
+    header.extend_from_slice(&ticks().to_le_bytes());
+    // No optional data
+    header.extend_from_slice(&0_u32.to_le_bytes());
+    header
+}
+
+/// A packet record: its header, with the address of the connection as optional data, then the
+/// opcode and payload.
+fn packet_record(
+    direction: PacketDirection,
+    connection_id: u32,
+    address: &SocketAddr,
+    opcode: u32,
+    payload: &[u8],
+) -> Vec<u8> {
+    let mut ip = [0; 16];
+    match address.ip().to_canonical() {
+        IpAddr::V4(v4) => ip[..4].copy_from_slice(&v4.octets()),
+        IpAddr::V6(v6) => ip = v6.octets(),
+    }
+
+    let mut record = Vec::with_capacity(44 + payload.len());
+    record.extend_from_slice(&direction.tag());
+    record.extend_from_slice(&connection_id.to_le_bytes());
+    record.extend_from_slice(&ticks().to_le_bytes());
+    record.extend_from_slice(&20_u32.to_le_bytes());
+    record.extend_from_slice(&(payload.len() as u32 + 4).to_le_bytes());
+    record.extend_from_slice(&ip);
+    record.extend_from_slice(&(address.port() as u32).to_le_bytes());
+    record.extend_from_slice(&opcode.to_le_bytes());
+    record.extend_from_slice(payload);
+    record
+}
+
+/// The path a full log file is moved to: the first free `<name>.<index>.<extension>`.
+fn rotated_path(path: &Path) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let extension = path.extension().unwrap_or_default().to_string_lossy();
+    (1..)
+        .map(|index| path.with_file_name(format!("{}.{}.{}", stem, index, extension)))
+        .find(|rotated| !rotated.exists())
+        .unwrap()
+}
+
+struct LogFile {
+    file: File,
+    size: u64,
+}
+
+impl LogFile {
+    /// Creates the log file, rotating the log of an earlier run so that it is kept.
+    fn create(path: &Path) -> Result<Self> {
+        let context = |e: std::io::Error| {
+            Error::msg(format!("Cannot open packet log {}: {}", path.display(), e))
+        };
+
+        if path.metadata().is_ok_and(|metadata| metadata.len() > 0) {
+            std::fs::rename(path, rotated_path(path)).map_err(context)?;
+        }
+
+        let header = file_header();
+        let mut file = File::create(path).map_err(context)?;
+        file.write_all(&header).map_err(context)?;
+        debug!(target: "network", "Logging packets to {}", path.display());
+        Ok(Self {
+            file,
+            size: header.len() as u64,
+        })
+    }
+}
+
+#[derive(Default)]
+struct PacketLogState {
+    config: PacketLogConfig,
+    file: Option<LogFile>,
+}
+
+/// The packet log of the world sockets, which can be started and stopped at runtime.
+#[derive(Default)]
+pub struct PacketLog {
+    enabled: AtomicBool,
+    state: Mutex<PacketLogState>,
+}
+
+impl PacketLog {
+    pub fn global() -> &'static PacketLog {
+        PACKET_LOG.get_or_init(PacketLog::default)
+    }
+
+    pub fn configure(&self, config: &Config) -> Result<()> {
+        self.set_config(PacketLogConfig::from_config(config)?)
+    }
+
+    /// Applies the settings, starting the log if it has a file.
+    /// The log goes on in the same file, started or stopped, while the path does not change.
+    pub fn set_config(&self, config: PacketLogConfig) -> Result<()> {
+        let mut state = self.state.lock().unwrap();
+        if state.config.path == config.path {
+            state.config = config;
+            return Ok(());
+        }
+
+        let enabled = config.path.is_some();
+        *state = PacketLogState { config, file: None };
+        self.enabled.store(false, Ordering::Relaxed);
+        drop(state);
+
+        self.set_enabled(enabled)
+    }
+
+    /// Starts or stops the log. Starting it fails without a configured file.
+    pub fn set_enabled(&self, enabled: bool) -> Result<()> {
+        let mut state = self.state.lock().unwrap();
+        if !enabled {
+            state.file = None;
+        } else if state.file.is_none() {
+            let Some(path) = &state.config.path else {
+                return Err(Error::msg("No PacketLogFile configured"));
+            };
+
+            state.file = Some(LogFile::create(path)?);
+        }
+
+        self.enabled.store(state.file.is_some(), Ordering::Relaxed);
+        Ok(())
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    /// Logs a packet, as sent or received on the wire, headers aside.
+    pub fn log(
+        &self,
+        direction: PacketDirection,
+        connection_id: u32,
+        address: &SocketAddr,
+        opcode: u32,
+        payload: &[u8],
+    ) {
+        if !self.is_enabled() {
+            return;
+        }
+
+        let record = packet_record(direction, connection_id, address, opcode, payload);
+        let mut state = self.state.lock().unwrap();
+        if let Err(e) = self.write(&mut state, &record) {
+            error!(target: "network", "Stopping the packet log: {}", e);
+            state.file = None;
+            self.enabled.store(false, Ordering::Relaxed);
+        }
+    }
+
+    fn write(&self, state: &mut PacketLogState, record: &[u8]) -> Result<()> {
+        let PacketLogState { config, file } = state;
+        let (Some(path), Some(log_file)) = (&config.path, file.as_mut()) else {
+            return Ok(());
+        };
+
+        if config.max_size > 0 && log_file.size + record.len() as u64 > config.max_size {
+            *log_file = LogFile::create(path)?;
+        }
+
+        log_file.file.write_all(record)?;
+        log_file.size += record.len() as u64;
+        Ok(())
+    }
+}
+
+/// Starts or stops the packet log. Returns false if it could not be started.
+#[no_mangle]
+pub extern "C" fn WorldPacketLog_SetEnabled(enabled: bool) -> bool {
+    match PacketLog::global().set_enabled(enabled) {
+        Ok(()) => true,
+        Err(e) => {
+            error!(target: "network", "{}", e);
+            false
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn WorldPacketLog_IsEnabled() -> bool {
+    PacketLog::global().is_enabled()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::packet_log::{PacketDirection, PacketLog, PacketLogConfig};
+    use std::net::SocketAddr;
+    use std::path::PathBuf;
+
+    fn log_directory(name: &str) -> PathBuf {
+        let directory =
+            std::env::temp_dir().join(format!("enturion-pkt-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&directory);
+        std::fs::create_dir_all(&directory).unwrap();
+        directory
+    }
+
+    /// The direction, connection, address, opcode and payload of a record.
+    type Record = ([u8; 4], u32, SocketAddr, u32, Vec<u8>);
+
+    fn records(data: &[u8]) -> Vec<Record> {
+        assert_eq!(data[..3], *b"PKT");
+        assert_eq!(data[3..5], [0x01, 0x03]);
+        assert_eq!(data[6..10], 12340_u32.to_le_bytes());
+
+        let u32_at = |data: &[u8], offset: usize| {
+            u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap())
+        };
+        let mut records = vec![];
+        let mut data = &data[66..];
+        while !data.is_empty() {
+            assert_eq!(u32_at(data, 12), 20);
+            let length = u32_at(data, 16) as usize;
+            let ip: [u8; 4] = data[20..24].try_into().unwrap();
+            let address = SocketAddr::from((ip, u32_at(data, 36) as u16));
+            let payload = data[44..40 + length].to_vec();
+            records.push((
+                data[..4].try_into().unwrap(),
+                u32_at(data, 4),
+                address,
+                u32_at(data, 40),
+                payload,
+            ));
+            data = &data[40 + length..];
+        }
+
+        records
+    }
+
+    #[test]
+    pub fn packets_are_logged() {
+        let directory = log_directory("log");
+        let path = directory.join("world.pkt");
+        let log = PacketLog::default();
+        let address: SocketAddr = "[::ffff:10.0.0.1]:50000".parse().unwrap();
+        log.log(PacketDirection::ClientToServer, 1, &address, 0x1DC, &[1]);
+        assert!(log.set_enabled(true).is_err());
+
+        log.set_config(PacketLogConfig {
+            path: Some(path.clone()),
+            max_size: 0,
+        })
+        .unwrap();
+        assert!(log.is_enabled());
+        log.log(PacketDirection::ClientToServer, 1, &address, 0x1DC, &[1, 0]);
+        log.log(PacketDirection::ServerToClient, 2, &address, 0x1DD, &[]);
+
+        // Reloading the same settings keeps the file
+        log.set_config(PacketLogConfig {
+            path: Some(path.clone()),
+            max_size: 1024,
+        })
+        .unwrap();
+        assert!(log.is_enabled());
+        log.log(PacketDirection::ServerToClient, 2, &address, 0x1DD, &[]);
+        log.set_enabled(false).unwrap();
+        log.log(PacketDirection::ServerToClient, 2, &address, 0x1DD, &[]);
+
+        let records = records(&std::fs::read(&path).unwrap());
+        let address: SocketAddr = "10.0.0.1:50000".parse().unwrap();
+        assert_eq!(
+            records,
+            [
+                (*b"CMSG", 1, address, 0x1DC, vec![1, 0]),
+                (*b"SMSG", 2, address, 0x1DD, vec![]),
+                (*b"SMSG", 2, address, 0x1DD, vec![]),
+            ]
+        );
+        assert!(!directory.join("world.1.pkt").exists());
+        let _ = std::fs::remove_dir_all(&directory);
+    }
+
+    #[test]
+    pub fn logs_are_rotated() {
+        let directory = log_directory("rotate");
+        let path = directory.join("world.pkt");
+        std::fs::write(&path, b"earlier run").unwrap();
+
+        let log = PacketLog::default();
+        log.set_config(PacketLogConfig {
+            path: Some(path.clone()),
+            max_size: 66 + 2 * 45,
+        })
+        .unwrap();
+        let address: SocketAddr = "127.0.0.1:1".parse().unwrap();
+        for opcode in 1..=3 {
+            log.log(PacketDirection::ClientToServer, 1, &address, opcode, &[0]);
+        }
+
+        assert_eq!(
+            std::fs::read(directory.join("world.1.pkt")).unwrap(),
+            b"earlier run"
+        );
+        let opcodes = |path: PathBuf| {
+            records(&std::fs::read(path).unwrap())
+                .into_iter()
+                .map(|record| record.3)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(opcodes(directory.join("world.2.pkt")), [1, 2]);
+        assert_eq!(opcodes(path), [3]);
+        let _ = std::fs::remove_dir_all(&directory);
+    }
+}
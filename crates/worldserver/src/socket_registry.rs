@@ -0,0 +1,132 @@
+use crate::world_socket::{SocketMessage, WorldSocketControl};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::Notify;
+use tokio::time;
+
+/// Keeps track of the live world sockets, so they can be closed before the world is stopped.
+pub struct SocketRegistry {
+    sockets: Mutex<HashMap<u32, Arc<WorldSocketControl>>>,
+    /// Set once the sockets are closed, the sockets registered later are closed right away.
+    closed: AtomicBool,
+    changed: Notify,
+}
+
+impl SocketRegistry {
+    pub fn new() -> Arc<Self> {
+        Arc::new(Self {
+            sockets: Mutex::default(),
+            closed: AtomicBool::new(false),
+            changed: Notify::new(),
+        })
+    }
+
+    /// Registers a new socket. The socket is removed from the registry
+    /// when the returned registration is dropped.
+    pub fn register(
+        self: &Arc<Self>,
+        id: u32,
+        control: Arc<WorldSocketControl>,
+    ) -> SocketRegistration {
+        let mut sockets = self.sockets.lock().unwrap();
+        if self.closed.load(Ordering::Relaxed) {
+            control.send(SocketMessage::Close);
+        }
+
+        sockets.insert(id, control);
+        SocketRegistration {
+            registry: self.clone(),
+            id,
+        }
+    }
+
+    /// Number of live sockets.
+    pub fn len(&self) -> usize {
+        self.sockets.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Closes every live socket, and the ones registered from now on.
+    pub fn close_all(&self) {
+        let sockets = self.sockets.lock().unwrap();
+        self.closed.store(true, Ordering::Relaxed);
+        for control in sockets.values() {
+            control.send(SocketMessage::Close);
+        }
+    }
+
+    /// Waits until all the sockets are gone.
+    /// Returns false if some socket is still alive after the timeout.
+    pub async fn wait_until_empty(&self, timeout: Duration) -> bool {
+        let wait = async {
+            loop {
+                let changed = self.changed.notified();
+                if self.is_empty() {
+                    return;
+                }
+
+                changed.await;
+            }
+        };
+
+        time::timeout(timeout, wait).await.is_ok()
+    }
+
+    fn unregister(&self, id: u32) {
+        self.sockets.lock().unwrap().remove(&id);
+        self.changed.notify_waiters();
+    }
+}
+
+/// The registration of a live socket.
+pub struct SocketRegistration {
+    registry: Arc<SocketRegistry>,
+    id: u32,
+}
+
+impl Drop for SocketRegistration {
+    fn drop(&mut self) {
+        self.registry.unregister(self.id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::socket_registry::SocketRegistry;
+    use crate::world_socket::{SocketMessage, WorldSocketControl};
+    use std::time::Duration;
+
+    #[tokio::test]
+    pub async fn sockets_are_closed() {
+        let registry = SocketRegistry::new();
+        let (first_control, mut first_messages) = WorldSocketControl::new();
+        let (second_control, mut second_messages) = WorldSocketControl::new();
+        let first = registry.register(1, first_control);
+        let second = registry.register(2, second_control);
+        assert_eq!(registry.len(), 2);
+
+        registry.close_all();
+        assert_eq!(first_messages.try_recv(), Ok(SocketMessage::Close));
+        assert_eq!(second_messages.try_recv(), Ok(SocketMessage::Close));
+        assert!(!registry.wait_until_empty(Duration::from_millis(10)).await);
+
+        let (late_control, mut late_messages) = WorldSocketControl::new();
+        drop(registry.register(3, late_control));
+        assert_eq!(late_messages.try_recv(), Ok(SocketMessage::Close));
+
+        let drop_sockets = tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            drop(first);
+            drop(second);
+        });
+
+        assert!(registry.wait_until_empty(Duration::from_secs(1)).await);
+        assert!(registry.is_empty());
+        drop_sockets.await.unwrap();
+    }
+}
@@ -0,0 +1,544 @@
+use crate::compression::{CompressionConfig, PacketCompressor};
+use crate::crypt::AuthCrypt;
+use crate::opcodes::{opcode_name_for_logging, Admission, Opcode, SessionState, SessionStatus};
+use crate::packet::{
+    server_header, AuthChallenge, AuthSessionRequest, ClientPacket, PacketFramer, WorldPacket,
+    BINCODE_CONFIGURATION,
+};
+use crate::packet_log::{PacketDirection, PacketLog};
+use crate::socket_registry::{SocketRegistration, SocketRegistry};
+use anyhow::{bail, Result};
+use bincode::error::EncodeError;
+use bytes::BytesMut;
+use enturion_shared::net::notifier::Notifier;
+use log::{debug, error, trace};
+use std::ffi::{c_char, c_void, CString};
+use std::mem::MaybeUninit;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU8, Ordering};
+use std::sync::Arc;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
+use tokio::net::TcpStream;
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+
+extern "C" {
+    fn WorldSocket_Free(world_socket: *mut c_void);
+    fn WorldSocket_HandleAuthSession(
+        world_socket: *const c_void,
+        auth_session: *const WorldAuthSession,
+    ) -> bool;
+    fn WorldSocket_HandlePacket(
+        world_socket: *const c_void,
+        opcode: u32,
+        data: *const u8,
+        size: usize,
+    ) -> bool;
+    fn WorldSocket_New(rs_world_socket: *mut c_void) -> *mut c_void;
+    fn WorldSocket_Start(world_socket: *const c_void);
+    fn WorldSocket_Update(world_socket: *const c_void);
+}
+
+/// Identifies the connections in the packet log.
+static NEXT_CONNECTION_ID: AtomicU32 = AtomicU32::new(1);
+
+#[derive(Clone, Copy)]
+struct CxxWorldSocket(*const c_void);
+unsafe impl Send for CxxWorldSocket {}
+
+/// The content of `CMSG_AUTH_SESSION`, for C++ to check it against the account.
+/// The pointers are only valid during the call to `WorldSocket_HandleAuthSession`.
+#[repr(C)]
+pub struct WorldAuthSession {
+    pub build: u32,
+    pub login_server_id: u32,
+    pub account: *const c_char,
+    pub login_server_type: u32,
+    pub local_challenge: [u8; 4],
+    pub region_id: u32,
+    pub battlegroup_id: u32,
+    pub realm_id: u32,
+    pub dos_response: u64,
+    pub digest: [u8; 20],
+    /// The seed sent in `SMSG_AUTH_CHALLENGE`.
+    pub auth_seed: [u8; 4],
+    pub addon_info: *const u8,
+    pub addon_info_size: usize,
+}
+
+/// Work queued for the task of a socket.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SocketMessage {
+    Write {
+        opcode: u16,
+        payload: Vec<u8>,
+    },
+    /// Sends `SMSG_AUTH_CHALLENGE` and starts reading the client packets.
+    SendAuthChallenge,
+    /// Encrypts the headers from now on, with the session key of the account.
+    InitEncryption(Vec<u8>),
+    /// Reads the client packets again, after `CMSG_AUTH_SESSION` has been handled.
+    ResumeReading,
+    /// Closes the connection once the queued packets are written.
+    DelayedClose,
+    Close,
+}
+
+/// Handle on a world socket, to queue work for it from any thread.
+/// C++ gets handles through `WorldSocket_GetControl`: they outlive the socket, and queued work
+/// is dropped once the socket is closed.
+pub struct WorldSocketControl {
+    sender: UnboundedSender<SocketMessage>,
+    closing: AtomicBool,
+    state: AtomicU8,
+}
+
+impl WorldSocketControl {
+    /// Creates the handle of a new socket, with the receiver of the work queued through it.
+    pub fn new() -> (Arc<Self>, UnboundedReceiver<SocketMessage>) {
+        let (sender, messages) = mpsc::unbounded_channel();
+        let control = Self {
+            sender,
+            closing: AtomicBool::new(false),
+            state: AtomicU8::new(SessionState::Unauthenticated as u8),
+        };
+
+        (Arc::new(control), messages)
+    }
+
+    pub fn send(&self, message: SocketMessage) {
+        if matches!(message, SocketMessage::DelayedClose | SocketMessage::Close) {
+            self.closing.store(true, Ordering::Relaxed);
+        }
+
+        let _ = self.sender.send(message);
+    }
+
+    /// Encodes and queues a typed packet.
+    pub fn send_packet<P: WorldPacket>(&self, packet: &P) -> Result<(), EncodeError> {
+        let payload = bincode::encode_to_vec(packet, BINCODE_CONFIGURATION)?;
+        self.send(SocketMessage::Write {
+            opcode: P::OPCODE as u16,
+            payload,
+        });
+        Ok(())
+    }
+
+    /// Gets the state of the session the client packets are checked against.
+    pub fn state(&self) -> SessionState {
+        SessionState::try_from(self.state.load(Ordering::Relaxed))
+            .unwrap_or(SessionState::Unauthenticated)
+    }
+
+    pub fn set_state(&self, state: SessionState) {
+        self.state.store(state as u8, Ordering::Relaxed);
+    }
+
+    pub fn is_open(&self) -> bool {
+        !self.closing.load(Ordering::Relaxed) && !self.sender.is_closed()
+    }
+}
+
+pub struct WorldSocket {
+    rx: OwnedReadHalf,
+    tx: OwnedWriteHalf,
+    socket_address: SocketAddr,
+    socket_address_as_str: CString,
+    connection_id: u32,
+    auth_seed: [u8; 4],
+    cxx_world_socket: MaybeUninit<CxxWorldSocket>,
+    control: Arc<WorldSocketControl>,
+    messages: Option<UnboundedReceiver<SocketMessage>>,
+    notifier: Arc<Notifier>,
+    compressor: PacketCompressor,
+    _registration: SocketRegistration,
+}
+
+impl WorldSocket {
+    pub fn new(
+        stream: TcpStream,
+        address: SocketAddr,
+        compression: CompressionConfig,
+        registry: &Arc<SocketRegistry>,
+    ) -> Pin<Box<Self>> {
+        let (rx, tx) = stream.into_split();
+        let (control, messages) = WorldSocketControl::new();
+        let connection_id = NEXT_CONNECTION_ID.fetch_add(1, Ordering::Relaxed);
+        let result = Self {
+            rx,
+            tx,
+            socket_address: address,
+            socket_address_as_str: CString::new(address.ip().to_canonical().to_string()).unwrap(),
+            connection_id,
+            auth_seed: rand::random(),
+            cxx_world_socket: MaybeUninit::uninit(),
+            _registration: registry.register(connection_id, control.clone()),
+            control,
+            messages: Some(messages),
+            notifier: Notifier::new(),
+            compressor: PacketCompressor::new(compression),
+        };
+
+        let mut boxed = Box::pin(result);
+
+        let cxx_world_socket =
+            unsafe { WorldSocket_New(boxed.as_ref().get_ref() as *const Self as *mut c_void) };
+        let _ = boxed
+            .cxx_world_socket
+            .write(CxxWorldSocket(cxx_world_socket));
+
+        boxed
+    }
+
+    fn cxx(&self) -> CxxWorldSocket {
+        unsafe { self.cxx_world_socket.assume_init_read() }
+    }
+
+    /// Runs the socket until the connection is closed. C++ checks the address first, then has
+    /// the auth challenge sent. Reading stops while `CMSG_AUTH_SESSION` is checked, and resumes
+    /// once the session is authenticated.
+    pub async fn start(&mut self) -> Result<()> {
+        trace!(target: "network", "Starting world socket for {}", self.socket_address);
+        let Some(mut messages) = self.messages.take() else {
+            return Ok(());
+        };
+
+        let notifier = self.notifier.clone();
+        unsafe { WorldSocket_Start(self.cxx().0) };
+
+        let mut buf = BytesMut::with_capacity(4096);
+        let mut framer = PacketFramer::default();
+        let mut crypt: Option<AuthCrypt> = None;
+        let mut reading = false;
+
+        loop {
+            tokio::select! {
+                result = self.rx.read_buf(&mut buf), if reading => match result {
+                    Ok(0) => return Ok(()),
+                    Ok(n) => trace!(target: "network", "Received {} bytes from {}", n, self.socket_address),
+                    Err(e) => {
+                        debug!(target: "network", "Failed to read from {}: {}", self.socket_address, e);
+                        return Err(e.into());
+                    }
+                },
+                message = messages.recv() => match message {
+                    Some(SocketMessage::Write { opcode, payload }) => {
+                        let (opcode, payload) = self
+                            .compressor
+                            .compress(opcode, &payload)
+                            .unwrap_or((opcode, payload));
+                        self.write_packet(opcode, &payload, crypt.as_mut()).await?;
+                        continue;
+                    }
+                    Some(SocketMessage::SendAuthChallenge) => {
+                        trace!(target: "network.opcode", "S->C: {} {}", self.socket_address, AuthChallenge::OPCODE);
+                        let challenge = AuthChallenge::new(self.auth_seed);
+                        let payload = bincode::encode_to_vec(challenge, BINCODE_CONFIGURATION)?;
+                        self.write_packet(AuthChallenge::OPCODE as u16, &payload, None).await?;
+                        reading = true;
+                    }
+                    Some(SocketMessage::InitEncryption(session_key)) => {
+                        crypt = Some(AuthCrypt::server(&session_key));
+                        continue;
+                    }
+                    Some(SocketMessage::ResumeReading) => {
+                        reading = true;
+                        if self.control.state() == SessionState::Unauthenticated {
+                            self.control.set_state(SessionState::Authed);
+                        }
+                    }
+                    Some(SocketMessage::DelayedClose) => {
+                        let _ = self.tx.flush().await;
+                        let _ = self.tx.shutdown().await;
+                        return Ok(());
+                    }
+                    Some(SocketMessage::Close) | None => {
+                        trace!(target: "network", "Closing world socket for {}", self.socket_address);
+                        return Ok(());
+                    }
+                },
+                _ = notifier.notified() => {},
+            }
+
+            // Handle the complete packets, until one has to wait for a query
+            while reading {
+                let packet = match framer.next_packet(&mut buf, crypt.as_mut()) {
+                    Ok(Some(packet)) => packet,
+                    Ok(None) => break,
+                    Err(e) => {
+                        error!(target: "network", "Client {} sent a malformed packet: {}", self.socket_address, e);
+                        return Ok(());
+                    }
+                };
+
+                PacketLog::global().log(
+                    PacketDirection::ClientToServer,
+                    self.connection_id,
+                    &self.socket_address,
+                    packet.opcode,
+                    &packet.payload,
+                );
+
+                let opcode = match self.admit(&packet) {
+                    Ok(Some(opcode)) => opcode,
+                    Ok(None) => continue,
+                    Err(e) => {
+                        error!(target: "network.opcode", "Client {} {}", self.socket_address, e);
+                        return Ok(());
+                    }
+                };
+
+                let handled = match opcode {
+                    Opcode::CMSG_AUTH_SESSION => {
+                        reading = false;
+                        self.handle_auth_session(&packet.payload)
+                    }
+                    opcode => unsafe {
+                        WorldSocket_HandlePacket(
+                            self.cxx().0,
+                            opcode as u32,
+                            packet.payload.as_ptr(),
+                            packet.payload.len(),
+                        )
+                    },
+                };
+
+                if !handled {
+                    return Ok(());
+                }
+            }
+
+            unsafe { WorldSocket_Update(self.cxx().0) };
+        }
+    }
+
+    /// Checks a client packet against the opcode table and the state of the session. Returns
+    /// `None` if the packet is dropped, and an error if the client may not send it at all.
+    fn admit(&self, packet: &ClientPacket) -> Result<Option<Opcode>> {
+        let info = match Opcode::try_from(packet.opcode) {
+            Ok(opcode) => opcode.info(),
+            Err(opcode) => bail!("sent undefined opcode {}", opcode_name_for_logging(opcode)),
+        };
+
+        let state = self.control.state();
+        match info.admission(state) {
+            Admission::Handle => Ok(Some(info.opcode)),
+            Admission::Ignore
+                if matches!(info.status, SessionStatus::Never | SessionStatus::Unhandled) =>
+            {
+                debug!(target: "network.opcode", "Received not handled opcode {} from {}", info.opcode, self.socket_address);
+                Ok(None)
+            }
+            Admission::Ignore => {
+                debug!(target: "network.opcode", "Received unexpected opcode {} from {} in state {:?}", info.opcode, self.socket_address, state);
+                Ok(None)
+            }
+            Admission::Close if state == SessionState::Unauthenticated => {
+                bail!("sent opcode {} without being authenticated", info.opcode)
+            }
+            Admission::Close => bail!("sent not allowed opcode {}", info.opcode),
+        }
+    }
+
+    /// Logs and writes a packet, encrypting its header once the client is authenticated.
+    async fn write_packet(
+        &mut self,
+        opcode: u16,
+        payload: &[u8],
+        crypt: Option<&mut AuthCrypt>,
+    ) -> Result<()> {
+        PacketLog::global().log(
+            PacketDirection::ServerToClient,
+            self.connection_id,
+            &self.socket_address,
+            opcode as u32,
+            payload,
+        );
+
+        let mut header = server_header(payload.len(), opcode);
+        if let Some(crypt) = crypt {
+            crypt.encrypt_send(&mut header);
+        }
+
+        if let Err(e) = self.write_all(&header, payload).await {
+            error!(target: "network", "Error writing packet to {}: {}", self.socket_address, e);
+            let _ = self.tx.shutdown().await;
+            return Err(e.into());
+        }
+
+        Ok(())
+    }
+
+    async fn write_all(&mut self, header: &[u8], payload: &[u8]) -> std::io::Result<()> {
+        self.tx.write_all(header).await?;
+        self.tx.write_all(payload).await
+    }
+
+    /// Parses `CMSG_AUTH_SESSION` and has C++ load the account.
+    /// Returns false if the connection has to be closed.
+    fn handle_auth_session(&self, payload: &[u8]) -> bool {
+        let request = match AuthSessionRequest::parse(payload) {
+            Ok(request) => request,
+            Err(e) => {
+                error!(target: "network", "Client {} sent a malformed CMSG_AUTH_SESSION: {}", self.socket_address, e);
+                return false;
+            }
+        };
+
+        // The account is read up to the first nul byte
+        let account = CString::new(request.account).unwrap();
+        let auth_session = WorldAuthSession {
+            build: request.build,
+            login_server_id: request.login_server_id,
+            account: account.as_ptr(),
+            login_server_type: request.login_server_type,
+            local_challenge: request.local_challenge,
+            region_id: request.region_id,
+            battlegroup_id: request.battlegroup_id,
+            realm_id: request.realm_id,
+            dos_response: request.dos_response,
+            digest: request.digest,
+            auth_seed: self.auth_seed,
+            addon_info: request.addon_info.as_ptr(),
+            addon_info_size: request.addon_info.len(),
+        };
+
+        unsafe { WorldSocket_HandleAuthSession(self.cxx().0, &auth_session) }
+    }
+
+    #[no_mangle]
+    pub unsafe extern "C" fn WorldSocket_GetRemoteIpAddress(this: *const c_void) -> *const c_char {
+        let this_obj = &*this.cast::<Self>();
+        this_obj.socket_address_as_str.as_ptr()
+    }
+
+    #[no_mangle]
+    pub unsafe extern "C" fn WorldSocket_GetRemotePort(this: *const c_void) -> u16 {
+        let this_obj = &*this.cast::<Self>();
+        this_obj.socket_address.port()
+    }
+
+    /// Gets a new handle on the control of the socket, to queue work from any thread.
+    ///
+    /// # Safety
+    /// Unsafe: the handle must be freed with `WorldSocketControl_Free`.
+    #[no_mangle]
+    pub unsafe extern "C" fn WorldSocket_GetControl(
+        this: *const c_void,
+    ) -> *const WorldSocketControl {
+        let this_obj = &*this.cast::<Self>();
+        Arc::into_raw(this_obj.control.clone())
+    }
+
+    /// Gets a new handle on the notifier of the socket, to have `WorldSocket_Update` run from
+    /// any thread.
+    ///
+    /// # Safety
+    /// Unsafe: the handle must be freed with `Notifier_Free`.
+    #[no_mangle]
+    pub unsafe extern "C" fn WorldSocket_GetNotifier(this: *const c_void) -> *const Notifier {
+        let this_obj = &*this.cast::<Self>();
+        this_obj.notifier.to_raw()
+    }
+}
+
+impl Drop for WorldSocket {
+    fn drop(&mut self) {
+        trace!(target: "network", "Connection to {} closed.", self.socket_address);
+        unsafe { WorldSocket_Free(self.cxx().0 as *mut c_void) };
+    }
+}
+
+/// Queues a packet. Its header is built and encrypted by the socket task.
+///
+/// # Safety
+/// Unsafe: control must be a live handle, data must be `size` bytes long.
+#[no_mangle]
+pub unsafe extern "C" fn WorldSocketControl_SendPacket(
+    control: *const WorldSocketControl,
+    opcode: u16,
+    data: *const u8,
+    size: usize,
+) {
+    let payload = match size {
+        0 => Vec::new(),
+        _ => std::slice::from_raw_parts(data, size).to_vec(),
+    };
+
+    (*control).send(SocketMessage::Write { opcode, payload });
+}
+
+/// # Safety
+/// Unsafe: control must be a live handle.
+#[no_mangle]
+pub unsafe extern "C" fn WorldSocketControl_SendAuthChallenge(control: *const WorldSocketControl) {
+    (*control).send(SocketMessage::SendAuthChallenge);
+}
+
+/// Encrypts the headers of the packets queued from now on, and of the received ones.
+///
+/// # Safety
+/// Unsafe: control must be a live handle, session_key must be `size` bytes long.
+#[no_mangle]
+pub unsafe extern "C" fn WorldSocketControl_InitEncryption(
+    control: *const WorldSocketControl,
+    session_key: *const u8,
+    size: usize,
+) {
+    let session_key = std::slice::from_raw_parts(session_key, size).to_vec();
+    (*control).send(SocketMessage::InitEncryption(session_key));
+}
+
+/// # Safety
+/// Unsafe: control must be a live handle.
+#[no_mangle]
+pub unsafe extern "C" fn WorldSocketControl_ResumeReading(control: *const WorldSocketControl) {
+    (*control).send(SocketMessage::ResumeReading);
+}
+
+/// # Safety
+/// Unsafe: control must be a live handle.
+#[no_mangle]
+pub unsafe extern "C" fn WorldSocketControl_DelayedClose(control: *const WorldSocketControl) {
+    (*control).send(SocketMessage::DelayedClose);
+}
+
+/// # Safety
+/// Unsafe: control must be a live handle.
+#[no_mangle]
+pub unsafe extern "C" fn WorldSocketControl_Close(control: *const WorldSocketControl) {
+    (*control).send(SocketMessage::Close);
+}
+
+/// Sets the state of the session the client packets are checked against.
+/// `state` is a `SessionState` value.
+///
+/// # Safety
+/// Unsafe: control must be a live handle.
+#[no_mangle]
+pub unsafe extern "C" fn WorldSocketControl_SetSessionState(
+    control: *const WorldSocketControl,
+    state: u8,
+) {
+    match SessionState::try_from(state) {
+        Ok(state) => (*control).set_state(state),
+        Err(state) => error!(target: "network", "Unknown session state {}", state),
+    }
+}
+
+/// # Safety
+/// Unsafe: control must be a live handle.
+#[no_mangle]
+pub unsafe extern "C" fn WorldSocketControl_IsOpen(control: *const WorldSocketControl) -> bool {
+    (*control).is_open()
+}
+
+/// # Safety
+/// Unsafe: control must be a live handle, it is freed by the call.
+#[no_mangle]
+pub unsafe extern "C" fn WorldSocketControl_Free(control: *const WorldSocketControl) {
+    if !control.is_null() {
+        drop(Arc::from_raw(control));
+    }
+}
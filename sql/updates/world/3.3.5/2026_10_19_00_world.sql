@@ -0,0 +1,4 @@
+-- Add the .debug packetlog command, toggling the world packet log
+DELETE FROM `command` WHERE `name`='debug packetlog';
+INSERT INTO `command` (`name`,`help`) VALUES
+('debug packetlog','Syntax: .debug packetlog [on/off]\r\n\r\nStarts or stops logging the world packets to PacketLogFile, toggles it without argument.');
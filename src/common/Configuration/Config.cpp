@@ -59,9 +59,13 @@ ConfigMgr* ConfigMgr::instance()
 
 bool ConfigMgr::Reload(std::vector<std::string>& errors)
 {
-    std::string error;
-    if (!LoadInitial(_filename, error))
-        errors.push_back(std::move(error));
+    // Reloaded in place, the Rust side keeps a reference to the configuration
+    char *reload_error;
+    Config_Reload(&_config, &reload_error);
+    if (reload_error != nullptr) {
+        errors.emplace_back(reload_error);
+        delete reload_error;
+    }
 
     return errors.empty();
 }
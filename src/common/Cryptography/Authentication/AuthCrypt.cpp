@@ -1,54 +0,0 @@
-/*
- * This file is part of the KitronCore Project. See AUTHORS file for Copyright information
- *
- * This program is free software; you can redistribute it and/or modify it
- * under the terms of the GNU General Public License as published by the
- * Free Software Foundation; either version 2 of the License, or (at your
- * option) any later version.
- *
- * This program is distributed in the hope that it will be useful, but WITHOUT
- * ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
- * FITNESS FOR A PARTICULAR PURPOSE. See the GNU General Public License for
- * more details.
- *
- * You should have received a copy of the GNU General Public License along
- * with this program. If not, see <http://www.gnu.org/licenses/>.
- */
-
-#include "AuthCrypt.h"
-#include "BigNumber.h"
-#include "Errors.h"
-#include "HMAC.h"
-
-#include <cstring>
-
-AuthCrypt::AuthCrypt() :
-    _initialized(false)
-{ }
-
-void AuthCrypt::Init(SessionKey const& K)
-{
-    uint8 ServerEncryptionKey[] = { 0xCC, 0x98, 0xAE, 0x04, 0xE8, 0x97, 0xEA, 0xCA, 0x12, 0xDD, 0xC0, 0x93, 0x42, 0x91, 0x53, 0x57 };
-    _serverEncrypt.Init(Kitron::Crypto::HMAC_SHA1::GetDigestOf(ServerEncryptionKey, K));
-    uint8 ServerDecryptionKey[] = { 0xC2, 0xB3, 0x72, 0x3C, 0xC6, 0xAE, 0xD9, 0xB5, 0x34, 0x3C, 0x53, 0xEE, 0x2F, 0x43, 0x67, 0xCE };
-    _clientDecrypt.Init(Kitron::Crypto::HMAC_SHA1::GetDigestOf(ServerDecryptionKey, K));
-
-    // Drop first 1024 bytes, as WoW uses ARC4-drop1024.
-    std::array<uint8, 1024> syncBuf;
-    _serverEncrypt.UpdateData(syncBuf);
-    _clientDecrypt.UpdateData(syncBuf);
-
-    _initialized = true;
-}
-
-void AuthCrypt::DecryptRecv(uint8 *data, size_t len)
-{
-    ASSERT(_initialized);
-    _clientDecrypt.UpdateData(data, len);
-}
-
-void AuthCrypt::EncryptSend(uint8 *data, size_t len)
-{
-    ASSERT(_initialized);
-    _serverEncrypt.UpdateData(data, len);
-}
@@ -79,7 +79,10 @@ using SRP6 = Kitron::Crypto::SRP6;
 }
 
 SRP6::SRP6(std::string const& username, Salt const& salt, Verifier const& verifier)
-    : _I(SHA1::GetDigestOf(username)), _b(Crypto::GetRandomBytes<32>()), _v(verifier), s(salt), B(_B(_b, _v)) {}
+    : SRP6(username, salt, verifier, Crypto::GetRandomBytes<PRIVATE_KEY_LENGTH>()) {}
+
+SRP6::SRP6(std::string const& username, Salt const& salt, Verifier const& verifier, PrivateKey const& b)
+    : _I(SHA1::GetDigestOf(username)), _b(b), _v(verifier), s(salt), B(_B(_b, _v)) {}
 
 std::optional<SessionKey> SRP6::VerifyChallengeResponse(EphemeralKey const& A, SHA1::Digest const& clientM)
 {
@@ -37,6 +37,8 @@ namespace Kitron::Crypto
             using Verifier = std::array<uint8, VERIFIER_LENGTH>;
             static constexpr size_t EPHEMERAL_KEY_LENGTH = 32;
             using EphemeralKey = std::array<uint8, EPHEMERAL_KEY_LENGTH>;
+            static constexpr size_t PRIVATE_KEY_LENGTH = 32;
+            using PrivateKey = std::array<uint8, PRIVATE_KEY_LENGTH>;
 
             static std::array<uint8, 1> const g;
             static std::array<uint8, 32> const N;
@@ -55,6 +57,8 @@ namespace Kitron::Crypto
             }
 
             SRP6(std::string const& username, Salt const& salt, Verifier const& verifier);
+            // b must be random, it is only given to replay recorded sessions
+            SRP6(std::string const& username, Salt const& salt, Verifier const& verifier, PrivateKey const& b);
             std::optional<SessionKey> VerifyChallengeResponse(EphemeralKey const& A, SHA1::Digest const& clientM);
 
         private:
@@ -16,24 +16,22 @@
  */
 
 #include "AuthCodes.h"
-#include "RealmList.h"
+#include "libenturion_authserver.h"
 
 namespace AuthHelper
 {
-    constexpr static uint32 MAX_PRE_BC_CLIENT_BUILD = 6141;
-
     bool IsPreBCAcceptedClientBuild(uint32 build)
     {
-        return build <= MAX_PRE_BC_CLIENT_BUILD && sRealmList->GetBuildInfo(build);
+        return BuildRegistry_IsPreBCAcceptedBuild(build);
     }
 
     bool IsPostBCAcceptedClientBuild(uint32 build)
     {
-        return build > MAX_PRE_BC_CLIENT_BUILD && sRealmList->GetBuildInfo(build);
+        return BuildRegistry_IsPostBCAcceptedBuild(build);
     }
 
     bool IsAcceptedClientBuild(uint32 build)
     {
-        return sRealmList->GetBuildInfo(build) != nullptr;
+        return BuildRegistry_IsAcceptedBuild(build);
     }
 }
@@ -27,7 +27,6 @@
 #include "DatabaseEnv.h"
 #include "DatabaseLoader.h"
 #include "IoContext.h"
-#include "IPLocation.h"
 #include "GitRevision.h"
 #include "MySQLThreading.h"
 #include "OpenSSLCrypto.h"
@@ -75,7 +74,36 @@ void KeepDatabaseAliveHandler();
 void BanExpiryHandler();
 variables_map GetConsoleArguments(int argc, char** argv, fs::path& configFile, std::string& configService);
 
-void MainLoopCallback() {
+void MainLoopCallback(uint32 /*diff*/) {
+}
+
+/// Copies the realm list into the Rust realm registry
+extern "C" void RealmList_Snapshot(void* snapshot)
+{
+    for (RealmBuildInfo const& build : sRealmList->GetBuilds())
+        RealmListSnapshot_AddBuild(snapshot, build.Build, build.MajorVersion, build.MinorVersion, build.BugfixVersion, build.WindowsHash.data(), build.MacHash.data());
+
+    for (auto const& [id, realm] : sRealmList->GetRealms())
+    {
+        std::string externalAddress = realm.ExternalAddress->to_string();
+        std::string localAddress = realm.LocalAddress->to_string();
+        std::string localSubnetMask = realm.LocalSubnetMask->to_string();
+
+        RealmListSnapshotEntry entry;
+        entry.id = id.Realm;
+        entry.build = realm.Build;
+        entry.name = realm.Name.c_str();
+        entry.external_address = externalAddress.c_str();
+        entry.local_address = localAddress.c_str();
+        entry.local_subnet_mask = localSubnetMask.c_str();
+        entry.port = realm.Port;
+        entry.realm_type = realm.Type;
+        entry.flags = uint8(realm.Flags);
+        entry.timezone = realm.Timezone;
+        entry.allowed_security_level = uint8(realm.AllowedSecurityLevel);
+        entry.population = realm.PopulationLevel;
+        RealmListSnapshot_AddRealm(snapshot, &entry);
+    }
 }
 
 int main(int argc, char** argv)
@@ -91,6 +119,13 @@ int main(int argc, char** argv)
     if (vm.count("help") || vm.count("version"))
         return 0;
 
+    // Print the documented configuration keys instead of starting
+    if (vm.count("print-config"))
+    {
+        AuthServerRsPrintConfig();
+        return 0;
+    }
+
 #if KITRON_PLATFORM == KITRON_PLATFORM_WINDOWS
     if (configService.compare("install") == 0)
         return WinServiceInstall() == true ? 0 : 1;
@@ -137,9 +172,6 @@ int main(int argc, char** argv)
 
     sSecretMgr->Initialize();
 
-    // Load IP Location Database
-    sIPLocation->Load();
-
     std::shared_ptr<void> dbHandle(nullptr, [](void*) { StopDB(); });
 
     std::shared_ptr<Kitron::Asio::IoContext> ioContext = std::make_shared<Kitron::Asio::IoContext>();
@@ -155,6 +187,13 @@ int main(int argc, char** argv)
         return 1;
     }
 
+    // Replay a captured session instead of accepting connections
+    if (vm.count("replay"))
+    {
+        std::string replayFile = vm["replay"].as<fs::path>().generic_string();
+        return AuthServerRsReplay(replayFile.c_str()) ? 0 : 1;
+    }
+
     // Set process priority according to configuration settings
     SetProcessPriority("server.authserver", sConfigMgr->GetIntDefault(CONFIG_PROCESSOR_AFFINITY, 0), sConfigMgr->GetBoolDefault(CONFIG_HIGH_PRIORITY, false));
 
@@ -181,7 +220,7 @@ int main(int argc, char** argv)
     std::thread t1([&ioContext]() { ioContext->run(); });
 
     // Start the io service worker loop
-    AuthServerRsMain(MainLoopCallback);
+    uint8 exitCode = AuthServerRsMain(MainLoopCallback);
 
     TC_LOG_INFO("server.authserver", "Halting process...");
 
@@ -190,7 +229,7 @@ int main(int argc, char** argv)
         t1.join();
     }
 
-    return 0;
+    return exitCode;
 }
 
 /// Initialize connection to the database
@@ -258,6 +297,8 @@ variables_map GetConsoleArguments(int argc, char** argv, fs::path& configFile, s
         ("version,v", "print version build info")
         ("config,c", value<fs::path>(&configFile)->default_value(fs::absolute(_KITRON_REALM_CONFIG)),
                      "use <arg> as configuration file")
+        ("replay", value<fs::path>(), "replay the auth session captured in <arg>, then exit")
+        ("print-config", "print the known configuration keys with their default value, then exit")
         ;
 #if KITRON_PLATFORM == KITRON_PLATFORM_WINDOWS
     options_description win("Windows platform specific options");
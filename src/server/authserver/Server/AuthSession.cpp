@@ -16,21 +16,15 @@
  */
 
 #include "AuthSession.h"
-#include "AES.h"
 #include "AuthCodes.h"
 #include "Config.h"
-#include "CryptoGenerics.h"
 #include "CryptoRandom.h"
 #include "DatabaseEnv.h"
 #include "Errors.h"
 #include "CryptoHash.h"
-#include "IPLocation.h"
 #include "Log.h"
 #include "RealmList.h"
-#include "SecretMgr.h"
-#include "TOTP.h"
 #include "Util.h"
-#include <boost/lexical_cast.hpp>
 #include <boost/asio/ip/address.hpp>
 #include "libenturion_authserver.h"
 
@@ -100,12 +94,9 @@ static_assert(sizeof(sAuthReconnectProof_C) == (1 + 16 + 20 + 20 + 1));
 
 #pragma pack(pop)
 
-std::array<uint8, 16> VersionChallenge = { { 0xBA, 0xA3, 0x1E, 0x99, 0xA0, 0x0B, 0x21, 0x57, 0xFC, 0x37, 0x3F, 0xB3, 0x69, 0xCD, 0xD2, 0xF1 } };
-
 #define MAX_ACCEPTED_CHALLENGE_SIZE (sizeof(AUTH_LOGON_CHALLENGE_C) + 16)
 
 #define AUTH_LOGON_CHALLENGE_INITIAL_SIZE 4
-#define REALM_LIST_PACKET_SIZE 5
 
 std::unordered_map<uint8, AuthHandler> AuthSession::InitHandlers()
 {
@@ -115,7 +106,6 @@ std::unordered_map<uint8, AuthHandler> AuthSession::InitHandlers()
     handlers[AUTH_LOGON_PROOF]         = { STATUS_LOGON_PROOF, sizeof(AUTH_LOGON_PROOF_C),        &AuthSession::HandleLogonProof };
     handlers[AUTH_RECONNECT_CHALLENGE] = { STATUS_CHALLENGE, AUTH_LOGON_CHALLENGE_INITIAL_SIZE, &AuthSession::HandleReconnectChallenge };
     handlers[AUTH_RECONNECT_PROOF]     = { STATUS_RECONNECT_PROOF, sizeof(AUTH_RECONNECT_PROOF_C),    &AuthSession::HandleReconnectProof };
-    handlers[REALM_LIST]               = { STATUS_AUTHED,    REALM_LIST_PACKET_SIZE,            &AuthSession::HandleRealmList };
 
     return handlers;
 }
@@ -160,6 +150,11 @@ extern "C" void AuthSession_WriteIntoBuffer(const void *authSession, const void*
     session->WriteIntoBuffer(data, size);
 }
 
+extern "C" void AuthSession_RequestRealmList(const void *authSession) {
+    auto session = (AuthSession*) authSession;
+    session->RequestRealmList();
+}
+
 extern "C" void AuthSession_Update(const void *authSession) {
     auto session = (AuthSession*) authSession;
     session->Update();
@@ -175,7 +170,8 @@ void AuthSession::WriteIntoBuffer(const void* data, size_t size) {
 }
 
 AuthSession::AuthSession(void* rsAuthSession) : _rsAuthSession(rsAuthSession), _messageBuffer(),
-    _status(STATUS_CHALLENGE), _build(0), _expversion(0) { }
+    _status(STATUS_CHALLENGE), _build(0), _expversion(0),
+    _notifier(AuthSession_GetNotifier(rsAuthSession), Notifier_Free) { }
 
 const char * AuthSession::GetRemoteIpAddress() {
     return AuthSession_GetRemoteIpAddress(_rsAuthSession);
@@ -185,12 +181,23 @@ uint16_t AuthSession::GetRemotePort() {
     return AuthSession_GetRemotePort(_rsAuthSession);
 }
 
+void AuthSession::SetStatus(AuthStatus status) {
+    _status = status;
+    AuthSession_SetStatus(_rsAuthSession, uint8(status));
+}
+
+QueryCallback AuthSession::AsyncQuery(LoginDatabasePreparedStatement* stmt)
+{
+    // The notifier is kept alive by the callback, the session may be gone when the query completes
+    return LoginDatabase.AsyncQuery(stmt, [notifier = _notifier]() { Notifier_Notify(notifier.get()); });
+}
+
 void AuthSession::Start()
 {
     LoginDatabasePreparedStatement* stmt = LoginDatabase.GetPreparedStatement(LOGIN_SEL_IP_INFO);
     stmt->setString(0, GetRemoteIpAddress());
 
-    _queryProcessor.AddCallback(LoginDatabase.AsyncQuery(stmt).WithPreparedCallback(std::bind(&AuthSession::CheckIpCallback, this, std::placeholders::_1)));
+    _queryProcessor.AddCallback(AsyncQuery(stmt).WithPreparedCallback(std::bind(&AuthSession::CheckIpCallback, this, std::placeholders::_1)));
 }
 
 bool AuthSession::Update()
@@ -229,6 +236,22 @@ void AuthSession::ReadHandler()
     MessageBuffer& packet = GetReadBuffer();
     while (packet.GetActiveSize())
     {
+        // Packets with a Rust handler are decoded and handled there
+        size_t consumed = 0;
+        switch (AuthSession_DispatchPacket(_rsAuthSession, packet.GetReadPointer(), packet.GetActiveSize(), &consumed))
+        {
+            case PACKET_HANDLED:
+                packet.ReadCompleted(consumed);
+                continue;
+            case PACKET_INCOMPLETE:
+                return;
+            case PACKET_REJECTED:
+                AuthSession_Disconnect(_rsAuthSession);
+                return;
+            case PACKET_UNHANDLED:
+                break;
+        }
+
         uint8 cmd = packet.GetReadPointer()[0];
         auto itr = Handlers.find(cmd);
         if (itr == Handlers.end())
@@ -281,34 +304,54 @@ void AuthSession::SendPacket(ByteBuffer& packet)
 
 bool AuthSession::HandleLogonChallenge()
 {
-    _status = STATUS_CLOSED;
+    SetStatus(STATUS_CLOSED);
 
-    sAuthLogonChallenge_C* challenge = reinterpret_cast<sAuthLogonChallenge_C*>(GetReadBuffer().GetReadPointer());
-    if (challenge->size - (sizeof(sAuthLogonChallenge_C) - AUTH_LOGON_CHALLENGE_INITIAL_SIZE - 1) != challenge->I_len)
+    MessageBuffer& packet = GetReadBuffer();
+    std::size_t packetSize = 0;
+    LogonChallengeRequest* challenge = LogonChallengeRequest_Decode(packet.GetReadPointer(), packet.GetActiveSize(), &packetSize);
+    if (!challenge)
         return false;
 
-    std::string login((char const*)challenge->I, challenge->I_len);
+    std::size_t length = 0;
+    uint8 const* field = LogonChallengeRequest_GetAccountName(challenge, &length);
+    if (LogonChallengeRequest_GetSize(challenge) != packetSize - AUTH_LOGON_CHALLENGE_INITIAL_SIZE)
+    {
+        LogonChallengeRequest_Free(challenge);
+        return false;
+    }
+
+    std::string login((char const*)field, length);
     TC_LOG_DEBUG("server.authserver", "[AuthChallenge] '%s'", login.c_str());
 
-    _build = challenge->build;
+    _build = LogonChallengeRequest_GetBuild(challenge);
+    AuthResult buildResult = BuildRegistry_CheckBuild(_build);
+    if (buildResult != WOW_SUCCESS)
+    {
+        LogonChallengeRequest_Free(challenge);
+        TC_LOG_DEBUG("server.authserver", "[AuthChallenge] '%s' uses unknown client build %u", login.c_str(), uint32(_build));
+        auto response = LogonChallengeErrorResponse_New(AUTH_LOGON_CHALLENGE, 0, buildResult);
+        LogonChallengeErrorResponse_Send(response, _rsAuthSession);
+        return true;
+    }
+
     _expversion = uint8(AuthHelper::IsPostBCAcceptedClientBuild(_build) ? POST_BC_EXP_FLAG : (AuthHelper::IsPreBCAcceptedClientBuild(_build) ? PRE_BC_EXP_FLAG : NO_VALID_EXP_FLAG));
-    std::array<char, 5> os;
-    os.fill('\0');
-    memcpy(os.data(), challenge->os, sizeof(challenge->os));
-    _os = os.data();
 
     // Restore string order as its byte order is reversed
+    field = LogonChallengeRequest_GetOs(challenge, &length);
+    _os.assign((char const*)field, length);
     std::reverse(_os.begin(), _os.end());
 
-    _localizationName.resize(4);
-    for (int i = 0; i < 4; ++i)
-        _localizationName[i] = challenge->country[4 - i - 1];
+    field = LogonChallengeRequest_GetCountry(challenge, &length);
+    _localizationName.assign((char const*)field, length);
+    std::reverse(_localizationName.begin(), _localizationName.end());
+
+    LogonChallengeRequest_Free(challenge);
 
     // Get the account details from the account table
     LoginDatabasePreparedStatement* stmt = LoginDatabase.GetPreparedStatement(LOGIN_SEL_LOGONCHALLENGE);
     stmt->setString(0, login);
 
-    _queryProcessor.AddCallback(LoginDatabase.AsyncQuery(stmt).WithPreparedCallback(std::bind(&AuthSession::LogonChallengeCallback, this, std::placeholders::_1)));
+    _queryProcessor.AddCallback(AsyncQuery(stmt).WithPreparedCallback(std::bind(&AuthSession::LogonChallengeCallback, this, std::placeholders::_1)));
     return true;
 }
 
@@ -324,6 +367,7 @@ void AuthSession::LogonChallengeCallback(PreparedQueryResult result)
     Field* fields = result->Fetch();
 
     _accountInfo.LoadResult(fields);
+    AuthSession_SetAccountName(_rsAuthSession, _accountInfo.Login.c_str());
 
     std::string ipAddress = std::string(GetRemoteIpAddress());
     uint16 port = GetRemotePort();
@@ -341,16 +385,15 @@ void AuthSession::LogonChallengeCallback(PreparedQueryResult result)
     }
     else
     {
-        if (IpLocationRecord const* location = sIPLocation->GetLocationRecord(ipAddress))
-            _ipCountry = location->CountryCode;
+        char const* ipCountry = AuthSession_GetIpCountry(_rsAuthSession);
 
         TC_LOG_DEBUG("server.authserver", "[AuthChallenge] Account '%s' is not locked to ip", _accountInfo.Login.c_str());
         if (_accountInfo.LockCountry.empty() || _accountInfo.LockCountry == "00")
             TC_LOG_DEBUG("server.authserver", "[AuthChallenge] Account '%s' is not locked to country", _accountInfo.Login.c_str());
-        else if (!_ipCountry.empty())
+        else if (ipCountry)
         {
-            TC_LOG_DEBUG("server.authserver", "[AuthChallenge] Account '%s' is locked to country: '%s' Player country is '%s'", _accountInfo.Login.c_str(), _accountInfo.LockCountry.c_str(), _ipCountry.c_str());
-            if (_ipCountry != _accountInfo.LockCountry)
+            TC_LOG_DEBUG("server.authserver", "[AuthChallenge] Account '%s' is locked to country: '%s' Player country is '%s'", _accountInfo.Login.c_str(), _accountInfo.LockCountry.c_str(), ipCountry);
+            if (!AuthSession_IsCountryAllowed(_rsAuthSession, _accountInfo.LockCountry.c_str()))
             {
                 auto response = LogonChallengeErrorResponse_New(AUTH_LOGON_CHALLENGE, 0, WOW_FAIL_UNLOCKABLE_LOCK);
                 LogonChallengeErrorResponse_Send(response, _rsAuthSession);
@@ -384,23 +427,40 @@ void AuthSession::LogonChallengeCallback(PreparedQueryResult result)
     {
         securityFlags = 4;
         _totpSecret = fields[9].GetBinary();
-        if (auto const& secret = sSecretMgr->GetSecret(SECRET_TOTP_MASTER_KEY))
+        size_t secretLength = _totpSecret->size();
+        TotpSecretResult result = Totp_DecryptSecret(_totpSecret->data(), &secretLength);
+        if (result == TOTP_SECRET_INVALID)
         {
-            bool success = Kitron::Crypto::AEDecrypt<Kitron::Crypto::AES>(*_totpSecret, *secret);
-            if (!success)
-            {
-                auto response = LogonChallengeErrorResponse_New(AUTH_LOGON_CHALLENGE, 0, WOW_FAIL_DB_BUSY);
-                LogonChallengeErrorResponse_Send(response, _rsAuthSession);
-                TC_LOG_ERROR("server.authserver", "[AuthChallenge] Account '%s' has invalid ciphertext for TOTP token key stored", _accountInfo.Login.c_str());
-                return;
-            }
+            auto response = LogonChallengeErrorResponse_New(AUTH_LOGON_CHALLENGE, 0, WOW_FAIL_DB_BUSY);
+            LogonChallengeErrorResponse_Send(response, _rsAuthSession);
+            TC_LOG_ERROR("server.authserver", "[AuthChallenge] Account '%s' has invalid ciphertext for TOTP token key stored", _accountInfo.Login.c_str());
+            return;
+        }
+
+        _totpSecret->resize(secretLength);
+
+        // Stored with the old master key, store it again with the current one
+        if (result == TOTP_SECRET_ROTATED)
+        {
+            std::vector<uint8> encrypted(secretLength + TOTP_ENCRYPTION_OVERHEAD);
+            encrypted.resize(Totp_EncryptSecret(_totpSecret->data(), secretLength, encrypted.data()));
+
+            LoginDatabasePreparedStatement* stmt = LoginDatabase.GetPreparedStatement(LOGIN_UPD_ACCOUNT_TOTP_SECRET);
+            stmt->setBinary(0, encrypted);
+            stmt->setUInt32(1, _accountInfo.Id);
+            LoginDatabase.Execute(stmt);
         }
     }
 
+    // Recorded in the packet capture, a replayed session gets the recorded value back
+    Kitron::Crypto::SRP6::PrivateKey b = Kitron::Crypto::GetRandomBytes<Kitron::Crypto::SRP6::PRIVATE_KEY_LENGTH>();
+    AuthSession_UseRandomBytes(_rsAuthSession, b.data(), b.size());
+
     _srp6.emplace(
         _accountInfo.Login,
         fields[10].GetBinary<Kitron::Crypto::SRP6::SALT_LENGTH>(),
-        fields[11].GetBinary<Kitron::Crypto::SRP6::VERIFIER_LENGTH>()
+        fields[11].GetBinary<Kitron::Crypto::SRP6::VERIFIER_LENGTH>(),
+        b
     );
 
     // Fill the response packet with the result
@@ -417,7 +477,7 @@ void AuthSession::LogonChallengeCallback(PreparedQueryResult result)
         pkt << uint8(32);
         pkt.append(_srp6->N);
         pkt.append(_srp6->s);
-        pkt.append(VersionChallenge.data(), VersionChallenge.size());
+        pkt.append(VERSION_CHALLENGE, sizeof(VERSION_CHALLENGE));
         pkt << uint8(securityFlags);            // security flags (0x0...0x04)
 
         if (securityFlags & 0x01)               // PIN input
@@ -441,7 +501,7 @@ void AuthSession::LogonChallengeCallback(PreparedQueryResult result)
         TC_LOG_DEBUG("server.authserver", "'%s:%d' [AuthChallenge] account %s is using '%s' locale (%u)",
             ipAddress.c_str(), port, _accountInfo.Login.c_str(), _localizationName.c_str(), GetLocaleByName(_localizationName));
 
-        _status = STATUS_LOGON_PROOF;
+        SetStatus(STATUS_LOGON_PROOF);
         SendPacket(pkt);
     } else {
         auto response = LogonChallengeErrorResponse_New(AUTH_LOGON_CHALLENGE, 0, WOW_FAIL_VERSION_INVALID);
@@ -453,7 +513,7 @@ void AuthSession::LogonChallengeCallback(PreparedQueryResult result)
 bool AuthSession::HandleLogonProof()
 {
     TC_LOG_DEBUG("server.authserver", "Entering _HandleLogonProof");
-    _status = STATUS_CLOSED;
+    SetStatus(STATUS_CLOSED);
 
     // Read the packet
     sAuthLogonProof_C *logonProof = reinterpret_cast<sAuthLogonProof_C*>(GetReadBuffer().GetReadPointer());
@@ -480,7 +540,7 @@ bool AuthSession::HandleLogonProof()
             GetReadBuffer().ReadCompleted(sizeof(size) + size);
 
             uint32 incomingToken = atoi(token.c_str());
-            tokenSuccess = Kitron::Crypto::TOTP::ValidateToken(*_totpSecret, incomingToken);
+            tokenSuccess = Totp_ValidateToken(_totpSecret->data(), _totpSecret->size(), incomingToken);
             memset(_totpSecret->data(), 0, _totpSecret->size());
         }
         else if (!sentToken && !_totpSecret)
@@ -549,7 +609,7 @@ bool AuthSession::HandleLogonProof()
         }
 
         SendPacket(packet);
-        _status = STATUS_AUTHED;
+        SetStatus(STATUS_AUTHED);
     }
     else
     {
@@ -616,7 +676,7 @@ bool AuthSession::HandleLogonProof()
 
 bool AuthSession::HandleReconnectChallenge()
 {
-    _status = STATUS_CLOSED;
+    SetStatus(STATUS_CLOSED);
 
     sAuthLogonChallenge_C* challenge = reinterpret_cast<sAuthLogonChallenge_C*>(GetReadBuffer().GetReadPointer());
     if (challenge->size - (sizeof(sAuthLogonChallenge_C) - AUTH_LOGON_CHALLENGE_INITIAL_SIZE - 1) != challenge->I_len)
@@ -643,7 +703,7 @@ bool AuthSession::HandleReconnectChallenge()
     LoginDatabasePreparedStatement* stmt = LoginDatabase.GetPreparedStatement(LOGIN_SEL_RECONNECTCHALLENGE);
     stmt->setString(0, login);
 
-    _queryProcessor.AddCallback(LoginDatabase.AsyncQuery(stmt).WithPreparedCallback(std::bind(&AuthSession::ReconnectChallengeCallback, this, std::placeholders::_1)));
+    _queryProcessor.AddCallback(AsyncQuery(stmt).WithPreparedCallback(std::bind(&AuthSession::ReconnectChallengeCallback, this, std::placeholders::_1)));
     return true;
 }
 
@@ -662,13 +722,15 @@ void AuthSession::ReconnectChallengeCallback(PreparedQueryResult result)
     Field* fields = result->Fetch();
 
     _accountInfo.LoadResult(fields);
+    AuthSession_SetAccountName(_rsAuthSession, _accountInfo.Login.c_str());
     _sessionKey = fields[9].GetBinary<SESSION_KEY_LENGTH>();
     Kitron::Crypto::GetRandomBytes(_reconnectProof);
-    _status = STATUS_RECONNECT_PROOF;
+    AuthSession_UseRandomBytes(_rsAuthSession, _reconnectProof.data(), _reconnectProof.size());
+    SetStatus(STATUS_RECONNECT_PROOF);
 
     pkt << uint8(WOW_SUCCESS);
     pkt.append(_reconnectProof);
-    pkt.append(VersionChallenge.data(), VersionChallenge.size());
+    pkt.append(VERSION_CHALLENGE, sizeof(VERSION_CHALLENGE));
 
     SendPacket(pkt);
 }
@@ -676,7 +738,7 @@ void AuthSession::ReconnectChallengeCallback(PreparedQueryResult result)
 bool AuthSession::HandleReconnectProof()
 {
     TC_LOG_DEBUG("server.authserver", "Entering _HandleReconnectProof");
-    _status = STATUS_CLOSED;
+    SetStatus(STATUS_CLOSED);
 
     sAuthReconnectProof_C *reconnectProof = reinterpret_cast<sAuthReconnectProof_C*>(GetReadBuffer().GetReadPointer());
 
@@ -707,7 +769,7 @@ bool AuthSession::HandleReconnectProof()
         pkt << uint8(WOW_SUCCESS);
         pkt << uint16(0);    // LoginFlags, 1 has account message
         SendPacket(pkt);
-        _status = STATUS_AUTHED;
+        SetStatus(STATUS_AUTHED);
         return true;
     }
     else
@@ -718,16 +780,13 @@ bool AuthSession::HandleReconnectProof()
     }
 }
 
-bool AuthSession::HandleRealmList()
+void AuthSession::RequestRealmList()
 {
-    TC_LOG_DEBUG("server.authserver", "Entering _HandleRealmList");
-
     LoginDatabasePreparedStatement* stmt = LoginDatabase.GetPreparedStatement(LOGIN_SEL_REALM_CHARACTER_COUNTS);
     stmt->setUInt32(0, _accountInfo.Id);
 
-    _queryProcessor.AddCallback(LoginDatabase.AsyncQuery(stmt).WithPreparedCallback(std::bind(&AuthSession::RealmListCallback, this, std::placeholders::_1)));
-    _status = STATUS_WAITING_FOR_REALM_LIST;
-    return true;
+    _queryProcessor.AddCallback(AsyncQuery(stmt).WithPreparedCallback(std::bind(&AuthSession::RealmListCallback, this, std::placeholders::_1)));
+    SetStatus(STATUS_WAITING_FOR_REALM_LIST);
 }
 
 void AuthSession::RealmListCallback(PreparedQueryResult result)
@@ -742,127 +801,21 @@ void AuthSession::RealmListCallback(PreparedQueryResult result)
         } while (result->NextRow());
     }
 
-    // Circle through realms in the RealmList and construct the return packet (including # of user characters in each realm)
-    ByteBuffer pkt;
-
-    size_t RealmListSize = 0;
-    for (RealmList::RealmMap::value_type const& i : sRealmList->GetRealms())
+    std::vector<uint32> realmIds;
+    std::vector<uint8> counts;
+    for (auto const& [realmId, count] : characterCounts)
     {
-        Realm const& realm = i.second;
-        // don't work with realms which not compatible with the client
-        bool okBuild = ((_expversion & POST_BC_EXP_FLAG) && realm.Build == _build) || ((_expversion & PRE_BC_EXP_FLAG) && !AuthHelper::IsPreBCAcceptedClientBuild(realm.Build));
-
-        // No SQL injection. id of realm is controlled by the database.
-        uint32 flag = realm.Flags;
-        RealmBuildInfo const* buildInfo = sRealmList->GetBuildInfo(realm.Build);
-        if (!okBuild)
-        {
-            if (!buildInfo)
-                continue;
-
-            flag |= REALM_FLAG_OFFLINE | REALM_FLAG_SPECIFYBUILD;   // tell the client what build the realm is for
-        }
-
-        if (!buildInfo)
-            flag &= ~REALM_FLAG_SPECIFYBUILD;
-
-        std::string name = realm.Name;
-        if (_expversion & PRE_BC_EXP_FLAG && flag & REALM_FLAG_SPECIFYBUILD)
-        {
-            std::ostringstream ss;
-            ss << name << " (" << buildInfo->MajorVersion << '.' << buildInfo->MinorVersion << '.' << buildInfo->BugfixVersion << ')';
-            name = ss.str();
-        }
-
-        uint8 lock = (realm.AllowedSecurityLevel > _accountInfo.SecurityLevel) ? 1 : 0;
-
-        pkt << uint8(realm.Type);                           // realm type
-        if (_expversion & POST_BC_EXP_FLAG)                 // only 2.x and 3.x clients
-            pkt << uint8(lock);                             // if 1, then realm locked
-        pkt << uint8(flag);                                 // RealmFlags
-        pkt << name;
-
-        auto address = boost::asio::ip::make_address(GetRemoteIpAddress());
-        pkt << boost::lexical_cast<std::string>(realm.GetAddressForClient(address));
-        pkt << float(realm.PopulationLevel);
-        pkt << uint8(characterCounts[realm.Id.Realm]);
-        pkt << uint8(realm.Timezone);                       // realm category
-        if (_expversion & POST_BC_EXP_FLAG)                 // 2.x and 3.x clients
-            pkt << uint8(realm.Id.Realm);
-        else
-            pkt << uint8(0x0);                              // 1.12.1 and 1.12.2 clients
-
-        if (_expversion & POST_BC_EXP_FLAG && flag & REALM_FLAG_SPECIFYBUILD)
-        {
-            pkt << uint8(buildInfo->MajorVersion);
-            pkt << uint8(buildInfo->MinorVersion);
-            pkt << uint8(buildInfo->BugfixVersion);
-            pkt << uint16(buildInfo->Build);
-        }
-
-        ++RealmListSize;
+        realmIds.push_back(realmId);
+        counts.push_back(count);
     }
 
-    if (_expversion & POST_BC_EXP_FLAG)                     // 2.x and 3.x clients
-    {
-        pkt << uint8(0x10);
-        pkt << uint8(0x00);
-    }
-    else                                                    // 1.12.1 and 1.12.2 clients
-    {
-        pkt << uint8(0x00);
-        pkt << uint8(0x02);
-    }
-
-    // make a ByteBuffer which stores the RealmList's size
-    ByteBuffer RealmListSizeBuffer;
-    RealmListSizeBuffer << uint32(0);
-    if (_expversion & POST_BC_EXP_FLAG)                     // only 2.x and 3.x clients
-        RealmListSizeBuffer << uint16(RealmListSize);
-    else
-        RealmListSizeBuffer << uint32(RealmListSize);
-
-    ByteBuffer hdr;
-    hdr << uint8(REALM_LIST);
-    hdr << uint16(pkt.size() + RealmListSizeBuffer.size());
-    hdr.append(RealmListSizeBuffer);                        // append RealmList's size buffer
-    hdr.append(pkt);                                        // append realms in the realmlist
-    SendPacket(hdr);
+    // The realm list packet is built from the Rust realm registry
+    AuthSession_SendRealmList(_rsAuthSession, _build, _expversion, uint8(_accountInfo.SecurityLevel), realmIds.data(), counts.data(), realmIds.size());
 
-    _status = STATUS_AUTHED;
+    SetStatus(STATUS_AUTHED);
 }
 
 bool AuthSession::VerifyVersion(uint8 const* a, int32 aLength, Kitron::Crypto::SHA1::Digest const& versionProof, bool isReconnect)
 {
-    if (!sConfigMgr->GetBoolDefault("StrictVersionCheck", false))
-        return true;
-
-    Kitron::Crypto::SHA1::Digest zeros = { };
-    Kitron::Crypto::SHA1::Digest const* versionHash = nullptr;
-    if (!isReconnect)
-    {
-        RealmBuildInfo const* buildInfo = sRealmList->GetBuildInfo(_build);
-        if (!buildInfo)
-            return false;
-
-        if (_os == "Win")
-            versionHash = &buildInfo->WindowsHash;
-        else if (_os == "OSX")
-            versionHash = &buildInfo->MacHash;
-
-        if (!versionHash)
-            return false;
-
-        if (zeros == *versionHash)
-            return true;                                                            // not filled serverside
-    }
-    else
-        versionHash = &zeros;
-
-    Kitron::Crypto::SHA1 version;
-    version.UpdateData(a, aLength);
-    version.UpdateData(*versionHash);
-    version.Finalize();
-
-    return (versionProof == version.GetDigest());
+    return BuildRegistry_VerifyVersion(_build, _os.c_str(), a, aLength, versionProof.data(), isReconnect);
 }
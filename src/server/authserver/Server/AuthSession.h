@@ -34,6 +34,7 @@ using boost::asio::ip::tcp;
 
 class Field;
 struct AuthHandler;
+struct Notifier;
 
 enum AuthStatus
 {
@@ -74,6 +75,7 @@ public:
 
     void Start();
     bool Update();
+    void RequestRealmList();
 
     void SendPacket(ByteBuffer& packet);
 
@@ -83,12 +85,13 @@ protected:
 private:
     const char* GetRemoteIpAddress();
     uint16_t GetRemotePort();
+    void SetStatus(AuthStatus status);
+    QueryCallback AsyncQuery(LoginDatabasePreparedStatement* stmt);
 
     bool HandleLogonChallenge();
     bool HandleLogonProof();
     bool HandleReconnectChallenge();
     bool HandleReconnectProof();
-    bool HandleRealmList();
 
     void CheckIpCallback(PreparedQueryResult result);
     void LogonChallengeCallback(PreparedQueryResult result);
@@ -106,11 +109,12 @@ private:
     Optional<std::vector<uint8>> _totpSecret;
     std::string _localizationName;
     std::string _os;
-    std::string _ipCountry;
     uint16 _build;
     uint8 _expversion;
 
     QueryCallbackProcessor _queryProcessor;
+    // Wakes the Rust session up when a query completed
+    std::shared_ptr<Notifier const> _notifier;
 };
 
 #pragma pack(push, 1)
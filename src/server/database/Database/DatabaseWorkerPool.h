@@ -22,6 +22,7 @@
 #include "DatabaseEnvFwd.h"
 #include "StringFormat.h"
 #include <array>
+#include <functional>
 #include <string>
 #include <vector>
 
@@ -154,7 +155,8 @@ class DatabaseWorkerPool
         //! Enqueues a query in prepared format that will set the value of the PreparedQueryResultFuture return object as soon as the query is executed.
         //! The return value is then processed in ProcessQueryCallback methods.
         //! Statement must be prepared with CONNECTION_ASYNC flag.
-        QueryCallback AsyncQuery(PreparedStatement<T>* stmt);
+        //! onComplete, if set, is called on the worker thread once the result is available.
+        QueryCallback AsyncQuery(PreparedStatement<T>* stmt, std::function<void()> onComplete = nullptr);
 
         //! Enqueues a vector of SQL operations (can be both adhoc and prepared) that will set the value of the QueryResultHolderFuture
         //! return object as soon as the query is executed.
@@ -140,14 +140,18 @@ bool PreparedStatementTask::Execute()
     if (m_has_result)
     {
         PreparedResultSet* result = m_conn->Query(m_stmt);
-        if (!result || !result->GetRowCount())
+        bool hasRows = result && result->GetRowCount();
+        if (!hasRows)
         {
             delete result;
-            m_result->set_value(PreparedQueryResult(nullptr));
-            return false;
+            result = nullptr;
         }
+
         m_result->set_value(PreparedQueryResult(result));
-        return true;
+        if (m_completionHandler)
+            m_completionHandler();
+
+        return hasRows;
     }
 
     return m_conn->Execute(m_stmt);
@@ -20,6 +20,7 @@
 
 #include "Define.h"
 #include "SQLOperation.h"
+#include <functional>
 #include <future>
 #include <vector>
 #include <variant>
@@ -120,10 +121,13 @@ class TC_DATABASE_API PreparedStatementTask : public SQLOperation
 
         bool Execute() override;
         PreparedQueryResultFuture GetFuture() { return m_result->get_future(); }
+        //! Called on the worker thread once the result is available
+        void SetCompletionHandler(std::function<void()> handler) { m_completionHandler = std::move(handler); }
 
     protected:
         PreparedStatementBase* m_stmt;
         bool m_has_result;
         PreparedQueryResultPromise* m_result;
+        std::function<void()> m_completionHandler;
 };
 #endif
@@ -184,7 +184,7 @@ void RBACData::LoadFromDB()
     LoadFromDBCallback(LoginDatabase.Query(stmt));
 }
 
-QueryCallback RBACData::LoadFromDBAsync()
+QueryCallback RBACData::LoadFromDBAsync(std::function<void()> onComplete)
 {
     ClearData();
 
@@ -194,7 +194,7 @@ QueryCallback RBACData::LoadFromDBAsync()
     stmt->setUInt32(0, GetId());
     stmt->setInt32(1, GetRealmId());
 
-    return LoginDatabase.AsyncQuery(stmt);
+    return LoginDatabase.AsyncQuery(stmt, std::move(onComplete));
 }
 
 void RBACData::LoadFromDBCallback(PreparedQueryResult result)
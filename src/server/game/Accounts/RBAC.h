@@ -42,6 +42,7 @@
 
 #include "Define.h"
 #include "DatabaseEnvFwd.h"
+#include <functional>
 #include <string>
 #include <set>
 #include <map>
@@ -914,7 +915,8 @@ class TC_GAME_API RBACData
 
         /// Loads all permissions assigned to current account
         void LoadFromDB();
-        QueryCallback LoadFromDBAsync();
+        /// onComplete is called on the database worker once the result is ready
+        QueryCallback LoadFromDBAsync(std::function<void()> onComplete = nullptr);
         void LoadFromDBCallback(PreparedQueryResult result);
 
         /// Sets security level
@@ -1926,6 +1926,8 @@ void Player::AddToWorld()
     for (uint8 i = PLAYER_SLOT_START; i < PLAYER_SLOT_END; ++i)
         if (m_items[i])
             m_items[i]->AddToWorld();
+
+    m_session->UpdateSocketState();
 }
 
 void Player::RemoveFromWorld()
@@ -1970,6 +1972,8 @@ void Player::RemoveFromWorld()
             SetViewpoint(viewpoint, false);
         }
     }
+
+    m_session->UpdateSocketState();
 }
 
 void Player::SetObjectScale(float scale)
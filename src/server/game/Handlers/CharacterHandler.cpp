@@ -977,7 +977,7 @@ void WorldSession::HandlePlayerLogin(LoginQueryHolder const& holder)
 
     // show time before shutdown if shutdown planned.
     if (sWorld->IsShuttingDown())
-        sWorld->ShutdownMsg(true, pCurrChar);
+        sWorld->ShutdownMsg(pCurrChar);
 
     if (sWorld->getBoolConfig(CONFIG_ALL_TAXI_PATHS))
         pCurrChar->SetTaxiCheater(true);
@@ -18,36 +18,21 @@
 #ifndef KITRON_PACKETLOG_H
 #define KITRON_PACKETLOG_H
 
-#include "Common.h"
-
-#include <boost/asio/ip/address.hpp>
-#include <mutex>
-
-enum Direction
-{
-    CLIENT_TO_SERVER,
-    SERVER_TO_CLIENT
-};
-
-class WorldPacket;
+#include "Define.h"
 
+/// The world packets are logged by the sockets, in the PKT 3.1 format of WowPacketParser.
+/// This switches the log configured by `PacketLogFile` at runtime.
 class TC_GAME_API PacketLog
 {
     private:
-        PacketLog();
-        ~PacketLog();
-        std::mutex _logPacketLock;
-        std::once_flag _initializeFlag;
+        PacketLog() = default;
 
     public:
         static PacketLog* instance();
 
-        void Initialize();
-        bool CanLogPacket() const { return (_file != nullptr); }
-        void LogPacket(WorldPacket const& packet, Direction direction, boost::asio::ip::address const& addr, uint16 port);
-
-    private:
-        FILE* _file;
+        /// Returns false if the log could not be started
+        bool SetEnabled(bool enabled);
+        bool IsEnabled() const;
 };
 
 #define sPacketLog PacketLog::instance()
@@ -55,6 +55,7 @@
 #include "WorldPacket.h"
 #include "WorldSocket.h"
 #include <zlib.h>
+#include "libenturion_worldserver.h"
 
 namespace {
 
@@ -366,8 +367,11 @@ bool WorldSession::Update(uint32 diff, PacketFilter& updater)
 
                     // some auth opcodes can be recieved before STATUS_LOGGEDIN_OR_RECENTLY_LOGGOUT opcodes
                     // however when we recieve CMSG_CHAR_ENUM we are surely no longer during the logout process.
-                    if (packet->GetOpcode() == CMSG_CHAR_ENUM)
+                    if (packet->GetOpcode() == CMSG_CHAR_ENUM && m_playerRecentlyLogout)
+                    {
                         m_playerRecentlyLogout = false;
+                        UpdateSocketState();
+                    }
 
                     if (AntiDOS.EvaluateOpcode(*packet, currentTime))
                     {
@@ -625,6 +629,7 @@ void WorldSession::LogoutPlayer(bool save)
     m_playerSave = false;
     m_playerRecentlyLogout = true;
     SetLogoutStartTime(0);
+    UpdateSocketState();
 }
 
 /// Kick a player out of the World
@@ -1222,6 +1227,22 @@ void WorldSession::SetPlayer(Player* player)
     // set m_GUID that can be used while player loggined and later until m_playerRecentlyLogout not reset
     if (_player)
         m_GUIDLow = _player->GetGUID().GetCounter();
+
+    UpdateSocketState();
+}
+
+void WorldSession::UpdateSocketState()
+{
+    if (!m_Socket)
+        return;
+
+    SessionState state = SESSION_STATE_AUTHED;
+    if (_player)
+        state = _player->IsInWorld() ? SESSION_STATE_LOGGED_IN : SESSION_STATE_TRANSFER;
+    else if (m_playerRecentlyLogout)
+        state = SESSION_STATE_RECENTLY_LOGGED_OUT;
+
+    m_Socket->SetSessionState(state);
 }
 
 void WorldSession::ProcessQueryCallbacks()
@@ -1265,7 +1286,7 @@ void WorldSession::LoadPermissions()
     _RBACData->LoadFromDB();
 }
 
-QueryCallback WorldSession::LoadPermissionsAsync()
+QueryCallback WorldSession::LoadPermissionsAsync(std::function<void()> onComplete)
 {
     uint32 id = GetAccountId();
     uint8 secLevel = GetSecurity();
@@ -1274,7 +1295,7 @@ QueryCallback WorldSession::LoadPermissionsAsync()
         id, _accountName.c_str(), realm.Id.Realm, secLevel);
 
     _RBACData = new rbac::RBACData(id, _accountName, realm.Id.Realm, secLevel);
-    return _RBACData->LoadFromDBAsync();
+    return _RBACData->LoadFromDBAsync(std::move(onComplete));
 }
 
 class AccountInfoQueryHolderPerRealm : public CharacterDatabaseQueryHolder
@@ -30,6 +30,7 @@
 #include "ObjectGuid.h"
 #include "Packet.h"
 #include "SharedDefines.h"
+#include <functional>
 #include <string>
 #include <map>
 #include <memory>
@@ -435,7 +436,7 @@ class TC_GAME_API WorldSession
         rbac::RBACData* GetRBACData();
         bool HasPermission(uint32 permissionId);
         void LoadPermissions();
-        QueryCallback LoadPermissionsAsync();
+        QueryCallback LoadPermissionsAsync(std::function<void()> onComplete = nullptr);
         void InvalidateRBACData(); // Used to force LoadPermissions at next HasPermission check
 
         AccountTypes GetSecurity() const { return _security; }
@@ -449,6 +450,8 @@ class TC_GAME_API WorldSession
         void SetSecurity(AccountTypes security) { _security = security; }
         std::string const& GetRemoteAddress() const { return m_Address; }
         void SetPlayer(Player* player);
+        /// Has the socket check the client packets against the current player state
+        void UpdateSocketState();
         uint8 Expansion() const { return m_expansion; }
 
         void InitWarden(SessionKey const& k, std::string const& os);
@@ -597,7 +600,7 @@ class TC_GAME_API WorldSession
     public:                                                 // opcodes handlers
 
         void Handle_NULL(WorldPacket& recvPacket);          // not used
-        void Handle_EarlyProccess(WorldPacket& recvPacket); // just mark packets processed in WorldSocket::ReadDataHandler
+        void Handle_EarlyProccess(WorldPacket& recvPacket); // just mark packets processed in WorldSocket::HandlePacket
         void Handle_ServerSide(WorldPacket& recvPacket);    // sever side only, can't be accepted from client
         void Handle_Deprecated(WorldPacket& recvPacket);    // never used anymore by client
 
@@ -16,40 +16,75 @@
  */
 
 #include "WorldSocket.h"
-#include "BigNumber.h"
 #include "DatabaseEnv.h"
 #include "GameTime.h"
 #include "CryptoHash.h"
-#include "CryptoRandom.h"
 #include "IPLocation.h"
 #include "Opcodes.h"
-#include "PacketLog.h"
-#include "Random.h"
 #include "RBAC.h"
 #include "Realm.h"
 #include "ScriptMgr.h"
 #include "World.h"
 #include "WorldSession.h"
 #include <memory>
-
-using boost::asio::ip::tcp;
-
-WorldSocket::WorldSocket(tcp::socket&& socket)
-    : Socket(std::move(socket)), _OverSpeedPings(0), _worldSession(nullptr), _authed(false), _sendBufferSize(4096)
+#include "libenturion_shared.h"
+#include "libenturion_worldserver.h"
+
+WorldSocket::WorldSocket(void* rsWorldSocket)
+    : _control(WorldSocket_GetControl(rsWorldSocket), WorldSocketControl_Free),
+    _notifier(WorldSocket_GetNotifier(rsWorldSocket), Notifier_Free),
+    _remoteAddress(boost::asio::ip::make_address(WorldSocket_GetRemoteIpAddress(rsWorldSocket))),
+    _remotePort(WorldSocket_GetRemotePort(rsWorldSocket)), _OverSpeedPings(0), _worldSession(nullptr), _authed(false)
 {
-    Kitron::Crypto::GetRandomBytes(_authSeed);
-    _headerBuffer.Resize(sizeof(ClientPktHeader));
 }
 
 WorldSocket::~WorldSocket() = default;
 
+extern "C" void* WorldSocket_New(void *rsWorldSocket) {
+    return new std::shared_ptr<WorldSocket>(std::make_shared<WorldSocket>(rsWorldSocket));
+}
+
+extern "C" void WorldSocket_Start(const void *worldSocket) {
+    auto socket = (std::shared_ptr<WorldSocket> const*) worldSocket;
+    (*socket)->Start();
+}
+
+extern "C" void WorldSocket_Update(const void *worldSocket) {
+    auto socket = (std::shared_ptr<WorldSocket> const*) worldSocket;
+    (*socket)->Update();
+}
+
+extern "C" bool WorldSocket_HandleAuthSession(const void *worldSocket, const WorldAuthSession *authSession) {
+    auto socket = (std::shared_ptr<WorldSocket> const*) worldSocket;
+    return (*socket)->HandleAuthSession(*authSession);
+}
+
+extern "C" bool WorldSocket_HandlePacket(const void *worldSocket, uint32_t opcode, const uint8_t *data, uintptr_t size) {
+    auto socket = (std::shared_ptr<WorldSocket> const*) worldSocket;
+    return (*socket)->HandlePacket(static_cast<OpcodeClient>(opcode), data, size);
+}
+
+/// The session may keep the socket alive, only the connection is gone
+extern "C" void WorldSocket_Free(void *worldSocket) {
+    auto socket = (std::shared_ptr<WorldSocket>*) worldSocket;
+    (*socket)->OnClose();
+    delete socket;
+}
+
+QueryCallback WorldSocket::AsyncQuery(LoginDatabasePreparedStatement* stmt)
+{
+    return LoginDatabase.AsyncQuery(stmt, [notifier = _notifier]() { Notifier_Notify(notifier.get()); });
+}
+
 void WorldSocket::Start()
 {
+    sScriptMgr->OnSocketOpen(shared_from_this());
+
     std::string ip_address = GetRemoteIpAddress().to_string();
     LoginDatabasePreparedStatement* stmt = LoginDatabase.GetPreparedStatement(LOGIN_SEL_IP_INFO);
     stmt->setString(0, ip_address);
 
-    _queryProcessor.AddCallback(LoginDatabase.AsyncQuery(stmt).WithPreparedCallback(std::bind(&WorldSocket::CheckIpCallback, this, std::placeholders::_1)));
+    _queryProcessor.AddCallback(AsyncQuery(stmt).WithPreparedCallback(std::bind(&WorldSocket::CheckIpCallback, this, std::placeholders::_1)));
 }
 
 void WorldSocket::CheckIpCallback(PreparedQueryResult result)
@@ -74,65 +109,12 @@ void WorldSocket::CheckIpCallback(PreparedQueryResult result)
         }
     }
 
-    AsyncRead();
-    HandleSendAuthSession();
+    WorldSocketControl_SendAuthChallenge(_control.get());
 }
 
-bool WorldSocket::Update()
+void WorldSocket::Update()
 {
-    EncryptablePacket* queued;
-    MessageBuffer buffer(_sendBufferSize);
-    while (_bufferQueue.Dequeue(queued))
-    {
-        ServerPktHeader header(queued->size() + 2, queued->GetOpcode());
-        if (queued->NeedsEncryption())
-            _authCrypt.EncryptSend(header.header, header.getHeaderLength());
-
-        if (buffer.GetRemainingSpace() < queued->size() + header.getHeaderLength())
-        {
-            QueuePacket(std::move(buffer));
-            buffer.Resize(_sendBufferSize);
-        }
-
-        if (buffer.GetRemainingSpace() >= queued->size() + header.getHeaderLength())
-        {
-            buffer.Write(header.header, header.getHeaderLength());
-            if (!queued->empty())
-                buffer.Write(queued->contents(), queued->size());
-        }
-        else    // single packet larger than 4096 bytes
-        {
-            MessageBuffer packetBuffer(queued->size() + header.getHeaderLength());
-            packetBuffer.Write(header.header, header.getHeaderLength());
-            if (!queued->empty())
-                packetBuffer.Write(queued->contents(), queued->size());
-
-            QueuePacket(std::move(packetBuffer));
-        }
-
-        delete queued;
-    }
-
-    if (buffer.GetActiveSize() > 0)
-        QueuePacket(std::move(buffer));
-
-    if (!BaseSocket::Update())
-        return false;
-
     _queryProcessor.ProcessReadyCallbacks();
-
-    return true;
-}
-
-void WorldSocket::HandleSendAuthSession()
-{
-    WorldPacket packet(SMSG_AUTH_CHALLENGE, 40);
-    packet << uint32(1);                                    // 1...31
-    packet.append(_authSeed);
-
-    packet.append(Kitron::Crypto::GetRandomBytes<32>());               // new encryption seeds
-
-    SendPacketAndLogOpcode(packet);
 }
 
 void WorldSocket::OnClose()
@@ -141,90 +123,28 @@ void WorldSocket::OnClose()
         std::lock_guard<std::mutex> sessionGuard(_worldSessionLock);
         _worldSession = nullptr;
     }
+
+    sScriptMgr->OnSocketClose(shared_from_this());
 }
 
-void WorldSocket::ReadHandler()
+bool WorldSocket::IsOpen() const
 {
-    if (!IsOpen())
-        return;
-
-    MessageBuffer& packet = GetReadBuffer();
-    while (packet.GetActiveSize() > 0)
-    {
-        if (_headerBuffer.GetRemainingSpace() > 0)
-        {
-            // need to receive the header
-            std::size_t readHeaderSize = std::min(packet.GetActiveSize(), _headerBuffer.GetRemainingSpace());
-            _headerBuffer.Write(packet.GetReadPointer(), readHeaderSize);
-            packet.ReadCompleted(readHeaderSize);
-
-            if (_headerBuffer.GetRemainingSpace() > 0)
-            {
-                // Couldn't receive the whole header this time.
-                ASSERT(packet.GetActiveSize() == 0);
-                break;
-            }
-
-            // We just received nice new header
-            if (!ReadHeaderHandler())
-            {
-                CloseSocket();
-                return;
-            }
-        }
-
-        // We have full read header, now check the data payload
-        if (_packetBuffer.GetRemainingSpace() > 0)
-        {
-            // need more data in the payload
-            std::size_t readDataSize = std::min(packet.GetActiveSize(), _packetBuffer.GetRemainingSpace());
-            _packetBuffer.Write(packet.GetReadPointer(), readDataSize);
-            packet.ReadCompleted(readDataSize);
-
-            if (_packetBuffer.GetRemainingSpace() > 0)
-            {
-                // Couldn't receive the whole data this time.
-                ASSERT(packet.GetActiveSize() == 0);
-                break;
-            }
-        }
-
-        // just received fresh new payload
-        ReadDataHandlerResult result = ReadDataHandler();
-        _headerBuffer.Reset();
-        if (result != ReadDataHandlerResult::Ok)
-        {
-            if (result != ReadDataHandlerResult::WaitingForQuery)
-                CloseSocket();
-
-            return;
-        }
-    }
-
-    AsyncRead();
+    return WorldSocketControl_IsOpen(_control.get());
 }
 
-bool WorldSocket::ReadHeaderHandler()
+void WorldSocket::CloseSocket()
 {
-    ASSERT(_headerBuffer.GetActiveSize() == sizeof(ClientPktHeader));
-
-    if (_authCrypt.IsInitialized())
-        _authCrypt.DecryptRecv(_headerBuffer.GetReadPointer(), sizeof(ClientPktHeader));
-
-    ClientPktHeader* header = reinterpret_cast<ClientPktHeader*>(_headerBuffer.GetReadPointer());
-    EndianConvertReverse(header->size);
-    EndianConvert(header->cmd);
+    WorldSocketControl_Close(_control.get());
+}
 
-    if (!header->IsValidSize() || !header->IsValidOpcode())
-    {
-        TC_LOG_ERROR("network", "WorldSocket::ReadHeaderHandler(): client %s sent malformed packet (size: %hu, cmd: %u)",
-            GetRemoteIpAddress().to_string().c_str(), header->size, header->cmd);
-        return false;
-    }
+void WorldSocket::DelayedCloseSocket()
+{
+    WorldSocketControl_DelayedClose(_control.get());
+}
 
-    header->size -= sizeof(header->cmd);
-    _packetBuffer.Resize(header->size);
-    return true;
+void WorldSocket::SetSessionState(uint8 state)
+{
+    WorldSocketControl_SetSessionState(_control.get(), state);
 }
 
 struct AuthSession
@@ -238,6 +158,7 @@ struct AuthSession
     uint32 RegionID = 0;
     uint64 DosResponse = 0;
     Kitron::Crypto::SHA1::Digest Digest = {};
+    std::array<uint8, 4> AuthSeed = {};
     std::string Account;
     ByteBuffer AddonInfo;
 };
@@ -292,17 +213,14 @@ struct AccountInfo
     }
 };
 
-WorldSocket::ReadDataHandlerResult WorldSocket::ReadDataHandler()
+bool WorldSocket::HandlePacket(OpcodeClient opcode, uint8 const* data, std::size_t size)
 {
-    ClientPktHeader* header = reinterpret_cast<ClientPktHeader*>(_headerBuffer.GetReadPointer());
-    OpcodeClient opcode = static_cast<OpcodeClient>(header->cmd);
+    WorldPacket packet(opcode, size);
+    if (size)
+        packet.append(data, size);
 
-    WorldPacket packet(opcode, std::move(_packetBuffer));
     WorldPacket* packetToQueue;
 
-    if (sPacketLog->CanLogPacket())
-        sPacketLog->LogPacket(packet, CLIENT_TO_SERVER, GetRemoteIpAddress(), GetRemotePort());
-
     std::unique_lock<std::mutex> sessionGuard(_worldSessionLock, std::defer_lock);
 
     switch (opcode)
@@ -312,35 +230,13 @@ WorldSocket::ReadDataHandlerResult WorldSocket::ReadDataHandler()
             LogOpcodeText(opcode, sessionGuard);
             try
             {
-                return HandlePing(packet) ? ReadDataHandlerResult::Ok : ReadDataHandlerResult::Error;
+                return HandlePing(packet);
             }
             catch (ByteBufferException const&)
             {
             }
-            TC_LOG_ERROR("network", "WorldSocket::ReadDataHandler(): client %s sent malformed CMSG_PING", GetRemoteIpAddress().to_string().c_str());
-            return ReadDataHandlerResult::Error;
-        }
-        case CMSG_AUTH_SESSION:
-        {
-            LogOpcodeText(opcode, sessionGuard);
-            if (_authed)
-            {
-                // locking just to safely log offending user is probably overkill but we are disconnecting him anyway
-                if (sessionGuard.try_lock())
-                    TC_LOG_ERROR("network", "WorldSocket::ProcessIncoming: received duplicate CMSG_AUTH_SESSION from %s", _worldSession->GetPlayerInfo().c_str());
-                return ReadDataHandlerResult::Error;
-            }
-
-            try
-            {
-                HandleAuthSession(packet);
-                return ReadDataHandlerResult::WaitingForQuery;
-            }
-            catch (ByteBufferException const&)
-            {
-            }
-            TC_LOG_ERROR("network", "WorldSocket::ReadDataHandler(): client %s sent malformed CMSG_AUTH_SESSION", GetRemoteIpAddress().to_string().c_str());
-            return ReadDataHandlerResult::Error;
+            TC_LOG_ERROR("network", "WorldSocket::HandlePacket(): client %s sent malformed CMSG_PING", GetRemoteIpAddress().to_string().c_str());
+            return false;
         }
         case CMSG_KEEP_ALIVE: // todo: handle this packet in the same way of CMSG_TIME_SYNC_RESP
             sessionGuard.lock();
@@ -348,10 +244,10 @@ WorldSocket::ReadDataHandlerResult WorldSocket::ReadDataHandler()
             if (_worldSession)
             {
                 _worldSession->ResetTimeOutTime(true);
-                return ReadDataHandlerResult::Ok;
+                return true;
             }
-            TC_LOG_ERROR("network", "WorldSocket::ReadDataHandler: client %s sent CMSG_KEEP_ALIVE without being authenticated", GetRemoteIpAddress().to_string().c_str());
-            return ReadDataHandlerResult::Error;
+            TC_LOG_ERROR("network", "WorldSocket::HandlePacket: client %s sent CMSG_KEEP_ALIVE without being authenticated", GetRemoteIpAddress().to_string().c_str());
+            return false;
         case CMSG_TIME_SYNC_RESP:
             packetToQueue = new WorldPacket(std::move(packet), std::chrono::steady_clock::now());
             break;
@@ -369,7 +265,7 @@ WorldSocket::ReadDataHandlerResult WorldSocket::ReadDataHandler()
     {
         TC_LOG_ERROR("network.opcode", "ProcessIncoming: Client not authed opcode = %u", uint32(opcode));
         delete packetToQueue;
-        return ReadDataHandlerResult::Error;
+        return false;
     }
 
     OpcodeHandler const* handler = opcodeTable[opcode];
@@ -377,7 +273,7 @@ WorldSocket::ReadDataHandlerResult WorldSocket::ReadDataHandler()
     {
         TC_LOG_ERROR("network.opcode", "No defined handler for opcode %s sent by %s", GetOpcodeNameForLogging(static_cast<OpcodeClient>(packet.GetOpcode())).c_str(), _worldSession->GetPlayerInfo().c_str());
         delete packetToQueue;
-        return ReadDataHandlerResult::Error;
+        return false;
     }
 
     // Our Idle timer will reset on any non PING opcodes on login screen, allowing us to catch people idling.
@@ -386,7 +282,7 @@ WorldSocket::ReadDataHandlerResult WorldSocket::ReadDataHandler()
     // Copy the packet to the heap before enqueuing
     _worldSession->QueuePacket(packetToQueue);
 
-    return ReadDataHandlerResult::Ok;
+    return true;
 }
 
 void WorldSocket::LogOpcodeText(OpcodeClient opcode, std::unique_lock<std::mutex> const& guard) const
@@ -413,36 +309,42 @@ void WorldSocket::SendPacket(WorldPacket const& packet)
     if (!IsOpen())
         return;
 
-    if (sPacketLog->CanLogPacket())
-        sPacketLog->LogPacket(packet, SERVER_TO_CLIENT, GetRemoteIpAddress(), GetRemotePort());
-
-    _bufferQueue.Enqueue(new EncryptablePacket(packet, _authCrypt.IsInitialized()));
+    WorldSocketControl_SendPacket(_control.get(), packet.GetOpcode(), packet.empty() ? nullptr : packet.contents(), packet.size());
 }
 
-void WorldSocket::HandleAuthSession(WorldPacket& recvPacket)
+bool WorldSocket::HandleAuthSession(WorldAuthSession const& request)
 {
-    std::shared_ptr<AuthSession> authSession = std::make_shared<AuthSession>();
+    std::unique_lock<std::mutex> sessionGuard(_worldSessionLock, std::defer_lock);
+    LogOpcodeText(CMSG_AUTH_SESSION, sessionGuard);
+    if (_authed)
+    {
+        // locking just to safely log offending user is probably overkill but we are disconnecting him anyway
+        if (sessionGuard.try_lock())
+            TC_LOG_ERROR("network", "WorldSocket::HandleAuthSession: received duplicate CMSG_AUTH_SESSION from %s", _worldSession->GetPlayerInfo().c_str());
+        return false;
+    }
 
-    // Read the content of the packet
-    recvPacket >> authSession->Build;
-    recvPacket >> authSession->LoginServerID;
-    recvPacket >> authSession->Account;
-    recvPacket >> authSession->LoginServerType;
-    recvPacket.read(authSession->LocalChallenge);
-    recvPacket >> authSession->RegionID;
-    recvPacket >> authSession->BattlegroupID;
-    recvPacket >> authSession->RealmID;               // realmId from auth_database.realmlist table
-    recvPacket >> authSession->DosResponse;
-    recvPacket.read(authSession->Digest);
-    authSession->AddonInfo.resize(recvPacket.size() - recvPacket.rpos());
-    recvPacket.read(authSession->AddonInfo.contents(), authSession->AddonInfo.size()); // .contents will throw if empty, thats what we want
+    std::shared_ptr<AuthSession> authSession = std::make_shared<AuthSession>();
+    authSession->Build = request.build;
+    authSession->LoginServerID = request.login_server_id;
+    authSession->Account = request.account;
+    authSession->LoginServerType = request.login_server_type;
+    std::copy(std::begin(request.local_challenge), std::end(request.local_challenge), authSession->LocalChallenge.begin());
+    authSession->RegionID = request.region_id;
+    authSession->BattlegroupID = request.battlegroup_id;
+    authSession->RealmID = request.realm_id;               // realmId from auth_database.realmlist table
+    authSession->DosResponse = request.dos_response;
+    std::copy(std::begin(request.digest), std::end(request.digest), authSession->Digest.begin());
+    std::copy(std::begin(request.auth_seed), std::end(request.auth_seed), authSession->AuthSeed.begin());
+    authSession->AddonInfo.append(request.addon_info, request.addon_info_size);
 
     // Get the account information from the auth database
     LoginDatabasePreparedStatement* stmt = LoginDatabase.GetPreparedStatement(LOGIN_SEL_ACCOUNT_INFO_BY_NAME);
     stmt->setInt32(0, int32(realm.Id.Realm));
     stmt->setString(1, authSession->Account);
 
-    _queryProcessor.AddCallback(LoginDatabase.AsyncQuery(stmt).WithPreparedCallback(std::bind(&WorldSocket::HandleAuthSessionCallback, this, authSession, std::placeholders::_1)));
+    _queryProcessor.AddCallback(AsyncQuery(stmt).WithPreparedCallback(std::bind(&WorldSocket::HandleAuthSessionCallback, this, authSession, std::placeholders::_1)));
+    return true;
 }
 
 void WorldSocket::HandleAuthSessionCallback(std::shared_ptr<AuthSession> authSession, PreparedQueryResult result)
@@ -475,7 +377,7 @@ void WorldSocket::HandleAuthSessionCallback(std::shared_ptr<AuthSession> authSes
     }
 
     // even if auth credentials are bad, try using the session key we have - client cannot read auth response error without it
-    _authCrypt.Init(account.SessionKey);
+    WorldSocketControl_InitEncryption(_control.get(), account.SessionKey.data(), account.SessionKey.size());
 
     // First reject the connection if packet contains invalid data or realm state doesn't allow logging in
     if (sWorld->IsClosed())
@@ -512,7 +414,7 @@ void WorldSocket::HandleAuthSessionCallback(std::shared_ptr<AuthSession> authSes
     sha.UpdateData(authSession->Account);
     sha.UpdateData(t);
     sha.UpdateData(authSession->LocalChallenge);
-    sha.UpdateData(_authSeed);
+    sha.UpdateData(authSession->AuthSeed);
     sha.UpdateData(account.SessionKey);
     sha.Finalize();
 
@@ -611,8 +513,9 @@ void WorldSocket::HandleAuthSessionCallback(std::shared_ptr<AuthSession> authSes
     if (wardenActive)
         _worldSession->InitWarden(account.SessionKey, account.OS);
 
-    _queryProcessor.AddCallback(_worldSession->LoadPermissionsAsync().WithPreparedCallback(std::bind(&WorldSocket::LoadSessionPermissionsCallback, this, std::placeholders::_1)));
-    AsyncRead();
+    _queryProcessor.AddCallback(_worldSession->LoadPermissionsAsync([notifier = _notifier]() { Notifier_Notify(notifier.get()); })
+        .WithPreparedCallback(std::bind(&WorldSocket::LoadSessionPermissionsCallback, this, std::placeholders::_1)));
+    WorldSocketControl_ResumeReading(_control.get());
 }
 
 void WorldSocket::LoadSessionPermissionsCallback(PreparedQueryResult result)
@@ -625,10 +528,8 @@ void WorldSocket::LoadSessionPermissionsCallback(PreparedQueryResult result)
 
 void WorldSocket::SendAuthResponseError(uint8 code)
 {
-    WorldPacket packet(SMSG_AUTH_RESPONSE, 1);
-    packet << uint8(code);
-
-    SendPacketAndLogOpcode(packet);
+    TC_LOG_TRACE("network.opcode", "S->C: %s %s", GetRemoteIpAddress().to_string().c_str(), GetOpcodeNameForLogging(SMSG_AUTH_RESPONSE).c_str());
+    AuthResponseError_Send(AuthResponseError_New(code), _control.get());
 }
 
 bool WorldSocket::HandlePing(WorldPacket& recvPacket)
@@ -19,84 +19,55 @@
 #define __WORLDSOCKET_H__
 
 #include "Common.h"
-#include "AuthCrypt.h"
-#include "ServerPktHeader.h"
-#include "Socket.h"
-#include "Util.h"
+#include "AsyncCallbackProcessor.h"
+#include "DatabaseEnvFwd.h"
+#include "Duration.h"
+#include "Opcodes.h"
 #include "WorldPacket.h"
-#include "WorldSession.h"
-#include "MPSCQueue.h"
-#include <boost/asio/ip/tcp.hpp>
-
-using boost::asio::ip::tcp;
-class EncryptablePacket : public WorldPacket
-{
-public:
-    EncryptablePacket(WorldPacket const& packet, bool encrypt) : WorldPacket(packet), _encrypt(encrypt)
-    {
-        SocketQueueLink.store(nullptr, std::memory_order_relaxed);
-    }
-
-    bool NeedsEncryption() const { return _encrypt; }
-
-    std::atomic<EncryptablePacket*> SocketQueueLink;
-
-private:
-    bool _encrypt;
-};
-
-namespace WorldPackets
-{
-    class ServerPacket;
-}
-#pragma pack(push, 1)
-
-struct ClientPktHeader
-{
-    uint16 size;
-    uint32 cmd;
-
-    bool IsValidSize() const { return size >= 4 && size < 10240; }
-    bool IsValidOpcode() const { return cmd < NUM_OPCODE_HANDLERS; }
-};
-
-#pragma pack(pop)
+#include <boost/asio/ip/address.hpp>
+#include <memory>
+#include <mutex>
 
 struct AuthSession;
-
-class TC_GAME_API WorldSocket : public Socket<WorldSocket>
+struct Notifier;
+struct WorldAuthSession;
+struct WorldSocketControl;
+class WorldSession;
+
+/// The game side of a world connection. The connection is run by the Rust world socket, which
+/// frames the packets and encrypts their headers, and hands the complete packets over.
+class TC_GAME_API WorldSocket : public std::enable_shared_from_this<WorldSocket>
 {
-    typedef Socket<WorldSocket> BaseSocket;
-
 public:
-    WorldSocket(tcp::socket&& socket);
+    explicit WorldSocket(void* rsWorldSocket);
     ~WorldSocket();
 
     WorldSocket(WorldSocket const& right) = delete;
     WorldSocket& operator=(WorldSocket const& right) = delete;
 
-    void Start() override;
-    bool Update() override;
+    void Start();
+    void Update();
+    void OnClose();
 
-    void SendPacket(WorldPacket const& packet);
+    bool HandleAuthSession(WorldAuthSession const& authSession);
+    bool HandlePacket(OpcodeClient opcode, uint8 const* data, std::size_t size);
 
-    void SetSendBufferSize(std::size_t sendBufferSize) { _sendBufferSize = sendBufferSize; }
+    void SendPacket(WorldPacket const& packet);
 
-protected:
-    void OnClose() override;
-    void ReadHandler() override;
-    bool ReadHeaderHandler();
+    /// Sets the state of the session the client packets are checked against, a SessionState value
+    void SetSessionState(uint8 state);
 
-    enum class ReadDataHandlerResult
-    {
-        Ok = 0,
-        Error = 1,
-        WaitingForQuery = 2
-    };
+    bool IsOpen() const;
+    void CloseSocket();
+    /// Closes the socket once the queued packets are sent
+    void DelayedCloseSocket();
 
-    ReadDataHandlerResult ReadDataHandler();
+    boost::asio::ip::address GetRemoteIpAddress() const { return _remoteAddress; }
+    uint16 GetRemotePort() const { return _remotePort; }
 
 private:
+    /// The callback runs on the next Update, which is triggered once the query completes
+    QueryCallback AsyncQuery(LoginDatabasePreparedStatement* stmt);
     void CheckIpCallback(PreparedQueryResult result);
 
     /// writes network.opcode log
@@ -104,16 +75,16 @@ private:
     void LogOpcodeText(OpcodeClient opcode, std::unique_lock<std::mutex> const& guard) const;
     /// sends and logs network.opcode without accessing WorldSession
     void SendPacketAndLogOpcode(WorldPacket const& packet);
-    void HandleSendAuthSession();
-    void HandleAuthSession(WorldPacket& recvPacket);
     void HandleAuthSessionCallback(std::shared_ptr<AuthSession> authSession, PreparedQueryResult result);
     void LoadSessionPermissionsCallback(PreparedQueryResult result);
     void SendAuthResponseError(uint8 code);
 
     bool HandlePing(WorldPacket& recvPacket);
 
-    std::array<uint8, 4> _authSeed;
-    AuthCrypt _authCrypt;
+    std::shared_ptr<WorldSocketControl const> _control;
+    std::shared_ptr<Notifier const> _notifier;
+    boost::asio::ip::address _remoteAddress;
+    uint16 _remotePort;
 
     TimePoint _LastPingTime;
     uint32 _OverSpeedPings;
@@ -122,11 +93,6 @@ private:
     WorldSession* _worldSession;
     bool _authed;
 
-    MessageBuffer _headerBuffer;
-    MessageBuffer _packetBuffer;
-    MPSCQueue<EncryptablePacket, &EncryptablePacket::SocketQueueLink> _bufferQueue;
-    std::size_t _sendBufferSize;
-
     QueryCallbackProcessor _queryProcessor;
     std::string _ipCountry;
 };
@@ -88,6 +88,7 @@
 #include "WeatherMgr.h"
 #include "WhoListStorage.h"
 #include "WorldSession.h"
+#include "libenturion_worldserver.h"
 
 #include <boost/asio/ip/address.hpp>
 
@@ -112,8 +113,6 @@ World::World()
     m_playerLimit = 0;
     m_allowedSecurityLevel = SEC_PLAYER;
     m_allowMovement = true;
-    m_ShutdownMask = 0;
-    m_ShutdownTimer = 0;
 
     m_maxActiveSessionCount = 0;
     m_maxQueuedSessionCount = 0;
@@ -257,7 +256,7 @@ void World::TriggerGuidAlert()
 
 void World::DoGuidWarningRestart()
 {
-    if (m_ShutdownTimer)
+    if (IsShuttingDown())
         return;
 
     ShutdownServ(1800, SHUTDOWN_MASK_RESTART, RESTART_EXIT_CODE);
@@ -266,7 +265,7 @@ void World::DoGuidWarningRestart()
 
 void World::DoGuidAlertRestart()
 {
-    if (m_ShutdownTimer)
+    if (IsShuttingDown())
         return;
 
     ShutdownServ(300, SHUTDOWN_MASK_RESTART, RESTART_EXIT_CODE, _alertRestartReason);
@@ -274,7 +273,7 @@ void World::DoGuidAlertRestart()
 
 void World::SendGuidWarning()
 {
-    if (!m_ShutdownTimer && _guidWarn && getIntConfig(CONFIG_RESPAWN_GUIDWARNING_FREQUENCY) > 0)
+    if (!IsShuttingDown() && _guidWarn && getIntConfig(CONFIG_RESPAWN_GUIDWARNING_FREQUENCY) > 0)
         SendServerMessage(SERVER_MSG_STRING, _guidWarningMsg.c_str());
     _warnDiff = 0;
 }
@@ -656,12 +655,6 @@ void World::LoadConfigSettings(bool reload)
 
     m_bool_configs[CONFIG_DURABILITY_LOSS_IN_PVP] = sConfigMgr->GetBoolDefault("DurabilityLoss.InPvP", false);
 
-    m_int_configs[CONFIG_COMPRESSION] = sConfigMgr->GetIntDefault("Compression", 1);
-    if (m_int_configs[CONFIG_COMPRESSION] < 1 || m_int_configs[CONFIG_COMPRESSION] > 9)
-    {
-        TC_LOG_ERROR("server.loading", "Compression level (%i) must be in range 1..9. Using default compression level (1).", m_int_configs[CONFIG_COMPRESSION]);
-        m_int_configs[CONFIG_COMPRESSION] = 1;
-    }
     m_bool_configs[CONFIG_ADDON_CHANNEL] = sConfigMgr->GetBoolDefault("AddonChannel", true);
     m_bool_configs[CONFIG_CLEAN_CHARACTER_DB] = sConfigMgr->GetBoolDefault("CleanCharacterDB", false);
     m_int_configs[CONFIG_PERSISTENT_CHARACTER_CLEAN_FLAGS] = sConfigMgr->GetIntDefault("PersistentCharacterCleanFlags", 0);
@@ -2975,31 +2968,25 @@ bool World::RemoveBanCharacter(std::string const& name)
 /// Update the game time
 void World::_UpdateGameTime()
 {
-    ///- update the time
-    time_t lastGameTime = GameTime::GetGameTime();
+    ///- update the time, the shutdown countdown is run by the Rust lifecycle
     GameTime::UpdateGameTimers();
+}
 
-    uint32 elapsed = uint32(GameTime::GetGameTime() - lastGameTime);
+/// Is a shutdown planned?
+bool World::IsShuttingDown() const
+{
+    WorldPlannedStop planned;
+    return WorldLifecycle_GetPlannedStop(&planned);
+}
 
-    ///- if there is a shutdown timer
-    if (!IsStopped() && m_ShutdownTimer > 0 && elapsed > 0)
-    {
-        ///- ... and it is overdue, stop the world (set m_stopEvent)
-        if (m_ShutdownTimer <= elapsed)
-        {
-            if (!(m_ShutdownMask & SHUTDOWN_MASK_IDLE) || GetActiveAndQueuedSessionCount() == 0)
-                m_stopEvent = true;                         // exist code already set
-            else
-                m_ShutdownTimer = 1;                        // minimum timer value to wait idle state
-        }
-        ///- ... else decrease it and if necessary display a shutdown countdown to the users
-        else
-        {
-            m_ShutdownTimer -= elapsed;
+/// Seconds left before the planned shutdown, 0 if there is none
+uint32 World::GetShutDownTimeLeft() const
+{
+    WorldPlannedStop planned;
+    if (!WorldLifecycle_GetPlannedStop(&planned))
+        return 0;
 
-            ShutdownMsg();
-        }
-    }
+    return planned.remaining_seconds;
 }
 
 /// Shutdown the server
@@ -3009,64 +2996,37 @@ void World::ShutdownServ(uint32 time, uint32 options, uint8 exitcode, const std:
     if (IsStopped())
         return;
 
-    m_ShutdownMask = options;
-    m_ExitCode = exitcode;
-
-    ///- If the shutdown time is 0, evaluate shutdown on next tick (no message)
-    if (time == 0)
-        m_ShutdownTimer = 1;
-    ///- Else set the shutdown timer and warn users
-    else
-    {
-        m_ShutdownTimer = time;
-        ShutdownMsg(true, nullptr, reason);
-    }
+    ///- The lifecycle runs the countdown and warns the users, an idle shutdown waits for the sessions to be gone
+    WorldLifecycle_PlanStop((options & SHUTDOWN_MASK_RESTART) != 0, time, reason.c_str(), exitcode, (options & SHUTDOWN_MASK_IDLE) != 0);
 
     sScriptMgr->OnShutdownInitiate(ShutdownExitCode(exitcode), ShutdownMask(options));
 }
 
-/// Display a shutdown message to the user(s)
-void World::ShutdownMsg(bool show, Player* player, const std::string& reason)
+/// Display the time left before the planned shutdown to a player, the countdown itself is announced by the lifecycle
+void World::ShutdownMsg(Player* player)
 {
+    WorldPlannedStop planned;
     // not show messages for idle shutdown mode
-    if (m_ShutdownMask & SHUTDOWN_MASK_IDLE)
+    if (!WorldLifecycle_GetPlannedStop(&planned) || planned.idle)
         return;
 
-    ///- Display a message every 12 hours, hours, 5 minutes, minute, 5 seconds and finally seconds
-    if (show ||
-        (m_ShutdownTimer < 5* MINUTE && (m_ShutdownTimer % 15) == 0) || // < 5 min; every 15 sec
-        (m_ShutdownTimer < 15 * MINUTE && (m_ShutdownTimer % MINUTE) == 0) || // < 15 min ; every 1 min
-        (m_ShutdownTimer < 30 * MINUTE && (m_ShutdownTimer % (5 * MINUTE)) == 0) || // < 30 min ; every 5 min
-        (m_ShutdownTimer < 12 * HOUR && (m_ShutdownTimer % HOUR) == 0) || // < 12 h ; every 1 h
-        (m_ShutdownTimer > 12 * HOUR && (m_ShutdownTimer % (12 * HOUR)) == 0)) // > 12 h ; every 12 h
-    {
-        std::string str = secsToTimeString(m_ShutdownTimer, TimeFormat::Numeric);
-        if (!reason.empty())
-            str += " - " + reason;
-
-        ServerMessageType msgid = (m_ShutdownMask & SHUTDOWN_MASK_RESTART) ? SERVER_MSG_RESTART_TIME : SERVER_MSG_SHUTDOWN_TIME;
-
-        SendServerMessage(msgid, str, player);
-        TC_LOG_DEBUG("misc", "Server is %s in %s", (m_ShutdownMask & SHUTDOWN_MASK_RESTART ? "restart" : "shuttingdown"), str.c_str());
-    }
+    std::string str = secsToTimeString(planned.remaining_seconds, TimeFormat::Numeric);
+    SendServerMessage(planned.restart ? SERVER_MSG_RESTART_TIME : SERVER_MSG_SHUTDOWN_TIME, str, player);
 }
 
 /// Cancel a planned server shutdown
 uint32 World::ShutdownCancel()
 {
-    // nothing cancel or too late
-    if (!m_ShutdownTimer || m_stopEvent)
+    // too late
+    if (m_stopEvent)
         return 0;
 
-    ServerMessageType msgid = (m_ShutdownMask & SHUTDOWN_MASK_RESTART) ? SERVER_MSG_RESTART_CANCELLED : SERVER_MSG_SHUTDOWN_CANCELLED;
-
-    uint32 oldTimer = m_ShutdownTimer;
-    m_ShutdownMask = 0;
-    m_ShutdownTimer = 0;
-    m_ExitCode = SHUTDOWN_EXIT_CODE;                       // to default value
-    SendServerMessage(msgid);
+    // nothing cancel, else the lifecycle warns the users
+    uint32 oldTimer = WorldLifecycle_CancelStop();
+    if (!oldTimer)
+        return 0;
 
-    TC_LOG_DEBUG("misc", "Server %s cancelled.", (m_ShutdownMask & SHUTDOWN_MASK_RESTART ? "restart" : "shutdown"));
+    TC_LOG_DEBUG("misc", "Server shutdown cancelled.");
 
     sScriptMgr->OnShutdownCancel();
     return oldTimer;
@@ -208,8 +208,7 @@ enum WorldFloatConfigs
 
 enum WorldIntConfigs
 {
-    CONFIG_COMPRESSION = 0,
-    CONFIG_INTERVAL_SAVE,
+    CONFIG_INTERVAL_SAVE = 0,
     CONFIG_INTERVAL_GRIDCLEAN,
     CONFIG_INTERVAL_MAPUPDATE,
     CONFIG_INTERVAL_CHANGEWEATHER,
@@ -659,11 +658,11 @@ class TC_GAME_API World
         void SendZoneText(uint32 zone, const char *text, WorldSession* self = nullptr, uint32 team = 0);
 
         /// Are we in the middle of a shutdown?
-        bool IsShuttingDown() const { return m_ShutdownTimer > 0; }
-        uint32 GetShutDownTimeLeft() const { return m_ShutdownTimer; }
+        bool IsShuttingDown() const;
+        uint32 GetShutDownTimeLeft() const;
         void ShutdownServ(uint32 time, uint32 options, uint8 exitcode, const std::string& reason = std::string());
         uint32 ShutdownCancel();
-        void ShutdownMsg(bool show = false, Player* player = nullptr, const std::string& reason = std::string());
+        void ShutdownMsg(Player* player);
         static uint8 GetExitCode() { return m_ExitCode; }
         static void StopNow(uint8 exitcode) { m_stopEvent = true; m_ExitCode = exitcode; }
         static bool IsStopped() { return m_stopEvent; }
@@ -795,8 +794,6 @@ class TC_GAME_API World
 
         static std::atomic<bool> m_stopEvent;
         static uint8 m_ExitCode;
-        uint32 m_ShutdownTimer;
-        uint32 m_ShutdownMask;
 
         uint32 m_CleaningFlags;
 
@@ -39,6 +39,7 @@ EndScriptData */
 #include "MapManager.h"
 #include "ObjectAccessor.h"
 #include "ObjectMgr.h"
+#include "PacketLog.h"
 #include "PoolMgr.h"
 #include "QuestPools.h"
 #include "RBAC.h"
@@ -119,6 +120,7 @@ public:
             { "asan outofbounds",   HandleDebugOutOfBounds,                rbac::RBAC_PERM_COMMAND_DEBUG,   Console::Yes },
             { "guidlimits",         HandleDebugGuidLimitsCommand,          rbac::RBAC_PERM_COMMAND_DEBUG,   Console::Yes },
             { "objectcount",        HandleDebugObjectCountCommand,         rbac::RBAC_PERM_COMMAND_DEBUG,   Console::Yes },
+            { "packetlog",          HandleDebugPacketLogCommand,           rbac::RBAC_PERM_COMMAND_DEBUG,   Console::Yes },
             { "questreset",         HandleDebugQuestResetCommand,          rbac::RBAC_PERM_COMMAND_DEBUG,   Console::Yes },
             { "warden force",       HandleDebugWardenForce,                rbac::RBAC_PERM_COMMAND_DEBUG,   Console::Yes }
         };
@@ -1696,6 +1698,23 @@ public:
         return true;
     }
 
+    static bool HandleDebugPacketLogCommand(ChatHandler* handler, Optional<bool> enableArg)
+    {
+        bool enable = !sPacketLog->IsEnabled();
+        if (enableArg)
+            enable = *enableArg;
+
+        if (!sPacketLog->SetEnabled(enable))
+        {
+            handler->SendSysMessage("Packet log could not be started, check PacketLogFile.");
+            handler->SetSentErrorMessage(true);
+            return false;
+        }
+
+        handler->PSendSysMessage("Packet log is %s.", enable ? "ON" : "OFF");
+        return true;
+    }
+
     static bool HandleDebugWardenForce(ChatHandler* handler, std::vector<uint16> checkIds)
     {
         if (checkIds.empty())
@@ -61,6 +61,7 @@ public:
     Realm const* GetRealm(RealmHandle const& id) const;
 
     RealmBuildInfo const* GetBuildInfo(uint32 build) const;
+    std::vector<RealmBuildInfo> const& GetBuilds() const { return _builds; }
 
 private:
     RealmList();
@@ -27,7 +27,6 @@
 #include "Configuration/Config.h"
 #include "DatabaseEnv.h"
 #include "DatabaseLoader.h"
-#include "DeadlineTimer.h"
 #include "GitRevision.h"
 #include "InstanceSaveMgr.h"
 #include "IoContext.h"
@@ -48,9 +47,8 @@
 #include "SharedDefines.h"
 #include "TCSoap.h"
 #include "ThreadPool.h"
+#include "Util.h"
 #include "World.h"
-#include "WorldSocket.h"
-#include "WorldSocketMgr.h"
 #include <openssl/opensslv.h>
 #include <openssl/crypto.h>
 #include <boost/asio/signal_set.hpp>
@@ -86,30 +84,6 @@ int m_ServiceStatus = -1;
 #include <timeapi.h>
 #endif
 
-class FreezeDetector
-{
-    public:
-    FreezeDetector(Kitron::Asio::IoContext& ioContext, uint32 maxCoreStuckTime)
-        : _timer(ioContext), _worldLoopCounter(0), _lastChangeMsTime(getMSTime()), _maxCoreStuckTimeInMs(maxCoreStuckTime) { }
-
-        static void Start(std::shared_ptr<FreezeDetector> const& freezeDetector)
-        {
-            freezeDetector->_timer.expires_from_now(boost::posix_time::seconds(5));
-            freezeDetector->_timer.async_wait([freezeDetectorRef = std::weak_ptr<FreezeDetector>(freezeDetector)](boost::system::error_code const& error)
-            {
-                return Handler(freezeDetectorRef, error);
-            });
-        }
-
-        static void Handler(std::weak_ptr<FreezeDetector> freezeDetectorRef, boost::system::error_code const& error);
-
-    private:
-        Kitron::Asio::DeadlineTimer _timer;
-        uint32 _worldLoopCounter;
-        uint32 _lastChangeMsTime;
-        uint32 _maxCoreStuckTimeInMs;
-};
-
 AsyncAcceptor* StartRaSocketAcceptor(Kitron::Asio::IoContext& ioContext);
 bool StartDB();
 void StopDB();
@@ -123,6 +97,38 @@ extern "C" int World_IsStopped() {
     return World::IsStopped() ? 1 : 0;
 }
 
+/// Whether an idle shutdown can happen: no session is active or queued
+extern "C" bool World_IsIdle() {
+    return sWorld->GetActiveAndQueuedSessionCount() == 0;
+}
+
+extern "C" uint8 World_GetExitCode() {
+    return World::GetExitCode();
+}
+
+/// Reloads the configuration, like the .reload config command
+extern "C" void World_ReloadConfig() {
+    TC_LOG_INFO("misc", "Re-Loading config settings...");
+    sWorld->LoadConfigSettings(true);
+    sMapMgr->InitializeVisibilityDistanceInfo();
+}
+
+/// Warns the players of a stop planned from the Rust lifecycle, a zero countdown being a cancellation
+extern "C" void World_AnnounceStop(bool restart, uint32 remainingSeconds, char const* reason) {
+    if (!remainingSeconds)
+    {
+        sWorld->SendServerMessage(restart ? SERVER_MSG_RESTART_CANCELLED : SERVER_MSG_SHUTDOWN_CANCELLED);
+        return;
+    }
+
+    std::string str = secsToTimeString(remainingSeconds, TimeFormat::Numeric);
+    if (*reason)
+        str += " - " + std::string(reason);
+
+    sWorld->SendServerMessage(restart ? SERVER_MSG_RESTART_TIME : SERVER_MSG_SHUTDOWN_TIME, str);
+    TC_LOG_DEBUG("misc", "Server is %s in %s", restart ? "restart" : "shuttingdown", str.c_str());
+}
+
 /// Launch the Kitron server
 extern int main(int argc, char** argv)
 {
@@ -138,6 +144,13 @@ extern int main(int argc, char** argv)
     if (vm.count("help") || vm.count("version"))
         return 0;
 
+    // Print the documented configuration keys instead of starting
+    if (vm.count("print-config"))
+    {
+        WorldServerRsPrintConfig();
+        return 0;
+    }
+
 #ifdef _WIN32
     if (configService == "install")
         return WinServiceInstall() ? 0 : 1;
@@ -306,32 +319,15 @@ extern int main(int argc, char** argv)
         });
     }
 
-    // Launch the worldserver listener socket
-    uint16 worldPort = uint16(sWorld->getIntConfig(CONFIG_PORT_WORLD));
-    std::string worldListener = sConfigMgr->GetStringDefault("BindIP", "0.0.0.0");
-
-    int networkThreads = sConfigMgr->GetIntDefault("Network.Threads", 1);
+    // The world sockets are accepted by the Rust main loop, on BindIP and WorldServerPort
+    sScriptMgr->OnNetworkStart();
 
-    if (networkThreads <= 0)
-    {
-        TC_LOG_ERROR("server.worldserver", "Network.Threads must be greater than 0");
-        World::StopNow(ERROR_EXIT_CODE);
-        return 1;
-    }
-
-    if (!sWorldSocketMgr.StartWorldNetwork(*ioContext, worldListener, worldPort, networkThreads))
-    {
-        TC_LOG_ERROR("server.worldserver", "Failed to initialize network");
-        World::StopNow(ERROR_EXIT_CODE);
-        return 1;
-    }
-
-    std::shared_ptr<void> sWorldSocketMgrHandle(nullptr, [](void*)
+    std::shared_ptr<void> sWorldNetworkHandle(nullptr, [](void*)
     {
         sWorld->KickAll();              // save and kick all players
         sWorld->UpdateSessions(1);      // real players unload required UpdateSessions call
 
-        sWorldSocketMgr.StopNetwork();
+        sScriptMgr->OnNetworkStop();
 
         ///- Clean database before leaving
         ClearOnlineAccounts();
@@ -342,15 +338,6 @@ extern int main(int argc, char** argv)
     realm.PopulationLevel = 0.0f;
     realm.Flags = RealmFlags(realm.Flags & ~uint32(REALM_FLAG_OFFLINE));
 
-    // Start the freeze check callback cycle in 5 seconds (cycle itself is 1 sec)
-    std::shared_ptr<FreezeDetector> freezeDetector;
-    if (int coreStuckTime = sConfigMgr->GetIntDefault("MaxCoreStuckTime", 60))
-    {
-        freezeDetector = std::make_shared<FreezeDetector>(*ioContext, coreStuckTime * 1000);
-        FreezeDetector::Start(freezeDetector);
-        TC_LOG_INFO("server.worldserver", "Starting up anti-freeze thread (%u seconds max stuck time)...", coreStuckTime);
-    }
-
     TC_LOG_INFO("server.worldserver", "%s (worldserver-daemon) ready...", GitRevision::GetFullVersion());
 
     sScriptMgr->OnStartup();
@@ -447,26 +434,10 @@ void ShutdownCLIThread(std::thread* cliThread)
     }
 }
 
-uint32 realPrevTime, realCurrTime, minUpdateDiff;
-uint32 maxCoreStuckTime, halfMaxCoreStuckTime;
-
-void MainLoopCallback() {
+void MainLoopCallback(uint32 diff) {
     ++World::m_worldLoopCounter;
-    realCurrTime = getMSTime();
-
-    uint32 diff = getMSTimeDiff(realPrevTime, realCurrTime);
-    if (diff < minUpdateDiff)
-    {
-        uint32 sleepTime = minUpdateDiff - diff;
-        if (sleepTime >= halfMaxCoreStuckTime)
-            TC_LOG_ERROR("server.worldserver", "WorldUpdateLoop() waiting for %u ms with MaxCoreStuckTime set to %u ms", sleepTime, maxCoreStuckTime);
-        // sleep until enough time passes that we can update all timers
-        std::this_thread::sleep_for(Milliseconds(sleepTime));
-        return;
-    }
 
     sWorld->Update(diff);
-    realPrevTime = realCurrTime;
 
 #ifdef _WIN32
     if (m_ServiceStatus == 0)
@@ -479,61 +450,18 @@ void MainLoopCallback() {
 
 void WorldUpdateLoop()
 {
-    minUpdateDiff = uint32(sConfigMgr->GetIntDefault("MinWorldUpdateTime", 1));
-    realCurrTime = 0;
-    realPrevTime = getMSTime();
-
-    maxCoreStuckTime = uint32(sConfigMgr->GetIntDefault("MaxCoreStuckTime", 60)) * 1000;
-    halfMaxCoreStuckTime = maxCoreStuckTime / 2;
-    if (!halfMaxCoreStuckTime)
-        halfMaxCoreStuckTime = std::numeric_limits<uint32>::max();
-
     LoginDatabase.WarnAboutSyncQueries(true);
     CharacterDatabase.WarnAboutSyncQueries(true);
     WorldDatabase.WarnAboutSyncQueries(true);
 
-    WorldServerRsMain(MainLoopCallback);
-    World::StopNow(SHUTDOWN_EXIT_CODE);
+    // Startup failures, like the network initialization, are logged by the Rust side
+    World::StopNow(WorldServerRsMain(MainLoopCallback));
 
     LoginDatabase.WarnAboutSyncQueries(false);
     CharacterDatabase.WarnAboutSyncQueries(false);
     WorldDatabase.WarnAboutSyncQueries(false);
 }
 
-void FreezeDetector::Handler(std::weak_ptr<FreezeDetector> freezeDetectorRef, boost::system::error_code const& error)
-{
-    if (!error)
-    {
-        if (std::shared_ptr<FreezeDetector> freezeDetector = freezeDetectorRef.lock())
-        {
-            uint32 curtime = getMSTime();
-
-            uint32 worldLoopCounter = World::m_worldLoopCounter;
-            if (freezeDetector->_worldLoopCounter != worldLoopCounter)
-            {
-                freezeDetector->_lastChangeMsTime = curtime;
-                freezeDetector->_worldLoopCounter = worldLoopCounter;
-            }
-            // possible freeze
-            else
-            {
-                uint32 msTimeDiff = getMSTimeDiff(freezeDetector->_lastChangeMsTime, curtime);
-                if (msTimeDiff > freezeDetector->_maxCoreStuckTimeInMs)
-                {
-                    TC_LOG_ERROR("server.worldserver", "World Thread hangs for %u ms, forcing a crash!", msTimeDiff);
-                    ABORT_MSG("World Thread hangs for %u ms, forcing a crash!", msTimeDiff);
-                }
-            }
-
-            freezeDetector->_timer.expires_from_now(boost::posix_time::seconds(1));
-            freezeDetector->_timer.async_wait([freezeDetectorRef](boost::system::error_code const& timerError)
-            {
-                return Handler(freezeDetectorRef, timerError);
-            });
-        }
-    }
-}
-
 AsyncAcceptor* StartRaSocketAcceptor(Kitron::Asio::IoContext& ioContext)
 {
     uint16 raPort = uint16(sConfigMgr->GetIntDefault("Ra.Port", 3443));
@@ -671,6 +599,7 @@ variables_map GetConsoleArguments(int argc, char** argv, fs::path& configFile, s
         ("config,c", value<fs::path>(&configFile)->default_value(fs::absolute(_KITRON_CORE_CONFIG)),
                      "use <arg> as configuration file")
         ("update-databases-only,u", "updates databases only")
+        ("print-config", "print the known configuration keys with their default value, then exit")
         ;
 #ifdef _WIN32
     options_description win("Windows platform specific options");